@@ -0,0 +1,178 @@
+//! CRSF command frame (0x32) events.
+//!
+//! ExpressLRS and Crossfire use command frames for receiver control such as
+//! binding and model match. The CRSF parser checks the frame and command CRCs
+//! and yields them as [`Packet::Commands`]; [`CrsfEvent`] reduces one to the
+//! addresses and command IDs the application acts on.
+//!
+//! Command frame layout:
+//!
+//! ```text
+//! [addr] [len] [0x32] [dest] [origin] [command] [sub_command] [data..] [cmd crc] [crc8]
+//! ```
+//!
+//! [`Packet::Commands`]: crate::Packet::Commands
+
+use uf_crsf::constants::CRSF_MAX_PACKET_SIZE;
+use uf_crsf::packets::{CrsfPacket, DirectCommands};
+
+/// CRSF frame type of command frames.
+pub const CRSF_FRAME_TYPE_COMMAND: u8 = 0x32;
+
+/// Command ID for receiver commands.
+pub const CRSF_COMMAND_RECEIVER: u8 = 0x10;
+
+/// Receiver sub-command that starts binding.
+pub const CRSF_SUBCOMMAND_BIND: u8 = 0x01;
+
+/// Event surfaced from non-RC CRSF frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CrsfEvent {
+    /// Command frame (0x32).
+    Command {
+        /// Destination device address
+        destination: u8,
+        /// Origin device address
+        origin: u8,
+        /// Command ID (e.g. [`CRSF_COMMAND_RECEIVER`])
+        command: u8,
+        /// Sub-command (e.g. [`CRSF_SUBCOMMAND_BIND`])
+        sub_command: u8,
+    },
+}
+
+impl CrsfEvent {
+    /// Returns true if this is a receiver bind command.
+    #[must_use]
+    pub const fn is_bind(&self) -> bool {
+        matches!(
+            self,
+            Self::Command {
+                command: CRSF_COMMAND_RECEIVER,
+                sub_command: CRSF_SUBCOMMAND_BIND,
+                ..
+            }
+        )
+    }
+}
+
+impl From<&DirectCommands> for CrsfEvent {
+    fn from(command: &DirectCommands) -> Self {
+        // uf-crsf keeps the command payload types private, so read the IDs
+        // back from the encoded payload: [dest] [origin] [command] [sub_command]
+        let mut payload = [0u8; CRSF_MAX_PACKET_SIZE];
+        // A parsed command always fits a maximum size frame
+        let _ = command.to_bytes(&mut payload);
+        Self::Command {
+            destination: command.dst_addr,
+            origin: command.src_addr,
+            command: payload[2],
+            sub_command: payload[3],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{command_frame, frame};
+    use crate::{CrsfParser, Packet};
+
+    /// Feed `bytes` to `parser`, returning the event of the command frame.
+    fn scan(parser: &mut CrsfParser, bytes: &[u8]) -> Option<CrsfEvent> {
+        let mut event = None;
+        for &byte in bytes {
+            match parser.push_byte(byte) {
+                Ok(Some(Packet::Commands(command))) => {
+                    assert!(event.is_none(), "more than one event");
+                    event = Some(CrsfEvent::from(&command));
+                }
+                Ok(_) => {}
+                Err(_) => parser.reset(),
+            }
+        }
+        event
+    }
+
+    #[test]
+    fn test_command_surfaced_between_rc_frames() {
+        let rc = frame(0x16, &[0u8; 22]);
+        // Bind command from the transmitter module
+        let bind = command_frame(&[0xEC, 0xEA, 0x10, 0x01]);
+
+        let mut parser = CrsfParser::new();
+        assert_eq!(scan(&mut parser, &rc), None);
+
+        let event = scan(&mut parser, &bind).unwrap();
+        assert_eq!(
+            event,
+            CrsfEvent::Command {
+                destination: 0xEC,
+                origin: 0xEA,
+                command: CRSF_COMMAND_RECEIVER,
+                sub_command: CRSF_SUBCOMMAND_BIND,
+            }
+        );
+        assert!(event.is_bind());
+
+        // Parser stays in sync for the RC stream that follows
+        assert_eq!(scan(&mut parser, &rc), None);
+        assert_eq!(scan(&mut parser, &bind), Some(event));
+    }
+
+    #[test]
+    fn test_bad_crc_and_garbage_are_skipped() {
+        let mut bad = command_frame(&[0xEC, 0xEA, 0x10, 0x01]);
+        let last = bad.len() - 1;
+        bad[last] ^= 0xFF;
+
+        let mut parser = CrsfParser::new();
+        assert_eq!(scan(&mut parser, &bad), None);
+        // Address byte with an impossible length
+        assert_eq!(scan(&mut parser, &[0x55, 0xC8, 0xFF]), None);
+
+        // Model selection, with the model number as data
+        let good = command_frame(&[0xEC, 0xEA, 0x10, 0x05, 3]);
+        let event = scan(&mut parser, &good).unwrap();
+        assert!(!event.is_bind());
+        assert!(matches!(
+            event,
+            CrsfEvent::Command {
+                sub_command: 0x05,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_bad_command_crc_ignored() {
+        let mut parser = CrsfParser::new();
+        // Valid frame CRC, but no inner command CRC
+        let short = frame(CRSF_FRAME_TYPE_COMMAND, &[0xEC, 0xEA, 0x10, 0x01]);
+        assert_eq!(scan(&mut parser, &short), None);
+    }
+
+    #[test]
+    fn test_inverted_line_after_rx_inversion() {
+        let bind = command_frame(&[0xEC, 0xEA, 0x10, 0x01]);
+
+        // An idle-low receiver puts the frame on the line inverted
+        let line: heapless::Vec<u8, 64> = bind.iter().map(|&byte| !byte).collect();
+        let mut parser = CrsfParser::new();
+        assert_eq!(scan(&mut parser, &line), None);
+
+        // The UART pad inverts it back, so the bytes decode unchanged
+        let restored: heapless::Vec<u8, 64> = line.iter().map(|&byte| !byte).collect();
+        let mut parser = CrsfParser::new();
+        assert_eq!(
+            scan(&mut parser, &restored),
+            Some(CrsfEvent::Command {
+                destination: 0xEC,
+                origin: 0xEA,
+                command: CRSF_COMMAND_RECEIVER,
+                sub_command: CRSF_SUBCOMMAND_BIND,
+            })
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Receiver detection at startup.
+//!
+//! [`ReceiverDetector`] waits for the first valid RC channels frame after boot
+//! and reports whether the receiver was found within a timeout, along with
+//! the LED pattern for each phase:
+//!
+//! | State       | LED pattern                        |
+//! |-------------|------------------------------------|
+//! | `Searching` | Fast blink (100 ms on, 100 ms off) |
+//! | `Found`     | Solid on                           |
+//! | `Failed`    | Short flash once per second        |
+//!
+//! A frame arriving after the timeout still moves the detector to `Found`,
+//! so a receiver that powers up late is picked up without a reboot.
+//!
+//! Time is injected as a millisecond timestamp, so the state machine is fully
+//! testable on the host.
+
+/// LED half-period while searching.
+const SEARCH_BLINK_MS: u64 = 100;
+
+/// LED period while failed.
+const FAILED_PERIOD_MS: u64 = 1_000;
+
+/// LED on-time within each failed period.
+const FAILED_FLASH_MS: u64 = 100;
+
+/// Detection phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DetectionState {
+    /// Waiting for the first RC frame.
+    Searching,
+    /// An RC frame was received.
+    Found,
+    /// No RC frame arrived within the timeout (still listening).
+    Failed,
+}
+
+/// Startup state machine that waits for the first RC channels frame.
+#[derive(Debug, Clone)]
+pub struct ReceiverDetector {
+    /// Time allowed for the first frame
+    timeout_ms: u64,
+    /// Timestamp detection started at
+    start_ms: u64,
+    /// Current phase
+    state: DetectionState,
+}
+
+impl ReceiverDetector {
+    /// Start searching at `now_ms`, failing after `timeout_ms` without a frame.
+    #[must_use]
+    pub const fn new(timeout_ms: u64, now_ms: u64) -> Self {
+        Self {
+            timeout_ms,
+            start_ms: now_ms,
+            state: DetectionState::Searching,
+        }
+    }
+
+    /// Get the current phase without advancing the timeout.
+    #[inline]
+    #[must_use]
+    pub const fn state(&self) -> DetectionState {
+        self.state
+    }
+
+    /// Returns true once an RC frame was received.
+    #[inline]
+    #[must_use]
+    pub const fn is_found(&self) -> bool {
+        matches!(self.state, DetectionState::Found)
+    }
+
+    /// Record a valid RC channels frame.
+    pub fn frame_received(&mut self) -> DetectionState {
+        self.state = DetectionState::Found;
+        self.state
+    }
+
+    /// Advance the timeout, returning the current phase.
+    pub fn poll(&mut self, now_ms: u64) -> DetectionState {
+        if self.state == DetectionState::Searching
+            && now_ms.saturating_sub(self.start_ms) >= self.timeout_ms
+        {
+            self.state = DetectionState::Failed;
+        }
+        self.state
+    }
+
+    /// LED level for the current phase at `now_ms` (`true` = on).
+    #[must_use]
+    pub const fn led_on(&self, now_ms: u64) -> bool {
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        match self.state {
+            DetectionState::Searching => (elapsed / SEARCH_BLINK_MS).is_multiple_of(2),
+            DetectionState::Found => true,
+            DetectionState::Failed => elapsed % FAILED_PERIOD_MS < FAILED_FLASH_MS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_found_on_frame() {
+        let mut detector = ReceiverDetector::new(2000, 500);
+        assert_eq!(detector.poll(500), DetectionState::Searching);
+        assert!(detector.led_on(500));
+        assert!(!detector.led_on(600));
+
+        assert_eq!(detector.poll(1500), DetectionState::Searching);
+        assert_eq!(detector.frame_received(), DetectionState::Found);
+        assert!(detector.is_found());
+
+        // Timeout no longer applies once found
+        assert_eq!(detector.poll(10_000), DetectionState::Found);
+        assert!(detector.led_on(10_050));
+    }
+
+    #[test]
+    fn test_failed_on_timeout_then_late_frame() {
+        let mut detector = ReceiverDetector::new(2000, 0);
+        assert_eq!(detector.poll(1999), DetectionState::Searching);
+        assert_eq!(detector.poll(2000), DetectionState::Failed);
+        assert_eq!(detector.state(), DetectionState::Failed);
+        assert!(!detector.is_found());
+
+        // Short flash at the start of each second
+        assert!(detector.led_on(3050));
+        assert!(!detector.led_on(3500));
+
+        // A late receiver is still detected
+        assert_eq!(detector.frame_received(), DetectionState::Found);
+        assert_eq!(detector.poll(5000), DetectionState::Found);
+    }
+}
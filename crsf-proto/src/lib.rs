@@ -7,7 +7,13 @@
 //!
 //! - Parse CRSF RC channel packets via `uf-crsf`
 //! - Configurable channel-to-gamepad mapping
-//! - Telemetry encoding for backchannel support
+//! - Text protocol transcoding for bench testing ([`channels_to_text`])
+//! - Command frame (0x32) events for bind/model match ([`CrsfEvent`])
+//! - Link statistics (0x14) decoding and link health ([`LinkStats`], [`LinkMonitor`])
+//! - PPM pulse train decoding into CRSF channel values ([`PpmDecoder`])
+//! - Startup receiver detection with LED patterns ([`ReceiverDetector`])
+//! - Telemetry encoding for backchannel support ([`CrsfTelemetryEncoder`], and
+//!   `CrsfTelemetrySink` with the `embedded-io` feature)
 //! - No chip-specific dependencies - works on any platform
 //! - Fully testable on host
 //!
@@ -36,20 +42,53 @@
 //! - Data bits: 8
 //! - Parity: None
 //! - Stop bits: 1
+//!
+//! Some receivers output inverted (idle-low) serial. Undo the inversion in the
+//! UART hardware (e.g. the RP2040 pad `invert_rx` option); the parser then
+//! receives the same bytes as on a normal line.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod command;
+pub mod detect;
+pub mod link;
 pub mod mapping;
+pub mod ppm;
 pub mod telemetry;
 
+#[cfg(test)]
+mod test_support;
+
+// Re-export command frame events
+pub use command::{
+    CrsfEvent, CRSF_COMMAND_RECEIVER, CRSF_FRAME_TYPE_COMMAND, CRSF_SUBCOMMAND_BIND,
+};
+
+// Re-export receiver detection
+pub use detect::{DetectionState, ReceiverDetector};
+
+// Re-export link statistics
+pub use link::{LinkMonitor, LinkStats, CRSF_FRAME_TYPE_LINK_STATISTICS};
+
 // Re-export main types from mapping
 pub use mapping::{
-    channels_to_gamepad, crsf_to_button, crsf_to_stick, crsf_to_trigger, ChannelMapping,
-    BUTTON_THRESHOLD, CRSF_CENTER, CRSF_MAX, CRSF_MIN, DEFAULT_MAPPING,
+    channels_to_gamepad, channels_to_text, crsf_to_button, crsf_to_stick, crsf_to_trigger,
+    ChannelMapping, BUTTON_THRESHOLD, CRSF_CENTER, CRSF_MAX, CRSF_MIN, DEFAULT_MAPPING,
+};
+
+// Re-export PPM decoding
+pub use ppm::{
+    us_to_crsf, PpmDecoder, PpmEdge, PpmFrame, PPM_CENTER_US, PPM_CHANNEL_MAX_US,
+    PPM_CHANNEL_MIN_US, PPM_MAX_CHANNELS, PPM_MIN_CHANNELS, PPM_SYNC_MIN_US,
 };
 
 // Re-export telemetry encoding
-pub use telemetry::{encode_telemetry, MAX_TELEMETRY_FRAME_SIZE};
+#[cfg(feature = "embedded-io")]
+pub use telemetry::CrsfTelemetrySink;
+pub use telemetry::{
+    altitude_to_crsf, angle_to_crsf, current_to_crsf, encode_telemetry, speed_to_crsf,
+    voltage_to_crsf, CrsfTelemetryEncoder, MAX_TELEMETRY_FRAME_SIZE,
+};
 
 // Re-export uf_crsf types that users will need
 pub use uf_crsf::packets::Packet;
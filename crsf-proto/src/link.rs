@@ -0,0 +1,227 @@
+//! CRSF link statistics (0x14) and link health.
+//!
+//! Receivers send link statistics frames alongside the RC channels, reporting
+//! RSSI, link quality (LQ) and SNR of the RF link. The CRSF parser yields
+//! them as [`Packet::LinkStatistics`], which converts into [`LinkStats`], and
+//! [`LinkMonitor`] combines the latest statistics with the RC frame timing to
+//! decide whether the link is still usable.
+//!
+//! Link statistics payload layout:
+//!
+//! ```text
+//! [rssi_1] [rssi_2] [lq] [snr] [antenna] [rf_mode] [tx_power] [down_rssi] [down_lq] [down_snr]
+//! ```
+//!
+//! RSSI values are sent as positive numbers meaning negative dBm.
+//!
+//! [`Packet::LinkStatistics`]: crate::Packet::LinkStatistics
+
+use uf_crsf::packets::LinkStatistics;
+
+/// CRSF frame type of link statistics frames.
+pub const CRSF_FRAME_TYPE_LINK_STATISTICS: u8 = 0x14;
+
+/// Latest link statistics reported by the receiver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinkStats {
+    /// Uplink RSSI per antenna (-dBm)
+    pub uplink_rssi: [u8; 2],
+    /// Uplink link quality (0-100 %)
+    pub uplink_link_quality: u8,
+    /// Uplink signal-to-noise ratio (dB)
+    pub uplink_snr: i8,
+    /// Antenna the receiver currently uses (0 or 1)
+    pub active_antenna: u8,
+    /// RF mode (packet rate index, transmitter specific)
+    pub rf_mode: u8,
+    /// Uplink transmit power (index, transmitter specific)
+    pub uplink_tx_power: u8,
+    /// Downlink (telemetry) RSSI (-dBm)
+    pub downlink_rssi: u8,
+    /// Downlink link quality (0-100 %)
+    pub downlink_link_quality: u8,
+    /// Downlink signal-to-noise ratio (dB)
+    pub downlink_snr: i8,
+}
+
+impl LinkStats {
+    /// Size of the link statistics payload in bytes.
+    pub const PAYLOAD_SIZE: usize = 10;
+
+    /// Uplink RSSI of the active antenna in dBm.
+    #[must_use]
+    pub const fn rssi_dbm(&self) -> i16 {
+        let raw = if self.active_antenna == 0 {
+            self.uplink_rssi[0]
+        } else {
+            self.uplink_rssi[1]
+        };
+        -(raw as i16)
+    }
+
+    /// Returns true if the receiver reports no uplink packets getting through.
+    #[inline]
+    #[must_use]
+    pub const fn is_link_lost(&self) -> bool {
+        self.uplink_link_quality == 0
+    }
+}
+
+impl From<&LinkStatistics> for LinkStats {
+    fn from(packet: &LinkStatistics) -> Self {
+        Self {
+            uplink_rssi: [packet.uplink_rssi_1, packet.uplink_rssi_2],
+            uplink_link_quality: packet.uplink_link_quality,
+            uplink_snr: packet.uplink_snr,
+            active_antenna: packet.active_antenna,
+            rf_mode: packet.rf_mode,
+            uplink_tx_power: packet.uplink_tx_power,
+            downlink_rssi: packet.downlink_rssi,
+            downlink_link_quality: packet.downlink_link_quality,
+            downlink_snr: packet.downlink_snr,
+        }
+    }
+}
+
+/// Tracks whether the CRSF link is still usable.
+///
+/// The link counts as connected while RC frames keep arriving within the
+/// timeout and the latest link statistics (if any) report a non-zero LQ.
+/// Times are milliseconds from any monotonic clock.
+#[derive(Debug, Clone)]
+pub struct LinkMonitor {
+    /// Latest link statistics
+    stats: Option<LinkStats>,
+    /// Time of the last RC frame
+    last_rc_ms: Option<u64>,
+    /// Maximum time between RC frames
+    timeout_ms: u64,
+}
+
+impl LinkMonitor {
+    /// Default RC frame timeout, several frames even at the slowest rates.
+    pub const DEFAULT_TIMEOUT_MS: u64 = 500;
+
+    /// Create a monitor that expects RC frames at least every `timeout_ms`.
+    #[must_use]
+    pub const fn new(timeout_ms: u64) -> Self {
+        Self {
+            stats: None,
+            last_rc_ms: None,
+            timeout_ms,
+        }
+    }
+
+    /// Record an RC channels frame received at `now_ms`.
+    pub fn rc_received(&mut self, now_ms: u64) {
+        self.last_rc_ms = Some(now_ms);
+    }
+
+    /// Record a link statistics frame.
+    pub fn stats_received(&mut self, stats: LinkStats) {
+        self.stats = Some(stats);
+    }
+
+    /// Get the latest link statistics, if any were received.
+    #[inline]
+    #[must_use]
+    pub const fn stats(&self) -> Option<LinkStats> {
+        self.stats
+    }
+
+    /// Get the latest uplink link quality (0-100 %), if known.
+    #[must_use]
+    pub fn link_quality(&self) -> Option<u8> {
+        self.stats.map(|stats| stats.uplink_link_quality)
+    }
+
+    /// Check if the link is usable at `now_ms`.
+    #[must_use]
+    pub fn is_connected(&self, now_ms: u64) -> bool {
+        let rc_recent = self
+            .last_rc_ms
+            .is_some_and(|last| now_ms.saturating_sub(last) <= self.timeout_ms);
+        rc_recent && !self.stats.is_some_and(|stats| stats.is_link_lost())
+    }
+}
+
+impl Default for LinkMonitor {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TIMEOUT_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::frame;
+    use crate::{CrsfParser, Packet};
+
+    const PAYLOAD: [u8; LinkStats::PAYLOAD_SIZE] = [70, 85, 98, 0xF6, 1, 5, 3, 60, 100, 12];
+
+    #[test]
+    fn test_from_parsed_packet() {
+        let frame = frame(CRSF_FRAME_TYPE_LINK_STATISTICS, &PAYLOAD);
+
+        let mut parser = CrsfParser::new();
+        let packet = frame.iter().find_map(|&b| parser.push_byte(b).unwrap());
+        let Some(Packet::LinkStatistics(packet)) = packet else {
+            panic!("expected link statistics");
+        };
+        let stats = LinkStats::from(&packet);
+        assert_eq!(stats.uplink_rssi, [70, 85]);
+        assert_eq!(stats.uplink_link_quality, 98);
+        assert_eq!(stats.uplink_snr, -10);
+        assert_eq!(stats.active_antenna, 1);
+        assert_eq!(stats.rf_mode, 5);
+        assert_eq!(stats.uplink_tx_power, 3);
+        assert_eq!(stats.downlink_rssi, 60);
+        assert_eq!(stats.downlink_link_quality, 100);
+        assert_eq!(stats.downlink_snr, 12);
+        // Antenna 1 is active
+        assert_eq!(stats.rssi_dbm(), -85);
+        assert!(!stats.is_link_lost());
+    }
+
+    #[test]
+    fn test_monitor_rc_timeout() {
+        let mut link = LinkMonitor::new(100);
+        assert!(!link.is_connected(0));
+
+        link.rc_received(1_000);
+        assert!(link.is_connected(1_100));
+        assert!(!link.is_connected(1_101));
+
+        link.rc_received(1_101);
+        assert!(link.is_connected(1_101));
+    }
+
+    #[test]
+    fn test_monitor_zero_link_quality() {
+        let mut link = LinkMonitor::default();
+        link.rc_received(0);
+        assert_eq!(link.link_quality(), None);
+        assert!(link.is_connected(10));
+
+        let mut stats = LinkStats {
+            uplink_rssi: [70, 85],
+            uplink_link_quality: 0,
+            uplink_snr: -10,
+            active_antenna: 1,
+            rf_mode: 5,
+            uplink_tx_power: 3,
+            downlink_rssi: 60,
+            downlink_link_quality: 100,
+            downlink_snr: 12,
+        };
+        link.stats_received(stats);
+        assert_eq!(link.link_quality(), Some(0));
+        assert!(!link.is_connected(10));
+
+        stats.uplink_link_quality = 40;
+        link.stats_received(stats);
+        assert_eq!(link.stats(), Some(stats));
+        assert!(link.is_connected(10));
+    }
+}
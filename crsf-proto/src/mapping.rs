@@ -3,7 +3,11 @@
 //! CRSF provides 16 RC channels (0-15) with 11-bit resolution (0-1984).
 //! This module maps those channels to gamepad controls.
 
-use gamepad_core::{AnalogStick, Buttons, GamepadState};
+use gamepad_core::{
+    split_axis_to_triggers, AnalogStick, Buttons, GamepadState, Serialize, SerializeError,
+    MAX_FULL_STATE_SIZE,
+};
+use heapless::Vec;
 
 /// Channel mapping configuration for CRSF to gamepad conversion.
 ///
@@ -33,6 +37,13 @@ pub struct ChannelMapping {
     pub invert_left_x: bool,
     /// Invert left stick Y axis.
     pub invert_left_y: bool,
+    /// Derive both triggers from the `left_trigger` channel, split at center.
+    ///
+    /// Below center drives the left trigger, above center the right one (a
+    /// single pedal axis on steering setups). `right_trigger` is ignored.
+    pub combined_triggers: bool,
+    /// Deadband around center for combined triggers, in stick units (0-32767).
+    pub trigger_deadband: u16,
 }
 
 /// Default RC channel mapping following standard conventions.
@@ -54,6 +65,8 @@ pub const DEFAULT_MAPPING: ChannelMapping = ChannelMapping {
     invert_right_y: false,
     invert_left_x: false,
     invert_left_y: false,
+    combined_triggers: false,
+    trigger_deadband: 0,
 };
 
 /// CRSF channel center value (11-bit).
@@ -107,8 +120,15 @@ pub fn channels_to_gamepad(channels: &[u16; 16], mapping: &ChannelMapping) -> Ga
     };
 
     // Map triggers
-    let left_trigger = crsf_to_trigger(channels[mapping.left_trigger]);
-    let right_trigger = crsf_to_trigger(channels[mapping.right_trigger]);
+    let (left_trigger, right_trigger) = if mapping.combined_triggers {
+        let axis = crsf_to_stick(channels[mapping.left_trigger], false);
+        split_axis_to_triggers(axis, mapping.trigger_deadband)
+    } else {
+        (
+            crsf_to_trigger(channels[mapping.left_trigger]),
+            crsf_to_trigger(channels[mapping.right_trigger]),
+        )
+    };
 
     // Map buttons from aux channels
     let mut buttons = Buttons::NONE;
@@ -140,6 +160,23 @@ pub fn channels_to_gamepad(channels: &[u16; 16], mapping: &ChannelMapping) -> Ga
     }
 }
 
+/// Map CRSF channel data to a text protocol full state frame.
+///
+/// Runs [`channels_to_gamepad`] and serializes the result as a checksummed
+/// `G...` line, so CRSF-derived states can be inspected and diffed as
+/// human-readable frames on the bench.
+///
+/// # Errors
+///
+/// Returns [`SerializeError::BufferTooSmall`] if the frame does not fit the
+/// vector (cannot happen for valid states).
+pub fn channels_to_text(
+    channels: &[u16; 16],
+    mapping: &ChannelMapping,
+) -> Result<Vec<u8, MAX_FULL_STATE_SIZE>, SerializeError> {
+    channels_to_gamepad(channels, mapping).serialize_to_vec()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,4 +216,71 @@ mod tests {
         assert!(crsf_to_button(CRSF_CENTER + 1));
         assert!(crsf_to_button(CRSF_MAX));
     }
+
+    #[test]
+    fn test_channels_to_text_round_trip() {
+        let mut channels = [CRSF_CENTER; 16];
+        channels[0] = CRSF_MAX; // Roll -> right stick X
+        channels[1] = CRSF_MIN; // Pitch -> right stick Y
+        channels[2] = 1500; // Throttle -> left trigger / left stick Y
+        channels[3] = 500; // Yaw -> left stick X
+        channels[5] = CRSF_MAX; // Aux -> A
+        channels[8] = CRSF_MAX; // Aux -> Y
+
+        let text = channels_to_text(&channels, &DEFAULT_MAPPING).unwrap();
+        assert!(text.starts_with(b"G0009:"));
+        assert!(text.ends_with(b"\n"));
+
+        let parsed = gamepad_core::parse(&text).unwrap();
+        assert_eq!(parsed, channels_to_gamepad(&channels, &DEFAULT_MAPPING));
+        assert_eq!(parsed.right_stick.x, 32767);
+        assert_eq!(parsed.right_stick.y, -32767);
+    }
+
+    #[test]
+    fn test_full_resolution_preserved() {
+        // Every 11-bit step maps to a distinct stick value (no 8-bit truncation)
+        for val in CRSF_MIN..CRSF_MAX {
+            assert!(crsf_to_stick(val, false) < crsf_to_stick(val + 1, false));
+        }
+
+        // Max channel reaches the full 16-bit range, intermediate inputs keep
+        // their low bits
+        let right_x = |value: u16| {
+            let mut channels = [CRSF_CENTER; 16];
+            channels[DEFAULT_MAPPING.right_stick_x] = value;
+            channels_to_gamepad(&channels, &DEFAULT_MAPPING)
+                .right_stick
+                .x
+        };
+        assert_eq!(right_x(CRSF_MAX), 32767);
+        assert_eq!(right_x(CRSF_CENTER + 1), 33);
+        assert_eq!(right_x(1500), 16779);
+    }
+
+    #[test]
+    fn test_combined_triggers() {
+        let mapping = ChannelMapping {
+            combined_triggers: true,
+            trigger_deadband: 1000,
+            ..DEFAULT_MAPPING
+        };
+        let triggers = |value: u16| {
+            let mut channels = [CRSF_CENTER; 16];
+            channels[mapping.left_trigger] = value;
+            let state = channels_to_gamepad(&channels, &mapping);
+            (state.left_trigger, state.right_trigger)
+        };
+
+        assert_eq!(triggers(CRSF_CENTER), (0, 0));
+        assert_eq!(triggers(CRSF_MAX), (0, 255));
+        assert_eq!(triggers(CRSF_MIN), (255, 0));
+
+        // Within the deadband (~30 CRSF steps) both stay released
+        assert_eq!(triggers(CRSF_CENTER + 25), (0, 0));
+        assert_eq!(triggers(CRSF_CENTER - 25), (0, 0));
+        let (left, right) = triggers(CRSF_CENTER + 500);
+        assert_eq!(left, 0);
+        assert!((120..130).contains(&right), "{right}");
+    }
 }
@@ -0,0 +1,294 @@
+//! PPM (pulse position modulation) decoding.
+//!
+//! Older RC receivers output all channels as one pulse train on a single
+//! wire: each channel is the time between two consecutive start edges
+//! (roughly 1000-2000 µs), and a long gap without edges (the sync gap, over
+//! 2.7 ms) marks the end of a frame.
+//!
+//! [`PpmDecoder`] turns a sequence of timestamped edges into frames of channel
+//! widths in microseconds. The edges come from chip-specific capture code;
+//! the decoder only sees `(edge, timestamp)` events, so it is fully testable
+//! on the host. [`PpmFrame::to_crsf_channels`] converts a frame to the CRSF
+//! channel range, so [`channels_to_gamepad`](crate::channels_to_gamepad)
+//! applies unchanged.
+
+use crate::mapping::{CRSF_CENTER, CRSF_MAX};
+
+/// Maximum number of channels in a PPM frame.
+pub const PPM_MAX_CHANNELS: usize = 16;
+
+/// Minimum number of channels for a frame to be reported.
+pub const PPM_MIN_CHANNELS: usize = 4;
+
+/// Shortest gap that is treated as the frame sync gap (µs).
+pub const PPM_SYNC_MIN_US: u32 = 2_700;
+
+/// Shortest valid channel width (µs).
+pub const PPM_CHANNEL_MIN_US: u32 = 800;
+
+/// Longest valid channel width (µs).
+pub const PPM_CHANNEL_MAX_US: u32 = 2_200;
+
+/// Channel width at stick center (µs).
+pub const PPM_CENTER_US: u16 = 1_500;
+
+/// Signal edge reported by the capture code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PpmEdge {
+    /// Low to high transition.
+    Rising,
+    /// High to low transition.
+    Falling,
+}
+
+/// One decoded PPM frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PpmFrame {
+    /// Channel widths in µs (unused channels are zero).
+    channels_us: [u16; PPM_MAX_CHANNELS],
+    /// Number of channels in the frame.
+    count: usize,
+}
+
+impl PpmFrame {
+    /// Get the channel widths in µs.
+    #[inline]
+    #[must_use]
+    pub fn channels_us(&self) -> &[u16] {
+        &self.channels_us[..self.count]
+    }
+
+    /// Convert the frame to CRSF channel values for
+    /// [`channels_to_gamepad`](crate::channels_to_gamepad).
+    ///
+    /// Channels missing from the frame are set to [`CRSF_CENTER`].
+    #[must_use]
+    pub fn to_crsf_channels(&self) -> [u16; 16] {
+        let mut channels = [CRSF_CENTER; 16];
+        for (out, &us) in channels.iter_mut().zip(self.channels_us()) {
+            *out = us_to_crsf(us);
+        }
+        channels
+    }
+}
+
+/// Convert a channel width in µs to a CRSF channel value (0-1984).
+///
+/// Uses the CRSF scale of 1500 µs at center and 0.625 µs per step, so
+/// 1000-2000 µs maps to 192-1792.
+#[inline]
+#[must_use]
+pub fn us_to_crsf(us: u16) -> u16 {
+    let offset = (i32::from(us) - i32::from(PPM_CENTER_US)) * 8 / 5;
+    (i32::from(CRSF_CENTER) + offset).clamp(0, i32::from(CRSF_MAX)) as u16
+}
+
+/// Decoder turning timestamped edges into [`PpmFrame`]s.
+///
+/// Only edges of the configured polarity are used; the other edge can be
+/// passed in too and is ignored. Timestamps are free-running microsecond
+/// counters and may wrap.
+///
+/// A channel width outside [`PPM_CHANNEL_MIN_US`]..=[`PPM_CHANNEL_MAX_US`]
+/// (a glitch or a missed edge) drops the current frame; decoding resumes
+/// after the next sync gap.
+#[derive(Debug, Clone)]
+pub struct PpmDecoder {
+    /// Edge that starts each channel.
+    edge: PpmEdge,
+    /// Timestamp of the previous start edge.
+    last_edge_us: Option<u32>,
+    /// Frame being decoded.
+    frame: PpmFrame,
+    /// Whether a sync gap was seen since the last error.
+    synced: bool,
+}
+
+impl PpmDecoder {
+    /// Create a decoder for normal PPM (channels start on rising edges).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_edge(PpmEdge::Rising)
+    }
+
+    /// Create a decoder whose channels start on `edge` (inverted PPM uses
+    /// [`PpmEdge::Falling`]).
+    #[must_use]
+    pub const fn with_edge(edge: PpmEdge) -> Self {
+        Self {
+            edge,
+            last_edge_us: None,
+            frame: PpmFrame {
+                channels_us: [0; PPM_MAX_CHANNELS],
+                count: 0,
+            },
+            synced: false,
+        }
+    }
+
+    /// Feed one edge, returning a frame when its sync gap was seen.
+    pub fn push(&mut self, edge: PpmEdge, timestamp_us: u32) -> Option<PpmFrame> {
+        if edge != self.edge {
+            return None;
+        }
+
+        let last = self.last_edge_us.replace(timestamp_us)?;
+        let width = timestamp_us.wrapping_sub(last);
+
+        if width >= PPM_SYNC_MIN_US {
+            let complete = self.synced && self.frame.count >= PPM_MIN_CHANNELS;
+            let frame = self.frame;
+            self.synced = true;
+            self.frame.count = 0;
+            return complete.then_some(frame);
+        }
+
+        if !self.synced {
+            return None;
+        }
+
+        let valid = (PPM_CHANNEL_MIN_US..=PPM_CHANNEL_MAX_US).contains(&width);
+        if !valid || self.frame.count == PPM_MAX_CHANNELS {
+            self.reset_frame();
+            return None;
+        }
+
+        self.frame.channels_us[self.frame.count] = width as u16;
+        self.frame.count += 1;
+        None
+    }
+
+    /// Drop the current frame and wait for the next sync gap.
+    pub fn reset(&mut self) {
+        self.last_edge_us = None;
+        self.reset_frame();
+    }
+
+    fn reset_frame(&mut self) {
+        self.synced = false;
+        self.frame.count = 0;
+    }
+}
+
+impl Default for PpmDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::mapping::{channels_to_gamepad, DEFAULT_MAPPING};
+    use std::vec::Vec;
+
+    /// Pulse length before each falling edge.
+    const PULSE_US: u32 = 300;
+
+    /// Sync gap after each frame.
+    const GAP_US: u32 = 10_000;
+
+    /// Feed a pulse train of `frames` starting at `start` (with `edge` as the
+    /// start edge), returning the decoded frames.
+    fn decode(
+        decoder: &mut PpmDecoder,
+        edge: PpmEdge,
+        frames: &[&[u32]],
+        start: u32,
+    ) -> Vec<PpmFrame> {
+        let other = match edge {
+            PpmEdge::Rising => PpmEdge::Falling,
+            PpmEdge::Falling => PpmEdge::Rising,
+        };
+        let mut decoded = Vec::new();
+        let mut pulse = |decoder: &mut PpmDecoder, t: u32| {
+            decoded.extend(decoder.push(edge, t));
+            decoded.extend(decoder.push(other, t.wrapping_add(PULSE_US)));
+        };
+
+        let mut t = start;
+        for widths in frames {
+            for width in widths.iter().chain(&[GAP_US]) {
+                pulse(decoder, t);
+                t = t.wrapping_add(*width);
+            }
+        }
+        // End of the last sync gap
+        pulse(decoder, t);
+        decoded
+    }
+
+    #[test]
+    fn test_decode_synthetic_frame() {
+        let widths: &[u32] = &[1500, 1000, 2000, 1250, 1100, 1900, 1500, 1500];
+        let mut decoder = PpmDecoder::new();
+
+        // The first frame only finds the sync gap
+        let frames = decode(&mut decoder, PpmEdge::Rising, &[widths, widths], 0);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].channels_us(),
+            &[1500, 1000, 2000, 1250, 1100, 1900, 1500, 1500]
+        );
+
+        let channels = frames[0].to_crsf_channels();
+        assert_eq!(&channels[..4], &[992, 192, 1792, 592]);
+        assert_eq!(channels[15], CRSF_CENTER);
+
+        // Centered CH1, high throttle on CH3
+        let state = channels_to_gamepad(&channels, &DEFAULT_MAPPING);
+        assert_eq!(state.right_stick.x, 0);
+        assert!(state.left_trigger > 200);
+    }
+
+    #[test]
+    fn test_inverted_edges_and_wrapping_timestamps() {
+        let widths: &[u32] = &[1000, 1200, 1400, 1600, 1800, 2000];
+        let mut decoder = PpmDecoder::with_edge(PpmEdge::Falling);
+
+        // Starts just before the counter wraps
+        let frames = decode(
+            &mut decoder,
+            PpmEdge::Falling,
+            &[widths, widths],
+            u32::MAX - 5_000,
+        );
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].channels_us(),
+            &[1000, 1200, 1400, 1600, 1800, 2000]
+        );
+    }
+
+    #[test]
+    fn test_glitch_drops_frame() {
+        let good: &[u32] = &[1500; 8];
+        // A 400 µs channel means an extra edge
+        let glitch: &[u32] = &[1500, 400, 1100, 1500, 1500];
+        // Too few channels for a frame
+        let short: &[u32] = &[1500; 3];
+
+        let mut decoder = PpmDecoder::new();
+        let frames = decode(
+            &mut decoder,
+            PpmEdge::Rising,
+            &[good, glitch, short, good],
+            0,
+        );
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].channels_us(), &[1500; 8]);
+    }
+
+    #[test]
+    fn test_us_to_crsf() {
+        assert_eq!(us_to_crsf(1500), CRSF_CENTER);
+        assert_eq!(us_to_crsf(988), 173);
+        assert_eq!(us_to_crsf(2012), 1811);
+        assert_eq!(us_to_crsf(0), 0);
+        assert_eq!(us_to_crsf(u16::MAX), CRSF_MAX);
+    }
+}
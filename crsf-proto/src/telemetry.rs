@@ -2,8 +2,12 @@
 //!
 //! Converts TelemetryData to CRSF packet format for transmission.
 
-use gamepad_core::{TelemetryData, TelemetryError};
-use uf_crsf::packets::{write_packet_to_buffer, Attitude, Battery, Gps, PacketAddress};
+#[cfg(feature = "embedded-io")]
+use gamepad_core::TelemetrySink;
+use gamepad_core::{TelemetryData, TelemetryEncoder, TelemetryError};
+use uf_crsf::packets::{
+    write_packet_to_buffer, Attitude, Battery, Gps, LinkStatistics, PacketAddress, Temp,
+};
 
 /// Convert TelemetryData to CRSF packets and write to buffer.
 ///
@@ -26,11 +30,8 @@ pub fn encode_telemetry(data: &TelemetryData, buf: &mut [u8]) -> Result<usize, T
             current_ma,
             remaining_pct,
         } => {
-            // Convert from our units to CRSF units
-            // voltage: mV -> 10mV (divide by 10)
-            // current: mA -> 10mA (divide by 10)
-            let voltage = (*voltage_mv / 10) as i16;
-            let current = (*current_ma / 10) as i16;
+            let voltage = voltage_to_crsf(*voltage_mv);
+            let current = current_to_crsf(*current_ma);
             let packet =
                 Battery::new(voltage, current, 0, *remaining_pct).map_err(|_| TelemetryError::Io)?;
             write_packet_to_buffer(buf, PacketAddress::FlightController, &packet)
@@ -44,12 +45,9 @@ pub fn encode_telemetry(data: &TelemetryData, buf: &mut [u8]) -> Result<usize, T
             speed_mps,
             sats,
         } => {
-            // Convert from our units to CRSF units
             // lat/lon: already in 1e7 degrees
-            // speed: m/s -> 0.01 km/h (multiply by 360)
-            // alt: meters -> with 1000m offset
-            let groundspeed = (*speed_mps as u16) * 360;
-            let altitude = (*alt_m as u16).saturating_add(1000);
+            let groundspeed = speed_to_crsf(*speed_mps);
+            let altitude = altitude_to_crsf(*alt_m);
             let packet = Gps::new(*lat, *lon, groundspeed, 0, altitude, *sats)
                 .map_err(|_| TelemetryError::Io)?;
             write_packet_to_buffer(buf, PacketAddress::FlightController, &packet)
@@ -57,19 +55,388 @@ pub fn encode_telemetry(data: &TelemetryData, buf: &mut [u8]) -> Result<usize, T
         }
 
         TelemetryData::Attitude { roll, pitch, yaw } => {
-            // Convert from degrees*100 to radians*10000
-            // degrees*100 -> radians*10000: multiply by (pi/180) * 100 = ~1.745
-            let roll_rad = ((*roll as i32) * 1745 / 1000) as i16;
-            let pitch_rad = ((*pitch as i32) * 1745 / 1000) as i16;
-            let yaw_rad = ((*yaw as i32) * 1745 / 1000) as i16;
+            let roll_rad = angle_to_crsf(*roll);
+            let pitch_rad = angle_to_crsf(*pitch);
+            let yaw_rad = angle_to_crsf(*yaw);
             let packet = Attitude::new(roll_rad, pitch_rad, yaw_rad).map_err(|_| TelemetryError::Io)?;
             write_packet_to_buffer(buf, PacketAddress::FlightController, &packet)
                 .map_err(|_| TelemetryError::BufferFull)
         }
 
-        TelemetryData::LinkQuality { .. } => Err(TelemetryError::NotSupported),
+        TelemetryData::Temperature { temp_decidegrees } => {
+            // Source 0 with a single temperature value
+            let packet = Temp::new(0, &[*temp_decidegrees]).map_err(|_| TelemetryError::Io)?;
+            write_packet_to_buffer(buf, PacketAddress::FlightController, &packet)
+                .map_err(|_| TelemetryError::BufferFull)
+        }
+
+        TelemetryData::LinkQuality { rssi, snr, lq } => {
+            // Uplink only, both antennas report the same RSSI
+            let rssi = (*rssi).min(0).unsigned_abs();
+            let packet = LinkStatistics::new(rssi, rssi, (*lq).min(100), *snr, 0, 0, 0, 0, 0, 0)
+                .map_err(|_| TelemetryError::Io)?;
+            write_packet_to_buffer(buf, PacketAddress::FlightController, &packet)
+                .map_err(|_| TelemetryError::BufferFull)
+        }
+    }
+}
+
+/// [`TelemetryEncoder`] producing CRSF frames via [`encode_telemetry`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrsfTelemetryEncoder;
+
+impl TelemetryEncoder for CrsfTelemetryEncoder {
+    fn encode(&mut self, data: &TelemetryData, buf: &mut [u8]) -> Result<usize, TelemetryError> {
+        encode_telemetry(data, buf)
+    }
+}
+
+/// [`TelemetrySink`] writing CRSF frames to a blocking [`embedded_io::Write`].
+///
+/// Wraps [`encode_telemetry`] so CRSF telemetry drops into the same pump as
+/// other sinks. On a full-duplex link ([`new`](Self::new)) every frame is
+/// written immediately.
+///
+/// On a half-duplex (single-wire) link the receiver only listens right after
+/// it sent an RC frame, so [`half_duplex`](Self::half_duplex) sinks write one
+/// frame per [`rc_frame_received`](Self::rc_frame_received) call. Outside of
+/// that slot, [`send_telemetry`](TelemetrySink::send_telemetry) returns
+/// [`TelemetryError::BufferFull`] without writing, and the caller retries
+/// after the next RC frame.
+#[cfg(feature = "embedded-io")]
+pub struct CrsfTelemetrySink<W> {
+    writer: W,
+    /// Only transmit in the slot after an RC frame
+    half_duplex: bool,
+    /// Whether the current slot is still unused
+    slot_open: bool,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> CrsfTelemetrySink<W> {
+    /// Create a sink for a full-duplex link, writing every frame immediately.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            half_duplex: false,
+            slot_open: false,
+        }
+    }
+
+    /// Create a sink for a half-duplex link, writing only after RC frames.
+    #[must_use]
+    pub fn half_duplex(writer: W) -> Self {
+        Self {
+            writer,
+            half_duplex: true,
+            slot_open: false,
+        }
+    }
+
+    /// Open the telemetry slot after an RC channels frame was received.
+    pub fn rc_frame_received(&mut self) {
+        self.slot_open = true;
+    }
+
+    /// Returns true if a frame may be written now.
+    #[inline]
+    #[must_use]
+    pub fn can_send(&self) -> bool {
+        !self.half_duplex || self.slot_open
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume the sink and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> TelemetrySink for CrsfTelemetrySink<W> {
+    async fn send_telemetry(&mut self, data: &TelemetryData) -> Result<(), TelemetryError> {
+        if !self.can_send() {
+            return Err(TelemetryError::BufferFull);
+        }
+
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(data, &mut buf)?;
+        self.slot_open = false;
+        self.writer
+            .write_all(&buf[..len])
+            .map_err(|_| TelemetryError::Io)
+    }
+
+    fn supports_telemetry(&self) -> bool {
+        true
     }
 }
 
 /// Maximum size for a CRSF telemetry frame.
 pub const MAX_TELEMETRY_FRAME_SIZE: usize = 64;
+
+/// Offset added to altitude in CRSF GPS frames (meters).
+const CRSF_ALTITUDE_OFFSET: i32 = 1000;
+
+/// Clamp an `i32` into the `i16` range.
+#[inline]
+const fn saturate_i16(value: i32) -> i16 {
+    if value > i16::MAX as i32 {
+        i16::MAX
+    } else if value < i16::MIN as i32 {
+        i16::MIN
+    } else {
+        value as i16
+    }
+}
+
+/// Convert battery voltage from mV to CRSF units (10 mV), saturating.
+#[inline]
+#[must_use]
+pub const fn voltage_to_crsf(voltage_mv: u16) -> i16 {
+    saturate_i16((voltage_mv / 10) as i32)
+}
+
+/// Convert battery current from mA to CRSF units (10 mA), saturating.
+#[inline]
+#[must_use]
+pub const fn current_to_crsf(current_ma: u16) -> i16 {
+    saturate_i16((current_ma / 10) as i32)
+}
+
+/// Convert ground speed from m/s to CRSF units (0.01 km/h), saturating.
+#[inline]
+#[must_use]
+pub const fn speed_to_crsf(speed_mps: u8) -> u16 {
+    (speed_mps as u16).saturating_mul(360)
+}
+
+/// Convert altitude in meters to CRSF units (meters + 1000).
+///
+/// Altitudes below -1000 m clamp to 0 instead of wrapping.
+#[inline]
+#[must_use]
+pub const fn altitude_to_crsf(alt_m: i16) -> u16 {
+    let altitude = alt_m as i32 + CRSF_ALTITUDE_OFFSET;
+    if altitude < 0 {
+        0
+    } else if altitude > u16::MAX as i32 {
+        u16::MAX
+    } else {
+        altitude as u16
+    }
+}
+
+/// Convert an angle from degrees * 100 to CRSF units (radians * 10000), saturating.
+///
+/// The factor is (pi / 180) * 100 = ~1.745, so angles beyond ~±187.8 degrees
+/// clamp at the `i16` limits.
+#[inline]
+#[must_use]
+pub const fn angle_to_crsf(centidegrees: i16) -> i16 {
+    saturate_i16(centidegrees as i32 * 1745 / 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CrsfParser, LinkStats, Packet};
+
+    #[test]
+    fn test_voltage_to_crsf() {
+        assert_eq!(voltage_to_crsf(0), 0);
+        assert_eq!(voltage_to_crsf(16_800), 1680);
+        assert_eq!(voltage_to_crsf(u16::MAX), 6553);
+    }
+
+    #[test]
+    fn test_current_to_crsf() {
+        assert_eq!(current_to_crsf(0), 0);
+        assert_eq!(current_to_crsf(12_345), 1234);
+        assert_eq!(current_to_crsf(u16::MAX), 6553);
+    }
+
+    #[test]
+    fn test_speed_to_crsf_saturates() {
+        assert_eq!(speed_to_crsf(0), 0);
+        assert_eq!(speed_to_crsf(10), 3600);
+        assert_eq!(speed_to_crsf(182), 65_520);
+        assert_eq!(speed_to_crsf(183), u16::MAX);
+        assert_eq!(speed_to_crsf(u8::MAX), u16::MAX);
+    }
+
+    #[test]
+    fn test_altitude_to_crsf_negative() {
+        assert_eq!(altitude_to_crsf(0), 1000);
+        assert_eq!(altitude_to_crsf(-50), 950);
+        assert_eq!(altitude_to_crsf(-1000), 0);
+        assert_eq!(altitude_to_crsf(i16::MIN), 0);
+        assert_eq!(altitude_to_crsf(i16::MAX), 33_767);
+    }
+
+    #[test]
+    fn test_angle_to_crsf_saturates() {
+        assert_eq!(angle_to_crsf(0), 0);
+        // 90 degrees = ~1.5707 rad
+        assert_eq!(angle_to_crsf(9000), 15_705);
+        assert_eq!(angle_to_crsf(-9000), -15_705);
+        assert_eq!(angle_to_crsf(18_000), 31_410);
+        assert_eq!(angle_to_crsf(i16::MAX), i16::MAX);
+        assert_eq!(angle_to_crsf(i16::MIN), i16::MIN);
+    }
+
+    #[test]
+    fn test_crsf_encoder_battery() {
+        let data = TelemetryData::Battery {
+            voltage_mv: 16_800,
+            current_ma: 12_345,
+            remaining_pct: 75,
+        };
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let encoder: &mut dyn TelemetryEncoder = &mut CrsfTelemetryEncoder;
+        let len = encoder.encode(&data, &mut buf).unwrap();
+
+        let mut direct = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        assert_eq!(len, encode_telemetry(&data, &mut direct).unwrap());
+        assert_eq!(buf, direct);
+        // Battery sensor frame with big-endian voltage
+        assert_eq!(buf[2], 0x08);
+        assert_eq!(&buf[3..5], &1680i16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_temperature_frame_round_trip() {
+        let data = TelemetryData::Temperature {
+            temp_decidegrees: -125,
+        };
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&data, &mut buf).unwrap();
+        assert_eq!(buf[0], PacketAddress::FlightController as u8);
+
+        let mut parser = CrsfParser::new();
+        let packet = buf[..len]
+            .iter()
+            .find_map(|&b| parser.push_byte(b).unwrap());
+        assert_eq!(packet, Some(Packet::Temp(Temp::new(0, &[-125]).unwrap())));
+
+        assert_eq!(
+            encode_telemetry(&data, &mut buf[..6]),
+            Err(TelemetryError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_link_quality_frame() {
+        let data = TelemetryData::LinkQuality {
+            rssi: -70,
+            snr: -5,
+            lq: 98,
+        };
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&data, &mut buf).unwrap();
+        assert_eq!(len, LinkStats::PAYLOAD_SIZE + 4);
+
+        let mut parser = CrsfParser::new();
+        let packet = buf[..len]
+            .iter()
+            .find_map(|&b| parser.push_byte(b).unwrap());
+        let Some(Packet::LinkStatistics(packet)) = packet else {
+            panic!("expected link statistics");
+        };
+        let stats = LinkStats::from(&packet);
+        assert_eq!(stats.rssi_dbm(), -70);
+        assert_eq!(stats.uplink_link_quality, 98);
+        assert_eq!(stats.uplink_snr, -5);
+        assert_eq!(stats.downlink_link_quality, 0);
+    }
+
+    #[cfg(feature = "embedded-io")]
+    mod sink {
+        extern crate std;
+
+        use super::*;
+        use crate::test_support::block_on;
+        use std::vec::Vec;
+
+        struct VecWriter(Vec<u8>);
+
+        impl embedded_io::ErrorType for VecWriter {
+            type Error = core::convert::Infallible;
+        }
+
+        impl embedded_io::Write for VecWriter {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                self.0.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        const BATTERY: TelemetryData = TelemetryData::Battery {
+            voltage_mv: 16_800,
+            current_ma: 12_345,
+            remaining_pct: 75,
+        };
+
+        const ATTITUDE: TelemetryData = TelemetryData::Attitude {
+            roll: 1000,
+            pitch: -500,
+            yaw: 9000,
+        };
+
+        fn encoded(data: &TelemetryData) -> Vec<u8> {
+            let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+            let len = encode_telemetry(data, &mut buf).unwrap();
+            buf[..len].to_vec()
+        }
+
+        #[test]
+        fn test_sink_writes_encoded_frames() {
+            let mut sink = CrsfTelemetrySink::new(VecWriter(Vec::new()));
+            assert!(sink.supports_telemetry());
+
+            assert_eq!(block_on(sink.send_telemetry(&BATTERY)), Ok(()));
+            assert_eq!(block_on(sink.send_telemetry(&ATTITUDE)), Ok(()));
+            let lq = TelemetryData::LinkQuality {
+                rssi: -70,
+                snr: 10,
+                lq: 100,
+            };
+            assert_eq!(block_on(sink.send_telemetry(&lq)), Ok(()));
+
+            let expected = [encoded(&BATTERY), encoded(&ATTITUDE), encoded(&lq)].concat();
+            assert_eq!(sink.into_inner().0, expected);
+        }
+
+        #[test]
+        fn test_half_duplex_sends_once_per_rc_frame() {
+            let mut sink = CrsfTelemetrySink::half_duplex(VecWriter(Vec::new()));
+            assert!(!sink.can_send());
+            assert_eq!(
+                block_on(sink.send_telemetry(&BATTERY)),
+                Err(TelemetryError::BufferFull)
+            );
+
+            sink.rc_frame_received();
+            assert_eq!(block_on(sink.send_telemetry(&BATTERY)), Ok(()));
+            assert_eq!(
+                block_on(sink.send_telemetry(&ATTITUDE)),
+                Err(TelemetryError::BufferFull)
+            );
+
+            sink.rc_frame_received();
+            assert_eq!(block_on(sink.send_telemetry(&ATTITUDE)), Ok(()));
+
+            let expected = [encoded(&BATTERY), encoded(&ATTITUDE)].concat();
+            assert_eq!(sink.into_inner().0, expected);
+        }
+    }
+}
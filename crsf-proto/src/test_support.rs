@@ -0,0 +1,65 @@
+//! Helpers shared by the unit tests.
+
+use crc::{Algorithm, Crc, CRC_8_DVB_S2};
+
+/// CRSF frame CRC, over type and payload.
+const FRAME_CRC: Crc<u8> = Crc::<u8>::new(&CRC_8_DVB_S2);
+
+/// Inner CRC of command frames, over type and command payload.
+const COMMAND_CRC: Crc<u8> = Crc::<u8>::new(&Algorithm {
+    width: 8,
+    poly: 0xBA,
+    init: 0x00,
+    refin: false,
+    refout: false,
+    xorout: 0x00,
+    check: 0x00,
+    residue: 0x00,
+});
+
+/// Build a frame addressed to the flight controller.
+pub fn frame(frame_type: u8, payload: &[u8]) -> heapless::Vec<u8, 64> {
+    let mut out = heapless::Vec::new();
+    out.push(0xC8).unwrap();
+    out.push(payload.len() as u8 + 2).unwrap();
+    out.push(frame_type).unwrap();
+    out.extend_from_slice(payload).unwrap();
+    let crc = FRAME_CRC.checksum(&out[2..]);
+    out.push(crc).unwrap();
+    out
+}
+
+/// Build a command frame (0x32), appending the inner command CRC to
+/// `payload` (`[dest] [origin] [command] [sub_command] [data..]`).
+pub fn command_frame(payload: &[u8]) -> heapless::Vec<u8, 64> {
+    let mut digest = COMMAND_CRC.digest();
+    digest.update(&[crate::CRSF_FRAME_TYPE_COMMAND]);
+    digest.update(payload);
+
+    let mut body: heapless::Vec<u8, 64> = heapless::Vec::new();
+    body.extend_from_slice(payload).unwrap();
+    body.push(digest.finalize()).unwrap();
+    frame(crate::CRSF_FRAME_TYPE_COMMAND, &body)
+}
+
+/// Run a future that is ready on its first poll (the test sinks never wait).
+#[cfg(feature = "embedded-io")]
+pub fn block_on<F: core::future::Future>(f: F) -> F::Output {
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_raw_waker() -> RawWaker {
+        fn noop(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    match core::pin::pin!(f).poll(&mut cx) {
+        Poll::Ready(result) => result,
+        Poll::Pending => panic!("Sink future returned Pending unexpectedly"),
+    }
+}
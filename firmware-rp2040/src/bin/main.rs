@@ -1,30 +1,82 @@
 #![no_std]
 #![no_main]
 
-use defmt::{error, info};
+use defmt::{error, info, warn};
 use defmt_rtt as _;
-use embassy_executor::Spawner;
+use embassy_executor::{SpawnError, Spawner};
 use embassy_rp::bind_interrupts;
-use embassy_rp::gpio::{Level, Output};
+use embassy_rp::gpio::{Input, Level, Output, Pull};
 use embassy_rp::peripherals::{UART1, USB};
-use embassy_rp::uart::{Config as UartConfig, Uart};
+use embassy_rp::uart::{
+    Config as UartConfig, DataBits, Parity as UartParity, StopBits as UartStopBits, Uart,
+};
 use embassy_rp::usb::Driver;
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
 use embassy_sync::signal::Signal;
+use embassy_time::Instant;
 use embassy_usb::class::hid::State;
 use embassy_usb::{Builder, Config as UsbConfig};
 use static_cell::StaticCell;
-use uart_to_gamepad_rp2040::{configure_usb_hid, GamepadState, InputSource, OutputSink, UsbHidOutput};
-
-#[cfg(feature = "proto-gamepad")]
+use uart_to_gamepad_rp2040::{
+    active_protocol, active_uart_params, diagnostics, GamepadState, HidProfile, IdleDetector,
+    InputSource, OutputSink, Parity, StopBits, UartParams,
+};
+
+#[cfg(not(feature = "mouse"))]
+use uart_to_gamepad_rp2040::{
+    configure_usb_hid, GamepadHidWriter, GamepadRequestHandler, UsbHidOutput,
+};
+#[cfg(all(
+    not(feature = "mouse"),
+    not(feature = "usb-keepalive"),
+    not(feature = "latency-probe")
+))]
+use uart_to_gamepad_rp2040::{ChangeFilter, CoalescingOutput};
+
+use embassy_futures::select::{select, Either};
+#[cfg(feature = "mouse")]
+use embassy_time::{Duration, Ticker};
+#[cfg(feature = "mouse")]
+use embassy_usb::class::hid::HidWriter;
+#[cfg(feature = "mouse")]
+use uart_to_gamepad_rp2040::{configure_usb_mouse, MouseOutput, DEFAULT_MOUSE_MAPPING};
+
+#[cfg(all(feature = "proto-gamepad", not(feature = "usb-cdc-input")))]
 use uart_to_gamepad_rp2040::UartInputSource;
 
+#[cfg(feature = "usb-cdc-input")]
+use embassy_usb::class::cdc_acm::{CdcAcmClass, State as CdcState};
+#[cfg(feature = "usb-cdc-input")]
+use uart_to_gamepad_rp2040::{CdcInputSource, UsbClass, CDC_PACKET_SIZE, USB_CLASS_ORDER};
+
 #[cfg(feature = "proto-crsf")]
-use uart_to_gamepad_rp2040::CrsfInputSource;
+use embassy_rp::adc::{Adc, Blocking, Channel as AdcPin, Config as AdcConfig};
+#[cfg(any(feature = "proto-crsf", not(feature = "mouse")))]
+use embassy_time::Timer;
+#[cfg(feature = "usb-keepalive")]
+use uart_to_gamepad_rp2040::KeepaliveOutput;
+#[cfg(feature = "proto-crsf")]
+use uart_to_gamepad_rp2040::{
+    CrsfBidirectionalSource, DetectionState, ReceiverDetector, TelemetryData, TelemetryKind,
+    TelemetryScheduler,
+};
+
+#[cfg(feature = "latency-probe")]
+use uart_to_gamepad_rp2040::{Buttons, LatencyProbe};
 
 #[cfg(feature = "proto-mavlink")]
 use uart_to_gamepad_rp2040::MavlinkInputSource;
 
+#[cfg(feature = "proto-ppm")]
+use uart_to_gamepad_rp2040::PpmInputSource;
+
+#[cfg(feature = "proto-adc")]
+use embassy_rp::adc::{Adc, Channel as AdcPin, Config as AdcConfig};
+#[cfg(feature = "proto-adc")]
+use uart_to_gamepad_rp2040::{
+    AdcCalibration, AdcChannel, AdcTarget, RpAdcInputSource, RpAdcReader, StickAxis,
+};
+
 #[cfg(feature = "dev-panic")]
 use panic_probe as _;
 #[cfg(feature = "prod-panic")]
@@ -33,6 +85,8 @@ use panic_reset as _;
 bind_interrupts!(struct Irqs {
     UART1_IRQ => embassy_rp::uart::InterruptHandler<UART1>;
     USBCTRL_IRQ => embassy_rp::usb::InterruptHandler<USB>;
+    #[cfg(feature = "proto-adc")]
+    ADC_IRQ_FIFO => embassy_rp::adc::InterruptHandler;
 });
 
 /// Signal for passing gamepad state from input to output task.
@@ -49,9 +103,23 @@ static CONTROL_BUF: StaticCell<[u8; 64]> = StaticCell::new();
 /// HID state.
 static HID_STATE: StaticCell<State> = StaticCell::new();
 
+/// HID request handler, receives rumble output reports.
+#[cfg(not(feature = "mouse"))]
+static HID_HANDLER: StaticCell<GamepadRequestHandler> = StaticCell::new();
+
+/// CDC-ACM state for text protocol input over USB serial.
+#[cfg(feature = "usb-cdc-input")]
+static CDC_STATE: StaticCell<CdcState> = StaticCell::new();
+
+/// Text protocol input: USB serial with `usb-cdc-input`, UART otherwise.
+#[cfg(all(feature = "proto-gamepad", not(feature = "usb-cdc-input")))]
+type TextInputSource = UartInputSource<'static>;
+#[cfg(feature = "usb-cdc-input")]
+type TextInputSource = CdcInputSource<'static>;
+
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
-    info!("UART-to-Gamepad starting...");
+    info!("UART-to-Gamepad starting ({} input)...", active_protocol());
 
     let p = embassy_rp::init(embassy_rp::config::Config::default());
 
@@ -59,16 +127,8 @@ async fn main(spawner: Spawner) {
     let signal = STATE_SIGNAL.init(Signal::new());
 
     // --- UART Setup ---
-    // Baud rate depends on protocol
-    #[cfg(feature = "proto-gamepad")]
-    const UART_BAUDRATE: u32 = 115_200;
-    #[cfg(feature = "proto-crsf")]
-    const UART_BAUDRATE: u32 = 420_000;
-    #[cfg(feature = "proto-mavlink")]
-    const UART_BAUDRATE: u32 = 115_200;
-
-    let mut uart_config = UartConfig::default();
-    uart_config.baudrate = UART_BAUDRATE;
+    // Baud rate and framing depend on protocol
+    let uart_config = uart_config(active_uart_params());
 
     let uart = Uart::new(
         p.UART1,
@@ -81,21 +141,54 @@ async fn main(spawner: Spawner) {
     );
 
     // Create protocol-specific input source
-    #[cfg(feature = "proto-gamepad")]
-    let input_source = {
+    #[cfg(all(feature = "proto-gamepad", not(feature = "usb-cdc-input")))]
+    let input_source: TextInputSource = {
         let (_tx, rx) = uart.split();
         UartInputSource::new(rx)
     };
+    // CRSF keeps the full UART to send telemetry back to the receiver
     #[cfg(feature = "proto-crsf")]
-    let input_source = {
-        let (_tx, rx) = uart.split();
-        CrsfInputSource::new(rx)
+    let input_source = CrsfBidirectionalSource::new(uart);
+    #[cfg(feature = "proto-crsf")]
+    let temp_sensor = TempSensor {
+        adc: Adc::new_blocking(p.ADC, AdcConfig::default()),
+        channel: AdcPin::new_temp_sensor(p.ADC_TEMP_SENSOR),
     };
     #[cfg(feature = "proto-mavlink")]
     let input_source = {
         let (_tx, rx) = uart.split();
         MavlinkInputSource::new(rx)
     };
+    #[cfg(feature = "proto-ppm")]
+    let input_source = {
+        let _ = uart;
+        PpmInputSource::new(Input::new(p.PIN_2, Pull::Down))
+    };
+    #[cfg(feature = "proto-adc")]
+    let input_source = {
+        let _ = uart;
+        let adc = Adc::new(p.ADC, Irqs, AdcConfig::default());
+        let pins = [
+            AdcPin::new_pin(p.PIN_26, Pull::None),
+            AdcPin::new_pin(p.PIN_27, Pull::None),
+            AdcPin::new_pin(p.PIN_28, Pull::None),
+        ];
+        RpAdcReader::input_source(adc, pins, ADC_CHANNELS, ADC_SAMPLE_INTERVAL)
+    };
+    // Input arrives over USB serial instead (created with the USB classes)
+    #[cfg(feature = "usb-cdc-input")]
+    let _ = uart;
+
+    // --- HID profile ---
+    // Jumpers to GND select the profile at boot: GPIO 4 XInput, GPIO 5 DInput
+    let hid_profile = {
+        let xinput = Input::new(p.PIN_4, Pull::Up);
+        let dinput = Input::new(p.PIN_5, Pull::Up);
+        // Let the pull-ups charge the pins before sampling
+        embassy_time::block_for(embassy_time::Duration::from_micros(10));
+        HidProfile::from_jumpers(xinput.is_low(), dinput.is_low())
+    };
+    info!("HID profile: {}", hid_profile);
 
     // --- USB Setup ---
     let usb_driver = Driver::new(p.USB, Irqs);
@@ -106,6 +199,14 @@ async fn main(spawner: Spawner) {
     usb_config.serial_number = Some("001");
     usb_config.max_power = 100;
     usb_config.max_packet_size_0 = 64;
+    // Composite CDC + HID device: interface association descriptors
+    #[cfg(feature = "usb-cdc-input")]
+    {
+        usb_config.device_class = 0xEF;
+        usb_config.device_sub_class = 0x02;
+        usb_config.device_protocol = 0x01;
+        usb_config.composite_with_iads = true;
+    }
 
     let config_descriptor = CONFIG_DESCRIPTOR.init([0; 256]);
     let bos_descriptor = BOS_DESCRIPTOR.init([0; 256]);
@@ -121,49 +222,166 @@ async fn main(spawner: Spawner) {
         control_buf,
     );
 
+    // Configure CDC-ACM and HID classes in the configured interface order
+    #[cfg(feature = "usb-cdc-input")]
+    let (input_source, hid_writer) = match USB_CLASS_ORDER[0] {
+        UsbClass::Hid => {
+            let hid_writer = configure_hid_class(&mut builder, hid_profile);
+            (configure_cdc_input(&mut builder), hid_writer)
+        }
+        UsbClass::Cdc => {
+            let input_source = configure_cdc_input(&mut builder);
+            (input_source, configure_hid_class(&mut builder, hid_profile))
+        }
+    };
+
     // Configure HID class
-    let hid_state = HID_STATE.init(State::new());
-    let hid_writer = configure_usb_hid(&mut builder, hid_state);
+    #[cfg(not(feature = "usb-cdc-input"))]
+    let hid_writer = configure_hid_class(&mut builder, hid_profile);
 
     // Build the USB device
     let usb_device = builder.build();
 
     // Create output
-    let usb_output = UsbHidOutput::new(hid_writer);
+    #[cfg(not(feature = "mouse"))]
+    let usb_output =
+        UsbHidOutput::new(hid_writer, hid_profile).with_report_deadzone(REPORT_DEADZONE);
+    #[cfg(feature = "mouse")]
+    let usb_output = MouseOutput::new(hid_writer, DEFAULT_MOUSE_MAPPING);
 
     // Optional: LED for error indication (on-board LED on Pico)
     let led = Output::new(p.PIN_25, Level::Low);
 
-    // Spawn tasks (unwrap the SpawnToken, then spawn)
-    spawner.spawn(usb_task(usb_device).unwrap());
-    spawner.spawn(input_task(input_source, signal, led).unwrap());
-    spawner.spawn(output_task(usb_output, signal).unwrap());
+    // Spawn tasks (take the SpawnToken, then spawn)
+    spawner.spawn(token(usb_task(usb_device)));
+    #[cfg(not(feature = "proto-crsf"))]
+    spawner.spawn(token(input_task(input_source, signal, led)));
+    #[cfg(feature = "proto-crsf")]
+    spawner.spawn(token(input_task(input_source, signal, led, temp_sensor)));
+    #[cfg(not(feature = "latency-probe"))]
+    spawner.spawn(token(output_task(usb_output, signal)));
+    #[cfg(feature = "latency-probe")]
+    spawner.spawn(token(output_task(
+        usb_output,
+        signal,
+        Output::new(p.PIN_3, Level::Low),
+    )));
 
     info!("UART-to-Gamepad initialized, waiting for data...");
 }
 
+/// Build the UART configuration from the protocol's line settings.
+fn uart_config(params: UartParams) -> UartConfig {
+    let mut config = UartConfig::default();
+    // Idle-low receivers: undo the line inversion in the GPIO pad, so the
+    // parser receives the normal byte stream
+    config.invert_rx = cfg!(feature = "uart-invert-rx");
+    config.baudrate = params.baud;
+    config.data_bits = DataBits::DataBits8;
+    config.parity = match params.parity {
+        Parity::None => UartParity::ParityNone,
+        Parity::Even => UartParity::ParityEven,
+        Parity::Odd => UartParity::ParityOdd,
+    };
+    config.stop_bits = match params.stop_bits {
+        StopBits::One => UartStopBits::STOP1,
+        StopBits::Two => UartStopBits::STOP2,
+    };
+    config
+}
+
+/// Take a task's SpawnToken, logging the last-known state if its pool is full.
+fn token<T>(token: Result<T, SpawnError>) -> T {
+    token.unwrap_or_else(|_| diagnostics::fatal("task pool exhausted"))
+}
+
+/// USB device builder for the RP2040 driver.
+type UsbBuilder = Builder<'static, Driver<'static, USB>>;
+
+/// Add the HID gamepad class with the `profile` descriptor to the USB device.
+#[cfg(not(feature = "mouse"))]
+fn configure_hid_class(builder: &mut UsbBuilder, profile: HidProfile) -> GamepadHidWriter<'static> {
+    configure_usb_hid(
+        builder,
+        HID_STATE.init(State::new()),
+        HID_HANDLER.init(GamepadRequestHandler),
+        profile,
+    )
+}
+
+/// Add the HID mouse class to the USB device (the mouse has no profiles).
+#[cfg(feature = "mouse")]
+fn configure_hid_class(
+    builder: &mut UsbBuilder,
+    _profile: HidProfile,
+) -> HidWriter<'static, Driver<'static, USB>, 8> {
+    configure_usb_mouse(builder, HID_STATE.init(State::new()))
+}
+
+/// Add the CDC-ACM class to the USB device and wrap its receive half.
+#[cfg(feature = "usb-cdc-input")]
+fn configure_cdc_input(builder: &mut UsbBuilder) -> TextInputSource {
+    let cdc_state = CDC_STATE.init(CdcState::new());
+    let class = CdcAcmClass::new(builder, cdc_state, CDC_PACKET_SIZE as u16);
+    let (_tx, rx) = class.split();
+    CdcInputSource::new(rx)
+}
+
+/// Stick deadzone applied to the HID report only, in i8 report units (0 = off).
+#[cfg(not(feature = "mouse"))]
+const REPORT_DEADZONE: u8 = 0;
+
+/// Unchanged input duration after which the LED shows the idle pattern.
+const IDLE_TIMEOUT_MS: u64 = 30_000;
+
+/// LED blink half-period while the input is idle.
+const IDLE_BLINK_MS: u64 = 1_000;
+
+/// Track input activity and drive the idle LED pattern.
+///
+/// The LED blinks slowly while the input is idle and is switched off on the
+/// first change, so error toggles stay visible during normal operation.
+fn update_idle_led(idle: &mut IdleDetector, state: &GamepadState, led: &mut Output<'static>) {
+    let now = Instant::now().as_millis();
+    let was_idle = idle.is_idle(now);
+
+    if idle.update(state, now) {
+        if was_idle {
+            info!("Input active");
+            led.set_low();
+        }
+    } else if idle.is_idle(now) {
+        led.set_level(Level::from((now / IDLE_BLINK_MS) % 2 == 0));
+    }
+}
+
 /// USB device task - runs the USB stack.
 #[embassy_executor::task]
 async fn usb_task(mut device: embassy_usb::UsbDevice<'static, Driver<'static, USB>>) {
     device.run().await;
 }
 
-/// Input task - reads from UART and signals the latest gamepad state.
+/// Input task - reads text protocol lines and signals the latest gamepad state.
 #[cfg(feature = "proto-gamepad")]
 #[embassy_executor::task]
 async fn input_task(
-    mut input: UartInputSource<'static>,
+    mut input: TextInputSource,
     signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
     mut led: Output<'static>,
 ) {
+    let mut idle = IdleDetector::new(IDLE_TIMEOUT_MS);
+
     loop {
         match input.receive().await {
             Ok(state) => {
+                diagnostics::record_state(&state);
+                update_idle_led(&mut idle, &state, &mut led);
                 // Signal the latest gamepad state (overwrites any pending value)
                 signal.signal(state);
             }
             Err(e) => {
-                error!("Input error: {:?}", e);
+                diagnostics::record_input_error(e);
+                error!("Input error: {:?} (overruns: {})", e, input.overrun_count());
                 // Signal neutral state on error to prevent stale inputs
                 signal.signal(GamepadState::neutral());
                 // Toggle LED to indicate error
@@ -173,20 +391,132 @@ async fn input_task(
     }
 }
 
+/// Time allowed for the first CRSF frame before the LED reports a failure.
+#[cfg(feature = "proto-crsf")]
+const RECEIVER_DETECT_TIMEOUT_MS: u64 = 3_000;
+
+/// LED update interval during receiver detection.
+#[cfg(feature = "proto-crsf")]
+const DETECT_LED_TICK_MS: u64 = 20;
+
+/// Wait for the first CRSF RC frame, driving the detection LED pattern.
+///
+/// Keeps listening after the timeout (showing the failed pattern), so a
+/// receiver that comes up late is still picked up. Returns the first state.
+#[cfg(feature = "proto-crsf")]
+async fn detect_receiver(
+    input: &mut CrsfBidirectionalSource<'static>,
+    led: &mut Output<'static>,
+) -> GamepadState {
+    let mut detector =
+        ReceiverDetector::new(RECEIVER_DETECT_TIMEOUT_MS, Instant::now().as_millis());
+    info!("Searching for CRSF receiver...");
+
+    loop {
+        // `receive` is cancellation safe, so racing it against the LED tick is fine
+        match select(input.receive(), Timer::after_millis(DETECT_LED_TICK_MS)).await {
+            Either::First(Ok(state)) => {
+                detector.frame_received();
+                info!("CRSF receiver found");
+                led.set_level(Level::from(detector.led_on(Instant::now().as_millis())));
+                return state;
+            }
+            Either::First(Err(e)) => error!("CRSF input error: {:?}", e),
+            Either::Second(()) => {}
+        }
+
+        let now = Instant::now().as_millis();
+        let previous = detector.state();
+        if detector.poll(now) == DetectionState::Failed && previous != DetectionState::Failed {
+            warn!("No CRSF receiver detected - check wiring and baud rate");
+        }
+        led.set_level(Level::from(detector.led_on(now)));
+    }
+}
+
+/// Telemetry sent back to the receiver: only the die temperature is measured
+/// on the board, so it is the only kind scheduled.
+#[cfg(feature = "proto-crsf")]
+const TELEMETRY_SCHEDULE: TelemetryScheduler =
+    TelemetryScheduler::new().with_interval(TelemetryKind::Temperature, 2_000);
+
+/// On-chip temperature sensor, sent back to the receiver as telemetry.
+#[cfg(feature = "proto-crsf")]
+struct TempSensor {
+    adc: Adc<'static, Blocking>,
+    channel: AdcPin<'static>,
+}
+
+#[cfg(feature = "proto-crsf")]
+impl TempSensor {
+    /// Read the die temperature in deci-degrees Celsius.
+    fn read_decidegrees(&mut self) -> Option<i16> {
+        let raw = self.adc.blocking_read(&mut self.channel).ok()?;
+        // RP2040 datasheet: T = 27 - (V - 0.706) / 0.001721, 12-bit ADC at 3.3 V
+        let microvolts = i32::from(raw) * 3_300_000 / 4096;
+        Some((270 - (microvolts - 706_000) * 10 / 1721) as i16)
+    }
+
+    /// Latest value of a telemetry kind (only temperature is measured here).
+    fn latest(&mut self, kind: TelemetryKind) -> Option<TelemetryData> {
+        match kind {
+            TelemetryKind::Temperature => Some(TelemetryData::Temperature {
+                temp_decidegrees: self.read_decidegrees()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Input task for CRSF protocol - reads CRSF frames and signals gamepad state.
+///
+/// Normal operation starts only after [`detect_receiver`] saw the first frame.
+/// Between frames, telemetry is sent back at the [`TelemetryScheduler`] rates.
 #[cfg(feature = "proto-crsf")]
 #[embassy_executor::task]
 async fn input_task(
-    mut input: CrsfInputSource<'static>,
+    mut input: CrsfBidirectionalSource<'static>,
     signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
     mut led: Output<'static>,
+    mut temp_sensor: TempSensor,
 ) {
+    let mut idle = IdleDetector::new(IDLE_TIMEOUT_MS);
+    let mut telemetry = TELEMETRY_SCHEDULE;
+
+    let first = detect_receiver(&mut input, &mut led).await;
+    diagnostics::record_state(&first);
+    update_idle_led(&mut idle, &first, &mut led);
+    signal.signal(first);
+
     loop {
-        match input.receive().await {
+        // `receive` is cancellation safe, so racing it against telemetry is fine
+        let due = telemetry
+            .next_due_ms()
+            .map_or(Instant::MAX, Instant::from_millis);
+        let result = match select(input.receive(), Timer::at(due)).await {
+            Either::First(result) => result,
+            Either::Second(()) => {
+                let now = Instant::now().as_millis();
+                if let Err(e) = input
+                    .send_scheduled_telemetry(&mut telemetry, now, |kind| temp_sensor.latest(kind))
+                    .await
+                {
+                    warn!("CRSF telemetry error: {:?}", e);
+                }
+                continue;
+            }
+        };
+        if let Some(event) = input.poll_event() {
+            info!("CRSF event: {:?}", event);
+        }
+        match result {
             Ok(state) => {
+                diagnostics::record_state(&state);
+                update_idle_led(&mut idle, &state, &mut led);
                 signal.signal(state);
             }
             Err(e) => {
+                diagnostics::record_input_error(e);
                 error!("CRSF input error: {:?}", e);
                 signal.signal(GamepadState::neutral());
                 led.toggle();
@@ -203,12 +533,17 @@ async fn input_task(
     signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
     mut led: Output<'static>,
 ) {
+    let mut idle = IdleDetector::new(IDLE_TIMEOUT_MS);
+
     loop {
         match input.receive().await {
             Ok(state) => {
+                diagnostics::record_state(&state);
+                update_idle_led(&mut idle, &state, &mut led);
                 signal.signal(state);
             }
             Err(e) => {
+                diagnostics::record_input_error(e);
                 error!("MAVLink input error: {:?}", e);
                 signal.signal(GamepadState::neutral());
                 led.toggle();
@@ -217,7 +552,101 @@ async fn input_task(
     }
 }
 
+/// Input task for PPM - decodes PPM frames and signals gamepad state.
+#[cfg(feature = "proto-ppm")]
+#[embassy_executor::task]
+async fn input_task(
+    mut input: PpmInputSource<'static>,
+    signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
+    mut led: Output<'static>,
+) {
+    let mut idle = IdleDetector::new(IDLE_TIMEOUT_MS);
+
+    loop {
+        match input.receive().await {
+            Ok(state) => {
+                diagnostics::record_state(&state);
+                update_idle_led(&mut idle, &state, &mut led);
+                signal.signal(state);
+            }
+            Err(e) => {
+                diagnostics::record_input_error(e);
+                error!("PPM input error: {:?}", e);
+                signal.signal(GamepadState::neutral());
+                led.toggle();
+            }
+        }
+    }
+}
+
+/// ADC channel bindings, in GPIO 26, 27, 28 order.
+///
+/// Replace the calibrations with readings measured on the actual joystick.
+#[cfg(feature = "proto-adc")]
+const ADC_CHANNELS: [AdcChannel; 3] = [
+    AdcChannel::new(
+        AdcTarget::Stick(StickAxis::LeftX),
+        AdcCalibration::FULL_12BIT,
+    ),
+    AdcChannel::new(
+        AdcTarget::Stick(StickAxis::LeftY),
+        AdcCalibration::FULL_12BIT,
+    ),
+    AdcChannel::new(AdcTarget::LeftTrigger, AdcCalibration::FULL_12BIT),
+];
+
+/// ADC sampling interval (250 Hz).
+#[cfg(feature = "proto-adc")]
+const ADC_SAMPLE_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_millis(4);
+
+/// Input task for analog joysticks - samples the ADC and signals gamepad state.
+#[cfg(feature = "proto-adc")]
+#[embassy_executor::task]
+async fn input_task(
+    mut input: RpAdcInputSource<'static, 3>,
+    signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
+    mut led: Output<'static>,
+) {
+    let mut idle = IdleDetector::new(IDLE_TIMEOUT_MS);
+
+    loop {
+        match input.receive().await {
+            Ok(state) => {
+                diagnostics::record_state(&state);
+                update_idle_led(&mut idle, &state, &mut led);
+                signal.signal(state);
+            }
+            Err(e) => {
+                diagnostics::record_input_error(e);
+                error!("ADC input error: {:?}", e);
+                signal.signal(GamepadState::neutral());
+                led.toggle();
+            }
+        }
+    }
+}
+
+/// Time the output task collects states before sending the last one.
+///
+/// Absorbs bursts (e.g. several `U` messages for one change) into a single
+/// report at the cost of this much added latency. 0 sends every state as
+/// soon as it is signaled.
+#[cfg(all(
+    not(feature = "mouse"),
+    not(feature = "usb-keepalive"),
+    not(feature = "latency-probe")
+))]
+const COALESCE_WINDOW_MS: u64 = 0;
+
 /// Output task - waits for gamepad state signals and sends to USB HID.
+///
+/// States equal to the last report are dropped, so a sender repeating its
+/// state does not cost a USB report each time.
+#[cfg(all(
+    not(feature = "mouse"),
+    not(feature = "usb-keepalive"),
+    not(feature = "latency-probe")
+))]
 #[embassy_executor::task]
 async fn output_task(
     mut output: UsbHidOutput<'static>,
@@ -227,11 +656,141 @@ async fn output_task(
     output.wait_ready().await;
     info!("USB HID ready, forwarding gamepad state...");
 
+    let output = ChangeFilter::new(output);
+    let mut output =
+        CoalescingOutput::new(output, COALESCE_WINDOW_MS, || Instant::now().as_millis());
+
     loop {
-        // Wait for the next gamepad state (blocks until signaled)
-        let state = signal.wait().await;
-        if let Err(e) = output.send(&state).await {
+        let result = match output.next_deadline_ms() {
+            // Wait for the next gamepad state (blocks until signaled)
+            None => output.send(&signal.wait().await).await,
+            // Keep absorbing states until the window closes
+            Some(deadline) => {
+                match select(signal.wait(), Timer::at(Instant::from_millis(deadline))).await {
+                    Either::First(state) => output.send(&state).await,
+                    Either::Second(()) => output.flush().await.map(|_| ()),
+                }
+            }
+        };
+        if let Err(e) = result {
             error!("Output error: {:?}", e);
         }
     }
 }
+
+/// Sender button the latency marker output (GPIO 3) is wired to.
+#[cfg(feature = "latency-probe")]
+const LATENCY_MARKER_BUTTON: Buttons = Buttons(1 << 15);
+
+/// Marker round trips longer than this count as lost.
+#[cfg(feature = "latency-probe")]
+const LATENCY_TIMEOUT_US: u64 = 100_000;
+
+/// Number of latency samples between statistics log lines.
+#[cfg(feature = "latency-probe")]
+const LATENCY_LOG_SAMPLES: u32 = 100;
+
+/// Output task with latency probe - like the plain output task, but flips the
+/// marker output after each report and times its return as a button change.
+#[cfg(feature = "latency-probe")]
+#[embassy_executor::task]
+async fn output_task(
+    mut output: UsbHidOutput<'static>,
+    signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
+    mut marker: Output<'static>,
+) {
+    // Wait for USB to be ready
+    output.wait_ready().await;
+    info!("USB HID ready, forwarding gamepad state (latency probe on GPIO 3)...");
+
+    let mut probe = LatencyProbe::new(LATENCY_MARKER_BUTTON, LATENCY_TIMEOUT_US);
+
+    loop {
+        // Time out so a lost marker does not stall the probe
+        let timeout = Timer::after_micros(LATENCY_TIMEOUT_US);
+        if let Either::First(state) = select(signal.wait(), timeout).await {
+            let now_us = Instant::now().as_micros();
+            if probe.on_receive(&state, now_us).is_some() {
+                let stats = probe.stats();
+                if stats.samples() % LATENCY_LOG_SAMPLES == 0 {
+                    info!(
+                        "Loopback latency: min {} us, mean {} us, max {} us ({} lost)",
+                        stats.min_us().unwrap_or(0),
+                        stats.mean_us().unwrap_or(0),
+                        stats.max_us().unwrap_or(0),
+                        stats.lost()
+                    );
+                }
+            }
+            if let Err(e) = output.send(&state).await {
+                error!("Output error: {:?}", e);
+            }
+        }
+
+        if let Some(level) = probe.send_marker(Instant::now().as_micros()) {
+            marker.set_level(Level::from(level));
+        }
+    }
+}
+
+/// Longest time without a gamepad report before the current one is re-sent.
+#[cfg(feature = "usb-keepalive")]
+const KEEPALIVE_INTERVAL_MS: u64 = 1_000;
+
+/// Output task with keepalive - like the plain output task, but re-sends the
+/// current report whenever the host has not seen one for [`KEEPALIVE_INTERVAL_MS`].
+#[cfg(feature = "usb-keepalive")]
+#[embassy_executor::task]
+async fn output_task(
+    mut output: UsbHidOutput<'static>,
+    signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
+) {
+    // Wait for USB to be ready
+    output.wait_ready().await;
+    info!("USB HID ready, forwarding gamepad state (keepalive enabled)...");
+
+    let mut output =
+        KeepaliveOutput::new(output, KEEPALIVE_INTERVAL_MS, || Instant::now().as_millis());
+
+    loop {
+        let deadline = Instant::from_millis(output.next_keepalive_ms());
+        let result = match select(signal.wait(), Timer::at(deadline)).await {
+            Either::First(state) => output.send(&state).await,
+            Either::Second(()) => output.keepalive().await.map(|_| ()),
+        };
+        if let Err(e) = result {
+            error!("Output error: {:?}", e);
+        }
+    }
+}
+
+/// Mouse report interval; a held stick moves the pointer once per tick.
+#[cfg(feature = "mouse")]
+const MOUSE_TICK: Duration = Duration::from_millis(10);
+
+/// Output task for mouse mode - sends a mouse report for the latest state every tick.
+#[cfg(feature = "mouse")]
+#[embassy_executor::task]
+async fn output_task(
+    mut output: MouseOutput<'static>,
+    signal: &'static Signal<CriticalSectionRawMutex, GamepadState>,
+) {
+    // Wait for USB to be ready
+    output.wait_ready().await;
+    info!("USB HID mouse ready, forwarding right stick...");
+
+    let mut state = GamepadState::neutral();
+    let mut ticker = Ticker::every(MOUSE_TICK);
+
+    loop {
+        // Keep the latest state; a new state is reported on the next tick
+        match select(signal.wait(), ticker.next()).await {
+            Either::First(new_state) => state = new_state,
+            Either::Second(()) => {
+                if let Err(e) = output.send(&state).await {
+                    error!("Output error: {:?}", e);
+                }
+            }
+        }
+    }
+}
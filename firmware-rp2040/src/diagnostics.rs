@@ -0,0 +1,45 @@
+//! Last-known state for post-mortem logging.
+//!
+//! The input task records every state it receives and every input error.
+//! On an unrecoverable error, [`fatal`] logs them via defmt before panicking,
+//! so with `dev-panic` the RTT output ends with what the bridge last saw
+//! instead of only the panic location. The text itself is built by
+//! [`gamepad_core::diagnostic_dump`], which is tested on the host.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use gamepad_core::{diagnostic_dump, BridgeMetrics, GamepadState, InputError};
+
+/// Last received state and the input counters.
+///
+/// `frames_forwarded` counts states received by the input task.
+static LAST: Mutex<CriticalSectionRawMutex, Cell<(GamepadState, BridgeMetrics)>> =
+    Mutex::new(Cell::new((GamepadState::neutral(), BridgeMetrics::new())));
+
+/// Record a successfully received state.
+pub fn record_state(state: &GamepadState) {
+    LAST.lock(|last| {
+        let (_, mut metrics) = last.get();
+        metrics.frames_forwarded = metrics.frames_forwarded.saturating_add(1);
+        last.set((*state, metrics));
+    });
+}
+
+/// Record an input error.
+pub fn record_input_error(err: InputError) {
+    LAST.lock(|last| {
+        let (state, mut metrics) = last.get();
+        metrics.record_input_error(err);
+        last.set((state, metrics));
+    });
+}
+
+/// Log the last-known state and counters, then panic with `reason`.
+pub fn fatal(reason: &str) -> ! {
+    let (state, metrics) = LAST.lock(Cell::get);
+    defmt::error!("Fatal: {=str}", reason);
+    defmt::error!("{=str}", diagnostic_dump(&state, &metrics).as_str());
+    panic!("{}", reason)
+}
@@ -0,0 +1,57 @@
+//! Analog joystick input on the RP2040 ADC.
+//!
+//! Potentiometer joysticks are wired to the ADC pins (GPIO 26-28) and sampled
+//! at a fixed interval. The calibration and conversion to stick and trigger
+//! values live in [`gamepad_core::AdcInputSource`] and are tested on the host;
+//! this module only provides the [`AdcReader`] for the RP2040 peripheral.
+
+use embassy_rp::adc::{Adc, Async, Channel};
+use embassy_time::{Duration, Ticker};
+use gamepad_core::{AdcChannel, AdcInputSource, AdcReader, InputError};
+
+/// ADC input source sampling `N` channels of the RP2040 ADC.
+pub type RpAdcInputSource<'d, const N: usize> = AdcInputSource<RpAdcReader<'d, N>, N>;
+
+/// [`AdcReader`] for the RP2040 ADC, sampling all channels once per tick.
+pub struct RpAdcReader<'d, const N: usize> {
+    /// ADC peripheral.
+    adc: Adc<'d, Async>,
+    /// Pins in channel order.
+    pins: [Channel<'d>; N],
+    /// Sample pacing.
+    ticker: Ticker,
+}
+
+impl<'d, const N: usize> RpAdcReader<'d, N> {
+    /// Create a reader sampling `pins` every `interval`.
+    #[must_use]
+    pub fn new(adc: Adc<'d, Async>, pins: [Channel<'d>; N], interval: Duration) -> Self {
+        Self {
+            adc,
+            pins,
+            ticker: Ticker::every(interval),
+        }
+    }
+
+    /// Create an input source binding `pins` to `channels` (same order).
+    #[must_use]
+    pub fn input_source(
+        adc: Adc<'d, Async>,
+        pins: [Channel<'d>; N],
+        channels: [AdcChannel; N],
+        interval: Duration,
+    ) -> RpAdcInputSource<'d, N> {
+        AdcInputSource::new(Self::new(adc, pins, interval), channels)
+    }
+}
+
+impl<const N: usize> AdcReader for RpAdcReader<'_, N> {
+    async fn wait_sample(&mut self) {
+        self.ticker.next().await;
+    }
+
+    async fn read(&mut self, index: usize) -> Result<u16, InputError> {
+        let pin = self.pins.get_mut(index).ok_or(InputError::Io)?;
+        self.adc.read(pin).await.map_err(|_| InputError::Io)
+    }
+}
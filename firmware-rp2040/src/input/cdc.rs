@@ -0,0 +1,184 @@
+//! USB CDC-ACM (virtual serial port) input source.
+//!
+//! Receives the text protocol over the Pico's USB serial port instead of the
+//! UART, while the gamepad is reported on a second (HID) interface of the
+//! same composite device. Messages and parsing are identical to
+//! [`UartInputSource`](super::UartInputSource):
+//!
+//! Full state: `G<buttons>:<lx>:<ly>:<rx>:<ry>:<lt>:<rt>*<checksum>\n`
+//! Update: `U<field>:<value>*<checksum>\n`
+//!
+//! CDC delivers data in USB packets with no regard for line boundaries; the
+//! packets are split back into bytes by [`PacketByteReader`] and framed by the
+//! line accumulator in [`ReaderInputSource`].
+//!
+//! # Class order on Windows
+//!
+//! Windows loads its composite driver (usbccgp) for the device and binds a
+//! function driver per interface group, so the order in which the classes are
+//! declared is visible to the host. [`USB_CLASS_ORDER`] declares CDC first by
+//! default: the serial port is then interface 0, which older Windows versions
+//! need for `usbser.sys` to bind. Some games and input layers only probe
+//! interface 0 for a gamepad; the `usb-hid-first` feature declares HID first
+//! for those hosts. Changing the order changes the interface numbers, so
+//! Windows treats it as a new device and re-installs drivers once.
+
+use defmt::Format;
+use embassy_rp::peripherals::USB;
+use embassy_rp::usb::Driver;
+use embassy_usb::class::cdc_acm::Receiver;
+use embassy_usb::driver::EndpointError;
+use gamepad_core::{
+    GamepadState, InputError, InputSource, PacketByteReader, PacketReader, ReaderInputSource,
+    StickTrim,
+};
+
+/// CDC-ACM bulk endpoint packet size (full-speed USB).
+pub const CDC_PACKET_SIZE: usize = 64;
+
+/// A class of the composite CDC + HID device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Format)]
+pub enum UsbClass {
+    /// CDC-ACM serial port carrying the text protocol
+    Cdc,
+    /// HID gamepad (or mouse) output
+    Hid,
+}
+
+impl UsbClass {
+    /// Number of interfaces the class declares.
+    #[must_use]
+    pub const fn interface_count(self) -> u8 {
+        match self {
+            // Communication + data interface
+            UsbClass::Cdc => 2,
+            UsbClass::Hid => 1,
+        }
+    }
+}
+
+/// Order in which the composite classes are added to the USB builder.
+///
+/// CDC first by default, HID first with the `usb-hid-first` feature (see the
+/// [module docs](self)).
+pub const USB_CLASS_ORDER: [UsbClass; 2] = if cfg!(feature = "usb-hid-first") {
+    [UsbClass::Hid, UsbClass::Cdc]
+} else {
+    [UsbClass::Cdc, UsbClass::Hid]
+};
+
+/// First interface number `class` gets when the classes are declared in `order`.
+#[must_use]
+pub const fn first_interface(order: [UsbClass; 2], class: UsbClass) -> u8 {
+    let mut interface = 0;
+    let mut i = 0;
+    while i < order.len() {
+        if order[i] as u8 == class as u8 {
+            return interface;
+        }
+        interface += order[i].interface_count();
+        i += 1;
+    }
+    interface
+}
+
+// Interface numbers for each ordering
+const _: () = {
+    let cdc_first = [UsbClass::Cdc, UsbClass::Hid];
+    assert!(first_interface(cdc_first, UsbClass::Cdc) == 0);
+    assert!(first_interface(cdc_first, UsbClass::Hid) == 2);
+
+    let hid_first = [UsbClass::Hid, UsbClass::Cdc];
+    assert!(first_interface(hid_first, UsbClass::Hid) == 0);
+    assert!(first_interface(hid_first, UsbClass::Cdc) == 1);
+
+    assert!(USB_CLASS_ORDER[0] as u8 != USB_CLASS_ORDER[1] as u8);
+};
+
+/// [`PacketReader`] over the receive half of a CDC-ACM class.
+///
+/// Waits for the host to open the port (DTR) before reading, and again after
+/// a disconnect.
+pub struct CdcPacketReader<'d> {
+    rx: Receiver<'d, Driver<'d, USB>>,
+    connected: bool,
+}
+
+impl PacketReader for CdcPacketReader<'_> {
+    async fn read_packet(&mut self, buf: &mut [u8]) -> Result<usize, InputError> {
+        if !self.connected {
+            self.rx.wait_connection().await;
+            self.connected = true;
+        }
+
+        match self.rx.read_packet(buf).await {
+            Ok(len) => Ok(len),
+            Err(EndpointError::Disabled) => {
+                self.connected = false;
+                Err(InputError::Disconnected)
+            }
+            // Packet larger than the buffer: bytes were lost, resync
+            Err(EndpointError::BufferOverflow) => Err(InputError::BufferOverflow),
+        }
+    }
+}
+
+/// USB CDC-based input source for receiving gamepad state.
+///
+/// [`receive`](InputSource::receive) is cancellation safe, like
+/// [`UartInputSource`](super::UartInputSource).
+pub struct CdcInputSource<'d> {
+    inner: ReaderInputSource<PacketByteReader<CdcPacketReader<'d>, CDC_PACKET_SIZE>>,
+}
+
+impl<'d> CdcInputSource<'d> {
+    /// Create a new CDC input source from the receive half of a CDC-ACM class.
+    #[must_use]
+    pub fn new(rx: Receiver<'d, Driver<'d, USB>>) -> Self {
+        let reader = CdcPacketReader {
+            rx,
+            connected: false,
+        };
+        Self {
+            inner: ReaderInputSource::new(PacketByteReader::new(reader)),
+        }
+    }
+
+    /// Get the current gamepad state (before trim is applied).
+    #[inline]
+    #[must_use]
+    pub fn current_state(&self) -> &GamepadState {
+        self.inner.current_state()
+    }
+
+    /// Get the stick trim applied to received states.
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &StickTrim {
+        self.inner.trim()
+    }
+
+    /// Whether an `H1` hold is freezing the reported state.
+    #[inline]
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.inner.is_held()
+    }
+
+    /// Number of lost-data events (oversized packets) seen so far.
+    #[inline]
+    #[must_use]
+    pub fn overrun_count(&self) -> u32 {
+        self.inner.overrun_count()
+    }
+}
+
+impl InputSource for CdcInputSource<'_> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        self.inner.receive().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.reader().reader().connected
+    }
+}
@@ -1,25 +1,41 @@
 //! CRSF input source implementation.
 //!
 //! Receives CRSF frames from UART and converts them to GamepadState.
+//!
+//! Link statistics packets are tracked alongside the RC channels: a source
+//! reports itself disconnected once RC frames stop or the receiver reports
+//! zero link quality (see [`LinkMonitor`]).
 
-use crsf_proto::{channels_to_gamepad, ChannelMapping, CrsfParser, Packet, DEFAULT_MAPPING};
+use crsf_proto::{
+    channels_to_gamepad, encode_telemetry, ChannelMapping, CrsfEvent, CrsfParser, LinkMonitor,
+    LinkStats, Packet, DEFAULT_MAPPING, MAX_TELEMETRY_FRAME_SIZE,
+};
 use embassy_rp::uart::{Async, Uart, UartRx};
-use gamepad_core::{GamepadState, InputError, InputSource};
+use embassy_time::Instant;
+use gamepad_core::{
+    GamepadState, InputError, InputSource, TelemetryData, TelemetryError, TelemetryKind,
+    TelemetryScheduler,
+};
 
 /// CRSF input source for receiving RC channel data.
 ///
 /// Parses CRSF frames from UART and converts channel data to GamepadState.
+///
+/// `receive` is cancellation safe: partial frames live in the parser owned by
+/// `self`, so a dropped future resumes the same frame on the next call.
 pub struct CrsfInputSource<'d> {
     /// UART receiver (RX only for basic input, full Uart for telemetry).
     rx: UartRx<'d, Async>,
     /// CRSF frame parser.
     parser: CrsfParser,
+    /// Latest command event, until taken with `poll_event`.
+    event: Option<CrsfEvent>,
     /// Current gamepad state (updated on each RC packet).
     state: GamepadState,
     /// Channel-to-gamepad mapping configuration.
     mapping: ChannelMapping,
-    /// Connection status (true if we've received valid packets recently).
-    connected: bool,
+    /// RC frame timing and latest link statistics.
+    link: LinkMonitor,
 }
 
 impl<'d> CrsfInputSource<'d> {
@@ -42,12 +58,37 @@ impl<'d> CrsfInputSource<'d> {
         Self {
             rx,
             parser: CrsfParser::new(),
+            event: None,
             state: GamepadState::neutral(),
             mapping,
-            connected: false,
+            link: LinkMonitor::new(LinkMonitor::DEFAULT_TIMEOUT_MS),
         }
     }
 
+    /// Take the latest command event (e.g. a bind request), if any.
+    ///
+    /// Command frames are picked up while [`receive`](InputSource::receive)
+    /// waits for RC packets; only the most recent unpolled one is kept.
+    /// Link statistics are stored separately (see [`link_stats`](Self::link_stats)).
+    pub fn poll_event(&mut self) -> Option<CrsfEvent> {
+        self.event.take()
+    }
+
+    /// Get the latest link statistics reported by the receiver, if any.
+    #[must_use]
+    pub fn link_stats(&self) -> Option<LinkStats> {
+        self.link.stats()
+    }
+
+    /// Get the latest uplink link quality (0-100 %), if known.
+    ///
+    /// Lets the application signal a degrading link (e.g. on the LED) before
+    /// the link is lost entirely.
+    #[must_use]
+    pub fn link_quality(&self) -> Option<u8> {
+        self.link.link_quality()
+    }
+
     /// Process incoming bytes until we get an RC channels packet.
     async fn read_next_rc_packet(&mut self) -> Result<[u16; 16], InputError> {
         let mut byte_buf = [0u8; 1];
@@ -62,13 +103,21 @@ impl<'d> CrsfInputSource<'d> {
             // Feed to parser
             match self.parser.push_byte(byte_buf[0]) {
                 Ok(Some(packet)) => {
-                    // Got a complete packet - check if it's RC channels
-                    if let Packet::RCChannels(rc) = packet {
-                        self.connected = true;
-                        return Ok(rc.0);
+                    // Got a complete packet - RC channels end the wait
+                    match packet {
+                        Packet::RCChannels(rc) => {
+                            self.link.rc_received(Instant::now().as_millis());
+                            return Ok(rc.0);
+                        }
+                        Packet::LinkStatistics(stats) => {
+                            self.link.stats_received(LinkStats::from(&stats));
+                        }
+                        Packet::Commands(command) => {
+                            self.event = Some(CrsfEvent::from(&command));
+                        }
+                        // Other packet types are ignored
+                        _ => {}
                     }
-                    // Other packet types are ignored for now
-                    // (could be used for link statistics, etc.)
                 }
                 Ok(None) => {
                     // Incomplete packet, continue reading
@@ -95,24 +144,28 @@ impl InputSource for CrsfInputSource<'_> {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected
+        self.link.is_connected(Instant::now().as_millis())
     }
 }
 
 /// CRSF input source with full UART for bidirectional communication.
 ///
 /// Use this when you need telemetry backchannel support.
+///
+/// `receive` is cancellation safe, like [`CrsfInputSource`].
 pub struct CrsfBidirectionalSource<'d> {
     /// Full UART for TX and RX.
     uart: Uart<'d, Async>,
     /// CRSF frame parser.
     parser: CrsfParser,
+    /// Latest command event, until taken with `poll_event`.
+    event: Option<CrsfEvent>,
     /// Current gamepad state.
     state: GamepadState,
     /// Channel mapping configuration.
     mapping: ChannelMapping,
-    /// Connection status.
-    connected: bool,
+    /// RC frame timing and latest link statistics.
+    link: LinkMonitor,
 }
 
 impl<'d> CrsfBidirectionalSource<'d> {
@@ -128,9 +181,10 @@ impl<'d> CrsfBidirectionalSource<'d> {
         Self {
             uart,
             parser: CrsfParser::new(),
+            event: None,
             state: GamepadState::neutral(),
             mapping,
-            connected: false,
+            link: LinkMonitor::new(LinkMonitor::DEFAULT_TIMEOUT_MS),
         }
     }
 
@@ -139,6 +193,62 @@ impl<'d> CrsfBidirectionalSource<'d> {
         &mut self.uart
     }
 
+    /// Send the telemetry kind that is due next according to `scheduler`.
+    ///
+    /// `latest` provides the most recent value of a kind; a kind without a
+    /// value is skipped until its next interval. Returns the kind that was
+    /// sent, or `None` if nothing was due.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelemetryError::NotSupported`] if CRSF has no frame for the
+    /// scheduled kind, or [`TelemetryError::Io`] if the UART write fails.
+    pub async fn send_scheduled_telemetry(
+        &mut self,
+        scheduler: &mut TelemetryScheduler,
+        now_ms: u64,
+        mut latest: impl FnMut(TelemetryKind) -> Option<TelemetryData>,
+    ) -> Result<Option<TelemetryKind>, TelemetryError> {
+        let Some(kind) = scheduler.next(now_ms) else {
+            return Ok(None);
+        };
+        let Some(data) = latest(kind) else {
+            return Ok(None);
+        };
+
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&data, &mut buf)?;
+        self.uart
+            .write(&buf[..len])
+            .await
+            .map_err(|_| TelemetryError::Io)?;
+        Ok(Some(kind))
+    }
+
+    /// Take the latest command event (e.g. a bind request), if any.
+    ///
+    /// Command frames are picked up while [`receive`](InputSource::receive)
+    /// waits for RC packets; only the most recent unpolled one is kept.
+    /// Link statistics are stored separately (see [`link_stats`](Self::link_stats)).
+    pub fn poll_event(&mut self) -> Option<CrsfEvent> {
+        self.event.take()
+    }
+
+    /// Get the latest link statistics reported by the receiver, if any.
+    #[must_use]
+    pub fn link_stats(&self) -> Option<LinkStats> {
+        self.link.stats()
+    }
+
+    /// Get the latest uplink link quality (0-100 %), if known.
+    ///
+    /// Lets the application signal a degrading link (e.g. on the LED) before
+    /// the link is lost entirely.
+    #[must_use]
+    pub fn link_quality(&self) -> Option<u8> {
+        self.link.link_quality()
+    }
+
     /// Process incoming bytes until we get an RC channels packet.
     async fn read_next_rc_packet(&mut self) -> Result<[u16; 16], InputError> {
         let mut byte_buf = [0u8; 1];
@@ -150,12 +260,17 @@ impl<'d> CrsfBidirectionalSource<'d> {
                 .map_err(|_| InputError::Io)?;
 
             match self.parser.push_byte(byte_buf[0]) {
-                Ok(Some(packet)) => {
-                    if let Packet::RCChannels(rc) = packet {
-                        self.connected = true;
-                        return Ok(rc.0);
-                    }
+                Ok(Some(Packet::RCChannels(rc))) => {
+                    self.link.rc_received(Instant::now().as_millis());
+                    return Ok(rc.0);
+                }
+                Ok(Some(Packet::LinkStatistics(stats))) => {
+                    self.link.stats_received(LinkStats::from(&stats));
+                }
+                Ok(Some(Packet::Commands(command))) => {
+                    self.event = Some(CrsfEvent::from(&command));
                 }
+                Ok(Some(_)) => {}
                 Ok(None) => {}
                 Err(_) => {
                     self.parser.reset();
@@ -173,6 +288,6 @@ impl InputSource for CrsfBidirectionalSource<'_> {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected
+        self.link.is_connected(Instant::now().as_millis())
     }
 }
@@ -8,6 +8,8 @@
 //!
 //! Full state: `G<buttons>:<lx>:<ly>:<rx>:<ry>:<lt>:<rt>*<checksum>\n`
 //! Update: `U<field>:<value>*<checksum>\n`
+//! Trim: `C<axis>:<offset>*<checksum>\n`
+//! Hold: `H<0|1>*<checksum>\n`
 //!
 //! See [`parse_message`] for full protocol specification.
 //!
@@ -21,9 +23,9 @@
 
 use embassy_rp::uart::{Async, Error as UartError, UartRx};
 use gamepad_core::{
-    parse_message, GamepadState, InputError, InputSource, ParsedMessage, MAX_LINE_LENGTH,
+    ByteReader, GamepadState, InputError, InputSource, ReaderInputSource, StickTrim,
+    MAX_LINE_LENGTH,
 };
-use heapless::Vec;
 
 /// Convert UART errors to [`InputError`].
 ///
@@ -38,81 +40,84 @@ fn uart_error_to_input_error(e: UartError) -> InputError {
     }
 }
 
-/// UART-based input source for receiving gamepad state.
-pub struct UartInputSource<'d> {
+/// [`ByteReader`] over an async UART receiver.
+///
+/// Receive overruns are reported as [`InputError::BufferOverflow`], which
+/// makes [`ReaderInputSource`] count them and resync on the next newline.
+struct UartByteReader<'d> {
     rx: UartRx<'d, Async>,
-    buffer: Vec<u8, MAX_LINE_LENGTH>,
-    /// Current gamepad state (updated incrementally or replaced fully)
-    state: GamepadState,
 }
 
-impl<'d> UartInputSource<'d> {
+impl ByteReader for UartByteReader<'_> {
+    async fn read_byte(&mut self) -> Result<u8, InputError> {
+        let mut byte = [0u8; 1];
+        self.rx
+            .read(&mut byte)
+            .await
+            .map_err(uart_error_to_input_error)?;
+        Ok(byte[0])
+    }
+}
+
+/// UART-based input source for receiving gamepad state.
+///
+/// # Cancellation Safety
+///
+/// [`receive`](InputSource::receive) is cancellation safe: every byte read
+/// from the UART is pushed into the line accumulator held by `self` before the
+/// next await point, so dropping the future mid-line (e.g. when losing a
+/// `select`) keeps the partial line and the next call completes it.
+///
+/// # Line Length
+///
+/// Lines longer than `N` bytes (default [`MAX_LINE_LENGTH`]) are rejected as
+/// overflow. Use e.g. `UartInputSource::<'_, 128>::new(rx)` for senders with
+/// extra fields.
+pub struct UartInputSource<'d, const N: usize = MAX_LINE_LENGTH> {
+    inner: ReaderInputSource<UartByteReader<'d>, N>,
+}
+
+impl<'d, const N: usize> UartInputSource<'d, N> {
     /// Create a new UART input source from the given UART receiver.
     #[must_use]
     pub fn new(rx: UartRx<'d, Async>) -> Self {
         Self {
-            rx,
-            buffer: Vec::new(),
-            state: GamepadState::neutral(),
+            inner: ReaderInputSource::new(UartByteReader { rx }),
         }
     }
 
-    /// Get the current gamepad state.
+    /// Get the current gamepad state (before trim is applied).
     #[inline]
     #[must_use]
     pub fn current_state(&self) -> &GamepadState {
-        &self.state
+        self.inner.current_state()
     }
 
-    /// Read bytes until a newline is found or buffer is full.
-    ///
-    /// If a line exceeds the buffer capacity, the rest of the line is
-    /// discarded to prevent cascading parse errors on subsequent reads.
-    async fn read_line(&mut self) -> Result<(), InputError> {
-        self.buffer.clear();
-        let mut byte = [0u8; 1];
-
-        loop {
-            self.rx
-                .read(&mut byte)
-                .await
-                .map_err(uart_error_to_input_error)?;
+    /// Get the stick trim applied to received states.
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &StickTrim {
+        self.inner.trim()
+    }
 
-            if byte[0] == b'\n' {
-                return Ok(());
-            }
+    /// Whether an `H1` hold is freezing the reported state.
+    #[inline]
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.inner.is_held()
+    }
 
-            if self.buffer.push(byte[0]).is_err() {
-                // Buffer overflow - discard rest of line until newline
-                loop {
-                    self.rx
-                        .read(&mut byte)
-                        .await
-                        .map_err(uart_error_to_input_error)?;
-                    if byte[0] == b'\n' {
-                        break;
-                    }
-                }
-                return Err(InputError::BufferOverflow);
-            }
-        }
+    /// Number of UART receive overruns (dropped bytes) seen so far.
+    #[inline]
+    #[must_use]
+    pub fn overrun_count(&self) -> u32 {
+        self.inner.overrun_count()
     }
 }
 
-impl InputSource for UartInputSource<'_> {
+impl<const N: usize> InputSource for UartInputSource<'_, N> {
     async fn receive(&mut self) -> Result<GamepadState, InputError> {
-        self.read_line().await?;
-
-        match parse_message(&self.buffer)? {
-            ParsedMessage::FullState(state) => {
-                self.state = state;
-            }
-            ParsedMessage::Update(update) => {
-                self.state.apply_update(update);
-            }
-        }
-
-        Ok(self.state)
+        self.inner.receive().await
     }
 
     fn is_connected(&self) -> bool {
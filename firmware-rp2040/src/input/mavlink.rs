@@ -27,6 +27,9 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 /// Uses a minimal built-in MAVLink parser to avoid atomic limitations
 /// on Cortex-M0 targets.
 ///
+/// `receive` is cancellation safe: partial messages live in the parser owned
+/// by `self`, so a dropped future resumes the same message on the next call.
+///
 /// Note: Heartbeat sending is not implemented. The ground station should
 /// continue sending even without heartbeat responses from this device.
 pub struct MavlinkInputSource<'d> {
@@ -4,22 +4,82 @@
 //! - `proto-gamepad`: Text-based gamepad protocol (default)
 //! - `proto-crsf`: CRSF/ExpressLRS protocol
 //! - `proto-mavlink`: MAVLink protocol
+//! - `proto-ppm`: PPM pulse train on a GPIO
+//! - `proto-adc`: Analog joysticks on the ADC pins
+//! - `usb-cdc-input`: Text-based gamepad protocol over USB serial
 
 #[cfg(feature = "proto-gamepad")]
 pub mod gamepad;
 
+#[cfg(feature = "usb-cdc-input")]
+pub mod cdc;
+
 #[cfg(feature = "proto-crsf")]
 pub mod crsf;
 
 #[cfg(feature = "proto-mavlink")]
 pub mod mavlink;
 
+#[cfg(feature = "proto-ppm")]
+pub mod ppm;
+
+#[cfg(feature = "proto-adc")]
+pub mod adc;
+
 // Re-export input sources for convenience
 #[cfg(feature = "proto-gamepad")]
 pub use gamepad::UartInputSource;
 
+#[cfg(feature = "usb-cdc-input")]
+pub use cdc::{
+    first_interface, CdcInputSource, CdcPacketReader, UsbClass, CDC_PACKET_SIZE, USB_CLASS_ORDER,
+};
+
 #[cfg(feature = "proto-crsf")]
 pub use crsf::{CrsfBidirectionalSource, CrsfInputSource};
 
 #[cfg(feature = "proto-mavlink")]
 pub use mavlink::MavlinkInputSource;
+
+#[cfg(feature = "proto-ppm")]
+pub use ppm::PpmInputSource;
+
+#[cfg(feature = "proto-adc")]
+pub use adc::{RpAdcInputSource, RpAdcReader};
+
+use gamepad_core::UartParams;
+
+/// UART settings of the input protocol selected at build time.
+///
+/// Protocols that do not use the UART (PPM, ADC) get the text protocol
+/// settings; the UART is still set up but stays unused.
+#[must_use]
+pub const fn active_uart_params() -> UartParams {
+    if cfg!(feature = "proto-crsf") {
+        UartParams::CRSF
+    } else if cfg!(feature = "proto-mavlink") {
+        UartParams::MAVLINK
+    } else {
+        UartParams::TEXT
+    }
+}
+
+/// Name of the input protocol selected at build time, for logging.
+#[must_use]
+pub const fn active_protocol() -> &'static str {
+    if cfg!(feature = "usb-cdc-input") {
+        "gamepad (USB serial)"
+    } else if cfg!(feature = "proto-gamepad") {
+        "gamepad"
+    } else if cfg!(feature = "proto-crsf") {
+        "crsf"
+    } else if cfg!(feature = "proto-mavlink") {
+        "mavlink"
+    } else if cfg!(feature = "proto-ppm") {
+        "ppm"
+    } else if cfg!(feature = "proto-adc") {
+        "adc"
+    } else {
+        "none"
+    }
+}
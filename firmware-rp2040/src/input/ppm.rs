@@ -0,0 +1,81 @@
+//! PPM input source implementation.
+//!
+//! Captures the PPM pulse train of older RC receivers on a single GPIO and
+//! converts the decoded channels to GamepadState.
+//!
+//! Edges are timestamped in the GPIO wait future with the embassy time
+//! driver (1 µs ticks on the RP2040). The pulse-width decoding itself lives
+//! in [`crsf_proto::PpmDecoder`] and is tested on the host.
+
+use crsf_proto::{channels_to_gamepad, ChannelMapping, PpmDecoder, PpmEdge, DEFAULT_MAPPING};
+use embassy_rp::gpio::Input;
+use embassy_time::Instant;
+use gamepad_core::{GamepadState, InputError, InputSource};
+
+/// PPM input source for receiving RC channel data from a single GPIO.
+///
+/// Channels map to gamepad controls like CRSF channels (CH1 = index 0), so
+/// the same [`ChannelMapping`] applies.
+///
+/// `receive` is cancellation safe: the partial frame lives in the decoder
+/// owned by `self`. An edge missed while no future is polled drops at most
+/// the current frame.
+pub struct PpmInputSource<'d> {
+    /// PPM signal pin.
+    pin: Input<'d>,
+    /// Pulse train decoder.
+    decoder: PpmDecoder,
+    /// Channel mapping configuration.
+    mapping: ChannelMapping,
+    /// Connection status.
+    connected: bool,
+}
+
+impl<'d> PpmInputSource<'d> {
+    /// Create a new PPM input source with default mapping.
+    #[must_use]
+    pub fn new(pin: Input<'d>) -> Self {
+        Self::with_mapping(pin, DEFAULT_MAPPING)
+    }
+
+    /// Create a new PPM input source with custom mapping.
+    #[must_use]
+    pub fn with_mapping(pin: Input<'d>, mapping: ChannelMapping) -> Self {
+        Self {
+            pin,
+            decoder: PpmDecoder::new(),
+            mapping,
+            connected: false,
+        }
+    }
+
+    /// Wait for edges until a complete frame was decoded.
+    async fn read_next_frame(&mut self) -> [u16; 16] {
+        loop {
+            self.pin.wait_for_any_edge().await;
+            // Truncation is fine: the decoder handles wrapping timestamps
+            let now_us = Instant::now().as_micros() as u32;
+            let edge = if self.pin.is_high() {
+                PpmEdge::Rising
+            } else {
+                PpmEdge::Falling
+            };
+
+            if let Some(frame) = self.decoder.push(edge, now_us) {
+                return frame.to_crsf_channels();
+            }
+        }
+    }
+}
+
+impl InputSource for PpmInputSource<'_> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        let channels = self.read_next_frame().await;
+        self.connected = true;
+        Ok(channels_to_gamepad(&channels, &self.mapping))
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
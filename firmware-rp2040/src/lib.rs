@@ -6,7 +6,7 @@
 //! # Overview
 //!
 //! The firmware runs on a Raspberry Pi Pico (RP2040) and:
-//! 1. Receives gamepad state over UART (115200 baud, 8N1)
+//! 1. Receives gamepad state over UART (115200 baud, 8N1 for the text protocol)
 //! 2. Parses the protocol messages (full state or incremental updates)
 //! 3. Outputs the state as a USB HID gamepad
 //!
@@ -16,7 +16,10 @@
 //! |----------|------|-------------|
 //! | UART1 TX | 8    | Serial transmit |
 //! | UART1 RX | 9    | Serial receive (gamepad data input) |
-//! | LED      | 25   | On-board LED (error indicator) |
+//! | LED      | 25   | On-board LED (error toggle, slow blink when idle) |
+//! | PPM in   | 2    | PPM signal input (`proto-ppm` only) |
+//! | Marker   | 3    | Latency loopback output (`latency-probe` only) |
+//! | ADC0-2   | 26-28 | Left stick X/Y, left trigger (`proto-adc` only) |
 //!
 //! # Architecture
 //!
@@ -32,16 +35,43 @@
 //!
 //! # Modules
 //!
+//! - [`diagnostics`]: Last-known state logged before a fatal panic ([`diagnostics::fatal`])
 //! - [`uart_input`]: UART-based input source ([`UartInputSource`])
-//! - [`usb_output`]: USB HID output ([`UsbHidOutput`], [`GamepadReport`])
+//! - `input::cdc`: USB serial input source (`CdcInputSource`, with the `usb-cdc-input` feature)
+//! - `input::ppm`: PPM receiver input source (`PpmInputSource`, with the `proto-ppm` feature)
+//! - `input::adc`: Analog joystick input source (`RpAdcInputSource`, with the `proto-adc` feature)
+//! - [`usb_output`]: USB HID output ([`UsbHidOutput`], [`HidProfile`], [`GamepadReport`],
+//!   [`GamepadReportFull`], `GamepadReportHat`)
+//! - `mouse_output`: USB HID mouse output (`MouseOutput`, with the `mouse` feature)
 //!
 //! # Features
 //!
-//! - **`dev-panic`** (default): Use `panic-probe` for development (prints panic info via RTT)
+//! - **`dev-panic`** (default): Use `panic-probe` for development (prints panic info via RTT,
+//!   preceded by the last-known state and error counters on fatal errors)
 //! - **`prod-panic`**: Use `panic-reset` for production (silent watchdog reset)
-//! - **`standard-hid`** (default): Standard HID gamepad descriptor (cross-platform)
-//! - **`xinput-compat`**: Xbox-style HID descriptor (better Windows game support)
+//! - **`standard-hid`** (default): Boot with the standard HID gamepad profile (cross-platform)
+//! - **`xinput-compat`**: Boot with the Xbox-style profile with 16-bit sticks (better Windows
+//!   game support); takes precedence over `standard-hid`. Either profile, or the DirectInput
+//!   joystick profile, can also be selected at boot by jumper ([`HidProfile`])
+//! - **`hat-switch`**: Boot with the standard profile with the D-pad as a HID hat switch
+//!   instead of four buttons, for games that only read a hat; `xinput-compat` takes
+//!   precedence
+//! - **`buttons-only`**: 16-button descriptor with a 2-byte report (no axes), for every profile
+//! - **`unsigned-sticks`**: Report sticks as unsigned 0-255 centered on 128, for games
+//!   that expect unsigned axes (8-bit profiles only)
 //! - **`uart-flow-control`**: Enable hardware flow control (CTS/RTS on GPIO 10/11)
+//! - **`usb-cdc-input`**: Receive the text protocol over USB serial (CDC-ACM) instead of
+//!   the UART, as a composite CDC + HID device
+//! - **`usb-hid-first`**: Declare the HID class before CDC in the composite device, for
+//!   hosts that only look for a gamepad on interface 0 (see `input::cdc`)
+//! - **`uart-invert-rx`**: Invert the UART RX pin for idle-low (inverted) receivers.
+//!   Only valid with `proto-crsf`; the inversion happens in the pin logic, so
+//!   the parser sees the same bytes as on a normal line
+//! - **`mouse`**: Present a USB HID mouse driven by the right stick (`MouseOutput`)
+//! - **`usb-keepalive`**: Re-send the gamepad report at a fixed interval while the
+//!   input is unchanged, for hosts that drop silent HID devices (`KeepaliveOutput`)
+//! - **`latency-probe`**: Toggle GPIO 3 after each report and log how long the
+//!   level takes to loop back through the sender as button 15 (`LatencyProbe`)
 //!
 //! # Re-exports
 //!
@@ -50,27 +80,142 @@
 
 #![no_std]
 
-// Ensure mutually exclusive HID descriptor features
-#[cfg(all(feature = "standard-hid", feature = "xinput-compat"))]
-compile_error!("Cannot enable both `standard-hid` and `xinput-compat` features - they define conflicting HID descriptors");
+// `standard-hid`, `xinput-compat` and `hat-switch` only pick the default
+// profile, but `buttons-only` replaces every descriptor
+#[cfg(all(
+    feature = "buttons-only",
+    any(
+        feature = "standard-hid",
+        feature = "xinput-compat",
+        feature = "hat-switch"
+    )
+))]
+compile_error!("`buttons-only` defines its own HID descriptor - disable `standard-hid`, `xinput-compat` and `hat-switch`");
+
+// Unsigned sticks change the 8-bit descriptors only
+#[cfg(all(feature = "unsigned-sticks", feature = "buttons-only"))]
+compile_error!("`unsigned-sticks` has no effect with `buttons-only` (no stick axes)");
+
+// Exactly one input protocol (`usb-cdc-input` enables `proto-gamepad`)
+#[cfg(not(any(
+    feature = "proto-gamepad",
+    feature = "proto-crsf",
+    feature = "proto-mavlink",
+    feature = "proto-ppm",
+    feature = "proto-adc"
+)))]
+compile_error!("No input protocol selected - enable one of `proto-gamepad`, `proto-crsf`, `proto-mavlink`, `proto-ppm` or `proto-adc`");
+#[cfg(any(
+    all(
+        feature = "proto-gamepad",
+        any(
+            feature = "proto-crsf",
+            feature = "proto-mavlink",
+            feature = "proto-ppm",
+            feature = "proto-adc"
+        )
+    ),
+    all(
+        feature = "proto-crsf",
+        any(
+            feature = "proto-mavlink",
+            feature = "proto-ppm",
+            feature = "proto-adc"
+        )
+    ),
+    all(
+        feature = "proto-mavlink",
+        any(feature = "proto-ppm", feature = "proto-adc")
+    ),
+    all(feature = "proto-ppm", feature = "proto-adc")
+))]
+compile_error!("Only one input protocol can be enabled - pick one of `proto-gamepad`, `proto-crsf`, `proto-mavlink`, `proto-ppm` or `proto-adc`");
+
+// RX inversion only makes sense for RC receiver links (the text and MAVLink
+// protocols always use idle-high UART levels)
+#[cfg(all(feature = "uart-invert-rx", not(feature = "proto-crsf")))]
+compile_error!("`uart-invert-rx` is only supported with `proto-crsf`");
+
+// The mouse output already reports on every tick
+#[cfg(all(feature = "usb-keepalive", feature = "mouse"))]
+compile_error!("`usb-keepalive` applies to the gamepad output - disable `mouse`");
+
+// The probe replaces the plain gamepad output task
+#[cfg(all(
+    feature = "latency-probe",
+    any(feature = "mouse", feature = "usb-keepalive")
+))]
+compile_error!(
+    "`latency-probe` measures the plain gamepad output - disable `mouse` and `usb-keepalive`"
+);
+
+// USB serial input carries the text protocol only
+#[cfg(all(
+    feature = "usb-cdc-input",
+    any(
+        feature = "proto-crsf",
+        feature = "proto-mavlink",
+        feature = "proto-ppm",
+        feature = "proto-adc"
+    )
+))]
+compile_error!("`usb-cdc-input` carries the text protocol - disable the other `proto-*` features");
 
 // Re-export core types for convenience
 pub use gamepad_core::{
-    parse, parse_message, AnalogStick, BridgeError, Buttons, GamepadBridge, GamepadFieldUpdate,
-    GamepadState, InputError, InputSource, OutputError, OutputSink, ParsedMessage, MAX_LINE_LENGTH,
+    parse, parse_message, AnalogStick, BridgeError, Buttons, ChangeFilter, CoalescingOutput,
+    GamepadBridge, GamepadFieldUpdate, GamepadState, IdleDetector, InputError, InputSource,
+    OutputError, OutputSink, Parity, ParsedMessage, StopBits, TelemetryData, TelemetryError,
+    TelemetryKind, TelemetryScheduler, UartParams, MAX_LINE_LENGTH,
 };
 
+pub mod diagnostics;
 pub mod input;
+pub use input::{active_protocol, active_uart_params};
+#[cfg(feature = "mouse")]
+pub mod mouse_output;
 pub mod usb_output;
 
 // Re-export input sources based on selected protocol
 #[cfg(feature = "proto-gamepad")]
 pub use input::UartInputSource;
 
+#[cfg(feature = "usb-cdc-input")]
+pub use input::{
+    first_interface, CdcInputSource, CdcPacketReader, UsbClass, CDC_PACKET_SIZE, USB_CLASS_ORDER,
+};
+
+#[cfg(feature = "proto-crsf")]
+pub use crsf_proto::{DetectionState, LinkStats, ReceiverDetector};
 #[cfg(feature = "proto-crsf")]
 pub use input::{CrsfBidirectionalSource, CrsfInputSource};
 
 #[cfg(feature = "proto-mavlink")]
 pub use input::MavlinkInputSource;
 
-pub use usb_output::{configure_usb_hid, GamepadReport, GamepadRequestHandler, UsbHidOutput};
+#[cfg(feature = "proto-ppm")]
+pub use input::PpmInputSource;
+
+#[cfg(feature = "proto-adc")]
+pub use gamepad_core::{AdcCalibration, AdcChannel, AdcTarget, StickAxis};
+#[cfg(feature = "proto-adc")]
+pub use input::{RpAdcInputSource, RpAdcReader};
+
+pub use gamepad_core::RumbleState;
+#[cfg(not(feature = "buttons-only"))]
+pub use usb_output::GamepadReportHat;
+pub use usb_output::{
+    configure_usb_hid, report_with_frame, rumble_signal, GamepadHidWriter, GamepadReport,
+    GamepadReportFull, GamepadRequestHandler, GuideMode, HidProfile, UsbHidOutput,
+};
+
+#[cfg(feature = "usb-keepalive")]
+pub use gamepad_core::KeepaliveOutput;
+
+#[cfg(feature = "latency-probe")]
+pub use gamepad_core::{LatencyProbe, LatencyStats};
+
+#[cfg(feature = "mouse")]
+pub use gamepad_core::{MouseMapping, DEFAULT_MOUSE_MAPPING};
+#[cfg(feature = "mouse")]
+pub use mouse_output::{configure_usb_mouse, MouseOutput};
@@ -0,0 +1,125 @@
+//! USB HID mouse output driven by the right stick.
+//!
+//! Enabled with the `mouse` feature. Each [`send`](OutputSink::send) runs one
+//! [`StickMouse`] tick, so the output task must keep sending the latest state
+//! at a fixed rate for a held stick to keep moving the pointer.
+
+use embassy_usb::class::hid::{HidWriter, State};
+use embassy_usb::Builder;
+use gamepad_core::{GamepadState, MouseMapping, MouseReport, OutputError, OutputSink, StickMouse};
+
+/// Size of a mouse report in bytes (buttons, X, Y).
+pub const MOUSE_REPORT_SIZE: usize = 3;
+
+/// Relative mouse HID Report Descriptor.
+///
+/// This descriptor defines a boot-compatible mouse with:
+/// - 3 buttons (left, right, middle)
+/// - Relative X/Y (signed 8-bit)
+pub const MOUSE_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x02, // Usage (Mouse)
+    0xA1, 0x01, // Collection (Application)
+    0x09, 0x01, //   Usage (Pointer)
+    0xA1, 0x00, //   Collection (Physical)
+    //
+    // --- Buttons (3 buttons + 5 bits padding) ---
+    0x05, 0x09, //     Usage Page (Button)
+    0x19, 0x01, //     Usage Minimum (Button 1)
+    0x29, 0x03, //     Usage Maximum (Button 3)
+    0x15, 0x00, //     Logical Minimum (0)
+    0x25, 0x01, //     Logical Maximum (1)
+    0x95, 0x03, //     Report Count (3)
+    0x75, 0x01, //     Report Size (1)
+    0x81, 0x02, //     Input (Data, Variable, Absolute)
+    0x95, 0x01, //     Report Count (1)
+    0x75, 0x05, //     Report Size (5)
+    0x81, 0x03, //     Input (Constant) - Padding
+    //
+    // --- Movement ---
+    0x05, 0x01, //     Usage Page (Generic Desktop)
+    0x09, 0x30, //     Usage (X)
+    0x09, 0x31, //     Usage (Y)
+    0x15, 0x81, //     Logical Minimum (-127)
+    0x25, 0x7F, //     Logical Maximum (127)
+    0x75, 0x08, //     Report Size (8)
+    0x95, 0x02, //     Report Count (2)
+    0x81, 0x06, //     Input (Data, Variable, Relative)
+    //
+    0xC0, //   End Collection
+    0xC0, // End Collection
+];
+
+/// Convert a mouse report to bytes.
+#[must_use]
+pub const fn mouse_report_bytes(report: &MouseReport) -> [u8; MOUSE_REPORT_SIZE] {
+    [
+        report.buttons,
+        report.x.to_ne_bytes()[0],
+        report.y.to_ne_bytes()[0],
+    ]
+}
+
+/// USB HID mouse output.
+///
+/// Translates right stick deflection into relative pointer movement and
+/// mapped buttons into clicks (see [`MouseMapping`]).
+pub struct MouseOutput<'d> {
+    writer: HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8>,
+    ready: bool,
+    mouse: StickMouse,
+}
+
+impl<'d> MouseOutput<'d> {
+    /// Create a new mouse output from the given HID writer.
+    #[must_use]
+    pub fn new(
+        writer: HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8>,
+        mapping: MouseMapping,
+    ) -> Self {
+        Self {
+            writer,
+            ready: false,
+            mouse: StickMouse::new(mapping),
+        }
+    }
+
+    /// Wait until the device is ready (USB enumerated).
+    pub async fn wait_ready(&mut self) {
+        self.writer.ready().await;
+        self.ready = true;
+    }
+}
+
+impl OutputSink for MouseOutput<'_> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        let report = self.mouse.tick(state);
+        self.writer
+            .write(&mouse_report_bytes(&report))
+            .await
+            .map_err(|_| OutputError::Io)
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready
+    }
+}
+
+/// Configure the USB HID mouse class in the USB builder.
+///
+/// Returns the HID writer for use by [`MouseOutput`].
+pub fn configure_usb_mouse<'d>(
+    builder: &mut Builder<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>>,
+    state: &'d mut State<'d>,
+) -> HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8> {
+    let config = embassy_usb::class::hid::Config {
+        report_descriptor: MOUSE_REPORT_DESCRIPTOR,
+        request_handler: None,
+        poll_ms: 1,
+        max_packet_size: 8,
+        hid_subclass: embassy_usb::class::hid::HidSubclass::No,
+        hid_boot_protocol: embassy_usb::class::hid::HidBootProtocol::None,
+    };
+
+    embassy_usb::class::hid::HidWriter::new(builder, state, config)
+}
@@ -1,17 +1,68 @@
 //! USB HID gamepad output implementation.
 
 use defmt::Format;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
 use embassy_usb::class::hid::{HidWriter, ReportId, RequestHandler, State};
 use embassy_usb::control::OutResponse;
 use embassy_usb::Builder;
-use gamepad_core::{GamepadState, OutputError, OutputSink};
+#[cfg(not(feature = "buttons-only"))]
+use gamepad_core::Dpad;
+use gamepad_core::{Buttons, FullStateFrame, GamepadState, OutputError, OutputSink, RumbleState};
+
+/// How the guide (Xbox/Home) button is placed in the HID report.
+///
+/// The guide button is bit 8 of [`Buttons`], i.e. HID button 9. Hosts treat it
+/// differently:
+///
+/// - **Linux/macOS/SDL**: a plain button that games can bind normally.
+/// - **Windows ([`HidProfile::XInput`])**: XInput itself does not expose the guide
+///   button, but overlays such as the Xbox Game Bar or Steam Big Picture may
+///   capture presses of the Home button before the game sees them.
+///
+/// [`Suppress`](GuideMode::Suppress) avoids the OS capture entirely, while
+/// [`Remap`](GuideMode::Remap) moves the press to a dedicated report bit (e.g.
+/// the otherwise unused button 16) that games can bind as a home button.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Format)]
+pub enum GuideMode {
+    /// Report the guide button as HID button 9 (default).
+    #[default]
+    Button,
+    /// Never report the guide button.
+    Suppress,
+    /// Report guide presses on the given button bit(s) instead.
+    Remap(Buttons),
+}
+
+impl GuideMode {
+    /// Apply the guide routing to a raw button bitfield.
+    #[must_use]
+    pub const fn apply(self, buttons: u16) -> u16 {
+        let guide = Buttons::GUIDE.raw();
+        match self {
+            GuideMode::Button => buttons,
+            GuideMode::Suppress => buttons & !guide,
+            GuideMode::Remap(target) => {
+                if buttons & guide != 0 {
+                    (buttons & !guide) | target.raw()
+                } else {
+                    buttons & !guide
+                }
+            }
+        }
+    }
+}
 
 /// USB HID Gamepad report structure.
 ///
 /// This matches the HID report descriptor defined below.
 /// Total size: 8 bytes (buttons: 2, sticks: 4x1, triggers: 2x1)
 ///
-/// Note: Stick values are scaled from i16 to i8 for HID compatibility.
+/// Note: Stick values are scaled from i16 to i8 for HID compatibility. With
+/// `unsigned-sticks` they are sent as 0-255 centered on 128 instead (see
+/// [`as_bytes`](Self::as_bytes)); the fields stay signed so the deadzone
+/// works the same in both modes.
+#[cfg(not(feature = "buttons-only"))]
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Format)]
 #[repr(C)]
 pub struct GamepadReport {
@@ -31,26 +82,69 @@ pub struct GamepadReport {
     pub right_trigger: u8,
 }
 
+#[cfg(not(feature = "buttons-only"))]
 impl GamepadReport {
     /// Size of the report in bytes.
     pub const SIZE: usize = 8;
 
     /// Convert the report to bytes.
+    ///
+    /// Sticks are encoded for the descriptor's logical range: two's
+    /// complement by default, or offset by 128 with `unsigned-sticks`.
     #[must_use]
-    pub fn as_bytes(&self) -> [u8; Self::SIZE] {
+    pub const fn as_bytes(&self) -> [u8; Self::SIZE] {
         let buttons_bytes = self.buttons.to_le_bytes();
         [
             buttons_bytes[0],
             buttons_bytes[1],
-            self.left_stick_x.to_ne_bytes()[0],
-            self.left_stick_y.to_ne_bytes()[0],
-            self.right_stick_x.to_ne_bytes()[0],
-            self.right_stick_y.to_ne_bytes()[0],
+            stick_byte(self.left_stick_x),
+            stick_byte(self.left_stick_y),
+            stick_byte(self.right_stick_x),
+            stick_byte(self.right_stick_y),
             self.left_trigger,
             self.right_trigger,
         ]
     }
 
+    /// Convert the report to bytes, prefixed with a report ID.
+    ///
+    /// Used when the report descriptor declares a Report ID, e.g. when several
+    /// collections of a composite device share one endpoint.
+    #[must_use]
+    pub const fn as_bytes_with_id(&self, report_id: u8) -> [u8; Self::SIZE + 1] {
+        prefix_report_id(report_id, self.as_bytes())
+    }
+
+    /// Build a report from a gamepad state with the given guide routing.
+    #[must_use]
+    pub const fn from_state(state: &GamepadState, guide: GuideMode) -> Self {
+        Self {
+            buttons: guide.apply(state.buttons.raw()),
+            // Scale i16 to i8 by taking the high byte
+            left_stick_x: (state.left_stick.x >> 8) as i8,
+            left_stick_y: (state.left_stick.y >> 8) as i8,
+            right_stick_x: (state.right_stick.x >> 8) as i8,
+            right_stick_y: (state.right_stick.y >> 8) as i8,
+            left_trigger: state.left_trigger,
+            right_trigger: state.right_trigger,
+        }
+    }
+
+    /// Zero stick axes whose scaled value is within `deadzone` of center.
+    ///
+    /// Applied to the i8 report values only, so the [`GamepadState`] the
+    /// report was built from (e.g. for echo or passthrough) is unaffected.
+    #[must_use]
+    pub const fn with_deadzone(self, deadzone: u8) -> Self {
+        Self {
+            left_stick_x: deadzone_i8(self.left_stick_x, deadzone),
+            left_stick_y: deadzone_i8(self.left_stick_y, deadzone),
+            right_stick_x: deadzone_i8(self.right_stick_x, deadzone),
+            right_stick_y: deadzone_i8(self.right_stick_y, deadzone),
+            ..self
+        }
+    }
+
     /// Neutral/zero report.
     #[must_use]
     pub const fn neutral() -> Self {
@@ -66,29 +160,698 @@ impl GamepadReport {
     }
 }
 
+#[cfg(not(feature = "buttons-only"))]
 impl From<&GamepadState> for GamepadReport {
     fn from(state: &GamepadState) -> Self {
+        Self::from_state(state, GuideMode::Button)
+    }
+}
+
+/// Encode an i8 report stick value as sent on the wire.
+#[cfg(not(feature = "buttons-only"))]
+const fn stick_byte(value: i8) -> u8 {
+    if cfg!(feature = "unsigned-sticks") {
+        // -128 -> 0, 0 -> 128, 127 -> 255
+        (value as u8) ^ 0x80
+    } else {
+        value as u8
+    }
+}
+
+// Unsigned sticks: center at 128, full deflection at the ends, symmetric
+#[cfg(feature = "unsigned-sticks")]
+const _: () = {
+    let state = GamepadState {
+        left_stick: gamepad_core::AnalogStick::new(0, i16::MAX),
+        right_stick: gamepad_core::AnalogStick::new(i16::MIN, 0),
+        ..GamepadState::neutral()
+    };
+    let bytes = GamepadReport::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[2] == 128 && bytes[3] == 255);
+    assert!(bytes[4] == 0 && bytes[5] == 128);
+
+    let state = GamepadState {
+        left_stick: gamepad_core::AnalogStick::new(12_800, -12_800),
+        ..GamepadState::neutral()
+    };
+    let bytes = GamepadReport::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[2] == 128 + 50 && bytes[3] == 128 - 50);
+};
+
+/// Zero `value` if its magnitude is at most `deadzone`.
+#[cfg(not(feature = "buttons-only"))]
+const fn deadzone_i8(value: i8, deadzone: u8) -> i8 {
+    if value.unsigned_abs() <= deadzone {
+        0
+    } else {
+        value
+    }
+}
+
+// The report deadzone zeroes small scaled values and leaves the state alone
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    let state = GamepadState {
+        left_stick: gamepad_core::AnalogStick::new(700, -600),
+        right_stick: gamepad_core::AnalogStick::new(2000, -2000),
+        ..GamepadState::neutral()
+    };
+    let report = GamepadReport::from_state(&state, GuideMode::Button);
+    assert!(report.left_stick_x == 2 && report.left_stick_y == -3);
+
+    let report = report.with_deadzone(4);
+    assert!(report.left_stick_x == 0 && report.left_stick_y == 0);
+    assert!(report.right_stick_x == 7 && report.right_stick_y == -8);
+    assert!(state.left_stick.x == 700 && state.left_stick.y == -600);
+
+    // Zero deadzone keeps every value
+    let report = GamepadReport::from_state(&state, GuideMode::Button).with_deadzone(0);
+    assert!(report.left_stick_x == 2);
+};
+
+// A report ID prefix leaves the 8-byte payload unchanged
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    let report = GamepadReport {
+        buttons: 0x0201,
+        left_stick_x: 3,
+        left_stick_y: 4,
+        right_stick_x: 5,
+        right_stick_y: 6,
+        left_trigger: 7,
+        right_trigger: 8,
+    };
+    let bytes = report.as_bytes_with_id(1);
+    assert!(bytes.len() == 9);
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i] == i as u8 + 1);
+        i += 1;
+    }
+};
+
+/// Encode a state as HID report bytes and as a text protocol frame.
+///
+/// For devices that report the state over USB and also echo it back upstream
+/// (e.g. on the UART TX line), so both encodings come from the same state in
+/// one call. The frame carries the full i16 stick values; the report is the
+/// scaled HID representation.
+#[must_use]
+pub fn report_with_frame(
+    state: &GamepadState,
+    guide: GuideMode,
+) -> ([u8; GamepadReport::SIZE], FullStateFrame) {
+    (
+        GamepadReport::from_state(state, guide).as_bytes(),
+        state.to_frame(),
+    )
+}
+
+// report_with_frame's report is the plain report of the state under each
+// guide routing (the frame side is covered by gamepad-proto's to_frame tests)
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    let state = GamepadState {
+        buttons: Buttons(Buttons::A.raw() | Buttons::GUIDE.raw() | Buttons::DPAD_LEFT.raw()),
+        left_stick: gamepad_core::AnalogStick::new(i16::MIN, 12_345),
+        right_stick: gamepad_core::AnalogStick::new(i16::MAX, -300),
+        left_trigger: 17,
+        right_trigger: 255,
+    };
+
+    let bytes = GamepadReport::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[2] == stick_byte(-128) && bytes[3] == stick_byte(48));
+    assert!(bytes[4] == stick_byte(127) && bytes[5] == stick_byte(-2));
+    assert!(bytes[6] == 17 && bytes[7] == 255);
+    let buttons = u16::from_le_bytes([bytes[0], bytes[1]]);
+    assert!(buttons == state.buttons.raw());
+
+    let bytes = GamepadReport::from_state(&state, GuideMode::Suppress).as_bytes();
+    let buttons = u16::from_le_bytes([bytes[0], bytes[1]]);
+    assert!(buttons == Buttons::A.raw() | Buttons::DPAD_LEFT.raw());
+
+    let bytes = GamepadReport::from_state(&state, GuideMode::Remap(Buttons::X)).as_bytes();
+    let buttons = u16::from_le_bytes([bytes[0], bytes[1]]);
+    assert!(buttons == Buttons::A.raw() | Buttons::DPAD_LEFT.raw() | Buttons::X.raw());
+};
+
+/// Prepend a report ID to a serialized report.
+const fn prefix_report_id<const N: usize, const M: usize>(
+    report_id: u8,
+    report: [u8; N],
+) -> [u8; M] {
+    assert!(M == N + 1);
+    let mut out = [0u8; M];
+    out[0] = report_id;
+    let mut i = 0;
+    while i < N {
+        out[i + 1] = report[i];
+        i += 1;
+    }
+    out
+}
+
+/// Full-resolution USB HID Gamepad report structure.
+///
+/// This matches the [`HidProfile::XInput`] report descriptor defined below.
+/// Total size: 12 bytes (buttons: 2, sticks: 4x2, triggers: 2x1)
+///
+/// Stick values are carried as full i16, so sources with more than 8 bits of
+/// precision (e.g. CRSF's 11-bit channels) reach the host unchanged. This is
+/// the 16-bit counterpart of the 8-bit [`GamepadReport`] used by the standard
+/// and DirectInput profiles.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Format)]
+#[repr(C)]
+pub struct GamepadReportFull {
+    /// Button bitfield (16 buttons)
+    pub buttons: u16,
+    /// Left stick X (-32767 to 32767)
+    pub left_stick_x: i16,
+    /// Left stick Y (-32767 to 32767)
+    pub left_stick_y: i16,
+    /// Right stick X (-32767 to 32767)
+    pub right_stick_x: i16,
+    /// Right stick Y (-32767 to 32767)
+    pub right_stick_y: i16,
+    /// Left trigger (0-255)
+    pub left_trigger: u8,
+    /// Right trigger (0-255)
+    pub right_trigger: u8,
+}
+
+impl GamepadReportFull {
+    /// Size of the report in bytes.
+    pub const SIZE: usize = 12;
+
+    /// Convert the report to bytes (little-endian fields).
+    #[must_use]
+    pub const fn as_bytes(&self) -> [u8; Self::SIZE] {
+        let buttons = self.buttons.to_le_bytes();
+        let lx = self.left_stick_x.to_le_bytes();
+        let ly = self.left_stick_y.to_le_bytes();
+        let rx = self.right_stick_x.to_le_bytes();
+        let ry = self.right_stick_y.to_le_bytes();
+        [
+            buttons[0],
+            buttons[1],
+            lx[0],
+            lx[1],
+            ly[0],
+            ly[1],
+            rx[0],
+            rx[1],
+            ry[0],
+            ry[1],
+            self.left_trigger,
+            self.right_trigger,
+        ]
+    }
+
+    /// Convert the report to bytes, prefixed with a report ID.
+    #[must_use]
+    pub const fn as_bytes_with_id(&self, report_id: u8) -> [u8; Self::SIZE + 1] {
+        prefix_report_id(report_id, self.as_bytes())
+    }
+
+    /// Build a report from a gamepad state with the given guide routing.
+    #[must_use]
+    pub const fn from_state(state: &GamepadState, guide: GuideMode) -> Self {
         Self {
-            buttons: state.buttons.raw(),
-            // Scale i16 to i8 by taking the high byte
-            left_stick_x: (state.left_stick.x >> 8) as i8,
-            left_stick_y: (state.left_stick.y >> 8) as i8,
-            right_stick_x: (state.right_stick.x >> 8) as i8,
-            right_stick_y: (state.right_stick.y >> 8) as i8,
+            buttons: guide.apply(state.buttons.raw()),
+            left_stick_x: clamp_axis_i16(state.left_stick.x),
+            left_stick_y: clamp_axis_i16(state.left_stick.y),
+            right_stick_x: clamp_axis_i16(state.right_stick.x),
+            right_stick_y: clamp_axis_i16(state.right_stick.y),
             left_trigger: state.left_trigger,
             right_trigger: state.right_trigger,
         }
     }
+
+    /// Zero stick axes within `deadzone` of center, in 8-bit report units.
+    ///
+    /// Axes with a magnitude up to `deadzone * 256` are zeroed, roughly the
+    /// threshold of [`GamepadReport::with_deadzone`], so the deadzone feels
+    /// the same with either descriptor; values outside it keep their full
+    /// resolution. A deadzone of 0 keeps every value.
+    #[must_use]
+    pub const fn with_deadzone(self, deadzone: u8) -> Self {
+        Self {
+            left_stick_x: deadzone_i16(self.left_stick_x, deadzone),
+            left_stick_y: deadzone_i16(self.left_stick_y, deadzone),
+            right_stick_x: deadzone_i16(self.right_stick_x, deadzone),
+            right_stick_y: deadzone_i16(self.right_stick_y, deadzone),
+            ..self
+        }
+    }
+
+    /// Neutral/zero report.
+    #[must_use]
+    pub const fn neutral() -> Self {
+        Self {
+            buttons: 0,
+            left_stick_x: 0,
+            left_stick_y: 0,
+            right_stick_x: 0,
+            right_stick_y: 0,
+            left_trigger: 0,
+            right_trigger: 0,
+        }
+    }
+}
+
+/// Clamp `value` to the symmetric stick range declared by the xinput
+/// descriptor (-32767 to 32767).
+const fn clamp_axis_i16(value: i16) -> i16 {
+    if value < -i16::MAX {
+        -i16::MAX
+    } else {
+        value
+    }
+}
+
+/// Zero `value` if its magnitude is at most `deadzone` in 8-bit report units.
+const fn deadzone_i16(value: i16, deadzone: u8) -> i16 {
+    if deadzone != 0 && value.unsigned_abs() <= (deadzone as u16) << 8 {
+        0
+    } else {
+        value
+    }
+}
+
+// The 16-bit deadzone is symmetric around center and off at 0
+const _: () = {
+    let report = GamepadReportFull {
+        buttons: 0,
+        left_stick_x: 255,
+        left_stick_y: -256,
+        right_stick_x: 1025,
+        right_stick_y: -1025,
+        left_trigger: 0,
+        right_trigger: 0,
+    };
+
+    let zeroed = report.with_deadzone(0);
+    assert!(zeroed.left_stick_x == 255 && zeroed.left_stick_y == -256);
+    assert!(deadzone_i16(1, 0) == 1 && deadzone_i16(-1, 0) == -1);
+
+    let zeroed = report.with_deadzone(4);
+    assert!(zeroed.left_stick_x == 0 && zeroed.left_stick_y == 0);
+    assert!(zeroed.right_stick_x == 1025 && zeroed.right_stick_y == -1025);
+    assert!(deadzone_i16(1024, 4) == 0 && deadzone_i16(-1024, 4) == 0);
+    assert!(deadzone_i16(i16::MIN, 127) == i16::MIN && deadzone_i16(-32512, 127) == 0);
+};
+
+impl From<&GamepadState> for GamepadReportFull {
+    fn from(state: &GamepadState) -> Self {
+        Self::from_state(state, GuideMode::Button)
+    }
+}
+
+/// USB HID Gamepad report with the D-pad as a hat switch.
+///
+/// This matches the [`HidProfile::Hat`] report descriptor defined below.
+/// Total size: 9 bytes ([`GamepadReport`] followed by the hat switch byte)
+///
+/// The four D-pad buttons are cleared from the button bitfield and reported
+/// as one hat value instead (see [`Dpad::hat`]); opposing directions pressed
+/// together read as centered.
+#[cfg(not(feature = "buttons-only"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Format)]
+pub struct GamepadReportHat {
+    /// Buttons (without D-pad), sticks and triggers
+    pub report: GamepadReport,
+    /// Hat switch (0-7 clockwise from up, [`Dpad::HAT_CENTERED`] = centered)
+    pub hat: u8,
+}
+
+#[cfg(not(feature = "buttons-only"))]
+impl GamepadReportHat {
+    /// Size of the report in bytes.
+    pub const SIZE: usize = GamepadReport::SIZE + 1;
+
+    /// Convert the report to bytes: the [`GamepadReport`] bytes, then the hat.
+    #[must_use]
+    pub const fn as_bytes(&self) -> [u8; Self::SIZE] {
+        let report = self.report.as_bytes();
+        let mut out = [0u8; Self::SIZE];
+        let mut i = 0;
+        while i < report.len() {
+            out[i] = report[i];
+            i += 1;
+        }
+        out[GamepadReport::SIZE] = self.hat;
+        out
+    }
+
+    /// Convert the report to bytes, prefixed with a report ID.
+    #[must_use]
+    pub const fn as_bytes_with_id(&self, report_id: u8) -> [u8; Self::SIZE + 1] {
+        prefix_report_id(report_id, self.as_bytes())
+    }
+
+    /// Build a report from a gamepad state with the given guide routing.
+    #[must_use]
+    pub const fn from_state(state: &GamepadState, guide: GuideMode) -> Self {
+        let buttons = Buttons(state.buttons.raw() & !Buttons::DPAD.raw());
+        let without_dpad = GamepadState { buttons, ..*state };
+        Self {
+            report: GamepadReport::from_state(&without_dpad, guide),
+            hat: state.buttons.dpad().hat(),
+        }
+    }
+
+    /// Zero stick axes within `deadzone` of center (see
+    /// [`GamepadReport::with_deadzone`]).
+    #[must_use]
+    pub const fn with_deadzone(self, deadzone: u8) -> Self {
+        Self {
+            report: self.report.with_deadzone(deadzone),
+            hat: self.hat,
+        }
+    }
+
+    /// Neutral report with a centered hat.
+    #[must_use]
+    pub const fn neutral() -> Self {
+        Self {
+            report: GamepadReport::neutral(),
+            hat: Dpad::HAT_CENTERED,
+        }
+    }
+}
+
+#[cfg(not(feature = "buttons-only"))]
+impl Default for GamepadReportHat {
+    fn default() -> Self {
+        Self::neutral()
+    }
+}
+
+#[cfg(not(feature = "buttons-only"))]
+impl From<&GamepadState> for GamepadReportHat {
+    fn from(state: &GamepadState) -> Self {
+        Self::from_state(state, GuideMode::Button)
+    }
+}
+
+/// Buttons-only USB HID report structure.
+///
+/// This matches the buttons-only HID report descriptor defined below.
+/// Total size: 2 bytes (buttons: 2)
+///
+/// Stick and trigger values are still parsed from the input but not reported.
+#[cfg(feature = "buttons-only")]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Format)]
+#[repr(C)]
+pub struct GamepadReport {
+    /// Button bitfield (16 buttons)
+    pub buttons: u16,
+}
+
+#[cfg(feature = "buttons-only")]
+impl GamepadReport {
+    /// Size of the report in bytes.
+    pub const SIZE: usize = 2;
+
+    /// Convert the report to bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> [u8; Self::SIZE] {
+        self.buttons.to_le_bytes()
+    }
+
+    /// Convert the report to bytes, prefixed with a report ID.
+    #[must_use]
+    pub const fn as_bytes_with_id(&self, report_id: u8) -> [u8; Self::SIZE + 1] {
+        prefix_report_id(report_id, self.as_bytes())
+    }
+
+    /// Build a report from a gamepad state with the given guide routing.
+    #[must_use]
+    pub fn from_state(state: &GamepadState, guide: GuideMode) -> Self {
+        Self {
+            buttons: guide.apply(state.buttons.raw()),
+        }
+    }
+
+    /// Stick deadzone; the buttons-only report has no axes, so this returns
+    /// the report unchanged.
+    #[must_use]
+    pub const fn with_deadzone(self, _deadzone: u8) -> Self {
+        self
+    }
+
+    /// Neutral/zero report.
+    #[must_use]
+    pub const fn neutral() -> Self {
+        Self { buttons: 0 }
+    }
+}
+
+#[cfg(feature = "buttons-only")]
+impl From<&GamepadState> for GamepadReport {
+    fn from(state: &GamepadState) -> Self {
+        Self {
+            buttons: state.buttons.raw(),
+        }
+    }
+}
+
+/// HID report descriptor and report layout, selected at runtime.
+///
+/// One firmware works with every host: the profile is chosen at boot (e.g.
+/// from jumpers, see [`from_jumpers`](Self::from_jumpers)) and passed to both
+/// [`configure_usb_hid`] and [`UsbHidOutput::new`], which must agree. The
+/// `standard-hid`, `xinput-compat` and `hat-switch` features only choose
+/// [`DEFAULT`](Self::DEFAULT).
+///
+/// With `buttons-only` every profile uses the buttons-only descriptor and
+/// 2-byte report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Format)]
+pub enum HidProfile {
+    /// Gamepad with 8-bit sticks (cross-platform)
+    Standard,
+    /// Xbox-style gamepad with 16-bit sticks (better Windows game support)
+    XInput,
+    /// Joystick with 8-bit sticks, for DirectInput games that only list
+    /// joysticks
+    DInput,
+    /// Gamepad with 8-bit sticks and the D-pad as a hat switch, for games
+    /// that ignore D-pad buttons
+    Hat,
+}
+
+impl HidProfile {
+    /// Profile used without jumpers: `XInput` with `xinput-compat`, else
+    /// `Hat` with `hat-switch`, `Standard` otherwise.
+    pub const DEFAULT: Self = if cfg!(feature = "xinput-compat") {
+        Self::XInput
+    } else if cfg!(feature = "hat-switch") {
+        Self::Hat
+    } else {
+        Self::Standard
+    };
+
+    /// Select the profile from boot jumpers (`true` = jumper fitted).
+    ///
+    /// `xinput` wins if both are fitted; with neither the
+    /// [`DEFAULT`](Self::DEFAULT) profile is used.
+    #[must_use]
+    pub const fn from_jumpers(xinput: bool, dinput: bool) -> Self {
+        match (xinput, dinput) {
+            (true, _) => Self::XInput,
+            (false, true) => Self::DInput,
+            (false, false) => Self::DEFAULT,
+        }
+    }
+
+    /// Get the HID report descriptor of this profile.
+    #[cfg(not(feature = "buttons-only"))]
+    #[must_use]
+    pub const fn report_descriptor(self) -> &'static [u8] {
+        match self {
+            Self::Standard => STANDARD_REPORT_DESCRIPTOR,
+            Self::XInput => XINPUT_REPORT_DESCRIPTOR,
+            Self::DInput => DINPUT_REPORT_DESCRIPTOR,
+            Self::Hat => HAT_REPORT_DESCRIPTOR,
+        }
+    }
+
+    /// Get the HID report descriptor of this profile.
+    #[cfg(feature = "buttons-only")]
+    #[must_use]
+    pub const fn report_descriptor(self) -> &'static [u8] {
+        BUTTONS_ONLY_REPORT_DESCRIPTOR
+    }
+
+    /// Returns true if reports carry full 16-bit sticks ([`GamepadReportFull`])
+    /// instead of [`GamepadReport`].
+    #[must_use]
+    pub const fn has_16bit_sticks(self) -> bool {
+        !cfg!(feature = "buttons-only") && matches!(self, Self::XInput)
+    }
+
+    /// Returns true if reports carry the D-pad as a hat switch
+    /// ([`GamepadReportHat`]).
+    #[must_use]
+    pub const fn has_hat_switch(self) -> bool {
+        !cfg!(feature = "buttons-only") && matches!(self, Self::Hat)
+    }
+
+    /// Size of an input report in bytes, without report ID.
+    #[cfg(not(feature = "buttons-only"))]
+    #[must_use]
+    pub const fn report_size(self) -> usize {
+        match self {
+            Self::XInput => GamepadReportFull::SIZE,
+            Self::Hat => GamepadReportHat::SIZE,
+            Self::Standard | Self::DInput => GamepadReport::SIZE,
+        }
+    }
+
+    /// Size of an input report in bytes, without report ID.
+    #[cfg(feature = "buttons-only")]
+    #[must_use]
+    pub const fn report_size(self) -> usize {
+        GamepadReport::SIZE
+    }
 }
 
-/// Standard HID Gamepad Report Descriptor.
+impl Default for HidProfile {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Stick Logical Minimum of the 8-bit descriptors (1-byte item).
+#[cfg(all(not(feature = "buttons-only"), not(feature = "unsigned-sticks")))]
+const STICK_MIN: u8 = 0x81; // -127
+#[cfg(all(not(feature = "buttons-only"), feature = "unsigned-sticks"))]
+const STICK_MIN: u8 = 0x00;
+
+/// Low byte of the stick Logical Maximum (2-byte item, so 255 stays positive).
+#[cfg(all(not(feature = "buttons-only"), not(feature = "unsigned-sticks")))]
+const STICK_MAX: u8 = 0x7F;
+#[cfg(all(not(feature = "buttons-only"), feature = "unsigned-sticks"))]
+const STICK_MAX: u8 = 0xFF;
+
+/// Standard HID Gamepad Report Descriptor ([`HidProfile::Standard`]).
 ///
 /// This descriptor defines a gamepad with:
 /// - 16 buttons
-/// - 2 analog sticks (X/Y each, signed 8-bit)
+/// - 2 analog sticks (X/Y each, signed 8-bit, or 0-255 with `unsigned-sticks`)
 /// - 2 triggers (unsigned 8-bit)
-#[cfg(feature = "standard-hid")]
-pub const REPORT_DESCRIPTOR: &[u8] = &[
+/// - Output: 2 rumble motor magnitudes (unsigned 8-bit, see [`RumbleState`])
+#[cfg(not(feature = "buttons-only"))]
+pub const STANDARD_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x05, // Usage (Gamepad)
+    0xA1, 0x01, // Collection (Application)
+    //
+    // --- Buttons (16 buttons) ---
+    0x05, 0x09, //   Usage Page (Button)
+    0x19, 0x01, //   Usage Minimum (Button 1)
+    0x29, 0x10, //   Usage Maximum (Button 16)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x95, 0x10, //   Report Count (16)
+    0x75, 0x01, //   Report Size (1)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Left Stick ---
+    0x05, 0x01, //   Usage Page (Generic Desktop)
+    0x09, 0x30, //   Usage (X)
+    0x09, 0x31, //   Usage (Y)
+    0x15, STICK_MIN, //   Logical Minimum (-127, or 0)
+    0x26, STICK_MAX, 0x00, //   Logical Maximum (127, or 255)
+    0x95, 0x02, //   Report Count (2)
+    0x75, 0x08, //   Report Size (8)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Right Stick ---
+    0x09, 0x32, //   Usage (Z)
+    0x09, 0x35, //   Usage (Rz)
+    0x95, 0x02, //   Report Count (2)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Triggers ---
+    0x09, 0x33, //   Usage (Rx) - Left trigger
+    0x09, 0x34, //   Usage (Ry) - Right trigger
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x00, //   Logical Maximum (255)
+    0x95, 0x02, //   Report Count (2)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Rumble (output) ---
+    0x06, 0x00, 0xFF, //   Usage Page (Vendor Defined 0xFF00)
+    0x09, 0x01, //   Usage (Strong motor)
+    0x09, 0x02, //   Usage (Weak motor)
+    0x95, 0x02, //   Report Count (2)
+    0x91, 0x02, //   Output (Data, Variable, Absolute)
+    //
+    0xC0, // End Collection
+];
+
+/// DirectInput Joystick Report Descriptor ([`HidProfile::DInput`]).
+///
+/// Same layout and report as [`STANDARD_REPORT_DESCRIPTOR`], declared as a
+/// joystick instead of a gamepad for DirectInput games that ignore gamepads.
+#[cfg(not(feature = "buttons-only"))]
+pub const DINPUT_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x04, // Usage (Joystick)
+    0xA1, 0x01, // Collection (Application)
+    //
+    // --- Buttons (16 buttons) ---
+    0x05, 0x09, //   Usage Page (Button)
+    0x19, 0x01, //   Usage Minimum (Button 1)
+    0x29, 0x10, //   Usage Maximum (Button 16)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x95, 0x10, //   Report Count (16)
+    0x75, 0x01, //   Report Size (1)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Left Stick ---
+    0x05, 0x01, //   Usage Page (Generic Desktop)
+    0x09, 0x30, //   Usage (X)
+    0x09, 0x31, //   Usage (Y)
+    0x15, STICK_MIN, //   Logical Minimum (-127, or 0)
+    0x26, STICK_MAX, 0x00, //   Logical Maximum (127, or 255)
+    0x95, 0x02, //   Report Count (2)
+    0x75, 0x08, //   Report Size (8)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Right Stick ---
+    0x09, 0x32, //   Usage (Z)
+    0x09, 0x35, //   Usage (Rz)
+    0x95, 0x02, //   Report Count (2)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Triggers ---
+    0x09, 0x33, //   Usage (Rx) - Left trigger
+    0x09, 0x34, //   Usage (Ry) - Right trigger
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x00, //   Logical Maximum (255)
+    0x95, 0x02, //   Report Count (2)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    // --- Rumble (output) ---
+    0x06, 0x00, 0xFF, //   Usage Page (Vendor Defined 0xFF00)
+    0x09, 0x01, //   Usage (Strong motor)
+    0x09, 0x02, //   Usage (Weak motor)
+    0x95, 0x02, //   Report Count (2)
+    0x91, 0x02, //   Output (Data, Variable, Absolute)
+    //
+    0xC0, // End Collection
+];
+
+/// Hat switch Gamepad Report Descriptor ([`HidProfile::Hat`]).
+///
+/// Same as [`STANDARD_REPORT_DESCRIPTOR`] plus a 4-bit hat switch (and 4 bits
+/// of padding) after the triggers. Buttons 12-15, the D-pad bits of the
+/// standard report, stay declared but are never pressed.
+#[cfg(not(feature = "buttons-only"))]
+pub const HAT_REPORT_DESCRIPTOR: &[u8] = &[
     0x05, 0x01, // Usage Page (Generic Desktop)
     0x09, 0x05, // Usage (Gamepad)
     0xA1, 0x01, // Collection (Application)
@@ -107,8 +870,8 @@ pub const REPORT_DESCRIPTOR: &[u8] = &[
     0x05, 0x01, //   Usage Page (Generic Desktop)
     0x09, 0x30, //   Usage (X)
     0x09, 0x31, //   Usage (Y)
-    0x15, 0x81, //   Logical Minimum (-127)
-    0x25, 0x7F, //   Logical Maximum (127)
+    0x15, STICK_MIN, //   Logical Minimum (-127, or 0)
+    0x26, STICK_MAX, 0x00, //   Logical Maximum (127, or 255)
     0x95, 0x02, //   Report Count (2)
     0x75, 0x08, //   Report Size (8)
     0x81, 0x02, //   Input (Data, Variable, Absolute)
@@ -127,15 +890,63 @@ pub const REPORT_DESCRIPTOR: &[u8] = &[
     0x95, 0x02, //   Report Count (2)
     0x81, 0x02, //   Input (Data, Variable, Absolute)
     //
+    // --- Hat switch (D-pad) ---
+    0x09, 0x39, //   Usage (Hat switch)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x07, //   Logical Maximum (7)
+    0x35, 0x00, //   Physical Minimum (0)
+    0x46, 0x3B, 0x01, //   Physical Maximum (315)
+    0x65, 0x14, //   Unit (Degrees)
+    0x95, 0x01, //   Report Count (1)
+    0x75, 0x04, //   Report Size (4)
+    0x81, 0x42, //   Input (Data, Variable, Absolute, Null State)
+    0x65, 0x00, //   Unit (None)
+    0x45, 0x00, //   Physical Maximum (0)
+    0x81, 0x03, //   Input (Constant) - 4-bit padding
+    //
+    // --- Rumble (output) ---
+    0x06, 0x00, 0xFF, //   Usage Page (Vendor Defined 0xFF00)
+    0x09, 0x01, //   Usage (Strong motor)
+    0x09, 0x02, //   Usage (Weak motor)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x26, 0xFF, 0x00, //   Logical Maximum (255)
+    0x95, 0x02, //   Report Count (2)
+    0x75, 0x08, //   Report Size (8)
+    0x91, 0x02, //   Output (Data, Variable, Absolute)
+    //
     0xC0, // End Collection
 ];
 
-/// XInput-compatible HID Report Descriptor.
+// The hat report matches its descriptor: D-pad bits move from the buttons to
+// the hat byte, centered when no (or no consistent) direction is pressed
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    assert!(report_bits(HAT_REPORT_DESCRIPTOR, INPUT_ITEM) == GamepadReportHat::SIZE * 8);
+    assert!(HidProfile::Hat.report_size() == GamepadReportHat::SIZE);
+    assert!(HidProfile::Hat.has_hat_switch() && !HidProfile::Standard.has_hat_switch());
+
+    let mut state = GamepadState::neutral();
+    state.buttons = Buttons(Buttons::A.raw() | Dpad::UpRight.buttons().raw());
+    let bytes = GamepadReportHat::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[0] == 0x01 && bytes[1] == 0x00);
+    assert!(bytes[8] == 1);
+
+    state.buttons = Buttons(Buttons::DPAD_LEFT.raw() | Buttons::DPAD_RIGHT.raw());
+    let bytes = GamepadReportHat::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[0] == 0x00 && bytes[1] == 0x00);
+    assert!(bytes[8] == Dpad::HAT_CENTERED);
+
+    let bytes = GamepadReportHat::neutral().as_bytes();
+    assert!(bytes[8] == Dpad::HAT_CENTERED);
+};
+
+/// XInput-compatible HID Report Descriptor ([`HidProfile::XInput`]).
 ///
 /// This descriptor attempts to be recognized as an Xbox controller
-/// for better compatibility with Windows games.
-#[cfg(feature = "xinput-compat")]
-pub const REPORT_DESCRIPTOR: &[u8] = &[
+/// for better compatibility with Windows games. Like the standard descriptor
+/// it declares a 2-byte rumble output report (see [`RumbleState`]).
+#[cfg(not(feature = "buttons-only"))]
+pub const XINPUT_REPORT_DESCRIPTOR: &[u8] = &[
     0x05, 0x01, // Usage Page (Generic Desktop)
     0x09, 0x05, // Usage (Gamepad)
     0xA1, 0x01, // Collection (Application)
@@ -176,30 +987,282 @@ pub const REPORT_DESCRIPTOR: &[u8] = &[
     0x75, 0x08, //     Report Size (8)
     0x81, 0x02, //     Input (Data, Variable, Absolute)
     //
+    // --- Rumble (output) ---
+    0x06, 0x00, 0xFF, //     Usage Page (Vendor Defined 0xFF00)
+    0x09, 0x01, //     Usage (Strong motor)
+    0x09, 0x02, //     Usage (Weak motor)
+    0x95, 0x02, //     Report Count (2)
+    0x91, 0x02, //     Output (Data, Variable, Absolute)
+    //
     0xC0, //   End Collection
     0xC0, // End Collection
 ];
 
+// Guide is HID button 9: bit 0 of the second byte of the xinput report
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    const fn guide_report(mode: GuideMode) -> [u8; GamepadReportFull::SIZE] {
+        GamepadReportFull {
+            buttons: mode.apply(Buttons::GUIDE.raw()),
+            ..GamepadReportFull::neutral()
+        }
+        .as_bytes()
+    }
+
+    let bytes = guide_report(GuideMode::Button);
+    assert!(bytes[0] == 0x00 && bytes[1] == 0x01);
+
+    let bytes = guide_report(GuideMode::Suppress);
+    assert!(bytes[0] == 0x00 && bytes[1] == 0x00);
+
+    // Button 16 (bit 15) as a dedicated home button
+    let bytes = guide_report(GuideMode::Remap(Buttons(1 << 15)));
+    assert!(bytes[0] == 0x00 && bytes[1] == 0x80);
+};
+
+// The xinput descriptor declares 16-bit sticks: the full report must match it
+// and carry full-scale values (e.g. a CRSF channel at max, 32767) unchanged
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    assert!(report_bits(XINPUT_REPORT_DESCRIPTOR, INPUT_ITEM) == GamepadReportFull::SIZE * 8);
+    assert!(HidProfile::XInput.report_size() == GamepadReportFull::SIZE);
+    assert!(core::mem::size_of::<GamepadReportFull>() == GamepadReportFull::SIZE);
+
+    let mut state = GamepadState::neutral();
+    state.left_stick.x = i16::MAX;
+    state.right_stick.y = -i16::MAX;
+    let bytes = GamepadReportFull::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes[2] == 0xFF && bytes[3] == 0x7F);
+    assert!(bytes[8] == 0x01 && bytes[9] == 0x80);
+
+    // Both ends of the i16 range encode within the declared logical range
+    let min = logical_i16(XINPUT_REPORT_DESCRIPTOR, LOGICAL_MINIMUM_ITEM);
+    let max = logical_i16(XINPUT_REPORT_DESCRIPTOR, LOGICAL_MAXIMUM_ITEM);
+    state.left_stick.y = i16::MIN;
+    state.right_stick.x = i16::MAX;
+    let bytes = GamepadReportFull::from_state(&state, GuideMode::Button).as_bytes();
+    assert!(bytes.len() == 12);
+    assert!(i16::from_le_bytes([bytes[4], bytes[5]]) == min);
+    assert!(i16::from_le_bytes([bytes[6], bytes[7]]) == max);
+};
+
+/// Buttons-only HID Report Descriptor, used by every profile with
+/// `buttons-only`.
+///
+/// This descriptor defines a minimal gamepad (e.g. arcade stick) with:
+/// - 16 buttons
+/// - No axes
+#[cfg(feature = "buttons-only")]
+pub const BUTTONS_ONLY_REPORT_DESCRIPTOR: &[u8] = &[
+    0x05, 0x01, // Usage Page (Generic Desktop)
+    0x09, 0x05, // Usage (Gamepad)
+    0xA1, 0x01, // Collection (Application)
+    //
+    // --- Buttons (16 buttons) ---
+    0x05, 0x09, //   Usage Page (Button)
+    0x19, 0x01, //   Usage Minimum (Button 1)
+    0x29, 0x10, //   Usage Maximum (Button 16)
+    0x15, 0x00, //   Logical Minimum (0)
+    0x25, 0x01, //   Logical Maximum (1)
+    0x95, 0x10, //   Report Count (16)
+    0x75, 0x01, //   Report Size (1)
+    0x81, 0x02, //   Input (Data, Variable, Absolute)
+    //
+    0xC0, // End Collection
+];
+
+// The buttons-only report must stay 2 bytes and match its descriptor
+#[cfg(feature = "buttons-only")]
+const _: () = {
+    assert!(GamepadReport::SIZE == 2);
+    assert!(core::mem::size_of::<GamepadReport>() == GamepadReport::SIZE);
+    assert!(report_bits(BUTTONS_ONLY_REPORT_DESCRIPTOR, INPUT_ITEM) == GamepadReport::SIZE * 8);
+    assert!(!HidProfile::XInput.has_16bit_sticks());
+};
+
+/// Main item tag of Input items.
+const INPUT_ITEM: u8 = 0x80;
+
+/// Main item tag of Output items.
+#[cfg(not(feature = "buttons-only"))]
+const OUTPUT_ITEM: u8 = 0x90;
+
+/// Global item tag of Logical Minimum.
+#[cfg(not(feature = "buttons-only"))]
+const LOGICAL_MINIMUM_ITEM: u8 = 0x14;
+
+/// Global item tag of Logical Maximum.
+#[cfg(not(feature = "buttons-only"))]
+const LOGICAL_MAXIMUM_ITEM: u8 = 0x24;
+
+/// Value of the first 16-bit `item` (Logical Minimum or Maximum) in a HID
+/// report descriptor, i.e. the range of the 16-bit axes.
+#[cfg(not(feature = "buttons-only"))]
+const fn logical_i16(descriptor: &[u8], item: u8) -> i16 {
+    let mut i = 0;
+
+    while i < descriptor.len() {
+        let prefix = descriptor[i];
+        let len = match prefix & 0x03 {
+            3 => 4,
+            n => n as usize,
+        };
+
+        if prefix & 0xFC == item && len == 2 {
+            return i16::from_le_bytes([descriptor[i + 1], descriptor[i + 2]]);
+        }
+
+        i += 1 + len;
+    }
+
+    panic!("no 16-bit logical range in descriptor")
+}
+
+/// Total size in bits of all `item` (Input or Output) main items in a HID
+/// report descriptor.
+///
+/// Walks the short items, tracking the global Report Size and Report Count,
+/// and sums `size * count` at each matching main item.
+const fn report_bits(descriptor: &[u8], item: u8) -> usize {
+    let mut bits = 0;
+    let mut report_size = 0;
+    let mut report_count = 0;
+    let mut i = 0;
+
+    while i < descriptor.len() {
+        let prefix = descriptor[i];
+        let len = match prefix & 0x03 {
+            3 => 4,
+            n => n as usize,
+        };
+
+        let mut value = 0usize;
+        let mut j = 0;
+        while j < len {
+            value |= (descriptor[i + 1 + j] as usize) << (8 * j);
+            j += 1;
+        }
+
+        match prefix & 0xFC {
+            0x74 => report_size = value,  // Report Size
+            0x94 => report_count = value, // Report Count
+            tag if tag == item => bits += report_size * report_count,
+            _ => {}
+        }
+
+        i += 1 + len;
+    }
+
+    bits
+}
+
+// The 8-bit reports must match their descriptors, and every rumble output
+// report what the request handler decodes
+#[cfg(not(feature = "buttons-only"))]
+const _: () = {
+    assert!(report_bits(STANDARD_REPORT_DESCRIPTOR, INPUT_ITEM) == GamepadReport::SIZE * 8);
+    assert!(report_bits(DINPUT_REPORT_DESCRIPTOR, INPUT_ITEM) == GamepadReport::SIZE * 8);
+    assert!(HidProfile::Standard.report_size() == GamepadReport::SIZE);
+    assert!(HidProfile::DInput.report_size() == GamepadReport::SIZE);
+
+    let profiles = [
+        HidProfile::Standard,
+        HidProfile::XInput,
+        HidProfile::DInput,
+        HidProfile::Hat,
+    ];
+    let mut i = 0;
+    while i < profiles.len() {
+        let descriptor = profiles[i].report_descriptor();
+        assert!(report_bits(descriptor, OUTPUT_ITEM) == RumbleState::SIZE * 8);
+        i += 1;
+    }
+};
+
+/// Largest input report sent by [`UsbHidOutput`] (HID writer buffer and
+/// endpoint packet size).
+pub const MAX_REPORT_SIZE: usize = 16;
+
+// Reports must still fit with a report ID prefix
+const _: () = assert!(GamepadReport::SIZE + 1 <= MAX_REPORT_SIZE);
+const _: () = assert!(GamepadReportFull::SIZE + 1 <= MAX_REPORT_SIZE);
+#[cfg(not(feature = "buttons-only"))]
+const _: () = assert!(GamepadReportHat::SIZE + 1 <= MAX_REPORT_SIZE);
+
+/// HID writer type used for gamepad reports.
+pub type GamepadHidWriter<'d> =
+    HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, MAX_REPORT_SIZE>;
+
 /// USB HID gamepad output.
 ///
-/// Wraps an embassy-usb HID writer to send gamepad reports.
+/// Wraps an embassy-usb HID writer to send gamepad reports. With the
+/// [`XInput`](HidProfile::XInput) profile the 16-bit [`GamepadReportFull`] is
+/// sent, with [`Hat`](HidProfile::Hat) the [`GamepadReportHat`], otherwise
+/// [`GamepadReport`].
 pub struct UsbHidOutput<'d> {
-    writer: HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8>,
+    writer: GamepadHidWriter<'d>,
     ready: bool,
+    /// Report layout, must match the descriptor the writer was configured with
+    profile: HidProfile,
+    /// Guide button routing applied to every report
+    guide: GuideMode,
+    /// Report ID prefixed to every report, if any
+    report_id: Option<u8>,
+    /// Stick deadzone applied to the 8-bit report (0 = off)
+    report_deadzone: u8,
 }
 
 impl<'d> UsbHidOutput<'d> {
     /// Create a new USB HID output from the given HID writer.
+    ///
+    /// `profile` must be the one passed to [`configure_usb_hid`].
     #[must_use]
-    pub fn new(
-        writer: HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8>,
-    ) -> Self {
+    pub fn new(writer: GamepadHidWriter<'d>, profile: HidProfile) -> Self {
         Self {
             writer,
             ready: false,
+            profile,
+            guide: GuideMode::Button,
+            report_id: None,
+            report_deadzone: 0,
         }
     }
 
+    /// Prefix every report with `report_id`.
+    ///
+    /// Required when the HID report descriptor declares a Report ID, e.g. for
+    /// composite devices sharing one endpoint. The built-in descriptors do not,
+    /// so only use this together with a matching custom descriptor.
+    #[must_use]
+    pub fn with_report_id(mut self, report_id: u8) -> Self {
+        self.report_id = Some(report_id);
+        self
+    }
+
+    /// Zero stick axes within `deadzone` of center in the HID report.
+    ///
+    /// Unlike an input-side deadzone this only changes what the host sees:
+    /// the state passed to [`send`](OutputSink::send) is left as is, so it
+    /// can still be echoed or passed through unmodified. `deadzone` is in i8
+    /// report units (see [`GamepadReport::with_deadzone`]), also with the
+    /// 16-bit [`XInput`](HidProfile::XInput) profile.
+    #[must_use]
+    pub fn with_report_deadzone(mut self, deadzone: u8) -> Self {
+        self.report_deadzone = deadzone;
+        self
+    }
+
+    /// Get the HID profile reports are encoded for.
+    #[must_use]
+    pub const fn profile(&self) -> HidProfile {
+        self.profile
+    }
+
+    /// Set how the guide button is reported (see [`GuideMode`]).
+    pub fn set_guide_mode(&mut self, mode: GuideMode) {
+        self.guide = mode;
+    }
+
     /// Wait until the device is ready (USB enumerated).
     pub async fn wait_ready(&mut self) {
         self.writer.ready().await;
@@ -209,11 +1272,33 @@ impl<'d> UsbHidOutput<'d> {
 
 impl OutputSink for UsbHidOutput<'_> {
     async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
-        let report = GamepadReport::from(state);
-        self.writer
-            .write(&report.as_bytes())
-            .await
-            .map_err(|_| OutputError::Io)
+        #[cfg(not(feature = "buttons-only"))]
+        if self.profile.has_hat_switch() {
+            let report =
+                GamepadReportHat::from_state(state, self.guide).with_deadzone(self.report_deadzone);
+            let result = match self.report_id {
+                Some(id) => self.writer.write(&report.as_bytes_with_id(id)).await,
+                None => self.writer.write(&report.as_bytes()).await,
+            };
+            return result.map_err(|_| OutputError::Io);
+        }
+
+        let result = if self.profile.has_16bit_sticks() {
+            let report = GamepadReportFull::from_state(state, self.guide)
+                .with_deadzone(self.report_deadzone);
+            match self.report_id {
+                Some(id) => self.writer.write(&report.as_bytes_with_id(id)).await,
+                None => self.writer.write(&report.as_bytes()).await,
+            }
+        } else {
+            let report =
+                GamepadReport::from_state(state, self.guide).with_deadzone(self.report_deadzone);
+            match self.report_id {
+                Some(id) => self.writer.write(&report.as_bytes_with_id(id)).await,
+                None => self.writer.write(&report.as_bytes()).await,
+            }
+        };
+        result.map_err(|_| OutputError::Io)
     }
 
     fn is_ready(&self) -> bool {
@@ -221,15 +1306,27 @@ impl OutputSink for UsbHidOutput<'_> {
     }
 }
 
+/// Latest rumble request from the host.
+static RUMBLE_SIGNAL: Signal<CriticalSectionRawMutex, RumbleState> = Signal::new();
+
+/// Get the signal carrying rumble requests from the host.
+///
+/// [`GamepadRequestHandler`] signals every rumble output report it receives;
+/// only the latest value is kept. The application can wait on it, e.g. to
+/// forward rumble back over the UART.
+#[must_use]
+pub fn rumble_signal() -> &'static Signal<CriticalSectionRawMutex, RumbleState> {
+    &RUMBLE_SIGNAL
+}
+
 /// HID request handler for gamepad output reports.
 ///
-/// This handler accepts all HID requests but doesn't process them because:
-/// - **Input-only device**: We only send input reports (device → host), not output reports
-/// - **No rumble/LED support**: Gamepads often receive output reports for rumble motors
-///   or LED indicators, but this bridge doesn't have such hardware
+/// - **Rumble**: Output reports (SET_REPORT) are decoded into a [`RumbleState`]
+///   and passed on through [`rumble_signal`]. Reports too short to decode are
+///   rejected.
 /// - **Idle rate**: The idle rate settings are not applicable for a polled input device
 ///
-/// All requests are accepted silently to maintain USB compliance without side effects.
+/// Other requests are accepted silently to maintain USB compliance without side effects.
 pub struct GamepadRequestHandler;
 
 impl RequestHandler for GamepadRequestHandler {
@@ -237,8 +1334,17 @@ impl RequestHandler for GamepadRequestHandler {
         None
     }
 
-    fn set_report(&mut self, _id: ReportId, _data: &[u8]) -> OutResponse {
-        OutResponse::Accepted
+    fn set_report(&mut self, id: ReportId, data: &[u8]) -> OutResponse {
+        if !matches!(id, ReportId::Out(_)) {
+            return OutResponse::Accepted;
+        }
+        match RumbleState::from_report(data) {
+            Some(rumble) => {
+                RUMBLE_SIGNAL.signal(rumble);
+                OutResponse::Accepted
+            }
+            None => OutResponse::Rejected,
+        }
     }
 
     fn set_idle_ms(&mut self, _id: Option<ReportId>, _duration_ms: u32) {}
@@ -250,16 +1356,20 @@ impl RequestHandler for GamepadRequestHandler {
 
 /// Configure the USB HID class in the USB builder.
 ///
-/// Returns the HID writer for use by the application.
+/// The report descriptor is taken from `profile`; pass the same profile to
+/// [`UsbHidOutput::new`]. `handler` receives the host's output reports (see
+/// [`rumble_signal`]). Returns the HID writer for use by the application.
 pub fn configure_usb_hid<'d>(
     builder: &mut Builder<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>>,
     state: &'d mut State<'d>,
-) -> HidWriter<'d, embassy_rp::usb::Driver<'d, embassy_rp::peripherals::USB>, 8> {
+    handler: &'d mut GamepadRequestHandler,
+    profile: HidProfile,
+) -> GamepadHidWriter<'d> {
     let config = embassy_usb::class::hid::Config {
-        report_descriptor: REPORT_DESCRIPTOR,
-        request_handler: None,
+        report_descriptor: profile.report_descriptor(),
+        request_handler: Some(handler),
         poll_ms: 1,
-        max_packet_size: 8,
+        max_packet_size: MAX_REPORT_SIZE as u16,
         hid_subclass: embassy_usb::class::hid::HidSubclass::No,
         hid_boot_protocol: embassy_usb::class::hid::HidBootProtocol::None,
     };
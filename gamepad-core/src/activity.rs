@@ -0,0 +1,239 @@
+//! Compact summary of which controls are in use.
+//!
+//! For logging how a controller is used (e.g. over a slow telemetry link),
+//! the full state is more than needed. [`ActivityEncoder`] compares each
+//! state with a baseline (normally neutral) and reduces it to an
+//! [`ActivitySummary`]: one bit per control group and the number of buttons
+//! that differ, two bytes on the wire.
+
+use gamepad_proto::{Buttons, GamepadState};
+
+/// Control groups that differ from the baseline, as a bitmask.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Activity(pub u8);
+
+impl Activity {
+    /// Left stick deflected
+    pub const LEFT_STICK: Self = Self(1 << 0);
+    /// Right stick deflected
+    pub const RIGHT_STICK: Self = Self(1 << 1);
+    /// Left trigger engaged
+    pub const LEFT_TRIGGER: Self = Self(1 << 2);
+    /// Right trigger engaged
+    pub const RIGHT_TRIGGER: Self = Self(1 << 3);
+    /// Any face, shoulder, menu or stick button changed
+    pub const BUTTONS: Self = Self(1 << 4);
+    /// Any D-pad direction changed
+    pub const DPAD: Self = Self(1 << 5);
+
+    /// Nothing differs from the baseline.
+    pub const NONE: Self = Self(0);
+
+    /// Check if all of the given groups are active.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, other: Activity) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Check if no group is active.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::ops::BitOr for Activity {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Activity of one state relative to the baseline.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ActivitySummary {
+    /// Control groups that differ from the baseline
+    pub active: Activity,
+    /// Number of buttons (including D-pad) that differ from the baseline
+    pub buttons: u8,
+}
+
+impl ActivitySummary {
+    /// Size of the encoded summary in bytes.
+    pub const SIZE: usize = 2;
+
+    /// Encode as `[activity bits, button count]`.
+    #[must_use]
+    pub const fn encode(&self) -> [u8; Self::SIZE] {
+        [self.active.0, self.buttons]
+    }
+
+    /// Decode a summary written by [`encode`](Self::encode).
+    #[must_use]
+    pub const fn decode(bytes: [u8; Self::SIZE]) -> Self {
+        Self {
+            active: Activity(bytes[0]),
+            buttons: bytes[1],
+        }
+    }
+}
+
+/// Reduces states to [`ActivitySummary`] values relative to a baseline.
+#[derive(Debug, Clone)]
+pub struct ActivityEncoder {
+    /// State counted as "not in use"
+    baseline: GamepadState,
+    /// Axis deviation above which a stick counts as deflected
+    stick_threshold: u16,
+    /// Deviation above which a trigger counts as engaged
+    trigger_threshold: u8,
+}
+
+impl ActivityEncoder {
+    /// Default stick threshold, about 5% of full deflection.
+    pub const DEFAULT_STICK_THRESHOLD: u16 = 1_600;
+
+    /// Default trigger threshold, about 5% of full travel.
+    pub const DEFAULT_TRIGGER_THRESHOLD: u8 = 12;
+
+    /// Create an encoder comparing against `baseline` with default thresholds.
+    #[must_use]
+    pub const fn new(baseline: GamepadState) -> Self {
+        Self {
+            baseline,
+            stick_threshold: Self::DEFAULT_STICK_THRESHOLD,
+            trigger_threshold: Self::DEFAULT_TRIGGER_THRESHOLD,
+        }
+    }
+
+    /// Set the deviations above which sticks and triggers count as active.
+    #[must_use]
+    pub const fn with_thresholds(mut self, stick: u16, trigger: u8) -> Self {
+        self.stick_threshold = stick;
+        self.trigger_threshold = trigger;
+        self
+    }
+
+    /// Get the baseline state.
+    #[inline]
+    #[must_use]
+    pub const fn baseline(&self) -> &GamepadState {
+        &self.baseline
+    }
+
+    /// Summarize `state` relative to the baseline.
+    #[must_use]
+    pub fn summarize(&self, state: &GamepadState) -> ActivitySummary {
+        let base = &self.baseline;
+        let axis_active = |a: i16, b: i16| a.abs_diff(b) > self.stick_threshold;
+        let trigger_active = |a: u8, b: u8| a.abs_diff(b) > self.trigger_threshold;
+        let changed = state.buttons.0 ^ base.buttons.0;
+
+        let groups = [
+            (
+                axis_active(state.left_stick.x, base.left_stick.x)
+                    || axis_active(state.left_stick.y, base.left_stick.y),
+                Activity::LEFT_STICK,
+            ),
+            (
+                axis_active(state.right_stick.x, base.right_stick.x)
+                    || axis_active(state.right_stick.y, base.right_stick.y),
+                Activity::RIGHT_STICK,
+            ),
+            (
+                trigger_active(state.left_trigger, base.left_trigger),
+                Activity::LEFT_TRIGGER,
+            ),
+            (
+                trigger_active(state.right_trigger, base.right_trigger),
+                Activity::RIGHT_TRIGGER,
+            ),
+            (changed & !Buttons::DPAD.0 != 0, Activity::BUTTONS),
+            (changed & Buttons::DPAD.0 != 0, Activity::DPAD),
+        ];
+
+        let active = groups
+            .into_iter()
+            .filter(|(is_active, _)| *is_active)
+            .fold(Activity::NONE, |acc, (_, group)| acc | group);
+        ActivitySummary {
+            active,
+            buttons: changed.count_ones() as u8,
+        }
+    }
+
+    /// Summarize `state` and encode it (see [`ActivitySummary::encode`]).
+    #[must_use]
+    pub fn encode(&self, state: &GamepadState) -> [u8; ActivitySummary::SIZE] {
+        self.summarize(state).encode()
+    }
+}
+
+impl Default for ActivityEncoder {
+    fn default() -> Self {
+        Self::new(GamepadState::neutral())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamepad_proto::AnalogStick;
+
+    #[test]
+    fn test_partially_active_state() {
+        let encoder = ActivityEncoder::default();
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::LB | Buttons::DPAD_UP,
+            left_stick: AnalogStick::new(0, -20_000),
+            // Within the threshold: resting noise
+            right_stick: AnalogStick::new(900, -1_000),
+            left_trigger: 0,
+            right_trigger: 128,
+        };
+
+        let summary = encoder.summarize(&state);
+        assert_eq!(
+            summary.active,
+            Activity::LEFT_STICK | Activity::RIGHT_TRIGGER | Activity::BUTTONS | Activity::DPAD
+        );
+        assert!(!summary.active.contains(Activity::RIGHT_STICK));
+        assert_eq!(summary.buttons, 3);
+
+        let bytes = encoder.encode(&state);
+        assert_eq!(bytes, [0b0011_1001, 3]);
+        assert_eq!(ActivitySummary::decode(bytes), summary);
+    }
+
+    #[test]
+    fn test_idle_and_custom_baseline() {
+        let encoder = ActivityEncoder::default();
+        let summary = encoder.summarize(&GamepadState::neutral());
+        assert!(summary.active.is_empty());
+        assert_eq!(summary.buttons, 0);
+
+        // A trigger resting half-pressed is not activity against its baseline
+        let baseline = GamepadState {
+            left_trigger: 128,
+            ..GamepadState::neutral()
+        };
+        let encoder = ActivityEncoder::new(baseline).with_thresholds(100, 0);
+        assert!(encoder.summarize(&baseline).active.is_empty());
+
+        let state = GamepadState {
+            left_trigger: 0,
+            right_stick: AnalogStick::new(101, 0),
+            ..baseline
+        };
+        assert_eq!(
+            encoder.summarize(&state).active,
+            Activity::LEFT_TRIGGER | Activity::RIGHT_STICK
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Analog joystick input sampled by an ADC.
+//!
+//! Truly analog joysticks (potentiometers) can be wired straight to the
+//! microcontroller's ADC instead of going through a second board. Each ADC
+//! channel is bound to one stick axis or trigger with an [`AdcCalibration`]
+//! describing the raw readings at the ends of travel and at rest, plus
+//! optional intermediate points for sticks that are non-linear mid-range.
+//!
+//! `embedded-hal-async` has no ADC trait, so the chip-specific driver is
+//! wrapped in the small [`AdcReader`] trait. The raw-to-axis conversion is
+//! plain arithmetic and is tested on the host.
+
+use core::future::Future;
+
+use crate::input::{InputError, InputSource};
+use gamepad_proto::{GamepadState, StickAxis};
+
+/// Maximum number of intermediate points of an [`AdcCalibration`].
+pub const MAX_CALIBRATION_POINTS: usize = 4;
+
+/// Raw reading and the stick value it should produce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CalibrationPoint {
+    /// Raw ADC reading
+    pub raw: u16,
+    /// Stick axis value at this reading
+    pub value: i16,
+}
+
+impl CalibrationPoint {
+    /// Create a calibration point.
+    #[must_use]
+    pub const fn new(raw: u16, value: i16) -> Self {
+        Self { raw, value }
+    }
+}
+
+/// Raw ADC readings at the ends of travel and at rest for one channel.
+///
+/// Readings below `min` or above `max` are clamped. `center` may be anywhere
+/// between them; each half of travel is scaled separately so an off-center
+/// resting point still reads 0.
+///
+/// Sticks that are non-linear mid-range can add up to
+/// [`MAX_CALIBRATION_POINTS`] intermediate points with
+/// [`with_point`](Self::with_point). Stick values are then interpolated
+/// linearly between neighbouring points, so each measured point maps exactly
+/// to its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AdcCalibration {
+    /// Reading at the low end of travel
+    pub min: u16,
+    /// Reading at rest
+    pub center: u16,
+    /// Reading at the high end of travel
+    pub max: u16,
+    /// Intermediate points, the first `point_count` are used
+    points: [CalibrationPoint; MAX_CALIBRATION_POINTS],
+    point_count: u8,
+}
+
+impl AdcCalibration {
+    /// Full range of a 12-bit ADC, centered.
+    pub const FULL_12BIT: Self = Self::new(0, 2048, 4095);
+
+    /// Create a calibration from measured readings.
+    #[must_use]
+    pub const fn new(min: u16, center: u16, max: u16) -> Self {
+        Self {
+            min,
+            center,
+            max,
+            points: [CalibrationPoint::new(0, 0); MAX_CALIBRATION_POINTS],
+            point_count: 0,
+        }
+    }
+
+    /// Add an intermediate point: `raw` should read as stick `value`.
+    ///
+    /// Points may be added in any order. Points at or outside `min`/`max` or
+    /// at `center` are ignored, as those readings are fixed at -32768, 32767
+    /// and 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_CALIBRATION_POINTS`] points are added (at
+    /// compile time for a `const` calibration).
+    #[must_use]
+    pub const fn with_point(mut self, raw: u16, value: i16) -> Self {
+        let index = self.point_count as usize;
+        assert!(
+            index < MAX_CALIBRATION_POINTS,
+            "too many calibration points"
+        );
+        self.points[index] = CalibrationPoint::new(raw, value);
+        self.point_count += 1;
+        self
+    }
+
+    /// Get the intermediate points in the order they were added.
+    #[must_use]
+    pub fn points(&self) -> &[CalibrationPoint] {
+        &self.points[..usize::from(self.point_count)]
+    }
+
+    /// Convert a raw reading to a stick axis value (-32768 to 32767).
+    #[must_use]
+    pub fn to_stick(&self, raw: u16) -> i16 {
+        let raw = raw.clamp(self.min, self.max.max(self.min));
+        if raw == self.center {
+            return 0;
+        }
+
+        // Nearest points below and above the reading; the center wins ties
+        // with the ends of travel
+        let mut low = CalibrationPoint::new(self.min, i16::MIN);
+        let mut high = CalibrationPoint::new(self.max, i16::MAX);
+        let inner = self
+            .points()
+            .iter()
+            .filter(|p| p.raw > self.min && p.raw < self.max && p.raw != self.center);
+        for point in [CalibrationPoint::new(self.center, 0)].iter().chain(inner) {
+            if point.raw <= raw && point.raw >= low.raw {
+                low = *point;
+            }
+            if point.raw > raw && point.raw <= high.raw {
+                high = *point;
+            }
+        }
+
+        // Interpolate from the point nearer the center, so values round
+        // toward it. Raw readings are subtracted as i64 and `max(1)` keeps
+        // inconsistent calibrations (e.g. `min > max`) from underflowing or
+        // dividing by zero
+        let (raw, low_raw, high_raw) = (i64::from(raw), i64::from(low.raw), i64::from(high.raw));
+        let span = (high_raw - low_raw).max(1);
+        let delta = i64::from(high.value) - i64::from(low.value);
+        let value = if raw < i64::from(self.center) {
+            i64::from(high.value) - (high_raw - raw) * delta / span
+        } else {
+            i64::from(low.value) + (raw - low_raw) * delta / span
+        };
+        value.clamp(i64::from(i16::MIN), i64::from(i16::MAX)) as i16
+    }
+
+    /// Convert a raw reading to a trigger value (0-255), ignoring `center`.
+    #[must_use]
+    pub fn to_trigger(&self, raw: u16) -> u8 {
+        let span = u32::from(self.max.saturating_sub(self.min));
+        if span == 0 {
+            return 0;
+        }
+        let offset = u32::from(raw.clamp(self.min, self.max).saturating_sub(self.min));
+        (offset * 255 / span) as u8
+    }
+}
+
+impl Default for AdcCalibration {
+    fn default() -> Self {
+        Self::FULL_12BIT
+    }
+}
+
+/// Gamepad control driven by one ADC channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AdcTarget {
+    /// One stick axis
+    Stick(StickAxis),
+    /// Left trigger
+    LeftTrigger,
+    /// Right trigger
+    RightTrigger,
+}
+
+/// Binding of one ADC channel to a gamepad control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AdcChannel {
+    /// Control written from this channel
+    pub target: AdcTarget,
+    /// Calibration of this channel
+    pub calibration: AdcCalibration,
+}
+
+impl AdcChannel {
+    /// Bind a channel to `target` with `calibration`.
+    #[must_use]
+    pub const fn new(target: AdcTarget, calibration: AdcCalibration) -> Self {
+        Self {
+            target,
+            calibration,
+        }
+    }
+
+    /// Write the converted reading to the bound control of `state`.
+    pub fn apply(&self, state: &mut GamepadState, raw: u16) {
+        let cal = &self.calibration;
+        match self.target {
+            AdcTarget::Stick(StickAxis::LeftX) => state.left_stick.x = cal.to_stick(raw),
+            AdcTarget::Stick(StickAxis::LeftY) => state.left_stick.y = cal.to_stick(raw),
+            AdcTarget::Stick(StickAxis::RightX) => state.right_stick.x = cal.to_stick(raw),
+            AdcTarget::Stick(StickAxis::RightY) => state.right_stick.y = cal.to_stick(raw),
+            AdcTarget::LeftTrigger => state.left_trigger = cal.to_trigger(raw),
+            AdcTarget::RightTrigger => state.right_trigger = cal.to_trigger(raw),
+        }
+    }
+}
+
+/// Async ADC driver reading one of several channels.
+///
+/// Channels are numbered by their position in the [`AdcInputSource`]
+/// channel list; the implementation maps them to its pins.
+pub trait AdcReader {
+    /// Wait until the next set of samples should be taken.
+    ///
+    /// Paces [`AdcInputSource::receive`]; the default returns immediately.
+    fn wait_sample(&mut self) -> impl Future<Output = ()> {
+        core::future::ready(())
+    }
+
+    /// Read the raw value of channel `index`.
+    fn read(&mut self, index: usize) -> impl Future<Output = Result<u16, InputError>>;
+}
+
+/// Input source building states from `N` ADC channels.
+///
+/// Controls without a channel stay neutral and no buttons are reported;
+/// combine with a button source through [`SplitInput`](crate::SplitInput).
+pub struct AdcInputSource<A, const N: usize> {
+    adc: A,
+    channels: [AdcChannel; N],
+    /// Whether the last sample succeeded
+    connected: bool,
+}
+
+impl<A: AdcReader, const N: usize> AdcInputSource<A, N> {
+    /// Create an input source sampling `channels` from `adc`.
+    #[must_use]
+    pub const fn new(adc: A, channels: [AdcChannel; N]) -> Self {
+        Self {
+            adc,
+            channels,
+            connected: false,
+        }
+    }
+
+    /// Get the channel bindings.
+    #[must_use]
+    pub const fn channels(&self) -> &[AdcChannel; N] {
+        &self.channels
+    }
+
+    /// Consume the source and return the ADC driver.
+    pub fn into_inner(self) -> A {
+        self.adc
+    }
+
+    /// Read all channels once and build a state.
+    async fn sample(&mut self) -> Result<GamepadState, InputError> {
+        let mut state = GamepadState::neutral();
+        for (index, channel) in self.channels.iter().enumerate() {
+            let raw = self.adc.read(index).await?;
+            channel.apply(&mut state, raw);
+        }
+        Ok(state)
+    }
+}
+
+impl<A: AdcReader, const N: usize> InputSource for AdcInputSource<A, N> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        self.adc.wait_sample().await;
+        let result = self.sample().await;
+        self.connected = result.is_ok();
+        result
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::block_on;
+
+    /// ADC returning fixed readings per channel.
+    struct FixedAdc([Result<u16, InputError>; 3]);
+
+    impl AdcReader for FixedAdc {
+        async fn read(&mut self, index: usize) -> Result<u16, InputError> {
+            self.0[index]
+        }
+    }
+
+    #[test]
+    fn test_stick_min_center_max() {
+        let cal = AdcCalibration::new(200, 2000, 3900);
+
+        assert_eq!(cal.to_stick(200), i16::MIN);
+        assert_eq!(cal.to_stick(2000), 0);
+        assert_eq!(cal.to_stick(3900), i16::MAX);
+
+        // Off-center rest: each half scales separately
+        assert_eq!(cal.to_stick(1100), -16384);
+        assert_eq!(cal.to_stick(2950), 16383);
+
+        // Outside the calibrated range clamps
+        assert_eq!(cal.to_stick(0), i16::MIN);
+        assert_eq!(cal.to_stick(4095), i16::MAX);
+    }
+
+    #[test]
+    fn test_multi_point_calibration() {
+        // Stick that moves fast near the low end and slow near the high end
+        let cal = AdcCalibration::new(0, 2000, 4000)
+            .with_point(3000, 24_000)
+            .with_point(1000, -8_000);
+        assert_eq!(cal.points().len(), 2);
+
+        // Every calibration point is hit exactly
+        let table = [
+            (0, i16::MIN),
+            (1000, -8_000),
+            (2000, 0),
+            (3000, 24_000),
+            (4000, i16::MAX),
+        ];
+        for (raw, value) in table {
+            assert_eq!(cal.to_stick(raw), value, "raw {raw}");
+        }
+
+        // Linear in between
+        assert_eq!(cal.to_stick(500), -20_384);
+        assert_eq!(cal.to_stick(1500), -4_000);
+        assert_eq!(cal.to_stick(2500), 12_000);
+        assert_eq!(cal.to_stick(3500), 28_383);
+        assert!((1000..1500).all(|raw| cal.to_stick(raw) < cal.to_stick(raw + 1)));
+    }
+
+    #[test]
+    fn test_calibration_points_at_fixed_readings_ignored() {
+        let plain = AdcCalibration::new(200, 2000, 3900);
+        let cal = plain
+            .with_point(200, 0)
+            .with_point(2000, 1000)
+            .with_point(3900, 0)
+            .with_point(4000, 0);
+        for raw in [0, 200, 1100, 2000, 2950, 3900, 4095] {
+            assert_eq!(cal.to_stick(raw), plain.to_stick(raw), "raw {raw}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too many calibration points")]
+    fn test_too_many_calibration_points() {
+        let mut cal = AdcCalibration::FULL_12BIT;
+        for raw in 0..=MAX_CALIBRATION_POINTS as u16 {
+            cal = cal.with_point(100 + raw, 0);
+        }
+    }
+
+    #[test]
+    fn test_trigger_range() {
+        let cal = AdcCalibration::new(500, 500, 3500);
+        assert_eq!(cal.to_trigger(0), 0);
+        assert_eq!(cal.to_trigger(500), 0);
+        assert_eq!(cal.to_trigger(2000), 127);
+        assert_eq!(cal.to_trigger(3500), 255);
+        assert_eq!(cal.to_trigger(4095), 255);
+    }
+
+    #[test]
+    fn test_degenerate_calibration() {
+        // Center at an end of travel, or a zero span, must not divide by zero
+        let cal = AdcCalibration::new(1000, 1000, 1000);
+        assert_eq!(cal.to_stick(0), 0);
+        assert_eq!(cal.to_stick(4095), 0);
+        assert_eq!(cal.to_trigger(4095), 0);
+
+        let cal = AdcCalibration::new(0, 4095, 4095);
+        assert_eq!(cal.to_stick(0), i16::MIN);
+        assert_eq!(cal.to_stick(4095), 0);
+    }
+
+    #[test]
+    fn test_swapped_min_max_calibration() {
+        // Readings clamp to `min`, which must not underflow against `max`
+        let cal = AdcCalibration::new(3000, 2048, 1000).with_point(2000, 100);
+        assert_eq!(cal.to_stick(0), i16::MIN);
+        assert_eq!(cal.to_stick(2048), i16::MIN);
+        assert_eq!(cal.to_stick(4095), i16::MIN);
+
+        let cal = AdcCalibration::new(3000, 4000, 1000);
+        assert_eq!(cal.to_stick(0), i16::MAX);
+        assert_eq!(cal.to_stick(4095), i16::MAX);
+        assert_eq!(cal.to_trigger(2000), 0);
+    }
+
+    #[test]
+    fn test_input_source_builds_state() {
+        let channels = [
+            AdcChannel::new(
+                AdcTarget::Stick(StickAxis::LeftX),
+                AdcCalibration::FULL_12BIT,
+            ),
+            AdcChannel::new(
+                AdcTarget::Stick(StickAxis::LeftY),
+                AdcCalibration::FULL_12BIT,
+            ),
+            AdcChannel::new(AdcTarget::RightTrigger, AdcCalibration::FULL_12BIT),
+        ];
+        let mut input = AdcInputSource::new(FixedAdc([Ok(4095), Ok(0), Ok(4095)]), channels);
+        assert!(!input.is_connected());
+
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.left_stick.x, i16::MAX);
+        assert_eq!(state.left_stick.y, i16::MIN);
+        assert_eq!(state.right_trigger, 255);
+        assert_eq!(state.right_stick, gamepad_proto::AnalogStick::NEUTRAL);
+        assert!(input.is_connected());
+
+        let mut input =
+            AdcInputSource::new(FixedAdc([Ok(2048), Err(InputError::Io), Ok(0)]), channels);
+        assert_eq!(block_on(input.receive()), Err(InputError::Io));
+        assert!(!input.is_connected());
+    }
+}
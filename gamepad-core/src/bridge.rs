@@ -1,7 +1,12 @@
 //! `GamepadBridge`: connects input sources to output sinks.
 
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+
 use crate::input::{InputError, InputSource};
-use crate::output::{OutputError, OutputSink};
+use crate::output::{OutputError, OutputPacer, OutputSink};
+use crate::telemetry::{TelemetryData, TelemetryError, TelemetrySink, TelemetryStats};
 use gamepad_proto::GamepadState;
 
 /// A bridge that forwards gamepad state from an input source to an output sink.
@@ -13,15 +18,162 @@ use gamepad_proto::GamepadState;
 ///
 /// On input errors, the bridge sends a neutral gamepad state to prevent
 /// stale inputs from persisting.
+///
+/// # Rate Limiting
+///
+/// Fast links (e.g. CRSF at 420000 baud) deliver states faster than the
+/// output can usefully consume. [`with_rate_limit`](Self::with_rate_limit)
+/// wraps the output in an [`OutputPacer`], which sends at most one state per
+/// interval: a state arriving before the interval elapsed replaces the
+/// pending one instead of being sent. [`run_paced`](Self::run_paced) races
+/// each receive against the pending state's deadline, so the latest state
+/// still goes out when the input goes quiet.
 pub struct GamepadBridge<I, O> {
     input: I,
     output: O,
+    metrics: BridgeMetrics,
+}
+
+/// Snapshot of the bridge's counters.
+///
+/// Retrieved with [`GamepadBridge::metrics`]. All counters saturate at
+/// `u32::MAX` instead of wrapping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BridgeMetrics {
+    /// States received and successfully sent to the output. States held back
+    /// by the output (see [`GamepadBridge::with_rate_limit`]) count once they
+    /// are sent.
+    pub frames_forwarded: u32,
+    /// Total input errors (of any kind).
+    pub input_errors: u32,
+    /// Input errors caused by malformed messages ([`InputError::Parse`]).
+    pub parse_errors: u32,
+    /// Input errors caused by checksum mismatches ([`InputError::Checksum`]).
+    pub checksum_errors: u32,
+    /// Input overflows / overruns ([`InputError::BufferOverflow`]).
+    pub overflows: u32,
+    /// Link losses reported by the input ([`InputError::Disconnected`]).
+    pub disconnects: u32,
+    /// Total output errors (of any kind).
+    pub output_errors: u32,
+    /// Reports the output dropped or could not accept
+    /// ([`OutputError::Dropped`], [`OutputError::Busy`]).
+    pub dropped_reports: u32,
+    /// Inputs that did not arrive in time ([`BridgeError::Timeout`]).
+    pub timeouts: u32,
+    /// Telemetry frames sent and dropped, per kind
+    /// ([`send_telemetry`](GamepadBridge::send_telemetry)).
+    pub telemetry: TelemetryStats,
+}
+
+impl BridgeMetrics {
+    /// Create a snapshot with all counters zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frames_forwarded: 0,
+            input_errors: 0,
+            parse_errors: 0,
+            checksum_errors: 0,
+            overflows: 0,
+            disconnects: 0,
+            output_errors: 0,
+            dropped_reports: 0,
+            timeouts: 0,
+            telemetry: TelemetryStats::new(),
+        }
+    }
+
+    /// Total errors from both sides of the bridge.
+    #[must_use]
+    pub const fn total_errors(&self) -> u32 {
+        self.input_errors.saturating_add(self.output_errors)
+    }
+
+    /// Count an input error in the total and its kind's counter.
+    ///
+    /// Called by the bridge itself; applications that run their own loop
+    /// can use it to keep the same counters.
+    pub fn record_input_error(&mut self, err: InputError) {
+        self.input_errors = self.input_errors.saturating_add(1);
+        let counter = match err {
+            InputError::Parse => &mut self.parse_errors,
+            InputError::Checksum => &mut self.checksum_errors,
+            InputError::BufferOverflow => &mut self.overflows,
+            InputError::Disconnected => &mut self.disconnects,
+            InputError::Io | InputError::Framing => return,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Count an output error in the total and, if the report was lost,
+    /// in [`dropped_reports`](Self::dropped_reports).
+    pub fn record_output_error(&mut self, err: OutputError) {
+        self.output_errors = self.output_errors.saturating_add(1);
+        if matches!(err, OutputError::Dropped | OutputError::Busy) {
+            self.dropped_reports = self.dropped_reports.saturating_add(1);
+        }
+    }
 }
 
 impl<I: InputSource, O: OutputSink> GamepadBridge<I, O> {
     /// Create a new bridge from an input source and output sink.
     pub fn new(input: I, output: O) -> Self {
-        Self { input, output }
+        Self {
+            input,
+            output,
+            metrics: BridgeMetrics::new(),
+        }
+    }
+
+    /// Forward at most one state per `min_interval_ms`, timed by `clock`.
+    ///
+    /// Wraps the output in an [`OutputPacer`]; see
+    /// [Rate Limiting](Self#rate-limiting). An interval of 0 forwards every
+    /// state.
+    #[must_use]
+    pub fn with_rate_limit<C: FnMut() -> u64>(
+        self,
+        min_interval_ms: u64,
+        clock: C,
+    ) -> GamepadBridge<I, OutputPacer<O, C>> {
+        GamepadBridge {
+            input: self.input,
+            output: OutputPacer::new(self.output, min_interval_ms, clock),
+            metrics: self.metrics,
+        }
+    }
+
+    /// Time at which the output's held back state is due, if there is one.
+    ///
+    /// See [`OutputSink::next_deadline_ms`].
+    #[must_use]
+    pub fn next_deadline_ms(&self) -> Option<u64> {
+        self.output.next_deadline_ms()
+    }
+
+    /// Send the output's held back state if its deadline has been reached.
+    ///
+    /// Returns `Ok(true)` if a state was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BridgeError::Output`] if writing to the output sink fails.
+    /// The state is dropped in that case.
+    pub async fn flush(&mut self) -> Result<bool, BridgeError> {
+        match self.output.flush().await {
+            Ok(sent) => {
+                if sent {
+                    self.metrics.frames_forwarded = self.metrics.frames_forwarded.saturating_add(1);
+                }
+                Ok(sent)
+            }
+            Err(e) => {
+                self.metrics.record_output_error(e);
+                Err(BridgeError::Output(e))
+            }
+        }
     }
 
     /// Run the bridge, forwarding gamepad state indefinitely.
@@ -33,6 +185,26 @@ impl<I: InputSource, O: OutputSink> GamepadBridge<I, O> {
         }
     }
 
+    /// Run the bridge like [`run`](Self::run), flushing states the output
+    /// held back (see [`with_rate_limit`](Self::with_rate_limit)).
+    ///
+    /// `timer` returns a future completing at the given time in milliseconds,
+    /// on the same clock as the output's deadlines (e.g.
+    /// `|ms| Timer::at(Instant::from_millis(ms))` with embassy-time). It is
+    /// only called while the output holds a state back; see
+    /// [`process_one_or_flush`](Self::process_one_or_flush).
+    ///
+    /// This method never returns under normal operation.
+    pub async fn run_paced<T, F>(&mut self, mut timer: F) -> !
+    where
+        T: Future<Output = ()>,
+        F: FnMut(u64) -> T,
+    {
+        loop {
+            let _ = self.process_one_or_flush(&mut timer).await;
+        }
+    }
+
     /// Process a single input and forward it to the output.
     ///
     /// Returns the result of the operation for testing purposes.
@@ -42,22 +214,160 @@ impl<I: InputSource, O: OutputSink> GamepadBridge<I, O> {
     /// Returns [`BridgeError::Input`] if reading from the input source fails.
     /// Returns [`BridgeError::Output`] if writing to the output sink fails.
     pub async fn process_one(&mut self) -> Result<(), BridgeError> {
-        match self.input.receive().await {
-            Ok(state) => {
-                self.output
-                    .send(&state)
-                    .await
-                    .map_err(BridgeError::Output)?;
-                Ok(())
+        let received = self.input.receive().await;
+        self.forward(received).await
+    }
+
+    /// Process a single input, or flush the output once its deadline passed.
+    ///
+    /// While the output holds a state back (see
+    /// [`next_deadline_ms`](Self::next_deadline_ms)), the receive races the
+    /// future returned by `timer(deadline)`. If the timer completes first, the
+    /// pending receive is dropped and the output flushed, so the last state of
+    /// a burst is not stuck until the next input. Otherwise this is the same
+    /// as [`process_one`](Self::process_one).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`process_one`](Self::process_one) and [`flush`](Self::flush).
+    pub async fn process_one_or_flush<T: Future<Output = ()>>(
+        &mut self,
+        timer: impl FnOnce(u64) -> T,
+    ) -> Result<(), BridgeError> {
+        let Some(deadline) = self.next_deadline_ms() else {
+            return self.process_one().await;
+        };
+
+        match self.receive_until(timer(deadline)).await {
+            Some(received) => self.forward(received).await,
+            None => self.flush().await.map(|_| ()),
+        }
+    }
+
+    /// Process a single input, giving up once `timeout` completes.
+    ///
+    /// `timeout` is any future that resolves when the wait should end, such
+    /// as a platform timer. If it resolves before an input arrives, the
+    /// pending receive is dropped and a neutral state is sent, so a stalled
+    /// input cannot hang the bridge or leave stale inputs applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BridgeError::Timeout`] if `timeout` completes first, otherwise
+    /// the same errors as [`process_one`](Self::process_one).
+    pub async fn process_one_until<T: Future<Output = ()>>(
+        &mut self,
+        timeout: T,
+    ) -> Result<(), BridgeError> {
+        match self.receive_until(timeout).await {
+            Some(received) => self.forward(received).await,
+            None => {
+                self.metrics.timeouts = self.metrics.timeouts.saturating_add(1);
+                self.send_neutral().await;
+                Err(BridgeError::Timeout)
+            }
+        }
+    }
+
+    /// Process a single input, giving up after `dur`.
+    ///
+    /// See [`process_one_until`](Self::process_one_until).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BridgeError::Timeout`] if no input arrives within `dur`,
+    /// otherwise the same errors as [`process_one`](Self::process_one).
+    #[cfg(feature = "embassy-time")]
+    pub async fn process_one_with_timeout(
+        &mut self,
+        dur: embassy_time::Duration,
+    ) -> Result<(), BridgeError> {
+        self.process_one_until(embassy_time::Timer::after(dur))
+            .await
+    }
+
+    /// Receive the next input, or `None` if `timeout` completes first.
+    async fn receive_until<T: Future<Output = ()>>(
+        &mut self,
+        timeout: T,
+    ) -> Option<Result<GamepadState, InputError>> {
+        let mut receive = pin!(self.input.receive());
+        let mut timeout = pin!(timeout);
+        poll_fn(|cx| {
+            if let Poll::Ready(result) = receive.as_mut().poll(cx) {
+                return Poll::Ready(Some(result));
             }
+            timeout.as_mut().poll(cx).map(|()| None)
+        })
+        .await
+    }
+
+    /// Forward a received state, or send neutral on an input error.
+    async fn forward(
+        &mut self,
+        received: Result<GamepadState, InputError>,
+    ) -> Result<(), BridgeError> {
+        match received {
+            Ok(state) => self.send_state(&state).await,
             Err(e) => {
-                // Send neutral state to prevent stale inputs
-                let _ = self.output.send(&GamepadState::neutral()).await;
+                self.metrics.record_input_error(e);
+                self.send_neutral().await;
                 Err(BridgeError::Input(e))
             }
         }
     }
 
+    /// Send a state to the output, counting it in the metrics.
+    ///
+    /// A state the output held back is counted by [`flush`](Self::flush).
+    async fn send_state(&mut self, state: &GamepadState) -> Result<(), BridgeError> {
+        if let Err(e) = self.output.send(state).await {
+            self.metrics.record_output_error(e);
+            return Err(BridgeError::Output(e));
+        }
+        if self.output.next_deadline_ms().is_none() {
+            self.metrics.frames_forwarded = self.metrics.frames_forwarded.saturating_add(1);
+        }
+        Ok(())
+    }
+
+    /// Send telemetry through `sink`, counting the result in the metrics.
+    ///
+    /// Telemetry pumps call this instead of the sink directly, so frames the
+    /// sink drops ([`TelemetryError::BufferFull`], [`TelemetryError::Io`], ...)
+    /// show up in [`BridgeMetrics::telemetry`] instead of vanishing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sink's error unchanged.
+    pub async fn send_telemetry<S: TelemetrySink>(
+        &mut self,
+        sink: &mut S,
+        data: &TelemetryData,
+    ) -> Result<(), TelemetryError> {
+        let result = sink.send_telemetry(data).await;
+        self.metrics.telemetry.record(data.kind(), result);
+        result
+    }
+
+    /// Send a neutral state to prevent stale inputs.
+    async fn send_neutral(&mut self) {
+        if let Err(out) = self.output.send(&GamepadState::neutral()).await {
+            self.metrics.record_output_error(out);
+        }
+    }
+
+    /// Get a snapshot of the bridge counters.
+    #[must_use]
+    pub fn metrics(&self) -> BridgeMetrics {
+        self.metrics
+    }
+
+    /// Reset all bridge counters to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics = BridgeMetrics::new();
+    }
+
     /// Get a reference to the input source.
     pub fn input(&self) -> &I {
         &self.input
@@ -92,6 +402,18 @@ pub enum BridgeError {
     Input(InputError),
     /// Error from the output sink.
     Output(OutputError),
+    /// No input arrived before the timeout.
+    Timeout,
+}
+
+impl core::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Input(e) => write!(f, "input: {e}"),
+            Self::Output(e) => write!(f, "output: {e}"),
+            Self::Timeout => write!(f, "input timeout"),
+        }
+    }
 }
 
 impl From<InputError> for BridgeError {
@@ -107,13 +429,14 @@ impl From<OutputError> for BridgeError {
 }
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy)]
 mod tests {
     extern crate std;
 
     use super::*;
+    use crate::test_support::block_on;
+    use core::cell::Cell;
     use core::future::Future;
-    use core::pin::Pin;
-    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
     use gamepad_proto::Buttons;
     use std::sync::{Arc, Mutex};
     use std::vec;
@@ -172,33 +495,6 @@ mod tests {
         }
     }
 
-    // Helper to run a future to completion (simple blocking executor)
-    fn block_on<F: Future>(mut f: F) -> F::Output {
-        fn noop_raw_waker() -> RawWaker {
-            fn noop(_: *const ()) {}
-            fn clone(_: *const ()) -> RawWaker {
-                noop_raw_waker()
-            }
-            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
-            RawWaker::new(core::ptr::null(), &VTABLE)
-        }
-
-        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
-        let mut cx = Context::from_waker(&waker);
-
-        // SAFETY: We don't move f after pinning
-        let mut f = unsafe { Pin::new_unchecked(&mut f) };
-
-        loop {
-            match f.as_mut().poll(&mut cx) {
-                Poll::Ready(result) => return result,
-                Poll::Pending => {
-                    panic!("Mock future returned Pending unexpectedly");
-                }
-            }
-        }
-    }
-
     #[test]
     fn test_bridge_forwards_state() {
         let mut state = GamepadState::neutral();
@@ -219,6 +515,126 @@ mod tests {
         assert_eq!(sent[0], state);
     }
 
+    fn stick(x: i16) -> GamepadState {
+        let mut state = GamepadState::neutral();
+        state.left_stick.x = x;
+        state
+    }
+
+    #[test]
+    fn test_bridge_rate_limit_coalesces() {
+        let now = Cell::new(100);
+        let input = MockInput::new((1..=5).map(|x| Ok(stick(x))).collect());
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(input, output).with_rate_limit(10, || now.get());
+
+        // First state goes out, the next three within the interval coalesce
+        for t in [100, 102, 104, 106] {
+            now.set(t);
+            block_on(bridge.process_one()).unwrap();
+        }
+        assert_eq!(*sent_ref.lock().unwrap(), [stick(1)]);
+        assert_eq!(bridge.next_deadline_ms(), Some(110));
+
+        // The next input after the interval supersedes the pending state
+        now.set(111);
+        block_on(bridge.process_one()).unwrap();
+        assert_eq!(bridge.next_deadline_ms(), None);
+        assert_eq!(*sent_ref.lock().unwrap(), [stick(1), stick(5)]);
+        assert_eq!(bridge.metrics().frames_forwarded, 2);
+    }
+
+    #[test]
+    fn test_bridge_rate_limit_flushes_final_state() {
+        let now = Cell::new(0);
+        let input = MockInput::new(vec![Ok(stick(1)), Ok(stick(2)), Ok(stick(3))]);
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(input, output).with_rate_limit(8, || now.get());
+        assert_eq!(block_on(bridge.flush()), Ok(false));
+
+        for _ in 0..3 {
+            block_on(bridge.process_one()).unwrap();
+        }
+
+        // Input stopped: the last state goes out once the interval elapsed
+        now.set(7);
+        assert_eq!(block_on(bridge.flush()), Ok(false));
+        assert_eq!(bridge.metrics().frames_forwarded, 1);
+        now.set(8);
+        assert_eq!(block_on(bridge.flush()), Ok(true));
+        assert_eq!(bridge.next_deadline_ms(), None);
+        assert_eq!(*sent_ref.lock().unwrap(), [stick(1), stick(3)]);
+        assert_eq!(bridge.metrics().frames_forwarded, 2);
+    }
+
+    #[test]
+    fn test_bridge_rate_limit_error_drops_pending() {
+        let now = Cell::new(0);
+        let input = MockInput::new(vec![Ok(stick(1)), Ok(stick(2)), Err(InputError::Parse)]);
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(input, output).with_rate_limit(8, || now.get());
+
+        block_on(bridge.process_one()).unwrap();
+        block_on(bridge.process_one()).unwrap();
+        assert!(block_on(bridge.process_one()).is_err());
+
+        // Neutral replaces the pending state, the stale one is never sent
+        now.set(100);
+        assert_eq!(block_on(bridge.flush()), Ok(true));
+        assert_eq!(
+            *sent_ref.lock().unwrap(),
+            [stick(1), GamepadState::neutral()]
+        );
+    }
+
+    #[test]
+    fn test_bridge_rate_limit_timer_flushes_when_input_quiet() {
+        // Yields its states, then never completes another receive
+        struct QuietInput(Vec<GamepadState>);
+
+        impl InputSource for QuietInput {
+            async fn receive(&mut self) -> Result<GamepadState, InputError> {
+                if self.0.is_empty() {
+                    core::future::pending().await
+                } else {
+                    Ok(self.0.remove(0))
+                }
+            }
+
+            fn is_connected(&self) -> bool {
+                true
+            }
+        }
+
+        let now = Cell::new(0);
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(QuietInput(vec![stick(1), stick(2)]), output)
+            .with_rate_limit(8, || now.get());
+
+        // Nothing held back yet: the timer is not consulted
+        for _ in 0..2 {
+            block_on(
+                bridge.process_one_or_flush(|_| -> core::future::Ready<()> { unreachable!() }),
+            )
+            .unwrap();
+        }
+        assert_eq!(*sent_ref.lock().unwrap(), [stick(1)]);
+
+        // The input went quiet, the timer for the deadline fires instead
+        now.set(8);
+        let result = block_on(bridge.process_one_or_flush(|deadline| {
+            assert_eq!(deadline, 8);
+            core::future::ready(())
+        }));
+        assert_eq!(result, Ok(()));
+        assert_eq!(bridge.next_deadline_ms(), None);
+        assert_eq!(*sent_ref.lock().unwrap(), [stick(1), stick(2)]);
+    }
+
     #[test]
     fn test_bridge_sends_neutral_on_error() {
         let input = MockInput::new(vec![Err(InputError::Parse)]);
@@ -234,4 +650,187 @@ mod tests {
         assert_eq!(sent.len(), 1);
         assert_eq!(sent[0], GamepadState::neutral());
     }
+
+    #[test]
+    fn test_bridge_metrics_counts() {
+        let state = GamepadState::neutral();
+        let input = MockInput::new(vec![
+            Ok(state),
+            Err(InputError::Checksum),
+            Ok(state),
+            Err(InputError::Parse),
+            Err(InputError::Checksum),
+            Err(InputError::Framing),
+            Ok(state),
+        ]);
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(input, output);
+
+        for _ in 0..7 {
+            let _ = block_on(bridge.process_one());
+        }
+
+        let metrics = bridge.metrics();
+        assert_eq!(
+            metrics,
+            BridgeMetrics {
+                frames_forwarded: 3,
+                input_errors: 4,
+                parse_errors: 1,
+                checksum_errors: 2,
+                overflows: 0,
+                disconnects: 0,
+                output_errors: 0,
+                dropped_reports: 0,
+                timeouts: 0,
+                telemetry: TelemetryStats::new(),
+            }
+        );
+        assert_eq!(metrics.total_errors(), 4);
+        // Every error still sent a neutral state
+        assert_eq!(sent_ref.lock().unwrap().len(), 7);
+
+        bridge.reset_metrics();
+        assert_eq!(bridge.metrics(), BridgeMetrics::default());
+    }
+
+    #[test]
+    fn test_bridge_metrics_output_errors() {
+        struct BusyOutput;
+
+        impl OutputSink for BusyOutput {
+            fn send(&mut self, _: &GamepadState) -> impl Future<Output = Result<(), OutputError>> {
+                core::future::ready(Err(OutputError::Busy))
+            }
+
+            fn is_ready(&self) -> bool {
+                false
+            }
+        }
+
+        let input = MockInput::new(vec![Ok(GamepadState::neutral()), Err(InputError::Io)]);
+        let mut bridge = GamepadBridge::new(input, BusyOutput);
+
+        assert_eq!(
+            block_on(bridge.process_one()),
+            Err(BridgeError::Output(OutputError::Busy))
+        );
+        assert_eq!(
+            block_on(bridge.process_one()),
+            Err(BridgeError::Input(InputError::Io))
+        );
+
+        let metrics = bridge.metrics();
+        assert_eq!(metrics.frames_forwarded, 0);
+        assert_eq!(metrics.input_errors, 1);
+        assert_eq!(metrics.output_errors, 2);
+        assert_eq!(metrics.dropped_reports, 2);
+    }
+
+    #[test]
+    fn test_bridge_timeout_sends_neutral() {
+        struct StalledInput;
+
+        impl InputSource for StalledInput {
+            fn receive(&mut self) -> impl Future<Output = Result<GamepadState, InputError>> {
+                core::future::pending()
+            }
+
+            fn is_connected(&self) -> bool {
+                true
+            }
+        }
+
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(StalledInput, output);
+
+        let result = block_on(bridge.process_one_until(core::future::ready(())));
+        assert_eq!(result, Err(BridgeError::Timeout));
+        assert_eq!(*sent_ref.lock().unwrap(), vec![GamepadState::neutral()]);
+        assert_eq!(bridge.metrics().timeouts, 1);
+    }
+
+    #[test]
+    fn test_bridge_timeout_passes_fast_input() {
+        let mut state = GamepadState::neutral();
+        state.left_trigger = 200;
+
+        let input = MockInput::new(vec![Ok(state), Err(InputError::Checksum)]);
+        let output = MockOutput::new();
+        let sent_ref = output.sent.clone();
+        let mut bridge = GamepadBridge::new(input, output);
+
+        // Input is ready before the (never-ending) timeout
+        let result = block_on(bridge.process_one_until(core::future::pending()));
+        assert_eq!(result, Ok(()));
+        let result = block_on(bridge.process_one_until(core::future::pending()));
+        assert_eq!(result, Err(BridgeError::Input(InputError::Checksum)));
+
+        assert_eq!(
+            *sent_ref.lock().unwrap(),
+            vec![state, GamepadState::neutral()]
+        );
+        assert_eq!(bridge.metrics().timeouts, 0);
+    }
+
+    #[test]
+    fn test_bridge_counts_telemetry_failures() {
+        use crate::telemetry::TelemetryKind;
+
+        // Rejects every third frame, alternating between error kinds
+        struct FlakySink {
+            calls: u32,
+        }
+
+        impl TelemetrySink for FlakySink {
+            fn send_telemetry(
+                &mut self,
+                _: &TelemetryData,
+            ) -> impl Future<Output = Result<(), TelemetryError>> {
+                self.calls += 1;
+                let result = match self.calls % 6 {
+                    0 => Err(TelemetryError::Io),
+                    3 => Err(TelemetryError::BufferFull),
+                    _ => Ok(()),
+                };
+                core::future::ready(result)
+            }
+        }
+
+        let attitude = TelemetryData::Attitude {
+            roll: 0,
+            pitch: 0,
+            yaw: 0,
+        };
+        let battery = TelemetryData::Battery {
+            voltage_mv: 11_100,
+            current_ma: 0,
+            remaining_pct: 80,
+        };
+
+        let mut bridge = GamepadBridge::new(MockInput::new(vec![]), MockOutput::new());
+        let mut sink = FlakySink { calls: 0 };
+        let mut errors = 0;
+        // Calls 1..=12: attitude on odd calls, battery on even ones
+        for i in 0..12 {
+            let data = if i % 2 == 0 { &attitude } else { &battery };
+            if block_on(bridge.send_telemetry(&mut sink, data)).is_err() {
+                errors += 1;
+            }
+        }
+
+        let telemetry = bridge.metrics().telemetry;
+        assert_eq!(errors, 4);
+        assert_eq!(telemetry.total_failed(), 4);
+        assert_eq!(telemetry.total_sent(), 8);
+        // Calls 3 and 9 (attitude), 6 and 12 (battery) failed
+        assert_eq!(telemetry.failed(TelemetryKind::Attitude), 2);
+        assert_eq!(telemetry.failed(TelemetryKind::Battery), 2);
+        assert_eq!(telemetry.sent(TelemetryKind::Attitude), 4);
+        assert_eq!(telemetry.failed(TelemetryKind::Gps), 0);
+        // Telemetry failures are not bridge errors
+        assert_eq!(bridge.metrics().total_errors(), 0);
+    }
 }
@@ -0,0 +1,159 @@
+//! Post-mortem diagnostic text.
+//!
+//! When the firmware hits an unrecoverable error, the last state it forwarded
+//! and the error counters are the most useful clues for what went wrong.
+//! [`diagnostic_dump`] formats both into one fixed-size line, so the firmware
+//! can log it right before panicking and the formatting is tested on the
+//! host.
+
+use core::fmt::Write;
+
+use crate::bridge::BridgeMetrics;
+use gamepad_proto::GamepadState;
+
+/// Maximum length of a diagnostic dump.
+///
+/// Fits the longest possible state and all counters at `u32::MAX`.
+pub const MAX_DIAGNOSTIC_SIZE: usize = 256;
+
+/// Formatted diagnostic line, see [`diagnostic_dump`].
+#[derive(Clone)]
+pub struct DiagnosticDump {
+    buf: [u8; MAX_DIAGNOSTIC_SIZE],
+    len: usize,
+    truncated: bool,
+}
+
+impl DiagnosticDump {
+    /// Get the formatted text.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Only ASCII is ever written, so any length is a char boundary
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+
+    /// Returns true if the text was cut off at [`MAX_DIAGNOSTIC_SIZE`].
+    #[inline]
+    #[must_use]
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl core::fmt::Debug for DiagnosticDump {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Write for DiagnosticDump {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        // Truncate instead of failing: a partial dump beats none
+        let take = s.len().min(MAX_DIAGNOSTIC_SIZE - self.len);
+        self.buf[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        self.truncated |= take < s.len();
+        Ok(())
+    }
+}
+
+/// Format the last-known state and the error counters.
+///
+/// The output is a single line of `key=value` pairs, e.g.
+/// `state B=0003 LX=100 LY=-100 RX=0 RY=0 LT=64 RT=0; frames=10 input_errors=2
+/// parse=1 checksum=1 overflow=0 disconnect=0 output_errors=0 dropped=0 timeouts=0`
+/// (without the line break). Buttons are hex as in the protocol.
+#[must_use]
+pub fn diagnostic_dump(state: &GamepadState, metrics: &BridgeMetrics) -> DiagnosticDump {
+    let mut dump = DiagnosticDump {
+        buf: [0; MAX_DIAGNOSTIC_SIZE],
+        len: 0,
+        truncated: false,
+    };
+
+    // Writing to the dump never fails
+    let _ = write!(
+        dump,
+        "state B={:04X} LX={} LY={} RX={} RY={} LT={} RT={}; ",
+        state.buttons.0,
+        state.left_stick.x,
+        state.left_stick.y,
+        state.right_stick.x,
+        state.right_stick.y,
+        state.left_trigger,
+        state.right_trigger,
+    );
+    let _ = write!(
+        dump,
+        "frames={} input_errors={} parse={} checksum={} overflow={} disconnect={} \
+         output_errors={} dropped={} timeouts={}",
+        metrics.frames_forwarded,
+        metrics.input_errors,
+        metrics.parse_errors,
+        metrics.checksum_errors,
+        metrics.overflows,
+        metrics.disconnects,
+        metrics.output_errors,
+        metrics.dropped_reports,
+        metrics.timeouts,
+    );
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputError;
+    use gamepad_proto::{AnalogStick, Buttons};
+
+    #[test]
+    fn test_dump_fields() {
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::B,
+            left_stick: AnalogStick::new(100, -100),
+            right_stick: AnalogStick::NEUTRAL,
+            left_trigger: 64,
+            right_trigger: 0,
+        };
+        let mut metrics = BridgeMetrics::new();
+        metrics.frames_forwarded = 10;
+        metrics.record_input_error(InputError::Parse);
+        metrics.record_input_error(InputError::Checksum);
+
+        let dump = diagnostic_dump(&state, &metrics);
+        assert_eq!(
+            dump.as_str(),
+            "state B=0003 LX=100 LY=-100 RX=0 RY=0 LT=64 RT=0; frames=10 input_errors=2 \
+             parse=1 checksum=1 overflow=0 disconnect=0 output_errors=0 dropped=0 timeouts=0"
+        );
+        assert!(!dump.is_truncated());
+    }
+
+    #[test]
+    fn test_longest_dump_fits() {
+        let state = GamepadState {
+            buttons: Buttons(u16::MAX),
+            left_stick: AnalogStick::new(i16::MIN, i16::MIN),
+            right_stick: AnalogStick::new(i16::MIN, i16::MIN),
+            left_trigger: u8::MAX,
+            right_trigger: u8::MAX,
+        };
+        let metrics = BridgeMetrics {
+            frames_forwarded: u32::MAX,
+            input_errors: u32::MAX,
+            parse_errors: u32::MAX,
+            checksum_errors: u32::MAX,
+            overflows: u32::MAX,
+            disconnects: u32::MAX,
+            output_errors: u32::MAX,
+            dropped_reports: u32::MAX,
+            timeouts: u32::MAX,
+            ..BridgeMetrics::new()
+        };
+
+        let dump = diagnostic_dump(&state, &metrics);
+        assert!(!dump.is_truncated());
+        assert!(dump.as_str().starts_with("state B=FFFF LX=-32768 "));
+        assert!(dump.as_str().ends_with(" timeouts=4294967295"));
+    }
+}
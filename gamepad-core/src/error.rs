@@ -0,0 +1,178 @@
+//! Unified error type.
+//!
+//! Each part of the crate keeps its own specific error ([`InputError`],
+//! [`OutputError`], [`BridgeError`], [`TelemetryError`], and the protocol's
+//! [`ParseError`] and [`SerializeError`]). Applications that only need to log
+//! or propagate errors can convert all of them into [`GamepadError`] with `?`.
+
+use crate::bridge::BridgeError;
+use crate::input::InputError;
+use crate::output::OutputError;
+use crate::telemetry::TelemetryError;
+use gamepad_proto::{ParseError, SerializeError};
+
+/// Any error produced by this crate or [`gamepad_proto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GamepadError {
+    /// Error from an input source.
+    Input(InputError),
+    /// Error from an output sink.
+    Output(OutputError),
+    /// No input arrived before the bridge timeout.
+    Timeout,
+    /// Error parsing a protocol message.
+    Parse(ParseError),
+    /// Error serializing a protocol message.
+    Serialize(SerializeError),
+    /// Error sending telemetry.
+    Telemetry(TelemetryError),
+}
+
+impl core::fmt::Display for GamepadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Input(e) => write!(f, "input error: {e}"),
+            Self::Output(e) => write!(f, "output error: {e}"),
+            Self::Timeout => write!(f, "input timeout"),
+            Self::Parse(e) => write!(f, "parse error: {e}"),
+            Self::Serialize(e) => write!(f, "serialize error: {e}"),
+            Self::Telemetry(e) => write!(f, "telemetry error: {e}"),
+        }
+    }
+}
+
+impl From<InputError> for GamepadError {
+    fn from(err: InputError) -> Self {
+        GamepadError::Input(err)
+    }
+}
+
+impl From<OutputError> for GamepadError {
+    fn from(err: OutputError) -> Self {
+        GamepadError::Output(err)
+    }
+}
+
+impl From<BridgeError> for GamepadError {
+    fn from(err: BridgeError) -> Self {
+        match err {
+            BridgeError::Input(e) => GamepadError::Input(e),
+            BridgeError::Output(e) => GamepadError::Output(e),
+            BridgeError::Timeout => GamepadError::Timeout,
+        }
+    }
+}
+
+impl From<ParseError> for GamepadError {
+    fn from(err: ParseError) -> Self {
+        GamepadError::Parse(err)
+    }
+}
+
+impl From<SerializeError> for GamepadError {
+    fn from(err: SerializeError) -> Self {
+        GamepadError::Serialize(err)
+    }
+}
+
+impl From<TelemetryError> for GamepadError {
+    fn from(err: TelemetryError) -> Self {
+        GamepadError::Telemetry(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_convert_each_error() {
+        let cases: [(GamepadError, GamepadError); 8] = [
+            (
+                InputError::Checksum.into(),
+                GamepadError::Input(InputError::Checksum),
+            ),
+            (
+                OutputError::Busy.into(),
+                GamepadError::Output(OutputError::Busy),
+            ),
+            (
+                BridgeError::Input(InputError::Framing).into(),
+                GamepadError::Input(InputError::Framing),
+            ),
+            (
+                BridgeError::Output(OutputError::NotReady).into(),
+                GamepadError::Output(OutputError::NotReady),
+            ),
+            (BridgeError::Timeout.into(), GamepadError::Timeout),
+            (
+                ParseError::Overflow.into(),
+                GamepadError::Parse(ParseError::Overflow),
+            ),
+            (
+                SerializeError::BufferTooSmall.into(),
+                GamepadError::Serialize(SerializeError::BufferTooSmall),
+            ),
+            (
+                TelemetryError::BufferFull.into(),
+                GamepadError::Telemetry(TelemetryError::BufferFull),
+            ),
+        ];
+
+        for (converted, expected) in cases {
+            assert_eq!(converted, expected);
+        }
+    }
+
+    #[test]
+    fn test_question_mark_converts() {
+        fn parse_then_send() -> Result<(), GamepadError> {
+            gamepad_proto::parse(b"G0000:0:0:0:0:0:0*00\n")?;
+            Err(OutputError::Dropped)?
+        }
+
+        assert_eq!(
+            parse_then_send(),
+            Err(GamepadError::Parse(ParseError::Checksum))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let cases = [
+            (
+                GamepadError::from(InputError::Disconnected),
+                "input error: disconnected",
+            ),
+            (
+                GamepadError::from(OutputError::Io),
+                "output error: I/O error",
+            ),
+            (GamepadError::from(BridgeError::Timeout), "input timeout"),
+            (
+                GamepadError::from(ParseError::Parse),
+                "parse error: invalid message format",
+            ),
+            (
+                GamepadError::from(SerializeError::WriteError),
+                "serialize error: write error",
+            ),
+            (
+                GamepadError::from(TelemetryError::NotSupported),
+                "telemetry error: not supported",
+            ),
+        ];
+
+        for (error, text) in cases {
+            assert_eq!(error.to_string(), text);
+        }
+        assert_eq!(
+            BridgeError::Input(InputError::BufferOverflow).to_string(),
+            "input: line too long"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Recent state history for derivative-based features.
+//!
+//! [`StateHistory`] keeps the last `N` timestamped states in a ring buffer, so
+//! features like flick detection or velocity-based aim can look at how the
+//! sticks moved instead of only where they are. Time is injected as a
+//! millisecond timestamp, as with [`IdleDetector`].
+//!
+//! [`IdleDetector`]: crate::IdleDetector
+
+use gamepad_proto::{GamepadState, StickAxis};
+
+/// Ring buffer of the `N` most recent timestamped states.
+#[derive(Debug, Clone)]
+pub struct StateHistory<const N: usize> {
+    /// Timestamped states, oldest overwritten first
+    entries: [(u64, GamepadState); N],
+    /// Index the next state is written to
+    head: usize,
+    /// Number of valid entries (at most `N`)
+    len: usize,
+}
+
+impl<const N: usize> StateHistory<N> {
+    /// Create an empty history.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [(0, GamepadState::neutral()); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Number of states recorded (at most `N`).
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no state has been recorded.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Record a state, dropping the oldest one when full.
+    pub fn push(&mut self, state: &GamepadState, now_ms: u64) {
+        if N == 0 {
+            return;
+        }
+        self.entries[self.head] = (now_ms, *state);
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Forget all recorded states.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Get the most recent state and its timestamp.
+    #[must_use]
+    pub fn latest(&self) -> Option<(u64, &GamepadState)> {
+        self.get(0)
+    }
+
+    /// Get the state recorded `age` pushes ago (0 = most recent).
+    #[must_use]
+    pub fn get(&self, age: usize) -> Option<(u64, &GamepadState)> {
+        if age >= self.len {
+            return None;
+        }
+        let (time, state) = &self.entries[(self.head + N - 1 - age) % N];
+        Some((*time, state))
+    }
+
+    /// Average velocity of one stick axis over the recorded window.
+    ///
+    /// Returns the change from the oldest to the newest state in axis units
+    /// per second, or `None` with fewer than two states or no elapsed time.
+    #[must_use]
+    pub fn velocity(&self, axis: StickAxis) -> Option<i32> {
+        let (newest_ms, newest) = self.latest()?;
+        let (oldest_ms, oldest) = self.get(self.len.checked_sub(1)?)?;
+        let elapsed_ms = newest_ms.checked_sub(oldest_ms).filter(|&dt| dt > 0)?;
+
+        let delta = i64::from(axis_value(newest, axis)) - i64::from(axis_value(oldest, axis));
+        // |delta| <= 65535, so the result fits in i32 for any elapsed time >= 1 ms
+        Some((delta * 1000 / i64::try_from(elapsed_ms).unwrap_or(i64::MAX)) as i32)
+    }
+}
+
+impl<const N: usize> Default for StateHistory<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read one stick axis from a state.
+const fn axis_value(state: &GamepadState, axis: StickAxis) -> i16 {
+    match axis {
+        StickAxis::LeftX => state.left_stick.x,
+        StickAxis::LeftY => state.left_stick.y,
+        StickAxis::RightX => state.right_stick.x,
+        StickAxis::RightY => state.right_stick.y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamepad_proto::AnalogStick;
+
+    fn left(x: i16, y: i16) -> GamepadState {
+        GamepadState {
+            left_stick: AnalogStick::new(x, y),
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_velocity_over_window() {
+        let mut history = StateHistory::<4>::new();
+        assert_eq!(history.velocity(StickAxis::LeftX), None);
+
+        history.push(&left(0, 0), 1000);
+        assert_eq!(history.velocity(StickAxis::LeftX), None);
+
+        // Steady flick to the right, Y drifting down
+        history.push(&left(4000, 100), 1010);
+        history.push(&left(8000, 200), 1020);
+        history.push(&left(12000, 300), 1030);
+
+        // 12000 units in 30 ms
+        assert_eq!(history.velocity(StickAxis::LeftX), Some(400_000));
+        assert_eq!(history.velocity(StickAxis::LeftY), Some(10_000));
+        assert_eq!(history.velocity(StickAxis::RightX), Some(0));
+    }
+
+    #[test]
+    fn test_oldest_dropped_when_full() {
+        let mut history = StateHistory::<3>::new();
+        for (i, t) in (0..5).zip((0..).step_by(20)) {
+            history.push(&left(i * 1000, 0), t);
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.latest(), Some((80, &left(4000, 0))));
+        assert_eq!(history.get(2), Some((40, &left(2000, 0))));
+        assert_eq!(history.get(3), None);
+        // Window now spans 2000 -> 4000 over 40 ms, moving back is negative
+        assert_eq!(history.velocity(StickAxis::LeftX), Some(50_000));
+
+        history.push(&left(-4000, 0), 100);
+        assert_eq!(history.velocity(StickAxis::LeftX), Some(-175_000));
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.latest(), None);
+    }
+
+    #[test]
+    fn test_no_elapsed_time() {
+        let mut history = StateHistory::<2>::new();
+        history.push(&left(0, 0), 500);
+        history.push(&left(1000, 0), 500);
+        assert_eq!(history.velocity(StickAxis::LeftX), None);
+    }
+}
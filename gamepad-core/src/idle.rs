@@ -0,0 +1,125 @@
+//! Idle detection for low-power and status indication.
+//!
+//! [`IdleDetector`] tracks how long the gamepad state has been unchanged.
+//! Time is injected as a millisecond timestamp on every call, so the detector
+//! works with any clock (e.g. `embassy_time::Instant::now().as_millis()`) and
+//! is fully testable on the host.
+
+use gamepad_proto::GamepadState;
+
+/// Tracks the time since the gamepad state last changed.
+#[derive(Debug, Clone)]
+pub struct IdleDetector {
+    /// Unchanged duration after which the input counts as idle
+    threshold_ms: u64,
+    /// Last observed state (`None` until the first update)
+    last: Option<GamepadState>,
+    /// Timestamp of the last state change
+    last_change_ms: u64,
+}
+
+impl IdleDetector {
+    /// Create a detector that reports idle after `threshold_ms` without changes.
+    ///
+    /// The idle time counts from timestamp 0 until the first update.
+    #[must_use]
+    pub const fn new(threshold_ms: u64) -> Self {
+        Self {
+            threshold_ms,
+            last: None,
+            last_change_ms: 0,
+        }
+    }
+
+    /// Get the idle threshold in milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn threshold_ms(&self) -> u64 {
+        self.threshold_ms
+    }
+
+    /// Record the current state.
+    ///
+    /// Returns `true` if the state differs from the previous one (the first
+    /// update always counts as a change).
+    pub fn update(&mut self, state: &GamepadState, now_ms: u64) -> bool {
+        let changed = self.last.as_ref() != Some(state);
+        if changed {
+            self.last = Some(*state);
+            self.last_change_ms = now_ms;
+        }
+        changed
+    }
+
+    /// Time since the last state change.
+    ///
+    /// Saturates at zero if `now_ms` is earlier than the last change.
+    #[must_use]
+    pub const fn idle_duration(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.last_change_ms)
+    }
+
+    /// Returns true if the state has been unchanged for at least the threshold.
+    #[must_use]
+    pub const fn is_idle(&self, now_ms: u64) -> bool {
+        self.idle_duration(now_ms) >= self.threshold_ms
+    }
+
+    /// Treat `now_ms` as activity without changing the tracked state.
+    pub fn reset(&mut self, now_ms: u64) {
+        self.last_change_ms = now_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamepad_proto::AnalogStick;
+
+    fn stick(x: i16) -> GamepadState {
+        GamepadState {
+            left_stick: AnalogStick::new(x, 0),
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_changing_then_static_input_goes_idle() {
+        let mut idle = IdleDetector::new(1000);
+
+        // Moving stick, one frame every 100 ms
+        for i in 0..10u64 {
+            assert!(idle.update(&stick(i as i16 * 100), i * 100));
+            assert!(!idle.is_idle(i * 100));
+        }
+
+        // Stick held still from t=900
+        let held = stick(900);
+        for t in (1000..1900).step_by(100) {
+            assert!(!idle.update(&held, t));
+            assert!(!idle.is_idle(t));
+        }
+        assert!(!idle.update(&held, 1900));
+        assert!(idle.is_idle(1900));
+        assert_eq!(idle.idle_duration(2500), 1600);
+
+        // Any change wakes it up again
+        assert!(idle.update(&stick(0), 2600));
+        assert!(!idle.is_idle(2600));
+        assert_eq!(idle.idle_duration(2600), 0);
+    }
+
+    #[test]
+    fn test_first_update_and_reset() {
+        let mut idle = IdleDetector::new(500);
+        assert!(idle.is_idle(500));
+
+        assert!(idle.update(&GamepadState::neutral(), 500));
+        assert!(!idle.is_idle(999));
+        assert!(idle.is_idle(1000));
+
+        idle.reset(1000);
+        assert!(!idle.is_idle(1499));
+        assert_eq!(idle.idle_duration(900), 0);
+    }
+}
@@ -1,7 +1,9 @@
 //! Input source trait and error types.
 
-use core::future::Future;
-use gamepad_proto::GamepadState;
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+use gamepad_proto::{Buttons, GamepadState};
 
 /// Error type for input operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -21,11 +23,25 @@ pub enum InputError {
     Framing,
 }
 
+impl core::fmt::Display for InputError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io => write!(f, "I/O error"),
+            Self::Parse => write!(f, "invalid message format"),
+            Self::Checksum => write!(f, "checksum mismatch"),
+            Self::Disconnected => write!(f, "disconnected"),
+            Self::BufferOverflow => write!(f, "line too long"),
+            Self::Framing => write!(f, "framing error"),
+        }
+    }
+}
+
 impl From<gamepad_proto::ParseError> for InputError {
     fn from(err: gamepad_proto::ParseError) -> Self {
         match err {
             gamepad_proto::ParseError::Parse => InputError::Parse,
             gamepad_proto::ParseError::Checksum => InputError::Checksum,
+            gamepad_proto::ParseError::Overflow => InputError::BufferOverflow,
         }
     }
 }
@@ -38,13 +54,718 @@ impl From<gamepad_proto::ParseError> for InputError {
 /// # `no_std` Compatibility
 ///
 /// All implementations must be `#![no_std]` compatible with no heap allocation.
+///
+/// # Cancellation Safety
+///
+/// The future returned by [`receive`](InputSource::receive) is routinely
+/// dropped before completion, e.g. when it loses an embassy `select` against a
+/// timer. Implementations must keep any partially received frame in `self`
+/// (not in locals of the future) and must not discard it when `receive` is
+/// called again, so that a dropped call never corrupts or loses the next frame.
 pub trait InputSource {
     /// Wait for and receive the next gamepad state update.
     ///
     /// This is an async operation that yields when no data is available.
     /// Returns the new gamepad state or an error.
+    ///
+    /// Must be cancellation safe; see the [trait docs](InputSource#cancellation-safety).
     fn receive(&mut self) -> impl Future<Output = Result<GamepadState, InputError>>;
 
     /// Check if the input source is connected/ready.
     fn is_connected(&self) -> bool;
 }
+
+/// Input source backed by a closure.
+///
+/// Each [`receive`](InputSource::receive) future invokes the closure once when
+/// first polled and completes immediately with its result, which makes this
+/// handy for tests and simple synthetic generators that don't warrant a
+/// dedicated type.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_core::{FnInputSource, GamepadState, InputSource};
+///
+/// let mut x = 0i16;
+/// let mut input = FnInputSource::new(move || {
+///     x += 100;
+///     let mut state = GamepadState::neutral();
+///     state.left_stick.x = x;
+///     Ok(state)
+/// });
+/// assert!(input.is_connected());
+/// ```
+pub struct FnInputSource<F> {
+    f: F,
+}
+
+impl<F> FnInputSource<F>
+where
+    F: FnMut() -> Result<GamepadState, InputError>,
+{
+    /// Create a new input source calling `f` for every state.
+    #[must_use]
+    pub const fn new(f: F) -> Self {
+        Self { f }
+    }
+
+    /// Consume the source and return the closure.
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+}
+
+impl<F> InputSource for FnInputSource<F>
+where
+    F: FnMut() -> Result<GamepadState, InputError>,
+{
+    fn receive(&mut self) -> impl Future<Output = Result<GamepadState, InputError>> {
+        // Run the closure when polled rather than when the future is created,
+        // so a future dropped unpolled (e.g. losing a select) discards nothing
+        poll_fn(move |_| Poll::Ready((self.f)()))
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+/// Input source combining a button board and a stick board.
+///
+/// Buttons are taken from the `B` source; sticks and triggers from the `S`
+/// source. Each [`receive`](InputSource::receive) waits for whichever source
+/// reports first and returns the merged state. Sources are polled in
+/// alternating order so a busy source cannot starve the other.
+///
+/// Errors from either source are returned as-is; the last good half of the
+/// state is kept. Both sub-sources must be cancellation safe, as the slower
+/// `receive` future is dropped whenever the other source reports first.
+pub struct SplitInput<B, S> {
+    buttons: B,
+    sticks: S,
+    /// Buttons from the last state reported by `buttons`
+    last_buttons: Buttons,
+    /// Sticks and triggers from the last state reported by `sticks`
+    last_analog: GamepadState,
+    /// Poll `sticks` before `buttons` on the next call
+    sticks_first: bool,
+}
+
+/// Result of whichever half of a [`SplitInput`] reported first.
+enum SplitUpdate {
+    Buttons(Result<GamepadState, InputError>),
+    Sticks(Result<GamepadState, InputError>),
+}
+
+impl<B: InputSource, S: InputSource> SplitInput<B, S> {
+    /// Combine a buttons-providing source and a sticks/triggers-providing source.
+    #[must_use]
+    pub fn new(buttons: B, sticks: S) -> Self {
+        Self {
+            buttons,
+            sticks,
+            last_buttons: Buttons::NONE,
+            last_analog: GamepadState::neutral(),
+            sticks_first: false,
+        }
+    }
+
+    /// Get the merged state from the last reports of both sources.
+    #[must_use]
+    pub fn current_state(&self) -> GamepadState {
+        GamepadState {
+            buttons: self.last_buttons,
+            ..self.last_analog
+        }
+    }
+
+    /// Consume the combinator and return the two sources.
+    pub fn into_inner(self) -> (B, S) {
+        (self.buttons, self.sticks)
+    }
+}
+
+impl<B: InputSource, S: InputSource> InputSource for SplitInput<B, S> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        let sticks_first = self.sticks_first;
+        self.sticks_first = !sticks_first;
+
+        let update = {
+            let mut buttons = pin!(self.buttons.receive());
+            let mut sticks = pin!(self.sticks.receive());
+            poll_fn(|cx| {
+                if sticks_first {
+                    if let Poll::Ready(result) = sticks.as_mut().poll(cx) {
+                        return Poll::Ready(SplitUpdate::Sticks(result));
+                    }
+                }
+                if let Poll::Ready(result) = buttons.as_mut().poll(cx) {
+                    return Poll::Ready(SplitUpdate::Buttons(result));
+                }
+                if !sticks_first {
+                    if let Poll::Ready(result) = sticks.as_mut().poll(cx) {
+                        return Poll::Ready(SplitUpdate::Sticks(result));
+                    }
+                }
+                Poll::Pending
+            })
+            .await
+        };
+
+        match update {
+            SplitUpdate::Buttons(result) => self.last_buttons = result?.buttons,
+            SplitUpdate::Sticks(result) => self.last_analog = result?,
+        }
+
+        Ok(self.current_state())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.buttons.is_connected() && self.sticks.is_connected()
+    }
+}
+
+/// Input source applying a closure to every received state.
+///
+/// For quick custom transforms without a dedicated wrapper type. Errors from
+/// the inner source are passed through without calling the closure. Being an
+/// [`InputSource`] itself, it composes with the other adapters.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_core::{AnalogStick, FnInputSource, GamepadState, InputSource, MapInput};
+///
+/// let inner = FnInputSource::new(|| Ok(GamepadState::neutral()));
+/// let input = MapInput::new(inner, |state: GamepadState| GamepadState {
+///     right_stick: AnalogStick::NEUTRAL,
+///     ..state
+/// });
+/// assert!(input.is_connected());
+/// ```
+pub struct MapInput<I, F> {
+    inner: I,
+    f: F,
+}
+
+impl<I, F> MapInput<I, F>
+where
+    I: InputSource,
+    F: FnMut(GamepadState) -> GamepadState,
+{
+    /// Wrap `inner`, passing every state it reports through `f`.
+    #[must_use]
+    pub const fn new(inner: I, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    /// Get a reference to the inner source.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Consume the adapter and return the inner source.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I, F> InputSource for MapInput<I, F>
+where
+    I: InputSource,
+    F: FnMut(GamepadState) -> GamepadState,
+{
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        self.inner.receive().await.map(&mut self.f)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+}
+
+/// Input source rewriting the button layout through a lookup table.
+///
+/// Start from [`IDENTITY_REMAP`] and change the entries to move.
+///
+/// Entry `n` of the table holds the buttons that bit `n` of the inner
+/// state's bitfield turns into: swap A and B, move START, or fix a
+/// mis-wired transmitter without touching its mapper. An entry of
+/// [`Buttons::NONE`] drops the button, one with several bits presses all of
+/// them. Sticks and triggers pass through unchanged.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_core::{Buttons, FnInputSource, GamepadState, Remap, IDENTITY_REMAP};
+///
+/// let mut table = IDENTITY_REMAP;
+/// table[0] = Buttons::B; // A -> B
+/// table[1] = Buttons::A; // B -> A
+///
+/// let inner = FnInputSource::new(|| Ok(GamepadState::neutral()));
+/// let input = Remap::new(inner, table);
+/// assert_eq!(input.map_buttons(Buttons::A | Buttons::X), Buttons::B | Buttons::X);
+/// ```
+pub struct Remap<I> {
+    inner: I,
+    table: [Buttons; 16],
+}
+
+/// [`Remap`] table mapping every button to itself.
+pub const IDENTITY_REMAP: [Buttons; 16] = {
+    let mut table = [Buttons::NONE; 16];
+    let mut bit = 0;
+    while bit < 16 {
+        table[bit] = Buttons(1 << bit);
+        bit += 1;
+    }
+    table
+};
+
+impl<I> Remap<I> {
+    /// Wrap `inner`, mapping the buttons of every state through `table`.
+    #[must_use]
+    pub const fn new(inner: I, table: [Buttons; 16]) -> Self {
+        Self { inner, table }
+    }
+
+    /// Get the lookup table.
+    #[inline]
+    #[must_use]
+    pub const fn table(&self) -> &[Buttons; 16] {
+        &self.table
+    }
+
+    /// Map a button bitfield through the table.
+    #[must_use]
+    pub fn map_buttons(&self, buttons: Buttons) -> Buttons {
+        buttons
+            .iter_pressed()
+            .fold(Buttons::NONE, |mapped, button| {
+                mapped | self.table[button.0.trailing_zeros() as usize]
+            })
+    }
+
+    /// Get a reference to the inner source.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Consume the adapter and return the inner source.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I: InputSource> InputSource for Remap<I> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        let mut state = self.inner.receive().await?;
+        state.buttons = self.map_buttons(state.buttons);
+        Ok(state)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+}
+
+/// What [`TimeoutInput`] reports once the link has timed out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FailsafeMode {
+    /// Report [`GamepadState::neutral`], so the output keeps being driven.
+    #[default]
+    Neutral,
+    /// Report [`InputError::Disconnected`] and leave the reaction to the caller.
+    Disconnected,
+}
+
+/// Failsafe wrapper reporting a neutral state when the link goes quiet.
+///
+/// Sources like MAVLink and CRSF keep their last state when packets stop
+/// arriving, which is dangerous when the output drives a vehicle.
+/// `TimeoutInput` remembers when the inner source last delivered a state;
+/// once `timeout_ms` passed without one, it reports the [`FailsafeMode`]
+/// result instead of inner errors, and the next good packet recovers.
+///
+/// An inner source that just waits for data never returns, so the owner
+/// races [`receive`](InputSource::receive) against a timer for
+/// [`deadline_ms`](Self::deadline_ms) and calls
+/// [`check_timeout`](Self::check_timeout) when the timer wins. As with
+/// [`OutputPacer`](crate::OutputPacer), the clock is injected as a closure
+/// returning milliseconds.
+pub struct TimeoutInput<I, C> {
+    inner: I,
+    clock: C,
+    timeout_ms: u64,
+    mode: FailsafeMode,
+    /// Time of the last state received (`None` before the first one)
+    last_ok_ms: Option<u64>,
+    /// Set once the timeout was reported, until the next state
+    timed_out: bool,
+}
+
+impl<I: InputSource, C: FnMut() -> u64> TimeoutInput<I, C> {
+    /// Wrap `inner`, failing safe after `timeout_ms` without a state.
+    ///
+    /// The timeout starts with the first state received.
+    pub const fn new(inner: I, timeout_ms: u64, mode: FailsafeMode, clock: C) -> Self {
+        Self {
+            inner,
+            clock,
+            timeout_ms,
+            mode,
+            last_ok_ms: None,
+            timed_out: false,
+        }
+    }
+
+    /// Time at which the link times out, or `None` before the first state.
+    #[must_use]
+    pub fn deadline_ms(&self) -> Option<u64> {
+        self.last_ok_ms
+            .map(|last| last.saturating_add(self.timeout_ms))
+    }
+
+    /// Returns true while the link is timed out.
+    #[inline]
+    #[must_use]
+    pub const fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Report the failsafe result if the deadline has passed.
+    ///
+    /// Returns `Some` once per timeout; `None` if the link is still alive or
+    /// the timeout was already reported.
+    pub fn check_timeout(&mut self) -> Option<Result<GamepadState, InputError>> {
+        let now = (self.clock)();
+        if self.timed_out || !self.expired(now) {
+            return None;
+        }
+        Some(self.failsafe())
+    }
+
+    /// Get a reference to the inner source.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Consume the wrapper and return the inner source.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    fn expired(&self, now_ms: u64) -> bool {
+        self.deadline_ms()
+            .is_some_and(|deadline| now_ms >= deadline)
+    }
+
+    fn failsafe(&mut self) -> Result<GamepadState, InputError> {
+        self.timed_out = true;
+        match self.mode {
+            FailsafeMode::Neutral => Ok(GamepadState::neutral()),
+            FailsafeMode::Disconnected => Err(InputError::Disconnected),
+        }
+    }
+}
+
+impl<I: InputSource, C: FnMut() -> u64> InputSource for TimeoutInput<I, C> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        let result = self.inner.receive().await;
+        let now = (self.clock)();
+        match result {
+            Ok(state) => {
+                self.last_ok_ms = Some(now);
+                self.timed_out = false;
+                Ok(state)
+            }
+            Err(_) if self.expired(now) => self.failsafe(),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.timed_out && self.inner.is_connected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::block_on;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_fn_input_counter() {
+        let mut count = 0i16;
+        let mut input = FnInputSource::new(|| {
+            count += 1;
+            let mut state = GamepadState::neutral();
+            state.left_stick.x = count * 1000;
+            Ok(state)
+        });
+
+        for expected in [1000, 2000, 3000] {
+            let state = block_on(input.receive()).unwrap();
+            assert_eq!(state.left_stick.x, expected);
+        }
+        assert!(input.is_connected());
+    }
+
+    #[test]
+    fn test_fn_input_propagates_errors() {
+        let mut calls = 0u8;
+        let mut input = FnInputSource::new(move || {
+            calls += 1;
+            if calls > 1 {
+                Err(InputError::Disconnected)
+            } else {
+                Ok(GamepadState::neutral())
+            }
+        });
+
+        assert_eq!(block_on(input.receive()), Ok(GamepadState::neutral()));
+        assert_eq!(block_on(input.receive()), Err(InputError::Disconnected));
+    }
+
+    fn stick_source() -> FnInputSource<impl FnMut() -> Result<GamepadState, InputError>> {
+        let mut x = 0i16;
+        FnInputSource::new(move || {
+            x += 1000;
+            Ok(GamepadState {
+                // Must be ignored by SplitInput
+                buttons: Buttons::START,
+                left_stick: gamepad_proto::AnalogStick::new(x, -x),
+                left_trigger: 50,
+                ..GamepadState::neutral()
+            })
+        })
+    }
+
+    #[test]
+    fn test_split_input_merges_halves() {
+        let buttons = FnInputSource::new(|| {
+            Ok(GamepadState {
+                buttons: Buttons::A | Buttons::X,
+                // Must be ignored by SplitInput
+                right_trigger: 200,
+                ..GamepadState::neutral()
+            })
+        });
+        let mut input = SplitInput::new(buttons, stick_source());
+
+        // Sources alternate: buttons first, then sticks
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.buttons, Buttons::A | Buttons::X);
+        assert_eq!(state.right_trigger, 0);
+        assert_eq!(state.left_stick.x, 0);
+
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.buttons, Buttons::A | Buttons::X);
+        assert_eq!(state.left_stick.x, 1000);
+        assert_eq!(state.left_stick.y, -1000);
+        assert_eq!(state.left_trigger, 50);
+        assert_eq!(state.right_trigger, 0);
+
+        assert_eq!(input.current_state(), state);
+        assert!(input.is_connected());
+    }
+
+    #[test]
+    fn test_split_input_error_keeps_last_half() {
+        let buttons = FnInputSource::new(|| Err(InputError::Checksum));
+        let mut input = SplitInput::new(buttons, stick_source());
+
+        assert_eq!(block_on(input.receive()), Err(InputError::Checksum));
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.buttons, Buttons::NONE);
+        assert_eq!(state.left_stick.x, 1000);
+
+        assert_eq!(block_on(input.receive()), Err(InputError::Checksum));
+        assert_eq!(input.current_state(), state);
+    }
+
+    #[test]
+    fn test_map_input_zeroes_right_stick() {
+        let inner = FnInputSource::new(|| {
+            Ok(GamepadState {
+                buttons: Buttons::A,
+                left_stick: gamepad_proto::AnalogStick::new(100, -100),
+                right_stick: gamepad_proto::AnalogStick::new(2000, -3000),
+                ..GamepadState::neutral()
+            })
+        });
+        let mut input = MapInput::new(inner, |state| GamepadState {
+            right_stick: gamepad_proto::AnalogStick::NEUTRAL,
+            ..state
+        });
+
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.right_stick, gamepad_proto::AnalogStick::NEUTRAL);
+        assert_eq!(state.left_stick, gamepad_proto::AnalogStick::new(100, -100));
+        assert_eq!(state.buttons, Buttons::A);
+        assert!(input.is_connected());
+    }
+
+    #[test]
+    fn test_map_input_propagates_errors() {
+        let mut fail = true;
+        let inner = FnInputSource::new(move || {
+            fail = !fail;
+            if fail {
+                Err(InputError::Framing)
+            } else {
+                Ok(GamepadState::neutral())
+            }
+        });
+        let mut input = MapInput::new(inner, |mut state: GamepadState| {
+            state.left_trigger = 42;
+            state
+        });
+
+        assert_eq!(block_on(input.receive()).unwrap().left_trigger, 42);
+        assert_eq!(block_on(input.receive()), Err(InputError::Framing));
+        assert_eq!(block_on(input.receive()).unwrap().left_trigger, 42);
+    }
+
+    #[test]
+    fn test_remap_swaps_a_and_b() {
+        let mut table = IDENTITY_REMAP;
+        table[0] = Buttons::B;
+        table[1] = Buttons::A;
+        let inner = FnInputSource::new(|| {
+            Ok(GamepadState {
+                buttons: Buttons::A | Buttons::START,
+                left_stick: gamepad_proto::AnalogStick::new(100, -100),
+                right_trigger: 200,
+                ..GamepadState::neutral()
+            })
+        });
+        let mut input = Remap::new(inner, table);
+
+        let state = block_on(input.receive()).unwrap();
+        assert_eq!(state.buttons, Buttons::B | Buttons::START);
+        assert_eq!(state.left_stick, gamepad_proto::AnalogStick::new(100, -100));
+        assert_eq!(state.right_trigger, 200);
+
+        assert_eq!(
+            input.map_buttons(Buttons::A | Buttons::B),
+            Buttons::A | Buttons::B
+        );
+        assert_eq!(input.map_buttons(Buttons::B), Buttons::A);
+        assert!(input.is_connected());
+    }
+
+    #[test]
+    fn test_remap_identity_is_noop() {
+        let states = [
+            Buttons::NONE,
+            Buttons::A | Buttons::START | Buttons::DPAD_LEFT,
+            Buttons(u16::MAX),
+        ]
+        .map(|buttons| GamepadState {
+            buttons,
+            left_trigger: 7,
+            ..GamepadState::neutral()
+        });
+        let script = states.map(Ok);
+        let mut input = Remap::new(scripted(&script), IDENTITY_REMAP);
+
+        for expected in states {
+            assert_eq!(block_on(input.receive()), Ok(expected));
+        }
+        assert_eq!(block_on(input.receive()), Err(InputError::Disconnected));
+    }
+
+    /// Input replaying `script`, one result per call, then disconnecting.
+    fn scripted(
+        script: &[Result<GamepadState, InputError>],
+    ) -> FnInputSource<impl FnMut() -> Result<GamepadState, InputError> + '_> {
+        let mut next = script.iter();
+        FnInputSource::new(move || *next.next().unwrap_or(&Err(InputError::Disconnected)))
+    }
+
+    #[test]
+    fn test_timeout_input_neutral_after_deadline() {
+        let active = GamepadState {
+            buttons: Buttons::A,
+            left_trigger: 200,
+            ..GamepadState::neutral()
+        };
+        let script = [Ok(active), Err(InputError::Checksum), Ok(active)];
+        let now = Cell::new(1_000);
+        let mut input =
+            TimeoutInput::new(scripted(&script), 100, FailsafeMode::Neutral, || now.get());
+
+        // Nothing to time out before the first state
+        assert_eq!(input.deadline_ms(), None);
+        assert_eq!(input.check_timeout(), None);
+
+        assert_eq!(block_on(input.receive()), Ok(active));
+        assert_eq!(input.deadline_ms(), Some(1_100));
+
+        // Errors within the window pass through
+        now.set(1_050);
+        assert_eq!(block_on(input.receive()), Err(InputError::Checksum));
+        assert_eq!(input.check_timeout(), None);
+
+        // Deadline reached: neutral once, then nothing until recovery
+        now.set(1_100);
+        assert_eq!(input.check_timeout(), Some(Ok(GamepadState::neutral())));
+        assert_eq!(input.check_timeout(), None);
+        assert!(input.is_timed_out());
+        assert!(!input.is_connected());
+
+        // The next packet recovers
+        now.set(1_500);
+        assert_eq!(block_on(input.receive()), Ok(active));
+        assert!(!input.is_timed_out());
+        assert!(input.is_connected());
+        assert_eq!(input.deadline_ms(), Some(1_600));
+    }
+
+    #[test]
+    fn test_timeout_input_disconnected_mode() {
+        let script = [Ok(GamepadState::neutral()), Err(InputError::Io)];
+        let now = Cell::new(0);
+        let mut input =
+            TimeoutInput::new(scripted(&script), 20, FailsafeMode::Disconnected, || {
+                now.get()
+            });
+
+        assert!(block_on(input.receive()).is_ok());
+
+        // An inner error after the deadline reports the failsafe result
+        now.set(25);
+        assert_eq!(block_on(input.receive()), Err(InputError::Disconnected));
+        assert!(input.is_timed_out());
+        assert_eq!(input.check_timeout(), None);
+    }
+
+    #[test]
+    fn test_split_input_waits_for_pending_source() {
+        struct Never;
+
+        impl InputSource for Never {
+            fn receive(&mut self) -> impl Future<Output = Result<GamepadState, InputError>> {
+                core::future::pending()
+            }
+
+            fn is_connected(&self) -> bool {
+                false
+            }
+        }
+
+        let mut input = SplitInput::new(Never, stick_source());
+        assert!(!input.is_connected());
+
+        // Only the sticks report, on every call
+        for expected in [1000, 2000, 3000] {
+            let state = block_on(input.receive()).unwrap();
+            assert_eq!(state.left_stick.x, expected);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Round-trip latency measurement with a loopback button.
+//!
+//! For benchmarking, the bridge drives a marker GPIO that is wired back into
+//! the input device as one of its buttons. [`LatencyProbe`] records when the
+//! marker level was changed and matches it against the first received state
+//! in which that button shows the new level; the difference is the round-trip
+//! latency through the sender, the link and the parser.
+//!
+//! Only one marker is in flight at a time, so a received state can never be
+//! matched to the wrong edge. Time is injected as a microsecond timestamp.
+
+use gamepad_proto::{Buttons, GamepadState};
+
+/// Round-trip latency statistics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LatencyStats {
+    /// Markers matched to a received state
+    samples: u32,
+    /// Markers not seen within the timeout
+    lost: u32,
+    /// Shortest round trip in µs
+    min_us: u64,
+    /// Longest round trip in µs
+    max_us: u64,
+    /// Sum of all round trips in µs
+    total_us: u64,
+}
+
+impl LatencyStats {
+    /// Number of markers matched to a received state.
+    #[inline]
+    #[must_use]
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Number of markers not seen within the timeout.
+    #[inline]
+    #[must_use]
+    pub const fn lost(&self) -> u32 {
+        self.lost
+    }
+
+    /// Shortest round trip in µs, or `None` without samples.
+    #[must_use]
+    pub const fn min_us(&self) -> Option<u64> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.min_us)
+        }
+    }
+
+    /// Longest round trip in µs, or `None` without samples.
+    #[must_use]
+    pub const fn max_us(&self) -> Option<u64> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.max_us)
+        }
+    }
+
+    /// Mean round trip in µs, or `None` without samples.
+    #[must_use]
+    pub const fn mean_us(&self) -> Option<u64> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.total_us / self.samples as u64)
+        }
+    }
+
+    fn record(&mut self, latency_us: u64) {
+        if self.samples == 0 {
+            self.min_us = latency_us;
+            self.max_us = latency_us;
+        } else {
+            self.min_us = self.min_us.min(latency_us);
+            self.max_us = self.max_us.max(latency_us);
+        }
+        self.samples = self.samples.saturating_add(1);
+        self.total_us = self.total_us.saturating_add(latency_us);
+    }
+}
+
+/// Correlates marker edges with received states.
+///
+/// Call [`send_marker`](Self::send_marker) when a report was sent and drive
+/// the marker output to the returned level, then pass every received state
+/// to [`on_receive`](Self::on_receive).
+#[derive(Debug, Clone)]
+pub struct LatencyProbe {
+    /// Button the marker output is wired to
+    button: Buttons,
+    /// Time after which a marker in flight counts as lost
+    timeout_us: u64,
+    /// Level the marker was last driven to
+    level: bool,
+    /// Send timestamp of the marker in flight
+    sent_us: Option<u64>,
+    /// Statistics of all markers so far
+    stats: LatencyStats,
+}
+
+impl LatencyProbe {
+    /// Create a probe for a marker wired to `button`, with the marker output
+    /// initially low.
+    #[must_use]
+    pub const fn new(button: Buttons, timeout_us: u64) -> Self {
+        Self {
+            button,
+            timeout_us,
+            level: false,
+            sent_us: None,
+            stats: LatencyStats {
+                samples: 0,
+                lost: 0,
+                min_us: 0,
+                max_us: 0,
+                total_us: 0,
+            },
+        }
+    }
+
+    /// Start a new marker, returning the level to drive the output to.
+    ///
+    /// Returns `None` while the previous marker is still in flight.
+    pub fn send_marker(&mut self, now_us: u64) -> Option<bool> {
+        self.expire(now_us);
+        if self.sent_us.is_some() {
+            return None;
+        }
+        self.level = !self.level;
+        self.sent_us = Some(now_us);
+        Some(self.level)
+    }
+
+    /// Check a received state for the marker in flight.
+    ///
+    /// Returns the round-trip latency in µs when the state shows the marker.
+    pub fn on_receive(&mut self, state: &GamepadState, now_us: u64) -> Option<u64> {
+        self.expire(now_us);
+        let sent_us = self.sent_us?;
+        if state.buttons.contains(self.button) != self.level {
+            return None;
+        }
+
+        let latency_us = now_us.saturating_sub(sent_us);
+        self.sent_us = None;
+        self.stats.record(latency_us);
+        Some(latency_us)
+    }
+
+    /// Returns true while a marker is waiting to be received.
+    #[inline]
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.sent_us.is_some()
+    }
+
+    /// Get the statistics of all markers so far.
+    #[inline]
+    #[must_use]
+    pub const fn stats(&self) -> &LatencyStats {
+        &self.stats
+    }
+
+    /// Count the marker in flight as lost once the timeout has passed.
+    fn expire(&mut self, now_us: u64) {
+        if let Some(sent_us) = self.sent_us {
+            if now_us.saturating_sub(sent_us) > self.timeout_us {
+                self.sent_us = None;
+                self.stats.lost = self.stats.lost.saturating_add(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKER: Buttons = Buttons(1 << 15);
+
+    fn state(marker: bool) -> GamepadState {
+        GamepadState {
+            buttons: if marker {
+                Buttons::A | MARKER
+            } else {
+                Buttons::A
+            },
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_match_alternating_markers() {
+        let mut probe = LatencyProbe::new(MARKER, 100_000);
+        assert_eq!(probe.on_receive(&state(true), 0), None);
+
+        assert_eq!(probe.send_marker(1_000), Some(true));
+        assert!(probe.is_pending());
+        assert_eq!(probe.send_marker(1_100), None);
+        // Frames already in flight still show the old level
+        assert_eq!(probe.on_receive(&state(false), 1_500), None);
+        assert_eq!(probe.on_receive(&state(true), 3_200), Some(2_200));
+        assert!(!probe.is_pending());
+
+        assert_eq!(probe.send_marker(4_000), Some(false));
+        assert_eq!(probe.on_receive(&state(true), 4_300), None);
+        assert_eq!(probe.on_receive(&state(false), 5_000), Some(1_000));
+        // Repeated states of the same level are not matched again
+        assert_eq!(probe.on_receive(&state(false), 5_100), None);
+
+        let stats = probe.stats();
+        assert_eq!(stats.samples(), 2);
+        assert_eq!(stats.lost(), 0);
+        assert_eq!(stats.min_us(), Some(1_000));
+        assert_eq!(stats.max_us(), Some(2_200));
+        assert_eq!(stats.mean_us(), Some(1_600));
+    }
+
+    #[test]
+    fn test_lost_marker_times_out() {
+        let mut probe = LatencyProbe::new(MARKER, 10_000);
+        assert_eq!(probe.stats().mean_us(), None);
+
+        assert_eq!(probe.send_marker(0), Some(true));
+        assert_eq!(probe.send_marker(10_000), None);
+
+        // Arrives after the timeout: counted as lost, not as a sample
+        assert_eq!(probe.on_receive(&state(true), 10_001), None);
+        assert_eq!(probe.stats().lost(), 1);
+        assert_eq!(probe.stats().samples(), 0);
+
+        assert_eq!(probe.send_marker(20_000), Some(false));
+        assert_eq!(probe.on_receive(&state(false), 20_400), Some(400));
+        assert_eq!(probe.stats().lost(), 1);
+        assert_eq!(probe.stats().samples(), 1);
+    }
+}
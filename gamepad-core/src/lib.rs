@@ -12,14 +12,38 @@
 //!   ([`GamepadState`], [`Buttons`], [`AnalogStick`], [`GamepadFieldUpdate`])
 //! - **Protocol** (re-exported from [`gamepad_proto`]): UART protocol parsing
 //!   and serialization ([`parse`], [`parse_message`], [`Serialize`], [`MessageBuilder`])
-//! - [`input`]: Input source trait ([`InputSource`])
-//! - [`output`]: Output sink trait ([`OutputSink`])
+//! - [`input`]: Input source trait ([`InputSource`]), closure adapters ([`FnInputSource`],
+//!   [`MapInput`]), button/stick board combinator ([`SplitInput`]), button
+//!   remapping ([`Remap`]) and link-loss failsafe ([`TimeoutInput`])
+//! - [`activity`]: Which controls are in use, as a two-byte summary ([`ActivityEncoder`])
+//! - [`adc`]: Analog joysticks on an ADC with per-channel calibration
+//!   and optional multi-point linearization ([`AdcInputSource`], [`AdcCalibration`])
+//! - [`latency`]: Round-trip latency through a loopback marker button ([`LatencyProbe`])
+//! - [`mouse`]: Right stick to relative mouse movement ([`StickMouse`])
+//! - [`output`]: Output sink trait ([`OutputSink`]), report pacing ([`OutputPacer`]),
+//!   keepalive re-sends ([`KeepaliveOutput`]), burst coalescing ([`CoalescingOutput`]),
+//!   duplicate suppression ([`ChangeFilter`]) and report timing statistics
+//!   (`TimingOutput`, `std` only)
 //! - [`bridge`]: Orchestrates input-to-output flow ([`GamepadBridge`])
-//! - [`telemetry`]: Bidirectional telemetry support ([`TelemetrySink`], [`TelemetrySource`])
+//! - [`diagnostic`]: Last state and error counters as one log line ([`diagnostic_dump`])
+//! - [`error`]: One error type converting from all specific errors ([`GamepadError`])
+//! - [`history`]: Recent states and stick velocity ([`StateHistory`])
+//! - [`idle`]: Time since the last state change ([`IdleDetector`])
+//! - [`reader`]: Line protocol input over any async byte or packet reader
+//!   ([`ReaderInputSource`], [`PacketByteReader`])
+//! - [`ring_buffer`]: Interrupt-fed SPSC queue input source ([`RingBufferInputSource`])
+//! - [`rumble`]: Rumble motor magnitudes from HID output reports ([`RumbleState`])
+//! - [`safety`]: Neutral cutoff for active inputs held unchanged too long ([`SafetyCutoff`])
+//! - [`telemetry`]: Bidirectional telemetry support ([`TelemetrySink`], [`TelemetrySource`],
+//!   [`TelemetryEncoder`]) and per-type send rates ([`TelemetryScheduler`])
+//! - [`text_telemetry`]: `T` telemetry frames for the text protocol backchannel
+//! - [`trigger_button`]: Trigger-driven buttons with a minimum press time ([`TriggerButton`])
+//! - [`uart`]: Baud rate and framing per serial protocol ([`UartParams`])
 //!
 //! # Protocol
 //!
-//! The protocol supports two message types:
+//! The protocol supports two message types, each ending in a CRC-8/SMBUS
+//! checksum of the payload (see [`calculate_crc8`]):
 //!
 //! **Full State** - Complete gamepad snapshot:
 //! ```text
@@ -37,23 +61,30 @@
 //! use gamepad_core::{parse_message, ParsedMessage, GamepadState};
 //!
 //! // Parse a full state message
-//! let msg = b"G0001:1000:-1000:0:0:128:64*XX"; // checksum placeholder
-//! // In real use, calculate proper checksum
+//! let msg = b"G0001:1000:-1000:0:0:128:64*75\n";
+//! let Ok(ParsedMessage::FullState(mut state)) = parse_message(msg) else {
+//!     panic!("valid message");
+//! };
+//! assert_eq!(state.left_stick.x, 1000);
 //!
 //! // Parse an incremental update
-//! let mut state = GamepadState::neutral();
-//! if let Ok(ParsedMessage::Update(update)) = parse_message(b"ULX:5000*29") {
-//!     state.apply_update(update);
-//!     assert_eq!(state.left_stick.x, 5000);
-//! }
+//! let Ok(ParsedMessage::Update(update)) = parse_message(b"ULX:5000*99\n") else {
+//!     panic!("valid message");
+//! };
+//! state.apply_update(update);
+//! assert_eq!(state.left_stick.x, 5000);
 //! ```
 //!
 //! # Features
 //!
-//! - **`std`**: Enable standard library support (for host testing)
+//! - **`std`**: Enable standard library support (for host testing) and `TimingOutput`
 //! - **`defmt`**: Enable defmt formatting (for embedded logging)
 //! - **`heapless`**: Enable `serialize_to_vec()` methods
-//! - **`embedded-io`**: Enable `serialize_io()` methods for I/O peripherals
+//! - **`embedded-io`**: Enable `serialize_io()` methods for I/O peripherals and
+//!   `TextTelemetrySink`
+//! - **`embassy-time`**: Enable `GamepadBridge::process_one_with_timeout()`
+//! - **`serde`**: Enable `Serialize`/`Deserialize` for the state types (for
+//!   host tooling such as recorders)
 //!
 //! # No-std Support
 //!
@@ -65,43 +96,126 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod activity;
+pub mod adc;
 pub mod bridge;
+pub mod diagnostic;
+pub mod error;
+pub mod history;
+pub mod idle;
 pub mod input;
+pub mod latency;
+pub mod mouse;
 pub mod output;
+pub mod reader;
+pub mod ring_buffer;
+pub mod rumble;
+pub mod safety;
 pub mod telemetry;
+pub mod text_telemetry;
+pub mod trigger_button;
+pub mod uart;
+
+#[cfg(test)]
+mod test_support;
 
 // Re-export all types and functions from gamepad-proto for convenience
+#[cfg(feature = "heapless")]
+pub use gamepad_proto::MultiUpdateBuilder;
 pub use gamepad_proto::{
     // CRC-8 checksum
     calculate_crc8,
     Crc8Digest,
     // Parser
+    decode_stream,
     parse,
+    parse_fields,
     parse_message,
+    parse_message_detailed,
+    parse_message_detailed_with,
+    parse_message_resync,
+    parse_message_with,
+    parse_with,
     // Serialization
     serialize_full_state,
+    // Transforms
+    split_axis_to_triggers,
+    stick_to_dpad,
     // Types
     AnalogStick,
+    ButtonEdges,
+    ButtonEvent,
     Buttons,
+    ChecksumMode,
+    DecodeStream,
+    DetailedParseError,
+    Dpad,
+    DpadResolution,
+    FieldLayout,
     FullStateBuilder,
+    FullStateFields,
+    FullStateFrame,
     GamepadFieldUpdate,
     GamepadState,
+    LineAccumulator,
     MessageBuilder,
     ParseError,
+    ParseErrorKind,
+    ParseOptions,
     ParsedMessage,
+    PressedButtons,
     Serialize,
     SerializeError,
+    StateField,
+    StickAxis,
+    StickTrim,
+    Transforms,
     UpdateBuilder,
+    MAX_DELTA_UPDATES,
     MAX_FULL_STATE_SIZE,
     MAX_LINE_LENGTH,
     MAX_UPDATE_SIZE,
+    NEUTRAL_FRAME,
+};
+
+// Binary frames
+pub use gamepad_proto::{
+    decode_binary_state, encode_binary_frame, encode_binary_state, BinaryFrame, BinaryParser,
+    BINARY_STATE_SIZE, BINARY_SYNC, MAX_BINARY_FRAME_SIZE, MAX_BINARY_PAYLOAD,
 };
 
 // Re-export local types
-pub use bridge::{BridgeError, GamepadBridge};
-pub use input::{InputError, InputSource};
-pub use output::{OutputError, OutputSink};
+pub use activity::{Activity, ActivityEncoder, ActivitySummary};
+pub use adc::{
+    AdcCalibration, AdcChannel, AdcInputSource, AdcReader, AdcTarget, CalibrationPoint,
+    MAX_CALIBRATION_POINTS,
+};
+pub use bridge::{BridgeError, BridgeMetrics, GamepadBridge};
+pub use diagnostic::{diagnostic_dump, DiagnosticDump, MAX_DIAGNOSTIC_SIZE};
+pub use error::GamepadError;
+pub use history::StateHistory;
+pub use idle::IdleDetector;
+pub use input::{
+    FailsafeMode, FnInputSource, InputError, InputSource, MapInput, Remap, SplitInput,
+    TimeoutInput, IDENTITY_REMAP,
+};
+pub use latency::{LatencyProbe, LatencyStats};
+pub use mouse::{MouseMapping, MouseReport, StickMouse, DEFAULT_MOUSE_MAPPING};
+pub use output::{
+    ChangeFilter, CoalescingOutput, KeepaliveOutput, OutputError, OutputPacer, OutputSink,
+};
+#[cfg(feature = "std")]
+pub use output::{TimingOutput, TimingStats};
+pub use reader::{ByteReader, PacketByteReader, PacketReader, ReaderInputSource};
+pub use ring_buffer::RingBufferInputSource;
+pub use rumble::RumbleState;
+pub use safety::SafetyCutoff;
 pub use telemetry::{
-    MockTelemetrySource, NullTelemetrySink, TelemetryData, TelemetryError, TelemetrySink,
-    TelemetrySource,
+    MockTelemetrySource, NullTelemetrySink, TelemetryData, TelemetryEncoder, TelemetryError,
+    TelemetryKind, TelemetryScheduler, TelemetrySink, TelemetrySource, TelemetryStats,
 };
+#[cfg(feature = "embedded-io")]
+pub use text_telemetry::TextTelemetrySink;
+pub use text_telemetry::{encode_text_telemetry, parse_text_telemetry, MAX_TEXT_TELEMETRY_SIZE};
+pub use trigger_button::{Trigger, TriggerButton};
+pub use uart::{Parity, StopBits, UartParams};
@@ -0,0 +1,258 @@
+//! Analog stick to relative mouse movement.
+//!
+//! [`StickMouse`] turns the right stick deflection of each [`GamepadState`]
+//! into a relative [`MouseReport`]. It is meant to be ticked at a fixed rate:
+//! a held stick keeps moving the pointer, with speed proportional to the
+//! deflection.
+//!
+//! Sub-count movement is accumulated between ticks so slow deflections still
+//! move the pointer smoothly, and each tick moves at most
+//! [`MouseMapping::speed`] counts per axis.
+
+use gamepad_proto::transform::apply_deadzone;
+use gamepad_proto::{Buttons, GamepadState};
+
+/// Full-scale stick deflection.
+const STICK_MAX: i32 = i16::MAX as i32;
+
+/// Mouse button bits in [`MouseReport::buttons`].
+pub const MOUSE_LEFT: u8 = 1 << 0;
+/// Right mouse button bit.
+pub const MOUSE_RIGHT: u8 = 1 << 1;
+/// Middle mouse button bit.
+pub const MOUSE_MIDDLE: u8 = 1 << 2;
+
+/// Stick-to-mouse configuration.
+///
+/// Customize this at compile-time by creating your own const.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MouseMapping {
+    /// Counts moved per tick at full deflection (1-127).
+    pub speed: u8,
+    /// Stick deadzone (see [`apply_deadzone`]).
+    pub deadzone: u16,
+    /// Gamepad button(s) mapped to the left mouse button.
+    pub left_click: Buttons,
+    /// Gamepad button(s) mapped to the right mouse button.
+    pub right_click: Buttons,
+    /// Gamepad button(s) mapped to the middle mouse button.
+    pub middle_click: Buttons,
+}
+
+/// Default stick-to-mouse mapping.
+///
+/// - Right stick -> pointer, up to 8 counts per tick
+/// - RS (right stick press) / A -> left click
+/// - B -> right click
+pub const DEFAULT_MOUSE_MAPPING: MouseMapping = MouseMapping {
+    speed: 8,
+    deadzone: 2000,
+    left_click: Buttons(Buttons::RS.raw() | Buttons::A.raw()),
+    right_click: Buttons::B,
+    middle_click: Buttons::NONE,
+};
+
+impl Default for MouseMapping {
+    fn default() -> Self {
+        DEFAULT_MOUSE_MAPPING
+    }
+}
+
+/// Relative mouse report produced by [`StickMouse::tick`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MouseReport {
+    /// Button bitfield ([`MOUSE_LEFT`], [`MOUSE_RIGHT`], [`MOUSE_MIDDLE`])
+    pub buttons: u8,
+    /// Relative X movement (positive is right)
+    pub x: i8,
+    /// Relative Y movement (positive is down, as in HID)
+    pub y: i8,
+}
+
+/// Converts right stick deflection into relative mouse movement.
+#[derive(Debug, Clone)]
+pub struct StickMouse {
+    mapping: MouseMapping,
+    /// Fractional X movement carried to the next tick (counts * `STICK_MAX`)
+    remainder_x: i32,
+    /// Fractional Y movement carried to the next tick (counts * `STICK_MAX`)
+    remainder_y: i32,
+}
+
+impl StickMouse {
+    /// Create a new converter with the given mapping.
+    #[must_use]
+    pub const fn new(mapping: MouseMapping) -> Self {
+        Self {
+            mapping,
+            remainder_x: 0,
+            remainder_y: 0,
+        }
+    }
+
+    /// Get the mapping in use.
+    #[inline]
+    #[must_use]
+    pub fn mapping(&self) -> &MouseMapping {
+        &self.mapping
+    }
+
+    /// Produce the mouse report for one tick.
+    ///
+    /// Stick Y is passed through with HID orientation, like the gamepad
+    /// report: positive Y moves the pointer down.
+    pub fn tick(&mut self, state: &GamepadState) -> MouseReport {
+        let speed = i32::from(self.mapping.speed.min(i8::MAX as u8));
+        let deadzone = self.mapping.deadzone;
+
+        let x = step(&mut self.remainder_x, state.right_stick.x, deadzone, speed);
+        let y = step(&mut self.remainder_y, state.right_stick.y, deadzone, speed);
+
+        MouseReport {
+            buttons: self.buttons(state.buttons),
+            x,
+            y,
+        }
+    }
+
+    /// Drop any accumulated sub-count movement.
+    pub fn reset(&mut self) {
+        self.remainder_x = 0;
+        self.remainder_y = 0;
+    }
+
+    fn buttons(&self, buttons: Buttons) -> u8 {
+        let pressed = |mapped: Buttons| !(buttons & mapped).is_empty();
+
+        let mut out = 0;
+        if pressed(self.mapping.left_click) {
+            out |= MOUSE_LEFT;
+        }
+        if pressed(self.mapping.right_click) {
+            out |= MOUSE_RIGHT;
+        }
+        if pressed(self.mapping.middle_click) {
+            out |= MOUSE_MIDDLE;
+        }
+        out
+    }
+}
+
+impl Default for StickMouse {
+    fn default() -> Self {
+        Self::new(DEFAULT_MOUSE_MAPPING)
+    }
+}
+
+/// Advance one axis by one tick, returning the whole counts to move.
+fn step(remainder: &mut i32, value: i16, deadzone: u16, speed: i32) -> i8 {
+    let value = apply_deadzone(value, deadzone);
+    if value == 0 {
+        // Don't let a leftover fraction creep the pointer after release
+        *remainder = 0;
+        return 0;
+    }
+
+    let total = *remainder + i32::from(value) * speed;
+    let counts = (total / STICK_MAX).clamp(-i32::from(i8::MAX), i32::from(i8::MAX));
+    *remainder = total - counts * STICK_MAX;
+    counts as i8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamepad_proto::AnalogStick;
+
+    fn right_stick(x: i16, y: i16) -> GamepadState {
+        GamepadState {
+            right_stick: AnalogStick::new(x, y),
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_full_right_moves_every_tick() {
+        let mut mouse = StickMouse::default();
+        let state = right_stick(i16::MAX, 0);
+
+        for _ in 0..10 {
+            let report = mouse.tick(&state);
+            assert_eq!(report.x, DEFAULT_MOUSE_MAPPING.speed as i8);
+            assert_eq!(report.y, 0);
+        }
+    }
+
+    #[test]
+    fn test_full_up_left_moves_negative() {
+        let mut mouse = StickMouse::default();
+        let report = mouse.tick(&right_stick(i16::MIN, i16::MIN));
+        assert_eq!(report.x, -(DEFAULT_MOUSE_MAPPING.speed as i8));
+        assert_eq!(report.y, -(DEFAULT_MOUSE_MAPPING.speed as i8));
+    }
+
+    #[test]
+    fn test_small_deflection_accumulates() {
+        let mapping = MouseMapping {
+            speed: 4,
+            deadzone: 0,
+            ..DEFAULT_MOUSE_MAPPING
+        };
+        let mut mouse = StickMouse::new(mapping);
+
+        // An eighth deflection at speed 4 is half a count per tick
+        let state = right_stick(i16::MAX / 8, 0);
+        let total: i32 = (0..8).map(|_| i32::from(mouse.tick(&state).x)).sum();
+        assert!((3..=4).contains(&total), "total {total}");
+    }
+
+    #[test]
+    fn test_deadzone_stops_and_clears_remainder() {
+        let mut mouse = StickMouse::default();
+        assert_eq!(
+            mouse.tick(&right_stick(1000, -1000)),
+            MouseReport::default()
+        );
+
+        let mapping = MouseMapping {
+            speed: 1,
+            deadzone: 100,
+            ..DEFAULT_MOUSE_MAPPING
+        };
+        let mut mouse = StickMouse::new(mapping);
+        assert_eq!(mouse.tick(&right_stick(20000, 0)).x, 0);
+        mouse.tick(&right_stick(0, 0));
+        // The earlier fraction was dropped on release
+        assert_eq!(mouse.tick(&right_stick(20000, 0)).x, 0);
+    }
+
+    #[test]
+    fn test_speed_is_rate_limited() {
+        let mapping = MouseMapping {
+            speed: u8::MAX,
+            ..DEFAULT_MOUSE_MAPPING
+        };
+        let mut mouse = StickMouse::new(mapping);
+        let report = mouse.tick(&right_stick(i16::MAX, i16::MIN));
+        assert_eq!(report.x, i8::MAX);
+        assert_eq!(report.y, -i8::MAX);
+    }
+
+    #[test]
+    fn test_click_mapping() {
+        let mut mouse = StickMouse::default();
+        let state = GamepadState {
+            buttons: Buttons::RS | Buttons::B,
+            ..GamepadState::neutral()
+        };
+        assert_eq!(mouse.tick(&state).buttons, MOUSE_LEFT | MOUSE_RIGHT);
+
+        let state = GamepadState {
+            buttons: Buttons::X,
+            ..GamepadState::neutral()
+        };
+        assert_eq!(mouse.tick(&state).buttons, 0);
+    }
+}
@@ -2,6 +2,8 @@
 
 use core::future::Future;
 use gamepad_proto::GamepadState;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 /// Error type for output operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +19,17 @@ pub enum OutputError {
     Busy,
 }
 
+impl core::fmt::Display for OutputError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io => write!(f, "I/O error"),
+            Self::NotReady => write!(f, "device not ready"),
+            Self::Dropped => write!(f, "report dropped"),
+            Self::Busy => write!(f, "endpoint busy"),
+        }
+    }
+}
+
 /// Async trait for gamepad output sinks.
 ///
 /// This trait abstracts the destination for gamepad data, enabling
@@ -33,4 +46,870 @@ pub trait OutputSink {
 
     /// Check if the output is ready to accept data.
     fn is_ready(&self) -> bool;
+
+    /// Time at which a state held back by the sink is due, if there is one.
+    ///
+    /// Sinks that delay states ([`OutputPacer`], [`CoalescingOutput`]) return
+    /// the time the owner should call [`flush`](Self::flush). The default
+    /// never holds a state back.
+    fn next_deadline_ms(&self) -> Option<u64> {
+        None
+    }
+
+    /// Send the held back state if its deadline has been reached.
+    ///
+    /// Returns `Ok(true)` if a report was sent. The default sends nothing.
+    fn flush(&mut self) -> impl Future<Output = Result<bool, OutputError>> {
+        core::future::ready(Ok(false))
+    }
+}
+
+/// Output wrapper that enforces a minimum spacing between reports.
+///
+/// Bursty input forwarded immediately produces unevenly spaced reports.
+/// `OutputPacer` sends a state right away only if at least `min_spacing_ms`
+/// passed since the previous report; otherwise it keeps the state as pending,
+/// replacing any older pending state, and returns `Ok(())`. The owner then
+/// calls [`flush`](Self::flush) once [`next_deadline_ms`](Self::next_deadline_ms)
+/// is reached (e.g. by selecting between new input and a timer), so the
+/// latest coalesced state always goes out.
+///
+/// The clock is injected as a closure returning milliseconds, which keeps
+/// the pacer host-testable.
+pub struct OutputPacer<O, C> {
+    output: O,
+    clock: C,
+    min_spacing_ms: u64,
+    /// Time of the last report sent (`None` before the first one)
+    last_sent_ms: Option<u64>,
+    /// Latest state not sent yet
+    pending: Option<GamepadState>,
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> OutputPacer<O, C> {
+    /// Wrap `output`, spacing reports at least `min_spacing_ms` apart.
+    pub const fn new(output: O, min_spacing_ms: u64, clock: C) -> Self {
+        Self {
+            output,
+            clock,
+            min_spacing_ms,
+            last_sent_ms: None,
+            pending: None,
+        }
+    }
+
+    /// Returns true if a state is waiting for its send slot.
+    #[inline]
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Time at which the pending state may be sent, if there is one.
+    #[must_use]
+    pub fn next_deadline_ms(&self) -> Option<u64> {
+        self.pending?;
+        Some(self.earliest_send_ms())
+    }
+
+    /// Send the pending state if its slot has been reached.
+    ///
+    /// Returns `Ok(true)` if a report was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wrapped output's error. The state is dropped in that case,
+    /// the next state offered will be sent instead.
+    pub async fn flush(&mut self) -> Result<bool, OutputError> {
+        let Some(state) = self.pending else {
+            return Ok(false);
+        };
+
+        let now = (self.clock)();
+        if now < self.earliest_send_ms() {
+            return Ok(false);
+        }
+
+        self.pending = None;
+        self.last_sent_ms = Some(now);
+        self.output.send(&state).await?;
+        Ok(true)
+    }
+
+    /// Get a reference to the wrapped output.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Get a mutable reference to the wrapped output.
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    /// Consume the pacer and return the wrapped output.
+    pub fn into_inner(self) -> O {
+        self.output
+    }
+
+    fn earliest_send_ms(&self) -> u64 {
+        self.last_sent_ms
+            .map_or(0, |last| last.saturating_add(self.min_spacing_ms))
+    }
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> OutputSink for OutputPacer<O, C> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        self.pending = Some(*state);
+        self.flush().await.map(|_| ())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.output.is_ready()
+    }
+
+    fn next_deadline_ms(&self) -> Option<u64> {
+        Self::next_deadline_ms(self)
+    }
+
+    async fn flush(&mut self) -> Result<bool, OutputError> {
+        Self::flush(self).await
+    }
+}
+
+/// Output wrapper that re-sends the current report when the link goes quiet.
+///
+/// Some hosts drop a HID device that stays silent too long. `KeepaliveOutput`
+/// forwards every state and remembers it; once `keepalive_interval_ms` passed
+/// without a report, [`keepalive`](Self::keepalive) sends the last state again
+/// even though it is unchanged (the opposite of deduplication). Before the
+/// first state a neutral report is due immediately, so the host sees a report
+/// right after connecting.
+///
+/// As with [`OutputPacer`], the owner calls [`keepalive`](Self::keepalive)
+/// once [`next_keepalive_ms`](Self::next_keepalive_ms) is reached, and the
+/// clock is injected as a closure returning milliseconds.
+pub struct KeepaliveOutput<O, C> {
+    output: O,
+    clock: C,
+    keepalive_interval_ms: u64,
+    /// Time of the last report sent (`None` before the first one)
+    last_sent_ms: Option<u64>,
+    /// State of the last report
+    last_state: GamepadState,
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> KeepaliveOutput<O, C> {
+    /// Wrap `output`, re-sending after `keepalive_interval_ms` without a report.
+    pub const fn new(output: O, keepalive_interval_ms: u64, clock: C) -> Self {
+        Self {
+            output,
+            clock,
+            keepalive_interval_ms,
+            last_sent_ms: None,
+            last_state: GamepadState::neutral(),
+        }
+    }
+
+    /// Get the keepalive interval in milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn keepalive_interval_ms(&self) -> u64 {
+        self.keepalive_interval_ms
+    }
+
+    /// Time at which the next keepalive report is due.
+    #[must_use]
+    pub fn next_keepalive_ms(&self) -> u64 {
+        self.last_sent_ms
+            .map_or(0, |last| last.saturating_add(self.keepalive_interval_ms))
+    }
+
+    /// Re-send the last state if the keepalive interval has elapsed.
+    ///
+    /// Returns `Ok(true)` if a report was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wrapped output's error. The keepalive is retried after
+    /// another interval.
+    pub async fn keepalive(&mut self) -> Result<bool, OutputError> {
+        let now = (self.clock)();
+        if now < self.next_keepalive_ms() {
+            return Ok(false);
+        }
+
+        self.last_sent_ms = Some(now);
+        self.output.send(&self.last_state).await?;
+        Ok(true)
+    }
+
+    /// Get a reference to the wrapped output.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Get a mutable reference to the wrapped output.
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    /// Consume the wrapper and return the wrapped output.
+    pub fn into_inner(self) -> O {
+        self.output
+    }
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> OutputSink for KeepaliveOutput<O, C> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        self.last_state = *state;
+        self.last_sent_ms = Some((self.clock)());
+        self.output.send(state).await
+    }
+
+    fn is_ready(&self) -> bool {
+        self.output.is_ready()
+    }
+}
+
+/// Output wrapper that absorbs input bursts before sending.
+///
+/// The first state after a quiet period opens a window of `window_ms`; states
+/// arriving within it replace each other and only the last one is sent once
+/// the window closes. This trades up to `window_ms` of latency for fewer
+/// redundant reports when a sender emits several updates back to back (e.g.
+/// one `U` message per changed field). Unlike [`OutputPacer`], which sends the
+/// first state of a burst right away, every report waits for the window.
+///
+/// As with [`OutputPacer`], the owner calls [`flush`](Self::flush) once
+/// [`next_deadline_ms`](Self::next_deadline_ms) is reached, and the clock is
+/// injected as a closure returning milliseconds. A window of 0 sends every
+/// state immediately.
+pub struct CoalescingOutput<O, C> {
+    output: O,
+    clock: C,
+    window_ms: u64,
+    /// Time the current window opened (`None` while no state is pending)
+    window_start_ms: Option<u64>,
+    /// Latest state of the current window
+    pending: Option<GamepadState>,
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> CoalescingOutput<O, C> {
+    /// Wrap `output`, collecting states for `window_ms` before each report.
+    pub const fn new(output: O, window_ms: u64, clock: C) -> Self {
+        Self {
+            output,
+            clock,
+            window_ms,
+            window_start_ms: None,
+            pending: None,
+        }
+    }
+
+    /// Get the coalescing window in milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn window_ms(&self) -> u64 {
+        self.window_ms
+    }
+
+    /// Returns true if a state is waiting for its window to close.
+    #[inline]
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Time at which the current window closes, if a state is pending.
+    #[must_use]
+    pub fn next_deadline_ms(&self) -> Option<u64> {
+        self.pending?;
+        self.window_start_ms
+            .map(|start| start.saturating_add(self.window_ms))
+    }
+
+    /// Send the pending state if its window has closed.
+    ///
+    /// Returns `Ok(true)` if a report was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wrapped output's error. The state is dropped in that case,
+    /// the next state offered opens a new window.
+    pub async fn flush(&mut self) -> Result<bool, OutputError> {
+        let Some(deadline) = self.next_deadline_ms() else {
+            return Ok(false);
+        };
+        if (self.clock)() < deadline {
+            return Ok(false);
+        }
+
+        self.window_start_ms = None;
+        match self.pending.take() {
+            Some(state) => self.output.send(&state).await.map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Get a reference to the wrapped output.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Get a mutable reference to the wrapped output.
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    /// Consume the wrapper and return the wrapped output.
+    pub fn into_inner(self) -> O {
+        self.output
+    }
+}
+
+impl<O: OutputSink, C: FnMut() -> u64> OutputSink for CoalescingOutput<O, C> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        if self.window_start_ms.is_none() {
+            self.window_start_ms = Some((self.clock)());
+        }
+        self.pending = Some(*state);
+        self.flush().await.map(|_| ())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.output.is_ready()
+    }
+
+    fn next_deadline_ms(&self) -> Option<u64> {
+        Self::next_deadline_ms(self)
+    }
+
+    async fn flush(&mut self) -> Result<bool, OutputError> {
+        Self::flush(self).await
+    }
+}
+
+/// Output wrapper that only forwards states that changed.
+///
+/// Input sources often repeat the same state at their frame rate, and
+/// forwarding each one costs a USB report. `ChangeFilter` compares every state
+/// with the last one sent successfully and drops it (returning `Ok(())`) if
+/// nothing changed. Buttons must match exactly; sticks and triggers may move
+/// by up to their threshold (0 by default) so resting jitter below the
+/// deadzone does not count as a change.
+///
+/// Call [`force_next`](Self::force_next) to let the next state through even if
+/// unchanged, e.g. to send a keep-alive report.
+pub struct ChangeFilter<O> {
+    output: O,
+    /// Axis deviation from the last sent state that counts as a change
+    stick_threshold: i16,
+    /// Trigger deviation from the last sent state that counts as a change
+    trigger_threshold: u8,
+    /// Last state sent successfully (`None` before the first one)
+    last_sent: Option<GamepadState>,
+    /// Send the next state regardless of changes
+    force: bool,
+}
+
+impl<O: OutputSink> ChangeFilter<O> {
+    /// Wrap `output`, forwarding only states that differ from the last one sent.
+    pub const fn new(output: O) -> Self {
+        Self {
+            output,
+            stick_threshold: 0,
+            trigger_threshold: 0,
+            last_sent: None,
+            force: false,
+        }
+    }
+
+    /// Set the deviations above which sticks and triggers count as changed.
+    #[must_use]
+    pub const fn with_thresholds(mut self, stick: i16, trigger: u8) -> Self {
+        self.stick_threshold = stick;
+        self.trigger_threshold = trigger;
+        self
+    }
+
+    /// Send the next state even if it did not change.
+    pub fn force_next(&mut self) {
+        self.force = true;
+    }
+
+    /// Check if `state` would be forwarded by the next [`send`](OutputSink::send).
+    #[must_use]
+    pub fn is_changed(&self, state: &GamepadState) -> bool {
+        let Some(last) = self.last_sent.as_ref() else {
+            return true;
+        };
+        self.force || !state.approx_eq(last, self.stick_threshold, self.trigger_threshold)
+    }
+
+    /// Get the last state sent successfully.
+    #[inline]
+    #[must_use]
+    pub const fn last_sent(&self) -> Option<&GamepadState> {
+        self.last_sent.as_ref()
+    }
+
+    /// Get a reference to the wrapped output.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Get a mutable reference to the wrapped output.
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    /// Consume the filter and return the wrapped output.
+    pub fn into_inner(self) -> O {
+        self.output
+    }
+}
+
+impl<O: OutputSink> OutputSink for ChangeFilter<O> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        if !self.is_changed(state) {
+            return Ok(());
+        }
+
+        // On error the last sent state stays, so the next state is retried
+        self.output.send(state).await?;
+        self.last_sent = Some(*state);
+        self.force = false;
+        Ok(())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.output.is_ready()
+    }
+}
+
+/// Inter-send interval statistics collected by [`TimingOutput`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingStats {
+    /// Number of intervals measured (one less than the number of sends)
+    pub intervals: u32,
+    /// Shortest interval between two sends
+    pub min: Duration,
+    /// Longest interval between two sends
+    pub max: Duration,
+    /// Mean interval between sends
+    pub avg: Duration,
+}
+
+/// Output wrapper that records the spacing of reports for jitter analysis.
+///
+/// Every [`send`](OutputSink::send) is timestamped before being forwarded to
+/// the wrapped output, so [`stats`](Self::stats) shows how input bursts and
+/// the latest-value signal coalescing translate into report timing.
+///
+/// [`new`](TimingOutput::new) uses [`Instant::now`]; tests can inject a clock
+/// with [`with_clock`](Self::with_clock).
+#[cfg(feature = "std")]
+pub struct TimingOutput<O, C = fn() -> Instant> {
+    output: O,
+    clock: C,
+    /// Time of the previous send (`None` before the first one)
+    last: Option<Instant>,
+    intervals: u32,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+#[cfg(feature = "std")]
+impl<O: OutputSink> TimingOutput<O> {
+    /// Wrap `output`, timestamping sends with the system clock.
+    pub fn new(output: O) -> Self {
+        Self::with_clock(output, Instant::now)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<O: OutputSink, C: FnMut() -> Instant> TimingOutput<O, C> {
+    /// Wrap `output`, timestamping sends with `clock`.
+    pub const fn with_clock(output: O, clock: C) -> Self {
+        Self {
+            output,
+            clock,
+            last: None,
+            intervals: 0,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            total: Duration::ZERO,
+        }
+    }
+
+    /// Interval statistics, or `None` before the second send.
+    #[must_use]
+    pub fn stats(&self) -> Option<TimingStats> {
+        if self.intervals == 0 {
+            return None;
+        }
+        Some(TimingStats {
+            intervals: self.intervals,
+            min: self.min,
+            max: self.max,
+            avg: self.total / self.intervals,
+        })
+    }
+
+    /// Clear the statistics; the next send starts a new measurement.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.intervals = 0;
+        self.min = Duration::MAX;
+        self.max = Duration::ZERO;
+        self.total = Duration::ZERO;
+    }
+
+    /// Get a reference to the wrapped output.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Consume the wrapper and return the wrapped output.
+    pub fn into_inner(self) -> O {
+        self.output
+    }
+
+    fn record(&mut self, now: Instant) {
+        if let Some(last) = self.last {
+            let interval = now.saturating_duration_since(last);
+            self.intervals = self.intervals.saturating_add(1);
+            self.min = self.min.min(interval);
+            self.max = self.max.max(interval);
+            self.total = self.total.saturating_add(interval);
+        }
+        self.last = Some(now);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<O: OutputSink, C: FnMut() -> Instant> OutputSink for TimingOutput<O, C> {
+    async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+        let now = (self.clock)();
+        self.record(now);
+        self.output.send(state).await
+    }
+
+    fn is_ready(&self) -> bool {
+        self.output.is_ready()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_support::block_on;
+    use core::cell::Cell;
+    use gamepad_proto::{AnalogStick, Buttons};
+    use std::vec::Vec;
+
+    /// Output recording each report with the time it was sent.
+    struct RecordingOutput<'a> {
+        now: &'a Cell<u64>,
+        sent: Vec<(u64, GamepadState)>,
+    }
+
+    impl OutputSink for RecordingOutput<'_> {
+        async fn send(&mut self, state: &GamepadState) -> Result<(), OutputError> {
+            self.sent.push((self.now.get(), *state));
+            Ok(())
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+    }
+
+    fn stick(x: i16) -> GamepadState {
+        GamepadState {
+            left_stick: AnalogStick::new(x, 0),
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_burst_is_coalesced_and_spaced() {
+        let now = Cell::new(100);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut pacer = OutputPacer::new(output, 8, || now.get());
+
+        // First report goes out immediately
+        block_on(pacer.send(&stick(1))).unwrap();
+        assert!(!pacer.has_pending());
+
+        // A burst within the spacing window only keeps the latest state
+        for (t, x) in [(101, 2), (102, 3), (103, 4)] {
+            now.set(t);
+            block_on(pacer.send(&stick(x))).unwrap();
+        }
+        assert_eq!(pacer.next_deadline_ms(), Some(108));
+
+        now.set(107);
+        assert_eq!(block_on(pacer.flush()), Ok(false));
+        now.set(108);
+        assert_eq!(block_on(pacer.flush()), Ok(true));
+        assert_eq!(pacer.next_deadline_ms(), None);
+
+        let sent = pacer.into_inner().sent;
+        assert_eq!(sent, [(100, stick(1)), (108, stick(4))]);
+    }
+
+    #[test]
+    fn test_spacing_honored_for_steady_input() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut pacer = OutputPacer::new(output, 10, || now.get());
+
+        // Input every 3 ms, flushing whenever the deadline passes
+        for t in (0..100).step_by(3) {
+            now.set(t);
+            block_on(pacer.flush()).unwrap();
+            block_on(pacer.send(&stick(t as i16))).unwrap();
+        }
+
+        let sent = pacer.into_inner().sent;
+        assert!(sent.len() >= 9, "only {} reports", sent.len());
+        for pair in sent.windows(2) {
+            assert!(pair[1].0 - pair[0].0 >= 10, "{pair:?}");
+        }
+        // Each report carries the newest state offered by its send time
+        for (t, state) in &sent {
+            let offered = u64::try_from(state.left_stick.x).unwrap();
+            assert!(offered <= *t && *t - offered <= 3, "{t}: {offered}");
+        }
+    }
+
+    #[test]
+    fn test_flush_without_pending_is_noop() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut pacer = OutputPacer::new(output, 10, || now.get());
+        assert_eq!(block_on(pacer.flush()), Ok(false));
+        assert!(pacer.is_ready());
+        assert!(pacer.output().sent.is_empty());
+    }
+
+    #[test]
+    fn test_keepalive_resends_after_interval() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut keepalive = KeepaliveOutput::new(output, 500, || now.get());
+
+        // Neutral report on connect, before any input
+        assert_eq!(keepalive.next_keepalive_ms(), 0);
+        assert_eq!(block_on(keepalive.keepalive()), Ok(true));
+        assert_eq!(block_on(keepalive.keepalive()), Ok(false));
+
+        now.set(100);
+        block_on(keepalive.send(&stick(7))).unwrap();
+        assert_eq!(keepalive.next_keepalive_ms(), 600);
+
+        // No new input: the same state goes out again once per interval
+        now.set(599);
+        assert_eq!(block_on(keepalive.keepalive()), Ok(false));
+        now.set(600);
+        assert_eq!(block_on(keepalive.keepalive()), Ok(true));
+        now.set(1100);
+        assert_eq!(block_on(keepalive.keepalive()), Ok(true));
+
+        // New input restarts the interval
+        now.set(1300);
+        block_on(keepalive.send(&stick(8))).unwrap();
+        now.set(1700);
+        assert_eq!(block_on(keepalive.keepalive()), Ok(false));
+
+        assert_eq!(keepalive.keepalive_interval_ms(), 500);
+        let sent = keepalive.into_inner().sent;
+        assert_eq!(
+            sent,
+            [
+                (0, GamepadState::neutral()),
+                (100, stick(7)),
+                (600, stick(7)),
+                (1100, stick(7)),
+                (1300, stick(8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalescing_window_sends_last_state_once() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut coalesce = CoalescingOutput::new(output, 5, || now.get());
+        assert_eq!(block_on(coalesce.flush()), Ok(false));
+
+        // Burst of three states within the window
+        for (t, x) in [(10, 1), (11, 2), (14, 3)] {
+            now.set(t);
+            block_on(coalesce.send(&stick(x))).unwrap();
+        }
+        assert!(coalesce.has_pending());
+        assert_eq!(coalesce.next_deadline_ms(), Some(15));
+        assert!(coalesce.output().sent.is_empty());
+
+        now.set(15);
+        assert_eq!(block_on(coalesce.flush()), Ok(true));
+        assert_eq!(block_on(coalesce.flush()), Ok(false));
+        assert_eq!(coalesce.next_deadline_ms(), None);
+
+        // The next state opens a new window
+        now.set(40);
+        block_on(coalesce.send(&stick(4))).unwrap();
+        assert_eq!(coalesce.next_deadline_ms(), Some(45));
+        now.set(50);
+        assert_eq!(block_on(coalesce.flush()), Ok(true));
+
+        let sent = coalesce.into_inner().sent;
+        assert_eq!(sent, [(15, stick(3)), (50, stick(4))]);
+    }
+
+    #[test]
+    fn test_zero_coalescing_window_sends_immediately() {
+        let now = Cell::new(7);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut coalesce = CoalescingOutput::new(output, 0, || now.get());
+        assert_eq!(coalesce.window_ms(), 0);
+
+        block_on(coalesce.send(&stick(1))).unwrap();
+        block_on(coalesce.send(&stick(2))).unwrap();
+        assert!(!coalesce.has_pending());
+        assert_eq!(coalesce.into_inner().sent, [(7, stick(1)), (7, stick(2))]);
+    }
+
+    #[test]
+    fn test_change_filter_suppresses_duplicates() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut filter = ChangeFilter::new(output);
+        assert_eq!(filter.last_sent(), None);
+
+        // The first state always goes out, repeats of it do not
+        for _ in 0..3 {
+            block_on(filter.send(&stick(5))).unwrap();
+        }
+        assert_eq!(filter.output().sent.len(), 1);
+        assert_eq!(filter.last_sent(), Some(&stick(5)));
+
+        // A pressed button is a change
+        let pressed = GamepadState {
+            buttons: Buttons::A,
+            ..stick(5)
+        };
+        now.set(1);
+        block_on(filter.send(&pressed)).unwrap();
+        block_on(filter.send(&pressed)).unwrap();
+
+        // Forcing lets one unchanged state through
+        now.set(2);
+        filter.force_next();
+        assert!(filter.is_changed(&pressed));
+        block_on(filter.send(&pressed)).unwrap();
+        block_on(filter.send(&pressed)).unwrap();
+
+        let sent = filter.into_inner().sent;
+        assert_eq!(sent, [(0, stick(5)), (1, pressed), (2, pressed)]);
+    }
+
+    #[test]
+    fn test_change_filter_thresholds() {
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let mut filter = ChangeFilter::new(output).with_thresholds(100, 2);
+        block_on(filter.send(&stick(0))).unwrap();
+
+        // Jitter within the thresholds is dropped
+        block_on(filter.send(&stick(100))).unwrap();
+        block_on(filter.send(&stick(-100))).unwrap();
+        let trigger = GamepadState {
+            right_trigger: 2,
+            ..stick(0)
+        };
+        block_on(filter.send(&trigger)).unwrap();
+        assert_eq!(filter.output().sent.len(), 1);
+
+        // Deviation is measured from the last sent state, not the last offered
+        block_on(filter.send(&stick(101))).unwrap();
+        block_on(filter.send(&stick(180))).unwrap();
+        let trigger = GamepadState {
+            right_trigger: 3,
+            ..stick(101)
+        };
+        block_on(filter.send(&trigger)).unwrap();
+
+        let sent = filter.into_inner().sent;
+        assert_eq!(sent, [(0, stick(0)), (0, stick(101)), (0, trigger)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timing_output_stats() {
+        use std::time::{Duration, Instant};
+
+        let now = Cell::new(0);
+        let output = RecordingOutput {
+            now: &now,
+            sent: Vec::new(),
+        };
+        let start = Instant::now();
+        let mut timing =
+            TimingOutput::with_clock(output, || start + Duration::from_millis(now.get()));
+
+        block_on(timing.send(&stick(0))).unwrap();
+        assert_eq!(timing.stats(), None);
+
+        // Intervals of 4, 12, 8 and 8 ms
+        for (i, t) in [4, 16, 24, 32].into_iter().enumerate() {
+            now.set(t);
+            block_on(timing.send(&stick(i as i16 + 1))).unwrap();
+        }
+
+        assert_eq!(
+            timing.stats(),
+            Some(TimingStats {
+                intervals: 4,
+                min: Duration::from_millis(4),
+                max: Duration::from_millis(12),
+                avg: Duration::from_millis(8),
+            })
+        );
+        assert_eq!(timing.output().sent.len(), 5);
+
+        timing.reset();
+        assert_eq!(timing.stats(), None);
+        assert_eq!(timing.into_inner().sent[4], (32, stick(4)));
+    }
 }
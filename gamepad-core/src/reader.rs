@@ -0,0 +1,467 @@
+//! Line protocol input source over a generic async byte reader.
+//!
+//! [`ReaderInputSource`] holds the transport-independent part of a text
+//! protocol input: line accumulation, message dispatch, stick trim and overrun
+//! recovery. Transports (UART, USB CDC, ...) only implement [`ByteReader`].
+
+use core::future::Future;
+
+use gamepad_proto::{GamepadState, LineAccumulator, ParsedMessage, StickTrim, MAX_LINE_LENGTH};
+
+use crate::input::{InputError, InputSource};
+
+/// Async source of raw protocol bytes.
+pub trait ByteReader {
+    /// Wait for and read the next byte.
+    ///
+    /// Transports should report lost bytes (e.g. a UART receive overrun) as
+    /// [`InputError::BufferOverflow`] so the caller can resynchronize.
+    fn read_byte(&mut self) -> impl Future<Output = Result<u8, InputError>>;
+}
+
+/// Async source of raw protocol bytes delivered in packets.
+///
+/// Packet-oriented transports such as USB CDC-ACM hand over up to one
+/// endpoint packet per read, with no regard for line boundaries. Wrap them in
+/// [`PacketByteReader`] to use them with [`ReaderInputSource`].
+pub trait PacketReader {
+    /// Wait for the next packet and copy it into `buf`.
+    ///
+    /// Returns the number of bytes written (may be zero).
+    fn read_packet(&mut self, buf: &mut [u8]) -> impl Future<Output = Result<usize, InputError>>;
+}
+
+/// [`ByteReader`] that hands out the bytes of [`PacketReader`] packets one at
+/// a time.
+///
+/// `N` is the largest packet the transport delivers (e.g. 64 for a full-speed
+/// USB bulk endpoint). Reading is cancellation safe: a new packet is only read
+/// once the previous one is used up, and unread bytes stay buffered in `self`.
+pub struct PacketByteReader<P, const N: usize> {
+    reader: P,
+    buf: [u8; N],
+    pos: usize,
+    len: usize,
+}
+
+impl<P: PacketReader, const N: usize> PacketByteReader<P, N> {
+    /// Wrap a packet reader.
+    #[must_use]
+    pub const fn new(reader: P) -> Self {
+        Self {
+            reader,
+            buf: [0; N],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Get a reference to the wrapped packet reader.
+    pub fn reader(&self) -> &P {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the wrapped packet reader.
+    pub fn reader_mut(&mut self) -> &mut P {
+        &mut self.reader
+    }
+}
+
+impl<P: PacketReader, const N: usize> ByteReader for PacketByteReader<P, N> {
+    async fn read_byte(&mut self) -> Result<u8, InputError> {
+        while self.pos >= self.len {
+            // Nothing unread is left, so a cancelled read loses no data
+            let len = self.reader.read_packet(&mut self.buf).await?;
+            self.pos = 0;
+            self.len = len.min(N);
+        }
+        let byte = self.buf[self.pos];
+        self.pos += 1;
+        Ok(byte)
+    }
+}
+
+/// State built up from parsed protocol messages.
+///
+/// Shared by the text protocol input sources: full states and updates change
+/// the live state, `C` messages the stick trim and `H` messages the hold.
+pub(crate) struct ProtocolState {
+    /// Current gamepad state (updated incrementally or replaced fully)
+    state: GamepadState,
+    /// Stick center trim set by `C` messages
+    trim: StickTrim,
+    /// State reported while an `H1` hold is active
+    held: Option<GamepadState>,
+}
+
+impl ProtocolState {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: GamepadState::neutral(),
+            trim: StickTrim::new(),
+            held: None,
+        }
+    }
+
+    pub(crate) fn current_state(&self) -> &GamepadState {
+        &self.state
+    }
+
+    pub(crate) fn trim(&self) -> &StickTrim {
+        &self.trim
+    }
+
+    pub(crate) fn is_held(&self) -> bool {
+        self.held.is_some()
+    }
+
+    /// Apply a message and return the state to report.
+    pub(crate) fn apply(&mut self, message: ParsedMessage) -> GamepadState {
+        match message {
+            ParsedMessage::FullState(state) => {
+                self.state = state;
+            }
+            ParsedMessage::Update(update) => {
+                self.state.apply_update(update);
+            }
+            ParsedMessage::Trim(axis, offset) => {
+                self.trim.set(axis, offset);
+            }
+            ParsedMessage::Hold(hold) => {
+                self.set_hold(hold);
+            }
+        }
+        self.output()
+    }
+
+    /// Engage or release the hold; re-engaging keeps the original frozen state.
+    fn set_hold(&mut self, hold: bool) {
+        self.held = if hold {
+            Some(self.held.unwrap_or_else(|| self.trim.apply(&self.state)))
+        } else {
+            None
+        };
+    }
+
+    /// State to report: the frozen state while held, the trimmed live state otherwise.
+    fn output(&self) -> GamepadState {
+        self.held.unwrap_or_else(|| self.trim.apply(&self.state))
+    }
+}
+
+/// Input source that parses text protocol lines from a [`ByteReader`].
+///
+/// When the reader reports an overrun, the partial line is discarded up to the
+/// next newline (the bytes around the gap cannot be trusted) and the overrun
+/// counter is incremented.
+///
+/// `receive` is cancellation safe: partial lines live in the accumulator owned
+/// by `self`.
+///
+/// Lines longer than `N` bytes are rejected as overflow (see
+/// [`LineAccumulator`]).
+pub struct ReaderInputSource<R, const N: usize = MAX_LINE_LENGTH> {
+    reader: R,
+    accumulator: LineAccumulator<N>,
+    /// Live state, trim and hold
+    protocol: ProtocolState,
+    /// Number of overruns reported by the reader
+    overruns: u32,
+}
+
+impl<R: ByteReader, const N: usize> ReaderInputSource<R, N> {
+    /// Create a new input source reading from `reader`.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            accumulator: LineAccumulator::new(),
+            protocol: ProtocolState::new(),
+            overruns: 0,
+        }
+    }
+
+    /// Get the current gamepad state (before trim is applied).
+    #[inline]
+    #[must_use]
+    pub fn current_state(&self) -> &GamepadState {
+        self.protocol.current_state()
+    }
+
+    /// Get the stick trim applied to received states.
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &StickTrim {
+        self.protocol.trim()
+    }
+
+    /// Whether an `H1` hold is active.
+    ///
+    /// While held, received frames still update [`current_state`](Self::current_state)
+    /// but `receive` keeps returning the state frozen when the hold began.
+    #[inline]
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.protocol.is_held()
+    }
+
+    /// Number of overruns (dropped bytes) reported by the reader.
+    ///
+    /// Saturates at `u32::MAX`.
+    #[inline]
+    #[must_use]
+    pub fn overrun_count(&self) -> u32 {
+        self.overruns
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Read bytes until a complete line has been parsed.
+    async fn read_message(&mut self) -> Result<ParsedMessage, InputError> {
+        loop {
+            let byte = match self.reader.read_byte().await {
+                Ok(byte) => byte,
+                Err(InputError::BufferOverflow) => {
+                    self.overruns = self.overruns.saturating_add(1);
+                    self.accumulator.resync();
+                    return Err(InputError::BufferOverflow);
+                }
+                Err(e) => return Err(e),
+            };
+
+            if let Some(result) = self.accumulator.push(byte) {
+                return Ok(result?);
+            }
+        }
+    }
+}
+
+impl<R: ByteReader, const N: usize> InputSource for ReaderInputSource<R, N> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        let message = self.read_message().await?;
+        Ok(self.protocol.apply(message))
+    }
+
+    fn is_connected(&self) -> bool {
+        // A byte stream is always "connected" while we hold the reader
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_support::block_on;
+    use gamepad_proto::{Buttons, Serialize};
+    use std::collections::VecDeque;
+
+    // Scripted reader: yields queued bytes and errors, then disconnects
+    struct MockReader {
+        script: VecDeque<Result<u8, InputError>>,
+    }
+
+    impl MockReader {
+        fn new() -> Self {
+            Self {
+                script: VecDeque::new(),
+            }
+        }
+
+        fn bytes(&mut self, bytes: &[u8]) {
+            self.script.extend(bytes.iter().map(|&b| Ok(b)));
+        }
+
+        fn error(&mut self, e: InputError) {
+            self.script.push_back(Err(e));
+        }
+    }
+
+    impl ByteReader for MockReader {
+        fn read_byte(&mut self) -> impl Future<Output = Result<u8, InputError>> {
+            core::future::ready(
+                self.script
+                    .pop_front()
+                    .unwrap_or(Err(InputError::Disconnected)),
+            )
+        }
+    }
+
+    // Scripted packet reader: yields queued packets, then disconnects
+    struct MockPackets {
+        packets: VecDeque<std::vec::Vec<u8>>,
+    }
+
+    impl PacketReader for MockPackets {
+        fn read_packet(
+            &mut self,
+            buf: &mut [u8],
+        ) -> impl Future<Output = Result<usize, InputError>> {
+            let result = self
+                .packets
+                .pop_front()
+                .map_or(Err(InputError::Disconnected), |p| {
+                    buf[..p.len()].copy_from_slice(&p);
+                    Ok(p.len())
+                });
+            core::future::ready(result)
+        }
+    }
+
+    fn frame(state: &GamepadState) -> ([u8; 64], usize) {
+        let mut buf = [0u8; 64];
+        let len = state.serialize(&mut buf).unwrap();
+        (buf, len)
+    }
+
+    #[test]
+    fn test_reader_full_state() {
+        let state = GamepadState {
+            buttons: Buttons::Y,
+            ..GamepadState::neutral()
+        };
+        let (buf, len) = frame(&state);
+
+        let mut reader = MockReader::new();
+        reader.bytes(&buf[..len]);
+        let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+        assert_eq!(block_on(input.receive()), Ok(state));
+        assert_eq!(input.overrun_count(), 0);
+    }
+
+    #[test]
+    fn test_reader_overrun_resyncs_on_next_newline() {
+        let first = GamepadState {
+            buttons: Buttons::A,
+            left_trigger: 50,
+            ..GamepadState::neutral()
+        };
+        let second = GamepadState {
+            buttons: Buttons::B,
+            right_trigger: 60,
+            ..GamepadState::neutral()
+        };
+        let (buf1, len1) = frame(&first);
+        let (buf2, len2) = frame(&second);
+
+        // Overrun in the middle of the first frame, tail still arrives
+        let mut reader = MockReader::new();
+        reader.bytes(&buf1[..8]);
+        reader.error(InputError::BufferOverflow);
+        reader.bytes(&buf1[12..len1]);
+        reader.bytes(&buf2[..len2]);
+        let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+        assert_eq!(block_on(input.receive()), Err(InputError::BufferOverflow));
+        assert_eq!(input.overrun_count(), 1);
+
+        // The damaged tail is skipped silently, the next frame parses
+        assert_eq!(block_on(input.receive()), Ok(second));
+        assert_eq!(input.overrun_count(), 1);
+    }
+
+    #[test]
+    fn test_reader_other_errors_not_counted() {
+        let mut reader = MockReader::new();
+        reader.error(InputError::Framing);
+        let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+        assert_eq!(block_on(input.receive()), Err(InputError::Framing));
+        assert_eq!(input.overrun_count(), 0);
+    }
+
+    #[test]
+    fn test_reader_long_line_is_not_an_overrun() {
+        let mut reader = MockReader::new();
+        reader.bytes(&[b'9'; 80]);
+        reader.bytes(b"\n");
+        let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+        assert_eq!(block_on(input.receive()), Err(InputError::BufferOverflow));
+        assert_eq!(input.overrun_count(), 0);
+    }
+
+    #[test]
+    fn test_reader_hold_freezes_until_released() {
+        let held = GamepadState {
+            buttons: Buttons::A,
+            ..GamepadState::neutral()
+        };
+        let moved = GamepadState {
+            buttons: Buttons::B,
+            left_trigger: 200,
+            ..GamepadState::neutral()
+        };
+        let (held_buf, held_len) = frame(&held);
+        let (moved_buf, moved_len) = frame(&moved);
+        let hold_on = std::format!("H1*{:02X}\n", gamepad_proto::calculate_crc8(b"1"));
+        let hold_off = std::format!("H0*{:02X}\n", gamepad_proto::calculate_crc8(b"0"));
+
+        let mut reader = MockReader::new();
+        reader.bytes(&held_buf[..held_len]);
+        reader.bytes(hold_on.as_bytes());
+        reader.bytes(&moved_buf[..moved_len]);
+        reader.bytes(hold_off.as_bytes());
+        let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+        assert_eq!(block_on(input.receive()), Ok(held));
+        assert_eq!(block_on(input.receive()), Ok(held));
+        assert!(input.is_held());
+
+        // Frames are consumed while held but not reported
+        assert_eq!(block_on(input.receive()), Ok(held));
+        assert_eq!(input.current_state(), &moved);
+
+        // Releasing reports the latest live state
+        assert_eq!(block_on(input.receive()), Ok(moved));
+        assert!(!input.is_held());
+    }
+
+    #[test]
+    fn test_packet_reader_lines_across_packet_boundaries() {
+        let states = [
+            GamepadState {
+                buttons: Buttons::A,
+                left_trigger: 10,
+                ..GamepadState::neutral()
+            },
+            GamepadState {
+                buttons: Buttons::B,
+                right_trigger: 20,
+                ..GamepadState::neutral()
+            },
+            GamepadState {
+                buttons: Buttons::X,
+                ..GamepadState::neutral()
+            },
+        ];
+        let mut stream = std::vec::Vec::new();
+        for state in &states {
+            let (buf, len) = frame(state);
+            stream.extend_from_slice(&buf[..len]);
+        }
+
+        // Odd packet sizes split every line somewhere, empty packets in between
+        for size in [1, 7, 16, 64] {
+            let mut packets: VecDeque<_> = stream.chunks(size).map(<[u8]>::to_vec).collect();
+            packets.push_front(std::vec::Vec::new());
+            let reader = PacketByteReader::<_, 64>::new(MockPackets { packets });
+            let mut input: ReaderInputSource<_> = ReaderInputSource::new(reader);
+
+            for state in &states {
+                assert_eq!(block_on(input.receive()), Ok(*state), "packet size {size}");
+            }
+            assert_eq!(block_on(input.receive()), Err(InputError::Disconnected));
+        }
+    }
+}
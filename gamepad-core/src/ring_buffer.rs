@@ -0,0 +1,293 @@
+//! Interrupt-fed ring buffer input source.
+//!
+//! For HALs where UART reception happens inside an interrupt handler, the ISR
+//! pushes received bytes into a lock-free single-producer/single-consumer
+//! queue ([`heapless::spsc::Queue`]). [`RingBufferInputSource`] owns the
+//! consumer half and drains it through a [`LineAccumulator`], decoupling byte
+//! reception from async parsing.
+//!
+//! # Example
+//!
+//! ```
+//! use gamepad_core::ring_buffer::RingBufferInputSource;
+//! use heapless::spsc::Queue;
+//!
+//! let mut queue: Queue<u8, 128> = Queue::new();
+//! let (mut producer, consumer) = queue.split();
+//!
+//! // In the ISR:
+//! for &byte in b"G0000:0:0:0:0:0:0*30\n" {
+//!     let _ = producer.enqueue(byte);
+//! }
+//!
+//! // In the async task:
+//! let mut input = RingBufferInputSource::new(consumer);
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use gamepad_proto::{GamepadState, LineAccumulator, StickTrim};
+use heapless::spsc::Consumer;
+
+use crate::input::{InputError, InputSource};
+use crate::reader::ProtocolState;
+
+/// Input source that drains bytes from an interrupt-fed SPSC queue.
+///
+/// `N` is the capacity parameter of the backing [`heapless::spsc::Queue`].
+///
+/// The ISR cannot wake the consumer directly, so when the queue runs dry
+/// [`receive`](InputSource::receive) yields to the executor and polls the
+/// queue again on the next scheduling round.
+///
+/// `receive` is cancellation safe: dequeued bytes are held in the
+/// accumulator owned by `self`, so a dropped future keeps the partial line.
+pub struct RingBufferInputSource<'a, const N: usize> {
+    consumer: Consumer<'a, u8, N>,
+    accumulator: LineAccumulator,
+    /// Live state, trim and hold
+    protocol: ProtocolState,
+}
+
+impl<'a, const N: usize> RingBufferInputSource<'a, N> {
+    /// Create a new input source from the consumer half of an SPSC queue.
+    #[must_use]
+    pub fn new(consumer: Consumer<'a, u8, N>) -> Self {
+        Self {
+            consumer,
+            accumulator: LineAccumulator::new(),
+            protocol: ProtocolState::new(),
+        }
+    }
+
+    /// Get the current gamepad state (before trim is applied).
+    #[inline]
+    #[must_use]
+    pub fn current_state(&self) -> &GamepadState {
+        self.protocol.current_state()
+    }
+
+    /// Get the stick trim applied to received states.
+    #[inline]
+    #[must_use]
+    pub fn trim(&self) -> &StickTrim {
+        self.protocol.trim()
+    }
+
+    /// Whether an `H1` hold is active.
+    ///
+    /// While held, received frames still update [`current_state`](Self::current_state)
+    /// but `receive` keeps returning the state frozen when the hold began.
+    #[inline]
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.protocol.is_held()
+    }
+
+    /// Drain queued bytes until a complete line has been parsed.
+    ///
+    /// Returns `None` if the queue ran empty before a line was completed.
+    fn drain(&mut self) -> Option<Result<GamepadState, InputError>> {
+        while let Some(byte) = self.consumer.dequeue() {
+            match self.accumulator.push(byte) {
+                Some(Ok(message)) => return Some(Ok(self.protocol.apply(message))),
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => {}
+            }
+        }
+        None
+    }
+}
+
+impl<const N: usize> InputSource for RingBufferInputSource<'_, N> {
+    async fn receive(&mut self) -> Result<GamepadState, InputError> {
+        loop {
+            if let Some(result) = self.drain() {
+                return result;
+            }
+            YieldNow(false).await;
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        // The queue is always "connected" while we hold the consumer
+        true
+    }
+}
+
+/// Future that returns `Pending` once, waking itself, before completing.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_support::poll_once;
+    use gamepad_proto::{Buttons, GamepadFieldUpdate, Serialize};
+    use heapless::spsc::{Producer, Queue};
+
+    fn enqueue_all<const N: usize>(producer: &mut Producer<'_, u8, N>, bytes: &[u8]) {
+        for &b in bytes {
+            producer.enqueue(b).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_full_state() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::X,
+            ..GamepadState::neutral()
+        };
+        let mut buf = [0u8; 64];
+        let len = state.serialize(&mut buf).unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+
+        assert_eq!(poll_once(input.receive()), Some(Ok(state)));
+    }
+
+    #[test]
+    fn test_ring_buffer_update_applies_to_state() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        let mut buf = [0u8; 32];
+        let len = GamepadFieldUpdate::LeftStickX(-1234)
+            .serialize(&mut buf)
+            .unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+
+        let state = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(state.left_stick.x, -1234);
+        assert_eq!(input.current_state().left_stick.x, -1234);
+    }
+
+    #[test]
+    fn test_ring_buffer_multiple_lines_in_queue() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        let mut buf = [0u8; 32];
+        let len = GamepadFieldUpdate::LeftTrigger(10)
+            .serialize(&mut buf)
+            .unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+        let len = GamepadFieldUpdate::RightTrigger(20)
+            .serialize(&mut buf)
+            .unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+
+        let first = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(first.left_trigger, 10);
+        assert_eq!(first.right_trigger, 0);
+
+        let second = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(second.left_trigger, 10);
+        assert_eq!(second.right_trigger, 20);
+    }
+
+    #[test]
+    fn test_ring_buffer_pending_until_line_complete() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        enqueue_all(&mut producer, b"G0000:0:0:0:0");
+        assert_eq!(poll_once(input.receive()), None);
+
+        // Partial line is kept across polls
+        enqueue_all(&mut producer, b":0:0*30\n");
+        assert_eq!(
+            poll_once(input.receive()),
+            Some(Ok(GamepadState::neutral()))
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer_checksum_error() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        enqueue_all(&mut producer, b"G0000:0:0:0:0:0:0*FF\n");
+        assert_eq!(poll_once(input.receive()), Some(Err(InputError::Checksum)));
+    }
+
+    #[test]
+    fn test_ring_buffer_cancelled_receive_keeps_partial_line() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        let state = GamepadState {
+            buttons: Buttons::B,
+            left_trigger: 200,
+            ..GamepadState::neutral()
+        };
+        let mut buf = [0u8; 64];
+        let len = state.serialize(&mut buf).unwrap();
+        let (head, tail) = buf[..len].split_at(len / 2);
+
+        // Pending receive consumes the first half of the frame and is dropped
+        enqueue_all(&mut producer, head);
+        assert_eq!(poll_once(input.receive()), None);
+        assert_eq!(input.accumulator.len(), head.len());
+
+        // A fresh receive completes the same frame
+        enqueue_all(&mut producer, tail);
+        assert_eq!(poll_once(input.receive()), Some(Ok(state)));
+
+        // And the following frame parses cleanly
+        let len = GamepadFieldUpdate::RightTrigger(7)
+            .serialize(&mut buf)
+            .unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+        let next = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(next.right_trigger, 7);
+        assert_eq!(next.left_trigger, 200);
+    }
+
+    #[test]
+    fn test_ring_buffer_trim_applies_to_following_states() {
+        let mut queue: Queue<u8, 128> = Queue::new();
+        let (mut producer, consumer) = queue.split();
+        let mut input = RingBufferInputSource::new(consumer);
+
+        let checksum = gamepad_proto::calculate_crc8(b"LX:300");
+        let trim = std::format!("CLX:300*{checksum:02X}\n");
+        enqueue_all(&mut producer, trim.as_bytes());
+        let state = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(state.left_stick.x, -300);
+
+        let mut buf = [0u8; 32];
+        let len = GamepadFieldUpdate::LeftStickX(300)
+            .serialize(&mut buf)
+            .unwrap();
+        enqueue_all(&mut producer, &buf[..len]);
+        let state = poll_once(input.receive()).unwrap().unwrap();
+        assert_eq!(state.left_stick.x, 0);
+        assert_eq!(input.current_state().left_stick.x, 300);
+    }
+}
@@ -0,0 +1,86 @@
+//! Rumble (force feedback) requests from the host.
+//!
+//! Games set rumble through a HID output report of two motor magnitudes.
+//! [`RumbleState::from_report`] decodes the report data, so the firmware only
+//! hands over the bytes it receives in SET_REPORT.
+
+/// Magnitudes of the two rumble motors.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RumbleState {
+    /// Low-frequency (heavy) motor, 0 = off
+    pub strong: u8,
+    /// High-frequency (light) motor, 0 = off
+    pub weak: u8,
+}
+
+impl RumbleState {
+    /// Size of the output report in bytes: `[strong, weak]`.
+    pub const SIZE: usize = 2;
+
+    /// Both motors off.
+    #[must_use]
+    pub const fn off() -> Self {
+        Self { strong: 0, weak: 0 }
+    }
+
+    /// Decode an output report without report ID.
+    ///
+    /// Returns `None` if `data` is shorter than [`SIZE`](Self::SIZE). Extra
+    /// bytes (e.g. padding added by the host) are ignored.
+    #[must_use]
+    pub fn from_report(data: &[u8]) -> Option<Self> {
+        match data {
+            [strong, weak, ..] => Some(Self {
+                strong: *strong,
+                weak: *weak,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Encode as an output report.
+    #[must_use]
+    pub const fn as_bytes(&self) -> [u8; Self::SIZE] {
+        [self.strong, self.weak]
+    }
+
+    /// Check if both motors are off.
+    #[inline]
+    #[must_use]
+    pub const fn is_off(&self) -> bool {
+        self.strong == 0 && self.weak == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_report() {
+        let rumble = RumbleState::from_report(&[0xC0, 0x20]).unwrap();
+        assert_eq!(
+            rumble,
+            RumbleState {
+                strong: 0xC0,
+                weak: 0x20
+            }
+        );
+        assert_eq!(rumble.as_bytes(), [0xC0, 0x20]);
+        assert!(!rumble.is_off());
+
+        // Padding is ignored
+        assert_eq!(
+            RumbleState::from_report(&[0, 0, 0xFF]),
+            Some(RumbleState::off())
+        );
+        assert!(RumbleState::off().is_off());
+    }
+
+    #[test]
+    fn test_from_short_report() {
+        assert_eq!(RumbleState::from_report(&[]), None);
+        assert_eq!(RumbleState::from_report(&[0xFF]), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Stuck-input safety cutoff.
+//!
+//! [`SafetyCutoff`] forces the neutral state when a non-neutral state stays
+//! exactly unchanged for longer than a configured duration, e.g. a full
+//! throttle left applied by a crashed sender. Unlike [`IdleDetector`], which
+//! reports inactivity, it guards against *active* inputs that look stuck.
+//!
+//! Real analog inputs jitter, so a held but live stick keeps changing by a few
+//! counts and never trips the cutoff. Time is injected as a millisecond
+//! timestamp, as with [`IdleDetector`].
+//!
+//! [`IdleDetector`]: crate::IdleDetector
+
+use gamepad_proto::GamepadState;
+
+/// Forces neutral output when an active state is held unchanged too long.
+#[derive(Debug, Clone)]
+pub struct SafetyCutoff {
+    /// Longest time an active state may stay unchanged
+    max_hold_ms: u64,
+    /// Last observed state (`None` until the first update)
+    last: Option<GamepadState>,
+    /// Timestamp of the last state change
+    last_change_ms: u64,
+    /// Whether neutral is currently being forced
+    tripped: bool,
+}
+
+impl SafetyCutoff {
+    /// Create a cutoff that trips after `max_hold_ms` of an unchanged active state.
+    #[must_use]
+    pub const fn new(max_hold_ms: u64) -> Self {
+        Self {
+            max_hold_ms,
+            last: None,
+            last_change_ms: 0,
+            tripped: false,
+        }
+    }
+
+    /// Get the maximum hold duration in milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn max_hold_ms(&self) -> u64 {
+        self.max_hold_ms
+    }
+
+    /// Returns true while neutral is being forced.
+    #[inline]
+    #[must_use]
+    pub const fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Filter the current state.
+    ///
+    /// Returns `state` unchanged, or the neutral state once an active state has
+    /// been held for at least the maximum hold duration. The cutoff stays
+    /// tripped until the input changes.
+    pub fn apply(&mut self, state: &GamepadState, now_ms: u64) -> GamepadState {
+        if self.last.as_ref() != Some(state) {
+            self.last = Some(*state);
+            self.last_change_ms = now_ms;
+            self.tripped = false;
+        } else if *state != GamepadState::neutral()
+            && now_ms.saturating_sub(self.last_change_ms) >= self.max_hold_ms
+        {
+            self.tripped = true;
+        }
+
+        if self.tripped {
+            GamepadState::neutral()
+        } else {
+            *state
+        }
+    }
+
+    /// Clear the trip and restart the hold timer at `now_ms`.
+    pub fn reset(&mut self, now_ms: u64) {
+        self.last_change_ms = now_ms;
+        self.tripped = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamepad_proto::AnalogStick;
+
+    fn throttle(y: i16) -> GamepadState {
+        GamepadState {
+            left_stick: AnalogStick::new(0, y),
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_held_full_throttle_forces_neutral() {
+        let mut cutoff = SafetyCutoff::new(1000);
+        let full = throttle(i16::MAX);
+
+        for t in (0..1000).step_by(100) {
+            assert_eq!(cutoff.apply(&full, t), full);
+        }
+        assert_eq!(cutoff.apply(&full, 1000), GamepadState::neutral());
+        assert!(cutoff.is_tripped());
+        assert_eq!(cutoff.apply(&full, 5000), GamepadState::neutral());
+
+        // Any change releases the cutoff
+        let moved = throttle(i16::MAX - 1);
+        assert_eq!(cutoff.apply(&moved, 5100), moved);
+        assert!(!cutoff.is_tripped());
+    }
+
+    #[test]
+    fn test_changing_input_passes_through() {
+        let mut cutoff = SafetyCutoff::new(1000);
+
+        // Live stick jittering around full throttle
+        for t in (0..5000).step_by(100) {
+            let state = throttle(i16::MAX - (t % 3) as i16);
+            assert_eq!(cutoff.apply(&state, t), state);
+        }
+        assert!(!cutoff.is_tripped());
+    }
+
+    #[test]
+    fn test_neutral_never_trips_and_reset() {
+        let mut cutoff = SafetyCutoff::new(500);
+        let neutral = GamepadState::neutral();
+        assert_eq!(cutoff.apply(&neutral, 0), neutral);
+        assert_eq!(cutoff.apply(&neutral, 10_000), neutral);
+        assert!(!cutoff.is_tripped());
+
+        let full = throttle(i16::MAX);
+        cutoff.apply(&full, 10_000);
+        assert_eq!(cutoff.apply(&full, 10_500), neutral);
+
+        cutoff.reset(10_500);
+        assert_eq!(cutoff.apply(&full, 10_999), full);
+        assert_eq!(cutoff.apply(&full, 11_000), neutral);
+    }
+}
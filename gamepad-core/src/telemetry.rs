@@ -6,7 +6,7 @@
 use core::future::Future;
 
 /// Telemetry data that can be sent back to transmitter/GCS.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum TelemetryData {
     /// Battery status telemetry.
@@ -49,6 +49,226 @@ pub enum TelemetryData {
         /// Link quality percentage (0-100).
         lq: u8,
     },
+    /// Temperature telemetry.
+    Temperature {
+        /// Temperature in degrees Celsius * 10.
+        temp_decidegrees: i16,
+    },
+}
+
+impl TelemetryData {
+    /// Get the kind of this telemetry data.
+    #[must_use]
+    pub const fn kind(&self) -> TelemetryKind {
+        match self {
+            TelemetryData::Battery { .. } => TelemetryKind::Battery,
+            TelemetryData::Gps { .. } => TelemetryKind::Gps,
+            TelemetryData::Attitude { .. } => TelemetryKind::Attitude,
+            TelemetryData::LinkQuality { .. } => TelemetryKind::LinkQuality,
+            TelemetryData::Temperature { .. } => TelemetryKind::Temperature,
+        }
+    }
+}
+
+/// Kind of [`TelemetryData`], without its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TelemetryKind {
+    /// [`TelemetryData::Battery`]
+    Battery,
+    /// [`TelemetryData::Gps`]
+    Gps,
+    /// [`TelemetryData::Attitude`]
+    Attitude,
+    /// [`TelemetryData::LinkQuality`]
+    LinkQuality,
+    /// [`TelemetryData::Temperature`]
+    Temperature,
+}
+
+impl TelemetryKind {
+    /// All kinds, in scheduling tie-break order.
+    pub const ALL: [TelemetryKind; 5] = [
+        TelemetryKind::Battery,
+        TelemetryKind::Gps,
+        TelemetryKind::Attitude,
+        TelemetryKind::LinkQuality,
+        TelemetryKind::Temperature,
+    ];
+
+    /// Position in [`ALL`](Self::ALL).
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Spreads telemetry kinds over time according to per-kind intervals.
+///
+/// Each kind is sent at most once per interval; fast-changing data (attitude)
+/// gets a short interval, slow data (GPS) a long one. When several kinds are
+/// due, the one that has waited longest goes first, so a link with fewer
+/// transmit slots than due frames still cycles through every kind.
+///
+/// Time is injected as a millisecond timestamp, as with
+/// [`IdleDetector`](crate::IdleDetector).
+///
+/// # Example
+///
+/// ```rust
+/// use gamepad_core::{TelemetryKind, TelemetryScheduler};
+///
+/// let mut scheduler = TelemetryScheduler::new()
+///     .with_interval(TelemetryKind::Attitude, 100)
+///     .with_interval(TelemetryKind::Gps, 1000);
+///
+/// // Everything enabled is due at start, then attitude comes back first
+/// assert_eq!(scheduler.next(0), Some(TelemetryKind::Gps));
+/// assert_eq!(scheduler.next(0), Some(TelemetryKind::Attitude));
+/// assert_eq!(scheduler.next(50), None);
+/// assert_eq!(scheduler.next(100), Some(TelemetryKind::Attitude));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TelemetryScheduler {
+    /// Interval per kind in milliseconds (0 = disabled)
+    intervals_ms: [u32; TelemetryKind::ALL.len()],
+    /// Timestamp each kind is next due at
+    due_ms: [u64; TelemetryKind::ALL.len()],
+}
+
+impl TelemetryScheduler {
+    /// Default rates for a CRSF link: attitude at 10 Hz, battery at 2 Hz, GPS
+    /// at 1 Hz and temperature every 2 s. Link quality is reported by the
+    /// receiver itself and stays disabled.
+    pub const DEFAULT: Self = Self::new()
+        .with_interval(TelemetryKind::Attitude, 100)
+        .with_interval(TelemetryKind::Battery, 500)
+        .with_interval(TelemetryKind::Gps, 1000)
+        .with_interval(TelemetryKind::Temperature, 2000);
+
+    /// Create a scheduler with every kind disabled.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            intervals_ms: [0; TelemetryKind::ALL.len()],
+            due_ms: [0; TelemetryKind::ALL.len()],
+        }
+    }
+
+    /// Set the interval of one kind in milliseconds (0 disables it).
+    #[must_use]
+    pub const fn with_interval(mut self, kind: TelemetryKind, interval_ms: u32) -> Self {
+        self.intervals_ms[kind.index()] = interval_ms;
+        self
+    }
+
+    /// Get the interval of one kind in milliseconds (0 = disabled).
+    #[inline]
+    #[must_use]
+    pub const fn interval_ms(&self, kind: TelemetryKind) -> u32 {
+        self.intervals_ms[kind.index()]
+    }
+
+    /// Get the kind to send at `now_ms`, if any is due.
+    ///
+    /// The returned kind is rescheduled one interval later, so call this only
+    /// when the frame can actually be sent.
+    pub fn next(&mut self, now_ms: u64) -> Option<TelemetryKind> {
+        let kind = TelemetryKind::ALL
+            .into_iter()
+            .filter(|kind| self.interval_ms(*kind) > 0 && self.due_ms[kind.index()] <= now_ms)
+            .min_by_key(|kind| self.due_ms[kind.index()])?;
+
+        // Keep the rate steady, but restart from now after a long gap
+        // instead of catching up on missed frames
+        let i = kind.index();
+        let interval = u64::from(self.intervals_ms[i]);
+        let next = self.due_ms[i] + interval;
+        self.due_ms[i] = if next > now_ms {
+            next
+        } else {
+            now_ms + interval
+        };
+        Some(kind)
+    }
+
+    /// Get the timestamp at which the next kind is due, if any is enabled.
+    #[must_use]
+    pub fn next_due_ms(&self) -> Option<u64> {
+        TelemetryKind::ALL
+            .into_iter()
+            .filter(|kind| self.interval_ms(*kind) > 0)
+            .map(|kind| self.due_ms[kind.index()])
+            .min()
+    }
+
+    /// Make every enabled kind due immediately (e.g. after a reconnect).
+    pub fn reset(&mut self) {
+        self.due_ms = [0; TelemetryKind::ALL.len()];
+    }
+}
+
+impl Default for TelemetryScheduler {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Per-kind counts of telemetry frames sent and failed.
+///
+/// Part of [`BridgeMetrics`](crate::BridgeMetrics), filled in by
+/// [`GamepadBridge::send_telemetry`](crate::GamepadBridge::send_telemetry).
+/// Counters saturate at `u32::MAX`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TelemetryStats {
+    /// Frames accepted by the sink, indexed like [`TelemetryKind::ALL`]
+    sent: [u32; TelemetryKind::ALL.len()],
+    /// Frames the sink rejected (dropped), indexed like [`TelemetryKind::ALL`]
+    failed: [u32; TelemetryKind::ALL.len()],
+}
+
+impl TelemetryStats {
+    /// Create stats with all counters zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sent: [0; TelemetryKind::ALL.len()],
+            failed: [0; TelemetryKind::ALL.len()],
+        }
+    }
+
+    /// Count the result of one send attempt.
+    pub fn record(&mut self, kind: TelemetryKind, result: Result<(), TelemetryError>) {
+        let counter = match result {
+            Ok(()) => &mut self.sent[kind.index()],
+            Err(_) => &mut self.failed[kind.index()],
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Frames of `kind` the sink accepted.
+    #[must_use]
+    pub const fn sent(&self, kind: TelemetryKind) -> u32 {
+        self.sent[kind.index()]
+    }
+
+    /// Frames of `kind` the sink rejected.
+    #[must_use]
+    pub const fn failed(&self, kind: TelemetryKind) -> u32 {
+        self.failed[kind.index()]
+    }
+
+    /// Frames accepted across all kinds.
+    #[must_use]
+    pub fn total_sent(&self) -> u32 {
+        self.sent.iter().fold(0, |sum, n| sum.saturating_add(*n))
+    }
+
+    /// Frames rejected across all kinds.
+    #[must_use]
+    pub fn total_failed(&self) -> u32 {
+        self.failed.iter().fold(0, |sum, n| sum.saturating_add(*n))
+    }
 }
 
 /// Error type for telemetry operations.
@@ -63,6 +283,16 @@ pub enum TelemetryError {
     BufferFull,
 }
 
+impl core::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::Io => write!(f, "I/O error"),
+            Self::BufferFull => write!(f, "transmit buffer full"),
+        }
+    }
+}
+
 /// Trait for sending telemetry data back through the input channel.
 ///
 /// Implement this for input sources that support bidirectional communication.
@@ -81,6 +311,24 @@ pub trait TelemetrySink {
     }
 }
 
+/// Trait for encoding telemetry data into a protocol's wire format.
+///
+/// Encoders only produce bytes; the caller transmits them. The trait is
+/// object safe, so the output format can be selected at runtime through
+/// `&mut dyn TelemetryEncoder` (e.g. CRSF from the radio side, MAVLink
+/// towards a flight controller).
+pub trait TelemetryEncoder {
+    /// Encode telemetry data into `buf`.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelemetryError::NotSupported`] if the format has no frame for
+    /// this data, or [`TelemetryError::BufferFull`] if `buf` is too small.
+    fn encode(&mut self, data: &TelemetryData, buf: &mut [u8]) -> Result<usize, TelemetryError>;
+}
+
 /// Trait for receiving telemetry data from an external source.
 ///
 /// This allows telemetry to come from various places:
@@ -120,3 +368,76 @@ impl TelemetrySink for NullTelemetrySink {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(
+        scheduler: &mut TelemetryScheduler,
+        until_ms: u64,
+        step_ms: u64,
+    ) -> [u32; TelemetryKind::ALL.len()] {
+        let mut counts = [0; TelemetryKind::ALL.len()];
+        for now in (0..until_ms).step_by(step_ms as usize) {
+            if let Some(kind) = scheduler.next(now) {
+                counts[kind.index()] += 1;
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn test_attitude_more_often_than_gps() {
+        let mut scheduler = TelemetryScheduler::DEFAULT;
+        let counts = count(&mut scheduler, 10_000, 10);
+
+        assert_eq!(counts[TelemetryKind::Attitude.index()], 100);
+        assert_eq!(counts[TelemetryKind::Battery.index()], 20);
+        assert_eq!(counts[TelemetryKind::Gps.index()], 10);
+        assert_eq!(counts[TelemetryKind::Temperature.index()], 5);
+        assert_eq!(counts[TelemetryKind::LinkQuality.index()], 0);
+    }
+
+    #[test]
+    fn test_scarce_slots_still_cycle() {
+        // One slot every 100 ms is not enough for all due kinds
+        let mut scheduler = TelemetryScheduler::DEFAULT;
+        let counts = count(&mut scheduler, 10_000, 100);
+
+        let attitude = counts[TelemetryKind::Attitude.index()];
+        let gps = counts[TelemetryKind::Gps.index()];
+        assert_eq!(counts.iter().sum::<u32>(), 100);
+        assert!(attitude > gps, "attitude {attitude}, gps {gps}");
+        assert!(gps > 0);
+        assert!(counts[TelemetryKind::Temperature.index()] > 0);
+    }
+
+    #[test]
+    fn test_next_due_and_reset() {
+        let mut scheduler = TelemetryScheduler::new();
+        assert_eq!(scheduler.next(0), None);
+        assert_eq!(scheduler.next_due_ms(), None);
+
+        let mut scheduler = scheduler.with_interval(TelemetryKind::Battery, 500);
+        assert_eq!(scheduler.next(0), Some(TelemetryKind::Battery));
+        assert_eq!(scheduler.next_due_ms(), Some(500));
+
+        // A long gap doesn't queue up missed frames
+        assert_eq!(scheduler.next(5000), Some(TelemetryKind::Battery));
+        assert_eq!(scheduler.next(5000), None);
+        assert_eq!(scheduler.next(5001), None);
+        assert_eq!(scheduler.next_due_ms(), Some(5500));
+
+        scheduler.reset();
+        assert_eq!(scheduler.next(5000), Some(TelemetryKind::Battery));
+    }
+
+    #[test]
+    fn test_kind() {
+        let data = TelemetryData::Temperature {
+            temp_decidegrees: 215,
+        };
+        assert_eq!(data.kind(), TelemetryKind::Temperature);
+    }
+}
@@ -0,0 +1,35 @@
+//! Helpers shared by the unit tests.
+
+use core::future::Future;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+fn noop_raw_waker() -> RawWaker {
+    fn noop(_: *const ()) {}
+    fn clone(_: *const ()) -> RawWaker {
+        noop_raw_waker()
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
+
+/// Waker that does nothing when woken.
+pub fn noop_waker() -> Waker {
+    // SAFETY: the vtable functions ignore the null data pointer
+    unsafe { Waker::from_raw(noop_raw_waker()) }
+}
+
+/// Poll a future once, returning `None` if it is still pending.
+pub fn poll_once<F: Future>(f: F) -> Option<F::Output> {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut f = core::pin::pin!(f);
+    match f.as_mut().poll(&mut cx) {
+        Poll::Ready(result) => Some(result),
+        Poll::Pending => None,
+    }
+}
+
+/// Run a future that is ready on its first poll (the test mocks never wait).
+pub fn block_on<F: Future>(f: F) -> F::Output {
+    poll_once(f).expect("Mock future returned Pending unexpectedly")
+}
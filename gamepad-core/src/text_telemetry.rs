@@ -0,0 +1,419 @@
+//! Telemetry backchannel over the UART text protocol.
+//!
+//! Telemetry travels in the opposite direction of gamepad input, using the same
+//! line framing and CRC-8/SMBUS checksum as `G` and `U` messages:
+//!
+//! ```text
+//! T<type>:<value>[:<value>...]*<checksum>\n
+//! ```
+//!
+//! | Type  | Values                                  |
+//! |-------|-----------------------------------------|
+//! | `BAT` | `voltage_mv:current_ma:remaining_pct`   |
+//! | `GPS` | `lat:lon:alt_m:speed_mps:sats`          |
+//! | `ATT` | `roll:pitch:yaw`                        |
+//! | `LQ`  | `rssi:snr:lq`                           |
+//! | `TMP` | `temp_decidegrees`                      |
+//!
+//! All values are signed or unsigned decimal integers in the units of the
+//! corresponding [`TelemetryData`] field.
+
+use core::fmt::Write as _;
+use core::str::FromStr;
+
+use gamepad_proto::{calculate_crc8, ParseError};
+
+use crate::telemetry::{TelemetryData, TelemetryError};
+
+/// Maximum size of an encoded telemetry frame (the longest is a GPS frame).
+pub const MAX_TEXT_TELEMETRY_SIZE: usize = 48;
+
+/// Encode telemetry data as a `T` frame into a buffer.
+///
+/// Returns the number of bytes written, including the trailing newline.
+///
+/// # Errors
+///
+/// Returns [`TelemetryError::BufferFull`] if the buffer is too small.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_core::{encode_text_telemetry, TelemetryData};
+///
+/// let data = TelemetryData::Battery { voltage_mv: 11100, current_ma: 1500, remaining_pct: 80 };
+/// let mut buf = [0u8; 48];
+/// let len = encode_text_telemetry(&data, &mut buf).unwrap();
+/// assert!(buf[..len].starts_with(b"TBAT:11100:1500:80*"));
+/// ```
+pub fn encode_text_telemetry(
+    data: &TelemetryData,
+    buf: &mut [u8],
+) -> Result<usize, TelemetryError> {
+    let mut w = SliceWriter { buf, pos: 0 };
+
+    let result = match *data {
+        TelemetryData::Battery {
+            voltage_mv,
+            current_ma,
+            remaining_pct,
+        } => write!(w, "TBAT:{voltage_mv}:{current_ma}:{remaining_pct}"),
+        TelemetryData::Gps {
+            lat,
+            lon,
+            alt_m,
+            speed_mps,
+            sats,
+        } => write!(w, "TGPS:{lat}:{lon}:{alt_m}:{speed_mps}:{sats}"),
+        TelemetryData::Attitude { roll, pitch, yaw } => write!(w, "TATT:{roll}:{pitch}:{yaw}"),
+        TelemetryData::LinkQuality { rssi, snr, lq } => write!(w, "TLQ:{rssi}:{snr}:{lq}"),
+        TelemetryData::Temperature { temp_decidegrees } => write!(w, "TTMP:{temp_decidegrees}"),
+    };
+    result.map_err(|_| TelemetryError::BufferFull)?;
+
+    let checksum = calculate_crc8(&w.buf[1..w.pos]);
+    writeln!(w, "*{checksum:02X}").map_err(|_| TelemetryError::BufferFull)?;
+
+    Ok(w.pos)
+}
+
+/// Parse a `T` frame back into telemetry data.
+///
+/// Accepts lines with or without the trailing `\n` or `\r\n`.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Checksum`] if the checksum does not match, or
+/// [`ParseError::Parse`] for any other malformed input.
+pub fn parse_text_telemetry(line: &[u8]) -> Result<TelemetryData, ParseError> {
+    let line = trim_line_ending(line);
+    if line.first() != Some(&b'T') {
+        return Err(ParseError::Parse);
+    }
+
+    let checksum_pos = line
+        .iter()
+        .rposition(|&b| b == b'*')
+        .ok_or(ParseError::Parse)?;
+    let payload = &line[1..checksum_pos];
+    let checksum_str =
+        core::str::from_utf8(&line[checksum_pos + 1..]).map_err(|_| ParseError::Parse)?;
+    if checksum_str.len() != 2 {
+        return Err(ParseError::Parse);
+    }
+    let received = u8::from_str_radix(checksum_str, 16).map_err(|_| ParseError::Parse)?;
+    if calculate_crc8(payload) != received {
+        return Err(ParseError::Checksum);
+    }
+
+    let payload = core::str::from_utf8(payload).map_err(|_| ParseError::Parse)?;
+    let mut fields = payload.split(':');
+    let kind = fields.next().ok_or(ParseError::Parse)?;
+
+    let data = match kind {
+        "BAT" => TelemetryData::Battery {
+            voltage_mv: next_field(&mut fields)?,
+            current_ma: next_field(&mut fields)?,
+            remaining_pct: next_field(&mut fields)?,
+        },
+        "GPS" => TelemetryData::Gps {
+            lat: next_field(&mut fields)?,
+            lon: next_field(&mut fields)?,
+            alt_m: next_field(&mut fields)?,
+            speed_mps: next_field(&mut fields)?,
+            sats: next_field(&mut fields)?,
+        },
+        "ATT" => TelemetryData::Attitude {
+            roll: next_field(&mut fields)?,
+            pitch: next_field(&mut fields)?,
+            yaw: next_field(&mut fields)?,
+        },
+        "LQ" => TelemetryData::LinkQuality {
+            rssi: next_field(&mut fields)?,
+            snr: next_field(&mut fields)?,
+            lq: next_field(&mut fields)?,
+        },
+        "TMP" => TelemetryData::Temperature {
+            temp_decidegrees: next_field(&mut fields)?,
+        },
+        _ => return Err(ParseError::Parse),
+    };
+
+    // Reject trailing values
+    if fields.next().is_some() {
+        return Err(ParseError::Parse);
+    }
+
+    Ok(data)
+}
+
+/// Telemetry sink that writes `T` frames to a blocking [`embedded_io::Write`].
+///
+/// This gives the text gamepad protocol a backchannel: wire the UART TX half
+/// (or any other writer) to the sink and feed it [`TelemetryData`].
+#[cfg(feature = "embedded-io")]
+pub struct TextTelemetrySink<W> {
+    writer: W,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> TextTelemetrySink<W> {
+    /// Create a new sink writing to `writer`.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume the sink and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> crate::telemetry::TelemetrySink for TextTelemetrySink<W> {
+    async fn send_telemetry(&mut self, data: &TelemetryData) -> Result<(), TelemetryError> {
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let len = encode_text_telemetry(data, &mut buf)?;
+        self.writer
+            .write_all(&buf[..len])
+            .map_err(|_| TelemetryError::Io)
+    }
+
+    fn supports_telemetry(&self) -> bool {
+        true
+    }
+}
+
+/// Parse the next `:`-separated value of a telemetry frame.
+fn next_field<'a, T: FromStr>(fields: &mut impl Iterator<Item = &'a str>) -> Result<T, ParseError> {
+    fields
+        .next()
+        .ok_or(ParseError::Parse)?
+        .parse()
+        .map_err(|_| ParseError::Parse)
+}
+
+/// Strip a trailing `\n` or `\r\n`.
+fn trim_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// [`core::fmt::Write`] adapter over a byte slice.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl core::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.pos + s.len();
+        if end > self.buf.len() {
+            return Err(core::fmt::Error);
+        }
+        self.buf[self.pos..end].copy_from_slice(s.as_bytes());
+        self.pos = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn battery() -> TelemetryData {
+        TelemetryData::Battery {
+            voltage_mv: 11100,
+            current_ma: 1500,
+            remaining_pct: 80,
+        }
+    }
+
+    fn round_trip(data: TelemetryData) -> TelemetryData {
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let len = encode_text_telemetry(&data, &mut buf).unwrap();
+        parse_text_telemetry(&buf[..len]).unwrap()
+    }
+
+    #[test]
+    fn test_encode_battery_frame() {
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let len = encode_text_telemetry(&battery(), &mut buf).unwrap();
+
+        let checksum = calculate_crc8(b"BAT:11100:1500:80");
+        let mut expected = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let mut w = SliceWriter {
+            buf: &mut expected,
+            pos: 0,
+        };
+        writeln!(w, "TBAT:11100:1500:80*{checksum:02X}").unwrap();
+        let expected_len = w.pos;
+
+        assert_eq!(&buf[..len], &expected[..expected_len]);
+    }
+
+    #[test]
+    fn test_battery_round_trip() {
+        assert_eq!(round_trip(battery()), battery());
+    }
+
+    #[test]
+    fn test_all_types_round_trip_extremes() {
+        let cases = [
+            TelemetryData::Battery {
+                voltage_mv: u16::MAX,
+                current_ma: 0,
+                remaining_pct: 100,
+            },
+            TelemetryData::Gps {
+                lat: i32::MIN,
+                lon: i32::MIN,
+                alt_m: i16::MIN,
+                speed_mps: u8::MAX,
+                sats: u8::MAX,
+            },
+            TelemetryData::Attitude {
+                roll: -18000,
+                pitch: 9000,
+                yaw: i16::MAX,
+            },
+            TelemetryData::LinkQuality {
+                rssi: i8::MIN,
+                snr: -5,
+                lq: 100,
+            },
+            TelemetryData::Temperature {
+                temp_decidegrees: i16::MIN,
+            },
+        ];
+        for data in cases {
+            assert_eq!(round_trip(data), data);
+        }
+    }
+
+    #[test]
+    fn test_longest_frame_fits() {
+        let data = TelemetryData::Gps {
+            lat: i32::MIN,
+            lon: i32::MIN,
+            alt_m: i16::MIN,
+            speed_mps: u8::MAX,
+            sats: u8::MAX,
+        };
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        assert!(encode_text_telemetry(&data, &mut buf).is_ok());
+    }
+
+    #[test]
+    fn test_encode_buffer_too_small() {
+        let mut buf = [0u8; 10];
+        assert_eq!(
+            encode_text_telemetry(&battery(), &mut buf),
+            Err(TelemetryError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_parse_bad_checksum() {
+        assert_eq!(
+            parse_text_telemetry(b"TBAT:11100:1500:80*00\n"),
+            Err(ParseError::Checksum)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        // Wrong prefix, missing checksum, unknown type, missing and extra values
+        let checksum = calculate_crc8(b"BAT:1:2");
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let mut w = SliceWriter {
+            buf: &mut buf,
+            pos: 0,
+        };
+        write!(w, "TBAT:1:2*{checksum:02X}").unwrap();
+        let len = w.pos;
+        assert_eq!(parse_text_telemetry(&buf[..len]), Err(ParseError::Parse));
+
+        assert_eq!(parse_text_telemetry(b"GBAT:1:2:3"), Err(ParseError::Parse));
+        assert_eq!(parse_text_telemetry(b"TBAT:1:2:3"), Err(ParseError::Parse));
+        assert_eq!(parse_text_telemetry(b""), Err(ParseError::Parse));
+
+        let checksum = calculate_crc8(b"XYZ:1");
+        let mut w = SliceWriter {
+            buf: &mut buf,
+            pos: 0,
+        };
+        write!(w, "TXYZ:1*{checksum:02X}").unwrap();
+        let len = w.pos;
+        assert_eq!(parse_text_telemetry(&buf[..len]), Err(ParseError::Parse));
+
+        let checksum = calculate_crc8(b"LQ:1:2:3:4");
+        let mut w = SliceWriter {
+            buf: &mut buf,
+            pos: 0,
+        };
+        write!(w, "TLQ:1:2:3:4*{checksum:02X}").unwrap();
+        let len = w.pos;
+        assert_eq!(parse_text_telemetry(&buf[..len]), Err(ParseError::Parse));
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let mut buf = [0u8; MAX_TEXT_TELEMETRY_SIZE];
+        let len = encode_text_telemetry(&battery(), &mut buf).unwrap();
+        // Replace "\n" with "\r\n"
+        buf[len - 1] = b'\r';
+        buf[len] = b'\n';
+        assert_eq!(parse_text_telemetry(&buf[..len + 1]), Ok(battery()));
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn test_sink_writes_frame() {
+        extern crate std;
+
+        use crate::telemetry::TelemetrySink;
+        use crate::test_support::noop_waker;
+        use core::future::Future;
+        use core::task::{Context, Poll};
+        use std::vec::Vec;
+
+        struct VecWriter(Vec<u8>);
+
+        impl embedded_io::ErrorType for VecWriter {
+            type Error = core::convert::Infallible;
+        }
+
+        impl embedded_io::Write for VecWriter {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                self.0.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut sink = TextTelemetrySink::new(VecWriter(Vec::new()));
+        assert!(sink.supports_telemetry());
+
+        let data = battery();
+        {
+            let mut fut = core::pin::pin!(sink.send_telemetry(&data));
+            assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+        }
+
+        let written = sink.into_inner().0;
+        assert_eq!(parse_text_telemetry(&written), Ok(battery()));
+    }
+}
@@ -0,0 +1,195 @@
+//! Trigger-to-button mapping with a minimum press time.
+//!
+//! [`TriggerButton`] presses a digital button while an analog trigger is past
+//! a threshold. Toggling the button exactly with the trigger makes quick taps
+//! last a single report, which macros and some games miss; an optional
+//! minimum hold keeps the button pressed for at least that long once it was
+//! triggered. Time is injected as a millisecond timestamp, as with
+//! [`IdleDetector`].
+//!
+//! [`IdleDetector`]: crate::IdleDetector
+
+use gamepad_proto::{Buttons, GamepadState};
+
+/// Analog trigger selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Trigger {
+    /// Left trigger (LT)
+    Left,
+    /// Right trigger (RT)
+    Right,
+}
+
+impl Trigger {
+    /// Read this trigger from a state.
+    #[inline]
+    #[must_use]
+    pub const fn value(self, state: &GamepadState) -> u8 {
+        match self {
+            Self::Left => state.left_trigger,
+            Self::Right => state.right_trigger,
+        }
+    }
+}
+
+/// Presses a button while a trigger is past a threshold.
+#[derive(Debug, Clone)]
+pub struct TriggerButton {
+    /// Trigger driving the button
+    trigger: Trigger,
+    /// Button pressed while the trigger is active
+    button: Buttons,
+    /// Trigger values above this press the button
+    threshold: u8,
+    /// Shortest time the button stays pressed once triggered
+    min_hold_ms: u64,
+    /// Timestamp the current press started (`None` while released)
+    pressed_since_ms: Option<u64>,
+    /// Whether the trigger was active at the last `apply`
+    trigger_active: bool,
+}
+
+impl TriggerButton {
+    /// Press `button` while `trigger` is above `threshold`, without a
+    /// minimum hold.
+    #[must_use]
+    pub const fn new(trigger: Trigger, button: Buttons, threshold: u8) -> Self {
+        Self {
+            trigger,
+            button,
+            threshold,
+            min_hold_ms: 0,
+            pressed_since_ms: None,
+            trigger_active: false,
+        }
+    }
+
+    /// Keep the button pressed for at least `min_hold_ms` once triggered.
+    #[must_use]
+    pub const fn with_min_hold(mut self, min_hold_ms: u64) -> Self {
+        self.min_hold_ms = min_hold_ms;
+        self
+    }
+
+    /// Get the minimum hold duration in milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn min_hold_ms(&self) -> u64 {
+        self.min_hold_ms
+    }
+
+    /// Returns true while the button is pressed.
+    #[inline]
+    #[must_use]
+    pub const fn is_pressed(&self) -> bool {
+        self.pressed_since_ms.is_some()
+    }
+
+    /// Timestamp at which a button held only by the minimum hold is released.
+    ///
+    /// Returns `None` while released or while the trigger was still active at
+    /// the last [`apply`](Self::apply). Call `apply` again at this time so the
+    /// release is reported even if no new input arrives.
+    #[must_use]
+    pub fn release_due_ms(&self) -> Option<u64> {
+        if self.trigger_active {
+            return None;
+        }
+        self.pressed_since_ms
+            .map(|since| since.saturating_add(self.min_hold_ms))
+    }
+
+    /// Filter the current state.
+    ///
+    /// Returns `state` with the button added while the trigger is active or
+    /// the minimum hold has not yet passed. Other buttons and the trigger
+    /// value itself are passed through.
+    pub fn apply(&mut self, state: &GamepadState, now_ms: u64) -> GamepadState {
+        let active = self.trigger.value(state) > self.threshold;
+        self.trigger_active = active;
+
+        match self.pressed_since_ms {
+            None if active => self.pressed_since_ms = Some(now_ms),
+            Some(since) if !active && now_ms.saturating_sub(since) >= self.min_hold_ms => {
+                self.pressed_since_ms = None;
+            }
+            _ => {}
+        }
+
+        let mut out = *state;
+        if self.is_pressed() {
+            out.buttons |= self.button;
+        }
+        out
+    }
+
+    /// Release the button immediately.
+    pub fn reset(&mut self) {
+        self.pressed_since_ms = None;
+        self.trigger_active = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(left: u8) -> GamepadState {
+        GamepadState {
+            left_trigger: left,
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_tap_held_for_min_duration() {
+        let mut map = TriggerButton::new(Trigger::Left, Buttons::LB, 128).with_min_hold(50);
+        assert_eq!(map.apply(&trigger(100), 0).buttons, Buttons::NONE);
+
+        // 5 ms tap
+        assert_eq!(map.apply(&trigger(255), 1000).buttons, Buttons::LB);
+        assert_eq!(map.apply(&trigger(0), 1005).buttons, Buttons::LB);
+        assert_eq!(map.release_due_ms(), Some(1050));
+
+        assert_eq!(map.apply(&trigger(0), 1049).buttons, Buttons::LB);
+        let released = map.apply(&trigger(0), 1050);
+        assert_eq!(released.buttons, Buttons::NONE);
+        assert_eq!(released.left_trigger, 0);
+        assert!(!map.is_pressed());
+        assert_eq!(map.release_due_ms(), None);
+    }
+
+    #[test]
+    fn test_long_press_follows_trigger() {
+        let mut map = TriggerButton::new(Trigger::Left, Buttons::LB, 128).with_min_hold(50);
+
+        let mut pressed = trigger(200);
+        pressed.buttons = Buttons::A;
+        assert_eq!(map.apply(&pressed, 0).buttons, Buttons::A | Buttons::LB);
+        // The trigger keeps the button pressed, no release is scheduled
+        assert_eq!(map.release_due_ms(), None);
+        assert_eq!(map.apply(&pressed, 300).buttons, Buttons::A | Buttons::LB);
+        assert_eq!(map.release_due_ms(), None);
+
+        // Held past the minimum: released together with the trigger
+        assert_eq!(map.apply(&trigger(10), 310).buttons, Buttons::NONE);
+    }
+
+    #[test]
+    fn test_without_min_hold_and_right_trigger() {
+        let mut map = TriggerButton::new(Trigger::Right, Buttons::RB, 0);
+        assert_eq!(map.min_hold_ms(), 0);
+
+        let state = GamepadState {
+            right_trigger: 1,
+            ..GamepadState::neutral()
+        };
+        assert_eq!(map.apply(&state, 0).buttons, Buttons::RB);
+        assert_eq!(map.apply(&trigger(255), 1).buttons, Buttons::NONE);
+
+        map.apply(&state, 2);
+        map.reset();
+        assert!(!map.is_pressed());
+    }
+}
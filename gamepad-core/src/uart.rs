@@ -0,0 +1,93 @@
+//! Serial line settings per input protocol.
+//!
+//! Every UART protocol fixes its own baud rate and framing, and a mismatch
+//! shows up only as a stream of framing or checksum errors. [`UartParams`]
+//! collects the settings in one table so the firmware configures the UART
+//! from the selected protocol instead of hand-written values.
+
+/// UART parity bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Parity {
+    /// No parity bit
+    None,
+    /// Even parity
+    Even,
+    /// Odd parity
+    Odd,
+}
+
+/// Number of UART stop bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StopBits {
+    /// One stop bit
+    One,
+    /// Two stop bits
+    Two,
+}
+
+/// Baud rate and framing of a serial protocol (always 8 data bits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UartParams {
+    /// Baud rate in bits per second
+    pub baud: u32,
+    /// Parity bit
+    pub parity: Parity,
+    /// Stop bits
+    pub stop_bits: StopBits,
+}
+
+impl UartParams {
+    /// Text gamepad protocol: 115200 8N1.
+    pub const TEXT: Self = Self::new_8n1(115_200);
+
+    /// CRSF with ExpressLRS receivers: 420000 8N1.
+    pub const CRSF: Self = Self::new_8n1(420_000);
+
+    /// MAVLink over a direct serial connection: 115200 8N1.
+    pub const MAVLINK: Self = Self::new_8n1(115_200);
+
+    /// SBUS: 100000 8E2 (the line is also inverted).
+    pub const SBUS: Self = Self {
+        baud: 100_000,
+        parity: Parity::Even,
+        stop_bits: StopBits::Two,
+    };
+
+    /// Settings with 8 data bits, no parity and one stop bit.
+    #[must_use]
+    pub const fn new_8n1(baud: u32) -> Self {
+        Self {
+            baud,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+        }
+    }
+}
+
+impl Default for UartParams {
+    fn default() -> Self {
+        Self::TEXT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_params() {
+        assert_eq!(UartParams::SBUS.baud, 100_000);
+        assert_eq!(UartParams::SBUS.parity, Parity::Even);
+        assert_eq!(UartParams::SBUS.stop_bits, StopBits::Two);
+
+        assert_eq!(UartParams::CRSF, UartParams::new_8n1(420_000));
+        assert_eq!(UartParams::CRSF.parity, Parity::None);
+        assert_eq!(UartParams::CRSF.stop_bits, StopBits::One);
+
+        assert_eq!(UartParams::default(), UartParams::new_8n1(115_200));
+        assert_eq!(UartParams::MAVLINK.baud, 115_200);
+    }
+}
@@ -0,0 +1,334 @@
+//! Byte-at-a-time line accumulator for streaming protocol input.
+//!
+//! Transports such as UART interrupts or DMA ring buffers deliver bytes one at
+//! a time without regard for message boundaries. [`LineAccumulator`] buffers
+//! those bytes until a newline is seen and then hands the complete line to
+//! [`parse_message()`](crate::parse_message).
+
+use crate::parser::{parse_message, ParseError, ParsedMessage, MAX_LINE_LENGTH};
+
+/// Accumulates bytes into lines and parses each completed line.
+///
+/// Lines longer than `N` bytes (default [`MAX_LINE_LENGTH`]) are discarded up
+/// to the next newline and reported as [`ParseError::Overflow`], so a single
+/// corrupt line does not cascade into errors on the following messages.
+///
+/// Choose a larger `N` for senders that append extra fields, or a smaller one
+/// to save RAM, e.g. `LineAccumulator::<128>::new()`. Without a turbofish the
+/// length comes from the binding's type, so write `let acc: LineAccumulator`
+/// for the default.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{LineAccumulator, ParsedMessage, GamepadState};
+///
+/// let mut acc: LineAccumulator = LineAccumulator::new();
+/// let mut result = None;
+/// for &byte in b"G0000:0:0:0:0:0:0*30\n" {
+///     if let Some(r) = acc.push(byte) {
+///         result = Some(r);
+///     }
+/// }
+/// assert_eq!(result, Some(Ok(ParsedMessage::FullState(GamepadState::neutral()))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineAccumulator<const N: usize = MAX_LINE_LENGTH> {
+    buf: [u8; N],
+    len: usize,
+    /// Set when the current line overflowed and is being discarded.
+    overflow: bool,
+    /// Set when the current line is being discarded silently (see [`resync`](Self::resync)).
+    skip: bool,
+}
+
+impl<const N: usize> LineAccumulator<N> {
+    /// Create an empty accumulator.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0u8; N],
+            len: 0,
+            overflow: false,
+            skip: false,
+        }
+    }
+
+    /// Feed a single byte.
+    ///
+    /// Returns `None` while a line is still incomplete, or `Some` with the
+    /// parse result once a newline terminates the line.
+    pub fn push(&mut self, byte: u8) -> Option<Result<ParsedMessage, ParseError>> {
+        if byte == b'\n' {
+            if self.skip {
+                self.reset();
+                return None;
+            }
+            let result = if self.overflow {
+                Err(ParseError::Overflow)
+            } else {
+                parse_message(&self.buf[..self.len])
+            };
+            self.reset();
+            return Some(result);
+        }
+
+        if self.overflow || self.skip {
+            return None;
+        }
+
+        if self.len < N {
+            self.buf[self.len] = byte;
+            self.len += 1;
+        } else {
+            // Line too long - discard the rest of it until newline
+            self.overflow = true;
+        }
+
+        None
+    }
+
+    /// Feed a chunk of bytes, e.g. one DMA transfer.
+    ///
+    /// Returns an iterator over the parse results of the lines completed in
+    /// this chunk. Bytes after the last newline stay buffered for the next
+    /// chunk. The bytes are consumed as the iterator advances, so drain it to
+    /// feed the whole chunk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadState, LineAccumulator, ParsedMessage};
+    ///
+    /// let mut acc: LineAccumulator = LineAccumulator::new();
+    /// assert_eq!(acc.feed(b"G0000:0:0:0").count(), 0);
+    /// let results: Vec<_> = acc.feed(b":0:0:0*30\nG00").collect();
+    /// assert_eq!(results, [Ok(ParsedMessage::FullState(GamepadState::neutral()))]);
+    /// assert_eq!(acc.len(), 3);
+    /// ```
+    pub fn feed<'a>(
+        &'a mut self,
+        chunk: &'a [u8],
+    ) -> impl Iterator<Item = Result<ParsedMessage, ParseError>> + 'a {
+        chunk.iter().filter_map(move |&byte| self.push(byte))
+    }
+
+    /// Discard any partially accumulated line.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.overflow = false;
+        self.skip = false;
+    }
+
+    /// Discard the partial line and ignore input up to the next newline.
+    ///
+    /// Call this after the transport reports lost bytes (e.g. a UART overrun):
+    /// the remainder of the interrupted line is dropped without producing a
+    /// result, and parsing resumes with the following line.
+    #[inline]
+    pub fn resync(&mut self) {
+        self.len = 0;
+        self.overflow = false;
+        self.skip = true;
+    }
+
+    /// Number of bytes buffered for the current line.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if no bytes are buffered for the current line.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<const N: usize> Default for LineAccumulator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Buttons, GamepadFieldUpdate, GamepadState};
+
+    fn push_all<const N: usize>(
+        acc: &mut LineAccumulator<N>,
+        bytes: &[u8],
+    ) -> Option<Result<ParsedMessage, ParseError>> {
+        let mut last = None;
+        for &b in bytes {
+            if let Some(r) = acc.push(b) {
+                last = Some(r);
+            }
+        }
+        last
+    }
+
+    #[test]
+    fn test_accumulator_full_state() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\n");
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+        assert!(acc.is_empty());
+    }
+
+    #[test]
+    fn test_accumulator_incomplete_line() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        assert_eq!(push_all(&mut acc, b"G0000:0:0"), None);
+        assert_eq!(acc.len(), 9);
+    }
+
+    #[test]
+    fn test_accumulator_split_across_pushes() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        assert_eq!(push_all(&mut acc, b"UB:00"), None);
+        let result = push_all(&mut acc, b"03*");
+        assert_eq!(result, None);
+
+        let checksum = crate::crc::calculate_crc8(b"B:0003");
+        let mut tail = [0u8; 3];
+        crate::fmt::write_hex_u8(&mut tail, checksum);
+        tail[2] = b'\n';
+        let result = push_all(&mut acc, &tail);
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::Update(GamepadFieldUpdate::Buttons(
+                Buttons::A | Buttons::B
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_accumulator_crlf() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\r\n");
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+    }
+
+    #[test]
+    fn test_accumulator_overflow_recovers() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        let long = [b'9'; MAX_LINE_LENGTH + 10];
+        assert_eq!(push_all(&mut acc, &long), None);
+        assert_eq!(acc.push(b'\n'), Some(Err(ParseError::Overflow)));
+
+        // Next line parses normally
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\n");
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+    }
+
+    #[test]
+    fn test_feed_lines_split_across_chunks() {
+        extern crate std;
+        use std::vec::Vec;
+
+        let stream = b"G0000:0:0:0:0:0:0*30\nUB:0003*87\nG0000:0:0:0:0:0:0*30\n";
+        assert_eq!(crate::crc::calculate_crc8(b"B:0003"), 0x87);
+
+        // Every split point gives the same three messages
+        for split in 0..=stream.len() {
+            let mut acc: LineAccumulator = LineAccumulator::new();
+            let mut results: Vec<_> = acc.feed(&stream[..split]).collect();
+            results.extend(acc.feed(&stream[split..]));
+
+            assert_eq!(results.len(), 3, "split at {split}");
+            assert!(results.iter().all(Result::is_ok), "split at {split}");
+            assert_eq!(
+                results[1],
+                Ok(ParsedMessage::Update(GamepadFieldUpdate::Buttons(
+                    Buttons::A | Buttons::B
+                )))
+            );
+            assert!(acc.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_feed_overflowing_line_reported_once() {
+        extern crate std;
+        use std::vec::Vec;
+
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        let long = [b'7'; MAX_LINE_LENGTH * 2];
+        assert_eq!(acc.feed(&long[..MAX_LINE_LENGTH]).count(), 0);
+        assert_eq!(acc.feed(&long[MAX_LINE_LENGTH..]).count(), 0);
+
+        let results: Vec<_> = acc.feed(b"\nG0000:0:0:0:0:0:0*30\n").collect();
+        assert_eq!(
+            results,
+            [
+                Err(ParseError::Overflow),
+                Ok(ParsedMessage::FullState(GamepadState::neutral()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_line_length() {
+        let mut acc = LineAccumulator::<32>::new();
+        let long = [b'5'; 40];
+        assert_eq!(acc.feed(&long).count(), 0);
+        assert_eq!(acc.len(), 32);
+        assert_eq!(acc.push(b'\n'), Some(Err(ParseError::Overflow)));
+
+        // Lines up to the limit still parse
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\n");
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+
+        // The default fits the 40-byte line, a larger one fits much more
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        assert_eq!(acc.feed(&long).count(), 0);
+        assert_eq!(acc.push(b'\n'), Some(Err(ParseError::Parse)));
+        let mut acc = LineAccumulator::<128>::new();
+        assert_eq!(acc.feed(&[b'5'; 100]).count(), 0);
+        assert_eq!(acc.len(), 100);
+    }
+
+    #[test]
+    fn test_accumulator_reset() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        push_all(&mut acc, b"garbage");
+        acc.reset();
+        assert!(acc.is_empty());
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\n");
+        assert!(matches!(result, Some(Ok(ParsedMessage::FullState(_)))));
+    }
+
+    #[test]
+    fn test_accumulator_resync_drops_rest_of_line() {
+        let mut acc: LineAccumulator = LineAccumulator::new();
+        push_all(&mut acc, b"G0000:0:0");
+        acc.resync();
+        assert!(acc.is_empty());
+
+        // Tail of the interrupted line is ignored without an error
+        assert_eq!(push_all(&mut acc, b":0:0:0:0*30\n"), None);
+
+        let result = push_all(&mut acc, b"G0000:0:0:0:0:0:0*30\n");
+        assert_eq!(
+            result,
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+    }
+}
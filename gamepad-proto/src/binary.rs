@@ -0,0 +1,364 @@
+//! Binary frame format and resynchronizing parser.
+//!
+//! A compact alternative to the text protocol for links where bandwidth
+//! matters more than readability. Each frame is:
+//!
+//! ```text
+//! 0xA5 <len> <payload: len bytes> <crc>
+//! ```
+//!
+//! `len` is the payload length (at most [`MAX_BINARY_PAYLOAD`]) and `crc` is
+//! the CRC-8 (see [`calculate_crc8`]) over the length byte and the payload.
+//! A full gamepad state is carried as a [`BINARY_STATE_SIZE`]-byte payload
+//! (see [`encode_binary_state`]).
+//!
+//! [`BinaryParser`] scans for the sync byte and validates length and CRC. A
+//! sync byte can also occur inside a payload, so on a bad frame the parser
+//! re-scans the buffered bytes after the false sync instead of dropping them,
+//! and a valid frame following a corrupt one is still recovered.
+
+use crate::crc::calculate_crc8;
+use crate::serialize::SerializeError;
+use crate::types::{AnalogStick, Buttons, GamepadState};
+
+/// Byte that starts every binary frame.
+pub const BINARY_SYNC: u8 = 0xA5;
+
+/// Maximum payload length of a binary frame.
+pub const MAX_BINARY_PAYLOAD: usize = 32;
+
+/// Maximum size of a binary frame (sync, length, payload, CRC).
+pub const MAX_BINARY_FRAME_SIZE: usize = MAX_BINARY_PAYLOAD + 3;
+
+/// Payload length of a full gamepad state.
+///
+/// Buttons (u16), four stick axes (i16) and two triggers (u8), multi-byte
+/// values little-endian.
+pub const BINARY_STATE_SIZE: usize = 12;
+
+/// A validated binary frame payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BinaryFrame {
+    payload: [u8; MAX_BINARY_PAYLOAD],
+    len: usize,
+}
+
+impl BinaryFrame {
+    /// Get the frame payload.
+    #[inline]
+    #[must_use]
+    pub fn payload(&self) -> &[u8] {
+        &self.payload[..self.len]
+    }
+}
+
+/// Encode `payload` as a binary frame into `buf`.
+///
+/// Returns the number of bytes written.
+///
+/// # Errors
+///
+/// Returns [`SerializeError::BufferTooSmall`] if the payload is longer than
+/// [`MAX_BINARY_PAYLOAD`] or the frame does not fit `buf`.
+pub fn encode_binary_frame(payload: &[u8], buf: &mut [u8]) -> Result<usize, SerializeError> {
+    let frame_len = payload.len() + 3;
+    if payload.len() > MAX_BINARY_PAYLOAD || buf.len() < frame_len {
+        return Err(SerializeError::BufferTooSmall);
+    }
+
+    buf[0] = BINARY_SYNC;
+    buf[1] = payload.len() as u8;
+    buf[2..frame_len - 1].copy_from_slice(payload);
+    buf[frame_len - 1] = calculate_crc8(&buf[1..frame_len - 1]);
+    Ok(frame_len)
+}
+
+/// Encode a gamepad state as a binary frame into `buf`.
+///
+/// # Errors
+///
+/// Returns [`SerializeError::BufferTooSmall`] if `buf` is shorter than
+/// `BINARY_STATE_SIZE + 3` bytes.
+pub fn encode_binary_state(state: &GamepadState, buf: &mut [u8]) -> Result<usize, SerializeError> {
+    let mut payload = [0u8; BINARY_STATE_SIZE];
+    payload[0..2].copy_from_slice(&state.buttons.raw().to_le_bytes());
+    payload[2..4].copy_from_slice(&state.left_stick.x.to_le_bytes());
+    payload[4..6].copy_from_slice(&state.left_stick.y.to_le_bytes());
+    payload[6..8].copy_from_slice(&state.right_stick.x.to_le_bytes());
+    payload[8..10].copy_from_slice(&state.right_stick.y.to_le_bytes());
+    payload[10] = state.left_trigger;
+    payload[11] = state.right_trigger;
+    encode_binary_frame(&payload, buf)
+}
+
+/// Decode a gamepad state from a binary frame.
+///
+/// Returns `None` if the payload is not [`BINARY_STATE_SIZE`] bytes long.
+#[must_use]
+pub fn decode_binary_state(frame: &BinaryFrame) -> Option<GamepadState> {
+    let p: &[u8; BINARY_STATE_SIZE] = frame.payload().try_into().ok()?;
+    let i16_at = |i: usize| i16::from_le_bytes([p[i], p[i + 1]]);
+
+    Some(GamepadState {
+        buttons: Buttons(u16::from_le_bytes([p[0], p[1]])),
+        left_stick: AnalogStick::new(i16_at(2), i16_at(4)),
+        right_stick: AnalogStick::new(i16_at(6), i16_at(8)),
+        left_trigger: p[10],
+        right_trigger: p[11],
+    })
+}
+
+/// Byte-at-a-time parser for binary frames.
+///
+/// Bytes before a sync byte are skipped. A frame with an oversized length or
+/// a CRC mismatch is counted in [`errors`](Self::errors), and scanning
+/// resumes at the byte after its sync byte, so no byte that could start a
+/// valid frame is lost.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{decode_binary_state, encode_binary_state, BinaryParser, GamepadState};
+///
+/// let state = GamepadState::neutral();
+/// let mut buf = [0u8; 16];
+/// let len = encode_binary_state(&state, &mut buf).unwrap();
+///
+/// let mut parser = BinaryParser::new();
+/// let frame = buf[..len].iter().find_map(|&byte| parser.push(byte)).unwrap();
+/// assert_eq!(decode_binary_state(&frame), Some(state));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryParser {
+    /// Bytes of the candidate frame, starting at a sync byte
+    buf: [u8; MAX_BINARY_FRAME_SIZE],
+    len: usize,
+    /// Frames rejected for length or CRC
+    errors: u32,
+}
+
+impl BinaryParser {
+    /// Create an empty parser.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0u8; MAX_BINARY_FRAME_SIZE],
+            len: 0,
+            errors: 0,
+        }
+    }
+
+    /// Feed a single byte.
+    ///
+    /// Returns a frame once its last byte arrived. If resynchronizing left a
+    /// second complete frame in the buffer, it is returned on the next call.
+    pub fn push(&mut self, byte: u8) -> Option<BinaryFrame> {
+        if self.len == 0 && byte != BINARY_SYNC {
+            return None;
+        }
+        // scan() always leaves a partial frame, so there is room for one more byte
+        self.buf[self.len] = byte;
+        self.len += 1;
+        self.scan()
+    }
+
+    /// Number of frames rejected for an invalid length or CRC.
+    #[inline]
+    #[must_use]
+    pub const fn errors(&self) -> u32 {
+        self.errors
+    }
+
+    /// Drop any buffered bytes.
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+
+    /// Look for a complete frame in the buffer, resyncing past bad ones.
+    fn scan(&mut self) -> Option<BinaryFrame> {
+        loop {
+            let start = self.buf[..self.len].iter().position(|&b| b == BINARY_SYNC);
+            self.discard(start.unwrap_or(self.len));
+            if self.len < 2 {
+                return None;
+            }
+
+            let payload_len = usize::from(self.buf[1]);
+            if payload_len > MAX_BINARY_PAYLOAD {
+                self.errors = self.errors.wrapping_add(1);
+                self.discard(1);
+                continue;
+            }
+
+            let frame_len = payload_len + 3;
+            if self.len < frame_len {
+                return None;
+            }
+
+            if calculate_crc8(&self.buf[1..frame_len - 1]) == self.buf[frame_len - 1] {
+                let mut frame = BinaryFrame {
+                    payload: [0u8; MAX_BINARY_PAYLOAD],
+                    len: payload_len,
+                };
+                frame.payload[..payload_len].copy_from_slice(&self.buf[2..frame_len - 1]);
+                self.discard(frame_len);
+                return Some(frame);
+            }
+
+            // False sync or corrupt frame: rescan after its sync byte
+            self.errors = self.errors.wrapping_add(1);
+            self.discard(1);
+        }
+    }
+
+    /// Remove the first `n` buffered bytes.
+    fn discard(&mut self, n: usize) {
+        self.buf.copy_within(n..self.len, 0);
+        self.len -= n;
+    }
+}
+
+impl Default for BinaryParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> GamepadState {
+        GamepadState {
+            buttons: Buttons::A | Buttons::GUIDE,
+            left_stick: AnalogStick::new(-32768, 1200),
+            right_stick: AnalogStick::new(32767, -5),
+            left_trigger: 0xA5,
+            right_trigger: 3,
+        }
+    }
+
+    fn parse_all(parser: &mut BinaryParser, bytes: &[u8]) -> ([Option<BinaryFrame>; 4], usize) {
+        let mut frames = [None; 4];
+        let mut count = 0;
+        for &byte in bytes {
+            if let Some(frame) = parser.push(byte) {
+                frames[count] = Some(frame);
+                count += 1;
+            }
+        }
+        (frames, count)
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut buf = [0u8; MAX_BINARY_FRAME_SIZE];
+        let len = encode_binary_state(&sample_state(), &mut buf).unwrap();
+        assert_eq!(len, BINARY_STATE_SIZE + 3);
+        assert_eq!(&buf[..2], &[BINARY_SYNC, BINARY_STATE_SIZE as u8]);
+
+        let mut parser = BinaryParser::new();
+        let (frames, count) = parse_all(&mut parser, &buf[..len]);
+        assert_eq!(count, 1);
+        assert_eq!(
+            decode_binary_state(&frames[0].unwrap()),
+            Some(sample_state())
+        );
+    }
+
+    #[test]
+    fn test_corrupt_frame_before_valid_one() {
+        let mut valid = [0u8; MAX_BINARY_FRAME_SIZE];
+        let len = encode_binary_state(&sample_state(), &mut valid).unwrap();
+
+        // Bad CRC, then a truncated frame whose length swallows the valid frame
+        let mut stream = [0u8; 64];
+        let mut n = 0;
+        for part in [
+            &[0x00, 0xA5, 0x02, 0x10, 0x20, 0x00][..],
+            &[0xA5, 0x0A, 0x01],
+            &valid[..len],
+        ] {
+            stream[n..n + part.len()].copy_from_slice(part);
+            n += part.len();
+        }
+
+        let mut parser = BinaryParser::new();
+        let (frames, count) = parse_all(&mut parser, &stream[..n]);
+        assert_eq!(count, 1);
+        assert_eq!(
+            decode_binary_state(&frames[0].unwrap()),
+            Some(sample_state())
+        );
+        assert_eq!(parser.errors(), 2);
+    }
+
+    #[test]
+    fn test_oversized_length_rejected() {
+        let mut parser = BinaryParser::new();
+        let (_, count) = parse_all(&mut parser, &[BINARY_SYNC, MAX_BINARY_PAYLOAD as u8 + 1]);
+        assert_eq!(count, 0);
+        assert_eq!(parser.errors(), 1);
+
+        let mut buf = [0u8; MAX_BINARY_FRAME_SIZE + 1];
+        let too_long = [0u8; MAX_BINARY_PAYLOAD + 1];
+        assert_eq!(
+            encode_binary_frame(&too_long, &mut buf),
+            Err(SerializeError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_wrong_payload_size_not_a_state() {
+        let mut buf = [0u8; MAX_BINARY_FRAME_SIZE];
+        let len = encode_binary_frame(&[1, 2, 3], &mut buf).unwrap();
+
+        let mut parser = BinaryParser::new();
+        let (frames, count) = parse_all(&mut parser, &buf[..len]);
+        assert_eq!(count, 1);
+        assert_eq!(frames[0].unwrap().payload(), &[1, 2, 3]);
+        assert_eq!(decode_binary_state(&frames[0].unwrap()), None);
+    }
+
+    /// Small deterministic PRNG (xorshift32) for generating noise.
+    fn next_random(seed: &mut u32) -> u32 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        *seed
+    }
+
+    #[test]
+    fn test_random_bytes_never_panic() {
+        let mut seed = 0x1234_5678;
+        let mut parser = BinaryParser::new();
+        let mut frame = [0u8; MAX_BINARY_FRAME_SIZE];
+        let len = encode_binary_state(&sample_state(), &mut frame).unwrap();
+
+        for round in 0..2_000 {
+            // Noise biased towards sync bytes and small lengths
+            for _ in 0..next_random(&mut seed) % 64 {
+                let byte = match next_random(&mut seed) % 4 {
+                    0 => BINARY_SYNC,
+                    1 => (next_random(&mut seed) % 40) as u8,
+                    _ => next_random(&mut seed) as u8,
+                };
+                if let Some(frame) = parser.push(byte) {
+                    assert!(frame.payload().len() <= MAX_BINARY_PAYLOAD);
+                }
+            }
+
+            // Every other round, a valid frame must still come through
+            if round % 2 == 0 {
+                parser.reset();
+                let (frames, count) = parse_all(&mut parser, &frame[..len]);
+                assert_eq!(count, 1);
+                assert_eq!(
+                    decode_binary_state(&frames[0].unwrap()),
+                    Some(sample_state())
+                );
+            }
+        }
+    }
+}
@@ -26,8 +26,13 @@
 //!     .unwrap();
 //! ```
 
-use crate::serialize::SerializeError;
-use crate::types::{AnalogStick, Buttons, GamepadFieldUpdate, GamepadState};
+use crate::serialize::{
+    serialize_state_with, serialize_update_with, SerializeError, MAX_FULL_STATE_SIZE,
+    MAX_UPDATE_SIZE,
+};
+use crate::types::{
+    AnalogStick, Buttons, FieldLayout, GamepadFieldUpdate, GamepadState, StickAxis,
+};
 
 /// Entry point for building protocol messages.
 ///
@@ -55,6 +60,9 @@ impl MessageBuilder {
     pub fn full_state() -> FullStateBuilder {
         FullStateBuilder {
             state: GamepadState::neutral(),
+            trusted: false,
+            active_low: false,
+            layout: FieldLayout::DEFAULT,
         }
     }
 
@@ -75,16 +83,192 @@ impl MessageBuilder {
     /// ```
     #[must_use]
     pub fn update() -> UpdateBuilder {
-        UpdateBuilder { update: None }
+        UpdateBuilder {
+            update: None,
+            trusted: false,
+            active_low: false,
+        }
+    }
+
+    /// Start building a batch of update messages, one per field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{Buttons, MessageBuilder};
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let (len, frames) = MessageBuilder::multi_update()
+    ///     .buttons(Buttons::A)
+    ///     .left_trigger(200)
+    ///     .serialize(&mut buf);
+    /// assert_eq!(frames, 2);
+    /// assert!(buf[..len].starts_with(b"UB:"));
+    /// ```
+    #[cfg(feature = "heapless")]
+    #[must_use]
+    pub fn multi_update() -> MultiUpdateBuilder {
+        MultiUpdateBuilder {
+            updates: heapless::Vec::new(),
+            trusted: false,
+            active_low: false,
+        }
+    }
+
+    /// Serialize the cheapest message(s) that bring `old` up to `new`.
+    ///
+    /// If at most [`MAX_DELTA_UPDATES`] fields changed, one update message is
+    /// written per changed field (e.g. a single `ULT:...` frame when only a
+    /// trigger moved). Otherwise a full state message is written. Returns the
+    /// written bytes, which are empty if nothing changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::BufferTooSmall`] if the buffer is too small.
+    /// A buffer of [`MAX_FULL_STATE_SIZE`] bytes is always sufficient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadState, MessageBuilder};
+    ///
+    /// let old = GamepadState::neutral();
+    /// let new = GamepadState { right_trigger: 200, ..old };
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let bytes = MessageBuilder::delta(&old, &new, &mut buf).unwrap();
+    /// assert!(bytes.starts_with(b"URT:200*"));
+    /// ```
+    pub fn delta<'a>(
+        old: &GamepadState,
+        new: &GamepadState,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], SerializeError> {
+        if old.diff(new).count() > MAX_DELTA_UPDATES {
+            let len = serialize_state_with(new, buf, true, &FieldLayout::DEFAULT)?;
+            return Ok(&buf[..len]);
+        }
+
+        let mut pos = 0;
+        for update in old.diff(new) {
+            pos += serialize_update_with(&update, &mut buf[pos..], true)?;
+        }
+        Ok(&buf[..pos])
+    }
+
+    /// Serialize each update as its own `U` frame, back to back.
+    ///
+    /// Frames are written in order until the next one no longer fits, so the
+    /// buffer never holds a truncated frame. Returns the total number of bytes
+    /// written and the number of frames; a frame count below `updates.len()`
+    /// means the buffer filled up. A buffer of `updates.len() *`
+    /// [`MAX_UPDATE_SIZE`] bytes always fits every frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadFieldUpdate, MessageBuilder};
+    ///
+    /// let updates = [
+    ///     GamepadFieldUpdate::LeftTrigger(10),
+    ///     GamepadFieldUpdate::RightTrigger(20),
+    /// ];
+    /// let mut buf = [0u8; 64];
+    /// let (len, frames) = MessageBuilder::update_many(&updates, &mut buf);
+    /// assert_eq!(frames, 2);
+    /// assert!(buf[..len].starts_with(b"ULT:10*"));
+    /// ```
+    #[must_use]
+    pub fn update_many(updates: &[GamepadFieldUpdate], buf: &mut [u8]) -> (usize, usize) {
+        let mut pos = 0;
+        let mut frames = 0;
+        for update in updates {
+            // Serialize into scratch space so a frame is placed only if it fits exactly
+            let mut frame = [0u8; MAX_UPDATE_SIZE];
+            let Ok(len) = serialize_update_with(update, &mut frame, true) else {
+                break;
+            };
+            let Some(dest) = buf.get_mut(pos..pos + len) else {
+                break;
+            };
+            dest.copy_from_slice(&frame[..len]);
+            pos += len;
+            frames += 1;
+        }
+        (pos, frames)
+    }
+
+    /// Append one `U` frame per update to `vec`, back to back.
+    ///
+    /// For batching several updates into one DMA or UART write. Frames are
+    /// appended whole: if the next frame no longer fits, `vec` keeps the
+    /// complete frames written so far (and anything it held before).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::BufferTooSmall`] if not all frames fit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadFieldUpdate, MessageBuilder};
+    ///
+    /// let mut vec = heapless::Vec::<u8, 64>::new();
+    /// let updates = [
+    ///     GamepadFieldUpdate::LeftTrigger(10),
+    ///     GamepadFieldUpdate::RightTrigger(20),
+    /// ];
+    /// MessageBuilder::append_updates(&mut vec, &updates).unwrap();
+    /// assert!(vec.starts_with(b"ULT:10*"));
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn append_updates<const N: usize>(
+        vec: &mut heapless::Vec<u8, N>,
+        updates: &[GamepadFieldUpdate],
+    ) -> Result<(), SerializeError> {
+        for update in updates {
+            let mut frame = [0u8; MAX_UPDATE_SIZE];
+            let len = serialize_update_with(update, &mut frame, true)?;
+            vec.extend_from_slice(&frame[..len])
+                .map_err(|_| SerializeError::BufferTooSmall)?;
+        }
+        Ok(())
+    }
+
+    /// Append a `G` frame for `state` followed by one `U` frame per update.
+    ///
+    /// Frames are appended whole, as with [`append_updates`](Self::append_updates).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::BufferTooSmall`] if not all frames fit.
+    #[cfg(feature = "heapless")]
+    pub fn append_batch<const N: usize>(
+        vec: &mut heapless::Vec<u8, N>,
+        state: &GamepadState,
+        updates: &[GamepadFieldUpdate],
+    ) -> Result<(), SerializeError> {
+        let mut frame = [0u8; MAX_FULL_STATE_SIZE];
+        let len = serialize_state_with(state, &mut frame, true, &FieldLayout::DEFAULT)?;
+        vec.extend_from_slice(&frame[..len])
+            .map_err(|_| SerializeError::BufferTooSmall)?;
+        Self::append_updates(vec, updates)
     }
 }
 
+/// Maximum number of changed fields [`MessageBuilder::delta()`] sends as
+/// individual update messages before falling back to a full state message.
+pub const MAX_DELTA_UPDATES: usize = 2;
+
 /// Builder for full state messages.
 ///
 /// Created via [`MessageBuilder::full_state()`].
 #[derive(Debug, Clone)]
 pub struct FullStateBuilder {
     state: GamepadState,
+    trusted: bool,
+    active_low: bool,
+    layout: FieldLayout,
 }
 
 impl FullStateBuilder {
@@ -159,6 +343,37 @@ impl FullStateBuilder {
         self
     }
 
+    /// Omit the `*<checksum>` suffix (trusted link mode).
+    ///
+    /// Only use this on reliable direct connections. The receiver must parse
+    /// with [`ChecksumMode::Omitted`](crate::ChecksumMode::Omitted) or
+    /// [`ChecksumMode::Optional`](crate::ChecksumMode::Optional).
+    #[must_use]
+    pub fn trusted(mut self) -> Self {
+        self.trusted = true;
+        self
+    }
+
+    /// Send the button bitfield inverted (active-low receivers).
+    ///
+    /// The receiver must parse with
+    /// [`ParseOptions::active_low_buttons`](crate::ParseOptions::active_low_buttons).
+    #[must_use]
+    pub fn active_low_buttons(mut self) -> Self {
+        self.active_low = true;
+        self
+    }
+
+    /// Write the fields in a custom order (see [`FieldLayout`]).
+    ///
+    /// The receiver must parse with the same
+    /// [`ParseOptions::layout`](crate::ParseOptions::layout).
+    #[must_use]
+    pub fn layout(mut self, layout: FieldLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
     /// Get the built state without serializing.
     #[must_use]
     pub fn build(self) -> GamepadState {
@@ -173,15 +388,39 @@ impl FullStateBuilder {
     ///
     /// Returns [`SerializeError::BufferTooSmall`] if the buffer is too small.
     pub fn serialize(self, buf: &mut [u8]) -> Result<usize, SerializeError> {
-        use crate::serialize::Serialize;
-        self.state.serialize(buf)
+        let mut state = self.state;
+        if self.active_low {
+            state.buttons = !state.buttons;
+        }
+        serialize_state_with(&state, buf, !self.trusted, &self.layout)
+    }
+
+    /// Serialize the message to the provided buffer, returning the written bytes.
+    ///
+    /// Equivalent to [`serialize`](Self::serialize) followed by `&buf[..len]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::BufferTooSmall`] if the buffer is too small.
+    pub fn serialize_slice(self, buf: &mut [u8]) -> Result<&[u8], SerializeError> {
+        let len = self.serialize(buf)?;
+        Ok(&buf[..len])
     }
 
     /// Serialize to a `heapless::Vec`.
+    ///
+    /// `N` must be at least [`MAX_FULL_STATE_SIZE`].
     #[cfg(feature = "heapless")]
     pub fn serialize_to_vec<const N: usize>(self) -> Result<heapless::Vec<u8, N>, SerializeError> {
-        use crate::serialize::Serialize;
-        self.state.serialize_to_vec()
+        if N < MAX_FULL_STATE_SIZE {
+            return Err(SerializeError::BufferTooSmall);
+        }
+        let mut vec = heapless::Vec::new();
+        vec.resize(N, 0)
+            .map_err(|_| SerializeError::BufferTooSmall)?;
+        let len = self.serialize(&mut vec)?;
+        vec.truncate(len);
+        Ok(vec)
     }
 
     /// Serialize to a `core::fmt::Write` implementation.
@@ -190,15 +429,20 @@ impl FullStateBuilder {
     ///
     /// Returns [`SerializeError::WriteError`] if the write fails.
     pub fn serialize_fmt<W: core::fmt::Write>(self, writer: &mut W) -> Result<(), SerializeError> {
-        use crate::serialize::Serialize;
-        self.state.serialize_fmt(writer)
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = self.serialize(&mut buf)?;
+        let s = core::str::from_utf8(&buf[..len]).map_err(|_| SerializeError::WriteError)?;
+        writer.write_str(s).map_err(|_| SerializeError::WriteError)
     }
 
     /// Serialize to an `embedded_io::Write` implementation.
     #[cfg(feature = "embedded-io")]
     pub fn serialize_io<W: embedded_io::Write>(self, writer: &mut W) -> Result<(), SerializeError> {
-        use crate::serialize::Serialize;
-        self.state.serialize_io(writer)
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = self.serialize(&mut buf)?;
+        writer
+            .write_all(&buf[..len])
+            .map_err(|_| SerializeError::WriteError)
     }
 }
 
@@ -217,6 +461,8 @@ impl Default for FullStateBuilder {
 #[derive(Debug, Clone)]
 pub struct UpdateBuilder {
     update: Option<GamepadFieldUpdate>,
+    trusted: bool,
+    active_low: bool,
 }
 
 impl UpdateBuilder {
@@ -227,6 +473,20 @@ impl UpdateBuilder {
         self
     }
 
+    /// Set the update to press `buttons`, leaving the others unchanged (`UB+`).
+    #[must_use]
+    pub fn press(mut self, buttons: Buttons) -> Self {
+        self.update = Some(GamepadFieldUpdate::ButtonsSet(buttons));
+        self
+    }
+
+    /// Set the update to release `buttons`, leaving the others unchanged (`UB-`).
+    #[must_use]
+    pub fn release(mut self, buttons: Buttons) -> Self {
+        self.update = Some(GamepadFieldUpdate::ButtonsClear(buttons));
+        self
+    }
+
     /// Set the update to a left stick X change.
     #[must_use]
     pub fn left_stick_x(mut self, value: i16) -> Self {
@@ -269,6 +529,34 @@ impl UpdateBuilder {
         self
     }
 
+    /// Set the update to add `delta` to one stick axis (`ULX+`, ...).
+    #[must_use]
+    pub fn stick_delta(mut self, axis: StickAxis, delta: i16) -> Self {
+        self.update = Some(GamepadFieldUpdate::stick_delta(axis, delta));
+        self
+    }
+
+    /// Omit the `*<checksum>` suffix (trusted link mode).
+    ///
+    /// Only use this on reliable direct connections. The receiver must parse
+    /// with [`ChecksumMode::Omitted`](crate::ChecksumMode::Omitted) or
+    /// [`ChecksumMode::Optional`](crate::ChecksumMode::Optional).
+    #[must_use]
+    pub fn trusted(mut self) -> Self {
+        self.trusted = true;
+        self
+    }
+
+    /// Send the button bitfield inverted (active-low receivers).
+    ///
+    /// The receiver must parse with
+    /// [`ParseOptions::active_low_buttons`](crate::ParseOptions::active_low_buttons).
+    #[must_use]
+    pub fn active_low_buttons(mut self) -> Self {
+        self.active_low = true;
+        self
+    }
+
     /// Get the built update without serializing.
     ///
     /// Returns `None` if no field was set.
@@ -286,19 +574,35 @@ impl UpdateBuilder {
     /// Returns [`SerializeError::BufferTooSmall`] if the buffer is too small,
     /// or if no field was set (nothing to serialize).
     pub fn serialize(self, buf: &mut [u8]) -> Result<usize, SerializeError> {
-        use crate::serialize::Serialize;
-        self.update
-            .ok_or(SerializeError::BufferTooSmall)?
-            .serialize(buf)
+        let update = match self.update.ok_or(SerializeError::BufferTooSmall)? {
+            GamepadFieldUpdate::Buttons(b) if self.active_low => GamepadFieldUpdate::Buttons(!b),
+            update => update,
+        };
+        serialize_update_with(&update, buf, !self.trusted)
+    }
+
+    /// Serialize the message to the provided buffer, returning the written bytes.
+    ///
+    /// Equivalent to [`serialize`](Self::serialize) followed by `&buf[..len]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError::BufferTooSmall`] if the buffer is too small,
+    /// or if no field was set (nothing to serialize).
+    pub fn serialize_slice(self, buf: &mut [u8]) -> Result<&[u8], SerializeError> {
+        let len = self.serialize(buf)?;
+        Ok(&buf[..len])
     }
 
     /// Serialize to a `heapless::Vec`.
     #[cfg(feature = "heapless")]
     pub fn serialize_to_vec<const N: usize>(self) -> Result<heapless::Vec<u8, N>, SerializeError> {
-        use crate::serialize::Serialize;
-        self.update
-            .ok_or(SerializeError::BufferTooSmall)?
-            .serialize_to_vec()
+        let mut vec = heapless::Vec::new();
+        vec.resize(N, 0)
+            .map_err(|_| SerializeError::BufferTooSmall)?;
+        let len = self.serialize(&mut vec)?;
+        vec.truncate(len);
+        Ok(vec)
     }
 
     /// Serialize to a `core::fmt::Write` implementation.
@@ -308,19 +612,20 @@ impl UpdateBuilder {
     /// Returns [`SerializeError::BufferTooSmall`] if no field was set.
     /// Returns [`SerializeError::WriteError`] if the write fails.
     pub fn serialize_fmt<W: core::fmt::Write>(self, writer: &mut W) -> Result<(), SerializeError> {
-        use crate::serialize::Serialize;
-        self.update
-            .ok_or(SerializeError::BufferTooSmall)?
-            .serialize_fmt(writer)
+        let mut buf = [0u8; MAX_UPDATE_SIZE];
+        let len = self.serialize(&mut buf)?;
+        let s = core::str::from_utf8(&buf[..len]).map_err(|_| SerializeError::WriteError)?;
+        writer.write_str(s).map_err(|_| SerializeError::WriteError)
     }
 
     /// Serialize to an `embedded_io::Write` implementation.
     #[cfg(feature = "embedded-io")]
     pub fn serialize_io<W: embedded_io::Write>(self, writer: &mut W) -> Result<(), SerializeError> {
-        use crate::serialize::Serialize;
-        self.update
-            .ok_or(SerializeError::BufferTooSmall)?
-            .serialize_io(writer)
+        let mut buf = [0u8; MAX_UPDATE_SIZE];
+        let len = self.serialize(&mut buf)?;
+        writer
+            .write_all(&buf[..len])
+            .map_err(|_| SerializeError::WriteError)
     }
 }
 
@@ -330,6 +635,155 @@ impl Default for UpdateBuilder {
     }
 }
 
+/// Builder for a batch of update messages.
+///
+/// Created via [`MessageBuilder::multi_update()`].
+///
+/// Unlike [`UpdateBuilder`], every setter adds its own field; each field is
+/// serialized as a separate `U` message, in the order it was first set.
+/// Setting a field again replaces its value in place.
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone)]
+pub struct MultiUpdateBuilder {
+    /// One absolute update per field
+    updates: heapless::Vec<GamepadFieldUpdate, 7>,
+    trusted: bool,
+    active_low: bool,
+}
+
+#[cfg(feature = "heapless")]
+impl MultiUpdateBuilder {
+    /// Add a buttons change.
+    #[must_use]
+    pub fn buttons(self, buttons: Buttons) -> Self {
+        self.set(GamepadFieldUpdate::Buttons(buttons))
+    }
+
+    /// Add a left stick X change.
+    #[must_use]
+    pub fn left_stick_x(self, value: i16) -> Self {
+        self.set(GamepadFieldUpdate::LeftStickX(value))
+    }
+
+    /// Add a left stick Y change.
+    #[must_use]
+    pub fn left_stick_y(self, value: i16) -> Self {
+        self.set(GamepadFieldUpdate::LeftStickY(value))
+    }
+
+    /// Add a right stick X change.
+    #[must_use]
+    pub fn right_stick_x(self, value: i16) -> Self {
+        self.set(GamepadFieldUpdate::RightStickX(value))
+    }
+
+    /// Add a right stick Y change.
+    #[must_use]
+    pub fn right_stick_y(self, value: i16) -> Self {
+        self.set(GamepadFieldUpdate::RightStickY(value))
+    }
+
+    /// Add a left trigger change.
+    #[must_use]
+    pub fn left_trigger(self, value: u8) -> Self {
+        self.set(GamepadFieldUpdate::LeftTrigger(value))
+    }
+
+    /// Add a right trigger change.
+    #[must_use]
+    pub fn right_trigger(self, value: u8) -> Self {
+        self.set(GamepadFieldUpdate::RightTrigger(value))
+    }
+
+    /// Add every field that differs between `old` and `new`
+    /// (see [`GamepadState::diff`]).
+    #[must_use]
+    pub fn diff(self, old: &GamepadState, new: &GamepadState) -> Self {
+        old.diff(new).fold(self, Self::set)
+    }
+
+    /// Omit the `*<checksum>` suffix (trusted link mode).
+    ///
+    /// See [`UpdateBuilder::trusted`].
+    #[must_use]
+    pub fn trusted(mut self) -> Self {
+        self.trusted = true;
+        self
+    }
+
+    /// Send the button bitfield inverted (active-low receivers).
+    ///
+    /// See [`UpdateBuilder::active_low_buttons`].
+    #[must_use]
+    pub fn active_low_buttons(mut self) -> Self {
+        self.active_low = true;
+        self
+    }
+
+    /// Get the updates in serialization order.
+    pub fn updates(&self) -> &[GamepadFieldUpdate] {
+        &self.updates
+    }
+
+    /// Returns true if no field was set.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Serialize each update as its own `U` frame, back to back.
+    ///
+    /// As with [`MessageBuilder::update_many`], frames are written whole until
+    /// the next one no longer fits. Returns the total number of bytes written
+    /// and the number of frames; a frame count below
+    /// [`updates`](Self::updates)`.len()` means the buffer filled up.
+    #[must_use]
+    pub fn serialize(&self, buf: &mut [u8]) -> (usize, usize) {
+        let mut pos = 0;
+        let mut frames = 0;
+        for update in &self.updates {
+            let update = match *update {
+                GamepadFieldUpdate::Buttons(b) if self.active_low => {
+                    GamepadFieldUpdate::Buttons(!b)
+                }
+                update => update,
+            };
+            let mut frame = [0u8; MAX_UPDATE_SIZE];
+            let Ok(len) = serialize_update_with(&update, &mut frame, !self.trusted) else {
+                break;
+            };
+            let Some(dest) = buf.get_mut(pos..pos + len) else {
+                break;
+            };
+            dest.copy_from_slice(&frame[..len]);
+            pos += len;
+            frames += 1;
+        }
+        (pos, frames)
+    }
+
+    /// Replace the update of the same field, or append a new one.
+    fn set(mut self, update: GamepadFieldUpdate) -> Self {
+        let field = update.field_index();
+        match self.updates.iter_mut().find(|u| u.field_index() == field) {
+            Some(existing) => *existing = update,
+            // Only the seven absolute fields are ever added, so this cannot fail
+            None => {
+                let _ = self.updates.push(update);
+            }
+        }
+        self
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl Default for MultiUpdateBuilder {
+    fn default() -> Self {
+        MessageBuilder::multi_update()
+    }
+}
+
 /// Convenience function to quickly serialize a full state to a buffer.
 ///
 /// This is equivalent to `MessageBuilder::full_state()` with all the given values.
@@ -378,7 +832,7 @@ mod tests {
     extern crate std;
 
     use super::*;
-    use crate::parser::{parse, parse_message, ParsedMessage};
+    use crate::parser::{decode_stream, parse, parse_message, ParsedMessage};
 
     #[test]
     fn test_full_state_builder_default() {
@@ -581,4 +1035,456 @@ mod tests {
         // Only the last setter should be preserved
         assert_eq!(update, GamepadFieldUpdate::RightStickY(200));
     }
+
+    #[test]
+    fn test_full_state_builder_trusted_round_trip() {
+        use crate::parser::{parse_with, ChecksumMode, ParseOptions};
+
+        let mut buf = [0u8; 64];
+        let len = MessageBuilder::full_state()
+            .buttons(Buttons::A)
+            .left_stick(-1000, 2000)
+            .triggers(10, 20)
+            .trusted()
+            .serialize(&mut buf)
+            .unwrap();
+
+        assert_eq!(&buf[..len], b"G0001:-1000:2000:0:0:10:20\n");
+
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let parsed = parse_with(&buf[..len], &options).unwrap();
+        assert_eq!(parsed.buttons, Buttons::A);
+        assert_eq!(parsed.left_stick.x, -1000);
+        assert_eq!(parsed.left_stick.y, 2000);
+        assert_eq!(parsed.left_trigger, 10);
+        assert_eq!(parsed.right_trigger, 20);
+    }
+
+    #[test]
+    fn test_full_state_builder_layout_round_trip() {
+        use crate::parser::{parse_with, ParseOptions};
+        use crate::types::StateField;
+
+        let layout = FieldLayout::new([
+            StateField::Buttons,
+            StateField::LeftTrigger,
+            StateField::RightTrigger,
+            StateField::LeftX,
+            StateField::LeftY,
+            StateField::RightX,
+            StateField::RightY,
+        ])
+        .unwrap();
+        let builder = MessageBuilder::full_state()
+            .buttons(Buttons::A)
+            .left_stick(100, -100)
+            .triggers(64, 32)
+            .layout(layout);
+        let expected = builder.clone().build();
+
+        let mut buf = [0u8; 64];
+        let len = builder.serialize(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"G0001:64:32:100:-100:0:0*23\n");
+
+        let options = ParseOptions::new().layout(layout);
+        assert_eq!(parse_with(&buf[..len], &options), Ok(expected));
+    }
+
+    #[test]
+    fn test_update_builder_trusted_round_trip() {
+        use crate::parser::{parse_message_with, ChecksumMode, ParseOptions};
+
+        let mut buf = [0u8; 32];
+        let len = MessageBuilder::update()
+            .right_stick_y(-42)
+            .trusted()
+            .serialize(&mut buf)
+            .unwrap();
+
+        assert_eq!(&buf[..len], b"URY:-42\n");
+
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let parsed = parse_message_with(&buf[..len], &options).unwrap();
+        assert_eq!(
+            parsed,
+            ParsedMessage::Update(GamepadFieldUpdate::RightStickY(-42))
+        );
+
+        // Default (checksummed) parser rejects the trusted frame
+        assert!(parse_message(&buf[..len]).is_err());
+    }
+
+    #[test]
+    fn test_update_builder_trusted_serialize_fmt() {
+        let mut s = std::string::String::new();
+        MessageBuilder::update()
+            .left_trigger(7)
+            .trusted()
+            .serialize_fmt(&mut s)
+            .unwrap();
+
+        assert_eq!(s, "ULT:7\n");
+    }
+
+    #[test]
+    fn test_full_state_builder_serialize_slice() {
+        let builder = MessageBuilder::full_state()
+            .buttons(Buttons::B)
+            .right_stick(-300, 300)
+            .right_trigger(99);
+
+        let mut len_buf = [0u8; 64];
+        let len = builder.clone().serialize(&mut len_buf).unwrap();
+
+        let mut slice_buf = [0u8; 64];
+        let written = builder.serialize_slice(&mut slice_buf).unwrap();
+
+        assert_eq!(written, &len_buf[..len]);
+        assert!(written.ends_with(b"\n"));
+    }
+
+    #[test]
+    fn test_update_builder_serialize_slice() {
+        let builder = MessageBuilder::update().left_stick_y(-1234);
+
+        let mut len_buf = [0u8; 32];
+        let len = builder.clone().serialize(&mut len_buf).unwrap();
+
+        let mut slice_buf = [0u8; 32];
+        let written = builder.serialize_slice(&mut slice_buf).unwrap();
+
+        assert_eq!(written, &len_buf[..len]);
+        assert_eq!(
+            parse_message(written).unwrap(),
+            ParsedMessage::Update(GamepadFieldUpdate::LeftStickY(-1234))
+        );
+    }
+
+    #[test]
+    fn test_serialize_slice_errors() {
+        let mut small = [0u8; 8];
+        assert_eq!(
+            MessageBuilder::full_state().serialize_slice(&mut small),
+            Err(SerializeError::BufferTooSmall)
+        );
+
+        let mut buf = [0u8; 32];
+        assert!(MessageBuilder::update().serialize_slice(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_delta_single_trigger_change_yields_update() {
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            left_trigger: 77,
+            ..old
+        };
+
+        let mut buf = [0u8; 64];
+        let bytes = MessageBuilder::delta(&old, &new, &mut buf).unwrap();
+
+        assert_eq!(bytes[0], b'U');
+        assert_eq!(
+            parse_message(bytes).unwrap(),
+            ParsedMessage::Update(GamepadFieldUpdate::LeftTrigger(77))
+        );
+    }
+
+    #[test]
+    fn test_delta_two_changes_yield_two_updates() {
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            left_trigger: 10,
+            right_trigger: 20,
+            ..old
+        };
+
+        let mut buf = [0u8; 64];
+        let bytes = MessageBuilder::delta(&old, &new, &mut buf).unwrap();
+
+        let mut state = old;
+        let mut frames = 0;
+        for line in bytes.split_inclusive(|&b| b == b'\n') {
+            match parse_message(line).unwrap() {
+                ParsedMessage::Update(update) => state.apply_update(update),
+                other => panic!("expected update frame, got {other:?}"),
+            }
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+        assert_eq!(state, new);
+    }
+
+    #[test]
+    fn test_delta_three_changes_yield_full_state() {
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            buttons: Buttons::A,
+            left_trigger: 10,
+            right_trigger: 20,
+            ..old
+        };
+
+        let mut buf = [0u8; 64];
+        let bytes = MessageBuilder::delta(&old, &new, &mut buf).unwrap();
+
+        assert_eq!(bytes[0], b'G');
+        assert_eq!(parse(bytes).unwrap(), new);
+    }
+
+    #[test]
+    fn test_delta_no_change_is_empty() {
+        let state = GamepadState::neutral();
+        let mut buf = [0u8; 64];
+        let bytes = MessageBuilder::delta(&state, &state, &mut buf).unwrap();
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_update_many_round_trip() {
+        let updates = [
+            GamepadFieldUpdate::Buttons(Buttons::A | Buttons::START),
+            GamepadFieldUpdate::LeftStickX(-32768),
+            GamepadFieldUpdate::RightTrigger(255),
+        ];
+        let mut buf = [0u8; 3 * MAX_UPDATE_SIZE];
+        let (len, frames) = MessageBuilder::update_many(&updates, &mut buf);
+        assert_eq!(frames, 3);
+
+        let mut decoded = decode_stream(&buf[..len]);
+        for expected in updates {
+            assert_eq!(decoded.next(), Some(Ok(ParsedMessage::Update(expected))));
+        }
+        assert_eq!(decoded.next(), None);
+    }
+
+    #[test]
+    fn test_update_many_bounded_by_buffer() {
+        let updates = [
+            GamepadFieldUpdate::LeftTrigger(1),
+            GamepadFieldUpdate::LeftTrigger(2),
+            GamepadFieldUpdate::LeftTrigger(3),
+        ];
+        let mut one = [0u8; 32];
+        let (len, _) = MessageBuilder::update_many(&updates[..1], &mut one);
+
+        // Room for two frames and part of a third
+        let mut buf = [0u8; 64];
+        let (written, frames) = MessageBuilder::update_many(&updates, &mut buf[..2 * len + 3]);
+        assert_eq!(frames, 2);
+        assert_eq!(written, 2 * len);
+        assert_eq!(decode_stream(&buf[..written]).count(), 2);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_multi_update_preserves_order() {
+        let mut buf = [0u8; 128];
+        let builder = MessageBuilder::multi_update()
+            .right_trigger(9)
+            .buttons(Buttons::X)
+            .left_stick_y(-300);
+        let (len, frames) = builder.serialize(&mut buf);
+        assert_eq!(frames, 3);
+
+        let mut decoded = decode_stream(&buf[..len]);
+        for expected in [
+            GamepadFieldUpdate::RightTrigger(9),
+            GamepadFieldUpdate::Buttons(Buttons::X),
+            GamepadFieldUpdate::LeftStickY(-300),
+        ] {
+            assert_eq!(decoded.next(), Some(Ok(ParsedMessage::Update(expected))));
+        }
+        assert_eq!(decoded.next(), None);
+        assert!(MessageBuilder::multi_update().is_empty());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_multi_update_duplicate_field_last_wins() {
+        let builder = MessageBuilder::multi_update()
+            .left_trigger(1)
+            .right_trigger(2)
+            .left_trigger(3);
+        assert_eq!(
+            builder.updates(),
+            [
+                GamepadFieldUpdate::LeftTrigger(3),
+                GamepadFieldUpdate::RightTrigger(2),
+            ]
+        );
+
+        // Replaying a diff covers every changed field once
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            buttons: Buttons::B,
+            left_trigger: 50,
+            ..old
+        };
+        let builder = MessageBuilder::multi_update()
+            .left_trigger(9)
+            .diff(&old, &new);
+        assert_eq!(
+            builder.updates(),
+            [
+                GamepadFieldUpdate::LeftTrigger(50),
+                GamepadFieldUpdate::Buttons(Buttons::B),
+            ]
+        );
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_multi_update_buffer_too_small() {
+        let builder = MessageBuilder::multi_update()
+            .left_stick_x(-32768)
+            .right_stick_x(-32768)
+            .trusted();
+        // ULX:-32768\n is 11 bytes: one frame fits, the second does not
+        let mut buf = [0u8; 20];
+        let (len, frames) = builder.serialize(&mut buf);
+        assert_eq!((len, frames), (11, 1));
+        assert_eq!(&buf[..len], b"ULX:-32768\n");
+
+        assert_eq!(builder.serialize(&mut []), (0, 0));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_append_updates_round_trip() {
+        let updates = [
+            GamepadFieldUpdate::Buttons(Buttons::A),
+            GamepadFieldUpdate::LeftStickY(-1234),
+            GamepadFieldUpdate::LeftTrigger(77),
+        ];
+        let mut vec = heapless::Vec::<u8, 64>::new();
+        MessageBuilder::append_updates(&mut vec, &updates).unwrap();
+
+        let mut decoded = decode_stream(&vec);
+        for expected in updates {
+            assert_eq!(decoded.next(), Some(Ok(ParsedMessage::Update(expected))));
+        }
+        assert_eq!(decoded.next(), None);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_append_updates_keeps_complete_frames() {
+        let updates = [GamepadFieldUpdate::LeftStickX(-32768); 3];
+        // ULX:-32768*CC\n is 14 bytes: two frames fit, the third does not
+        let mut vec = heapless::Vec::<u8, 40>::new();
+        assert_eq!(
+            MessageBuilder::append_updates(&mut vec, &updates),
+            Err(SerializeError::BufferTooSmall)
+        );
+        assert_eq!(vec.len(), 28);
+        assert_eq!(decode_stream(&vec).count(), 2);
+        assert!(decode_stream(&vec).all(|msg| msg.is_ok()));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_append_batch() {
+        let state = GamepadState {
+            right_trigger: 9,
+            ..GamepadState::neutral()
+        };
+        let updates = [GamepadFieldUpdate::ButtonsSet(Buttons::B)];
+        let mut vec = heapless::Vec::<u8, 64>::new();
+        MessageBuilder::append_batch(&mut vec, &state, &updates).unwrap();
+
+        let mut decoded = decode_stream(&vec);
+        assert_eq!(decoded.next(), Some(Ok(ParsedMessage::FullState(state))));
+        assert_eq!(decoded.next(), Some(Ok(ParsedMessage::Update(updates[0]))));
+        assert_eq!(decoded.next(), None);
+
+        // Not even the full state fits
+        let mut small = heapless::Vec::<u8, 16>::new();
+        assert_eq!(
+            MessageBuilder::append_batch(&mut small, &state, &updates),
+            Err(SerializeError::BufferTooSmall)
+        );
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn test_update_many_empty() {
+        let mut buf = [0u8; 8];
+        assert_eq!(MessageBuilder::update_many(&[], &mut buf), (0, 0));
+    }
+
+    #[test]
+    fn test_active_low_buttons_round_trip() {
+        use crate::parser::{parse_message_with, parse_with, ParseOptions};
+
+        let mut buf = [0u8; 64];
+        let len = MessageBuilder::full_state()
+            .buttons(Buttons::A)
+            .active_low_buttons()
+            .serialize(&mut buf)
+            .unwrap();
+        assert!(buf[..len].starts_with(b"GFFFE:0:0:0:0:0:0*"));
+
+        let options = ParseOptions::new().active_low_buttons(true);
+        let state = parse_with(&buf[..len], &options).unwrap();
+        assert_eq!(state.buttons, Buttons::A);
+
+        let len = MessageBuilder::update()
+            .buttons(Buttons::NONE)
+            .active_low_buttons()
+            .serialize(&mut buf)
+            .unwrap();
+        assert!(buf[..len].starts_with(b"UB:FFFF*"));
+        assert_eq!(
+            parse_message_with(&buf[..len], &options),
+            Ok(ParsedMessage::Update(GamepadFieldUpdate::Buttons(
+                Buttons::NONE
+            )))
+        );
+
+        // Non-button updates are unaffected
+        let len = MessageBuilder::update()
+            .left_trigger(5)
+            .active_low_buttons()
+            .serialize(&mut buf)
+            .unwrap();
+        assert!(buf[..len].starts_with(b"ULT:5*"));
+    }
+
+    #[test]
+    fn test_partial_button_updates_round_trip() {
+        let mut buf = [0u8; MAX_UPDATE_SIZE];
+        let mut state = GamepadState {
+            buttons: Buttons::B,
+            ..GamepadState::neutral()
+        };
+
+        let len = MessageBuilder::update()
+            .press(Buttons::A)
+            .serialize(&mut buf)
+            .unwrap();
+        assert!(buf[..len].starts_with(b"UB+:0001*"));
+        match parse_message(&buf[..len]) {
+            Ok(ParsedMessage::Update(update)) => {
+                assert_eq!(update, GamepadFieldUpdate::ButtonsSet(Buttons::A));
+                state.apply_update(update);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(state.buttons, Buttons::A | Buttons::B);
+
+        let len = MessageBuilder::update()
+            .release(Buttons::A)
+            .serialize(&mut buf)
+            .unwrap();
+        assert!(buf[..len].starts_with(b"UB-:0001*"));
+        match parse_message(&buf[..len]) {
+            Ok(ParsedMessage::Update(update)) => {
+                assert_eq!(update, GamepadFieldUpdate::ButtonsClear(Buttons::A));
+                state.apply_update(update);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(state.buttons, Buttons::B);
+    }
 }
@@ -1,6 +1,8 @@
 //! CRC-8 checksum for protocol messages.
 //!
 //! Uses CRC-8/SMBUS algorithm with a 256-byte lookup table for fast calculation.
+//! Parameters: polynomial 0x07, initial value 0x00, input and output not
+//! reflected, no final XOR. Both the parser and the serializer use it.
 
 use crc::{Crc, CRC_8_SMBUS};
 
@@ -72,6 +74,38 @@ mod tests {
         assert_eq!(calculate_crc8(&[0x00]), 0x00);
     }
 
+    #[test]
+    fn test_crc8_check_value() {
+        // Standard catalogue check value for CRC-8/SMBUS
+        assert_eq!(calculate_crc8(b"123456789"), 0xF4);
+    }
+
+    #[test]
+    fn test_crc8_matches_bitwise_reference() {
+        // Plain shift-register implementation, as a host tool would write it
+        fn reference(data: &[u8]) -> u8 {
+            let mut crc = 0u8;
+            for &byte in data {
+                crc ^= byte;
+                for _ in 0..8 {
+                    crc = if crc & 0x80 != 0 {
+                        (crc << 1) ^ 0x07
+                    } else {
+                        crc << 1
+                    };
+                }
+            }
+            crc
+        }
+
+        for data in [&b"0001:100:-100:0:0:64:32"[..], b"LX:5000", b"B+:0010"] {
+            assert_eq!(calculate_crc8(data), reference(data));
+        }
+        // An XOR fold gives a different checksum for the same payload
+        let xor = b"0001:100:-100:0:0:64:32".iter().fold(0, |acc, b| acc ^ b);
+        assert_ne!(calculate_crc8(b"0001:100:-100:0:0:64:32"), xor);
+    }
+
     #[test]
     fn test_crc8_digest_matches_batch() {
         let data = b"0000:0:0:0:0:0:0";
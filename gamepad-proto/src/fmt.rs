@@ -126,6 +126,31 @@ pub fn write_u8(buf: &mut [u8], value: u8) -> usize {
     len
 }
 
+/// Number of bytes [`write_i16`] writes for `value` (1-6).
+#[inline]
+pub const fn i16_len(value: i16) -> usize {
+    let sign = if value < 0 { 1 } else { 0 };
+    sign + u16_digits(value.unsigned_abs())
+}
+
+/// Number of bytes [`write_u8`] writes for `value` (1-3).
+#[inline]
+pub const fn u8_len(value: u8) -> usize {
+    u16_digits(value as u16)
+}
+
+/// Number of decimal digits in `value` (1-5).
+#[inline]
+const fn u16_digits(value: u16) -> usize {
+    match value {
+        0..=9 => 1,
+        10..=99 => 2,
+        100..=999 => 3,
+        1000..=9999 => 4,
+        _ => 5,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,4 +234,28 @@ mod tests {
         let len = write_u8(&mut buf, 64);
         assert_eq!(&buf[..len], b"64");
     }
+
+    #[test]
+    fn test_decimal_len_matches_written() {
+        let mut buf = [0u8; 6];
+        for value in [
+            0,
+            1,
+            -1,
+            9,
+            10,
+            -10,
+            99,
+            100,
+            9999,
+            10000,
+            i16::MAX,
+            i16::MIN,
+        ] {
+            assert_eq!(i16_len(value), write_i16(&mut buf, value), "{value}");
+        }
+        for value in [0, 9, 10, 99, 100, u8::MAX] {
+            assert_eq!(u8_len(value), write_u8(&mut buf, value), "{value}");
+        }
+    }
 }
@@ -4,22 +4,44 @@
 //!
 //! - **Types**: Core data structures for representing gamepad state
 //!   - [`Buttons`] - Button state bitfield
+//!   - [`ButtonEvent`] - Press/release transition from [`Buttons::edges`]
 //!   - [`AnalogStick`] - Analog stick X/Y position
 //!   - [`GamepadState`] - Complete gamepad snapshot
 //!   - [`GamepadFieldUpdate`] - Single field update for incremental messages
+//!   - [`StickTrim`] - Runtime stick center trim set by `C` messages
+//!   - `NormalizedState` - Float view for host visualization (`std` only)
 //!
 //! - **Parsing**: Parse incoming protocol messages
 //!   - [`parse()`] - Parse a full state message
 //!   - [`parse_message()`] - Parse any message type
+//!   - [`parse_with()`] / [`parse_message_with()`] - Parse with [`ParseOptions`]
+//!   - [`parse_message_resync()`] - Parse past leading garbage from a mid-frame join
+//!   - [`parse_fields()`] - Borrow the raw fields of a full state message
+//!   - [`decode_stream()`] - Iterate over the messages in a multi-frame buffer
 //!   - [`ParsedMessage`] - Result of parsing
+//!   - [`LineAccumulator`] - Byte-at-a-time line buffering for streaming input
 //!
 //! - **Serialization**: Serialize outgoing protocol messages
 //!   - [`Serialize`] trait - Extension trait for serialization
 //!   - [`MessageBuilder`] - Fluent builder API
+//!   - [`NEUTRAL_FRAME`] - Ready-made neutral full state frame
+//!   - [`FullStateFrame`] - Fixed-size full state frame from [`GamepadState::to_frame()`]
+//!
+//! - **Binary frames**: Compact `0xA5`-synced framing (see [`binary`])
+//!   - [`BinaryParser`] - Resynchronizing byte-at-a-time frame parser
+//!   - [`encode_binary_state()`] / [`decode_binary_state()`] - Full state payloads
+//!
+//! - **Transforms**: Report-ready analog processing
+//!   - [`Transforms`] - Calibration, deadzone, expo and clamp configuration
+//!   - [`GamepadState::canonicalize()`] - Apply the pipeline in a fixed order
 //!
 //! # Protocol Format
 //!
-//! The protocol uses ASCII text messages with CRC-8/SMBUS checksums.
+//! The protocol uses ASCII text messages with CRC-8/SMBUS checksums
+//! (polynomial 0x07, initial value 0x00, no reflection, no final XOR; the
+//! check value of `123456789` is 0xF4). The parser and the serializer both
+//! use [`calculate_crc8`], so host tools must compute the same CRC over the
+//! bytes between the prefix letter and `*`, not an XOR of the bytes.
 //!
 //! ## Full State Message
 //!
@@ -40,7 +62,34 @@
 //! U<field>:<value>*<checksum>\n
 //! ```
 //!
-//! Fields: `B` (buttons hex), `LX`, `LY`, `RX`, `RY` (i16), `LT`, `RT` (u8)
+//! Fields: `B` (buttons hex), `LX`, `LY`, `RX`, `RY` (i16), `LT`, `RT` (u8).
+//! `B+` / `B-` press or release only the buttons set in the hex mask.
+//! `LX+`, `LY+`, `RX+`, `RY+` add the i16 value to the current stick axis,
+//! saturating at its limits (for relative, mouse-like input).
+//!
+//! ## Stick Trim Message
+//!
+//! ```text
+//! C<axis>:<offset>*<checksum>\n
+//! ```
+//!
+//! Axes: `LX`, `LY`, `RX`, `RY`. Input sources subtract the i16 offset from
+//! subsequent readings of that axis (see [`StickTrim`]).
+//!
+//! ## Hold Message
+//!
+//! ```text
+//! H<0|1>*<checksum>\n
+//! ```
+//!
+//! `H1` freezes the state reported by input sources (incoming frames are
+//! still consumed), `H0` releases it.
+//!
+//! ## Trusted Link Mode
+//!
+//! On reliable direct connections the `*<checksum>` suffix may be omitted
+//! (e.g. `G0001:0:0:0:0:0:0\n`). Serialize with the builder's `trusted()` flag
+//! and parse with [`ChecksumMode::Omitted`]. Checksummed mode is the default.
 //!
 //! # Examples
 //!
@@ -49,12 +98,13 @@
 //! ```
 //! use gamepad_proto::{parse_message, ParsedMessage, GamepadState};
 //!
-//! // Parse a full state message (with valid checksum)
-//! let msg = b"G0001:100:-100:0:0:64:32*54\n";
-//! if let Ok(ParsedMessage::FullState(state)) = parse_message(msg) {
-//!     assert!(state.buttons.is_pressed(gamepad_proto::Buttons::A));
-//!     assert_eq!(state.left_stick.x, 100);
-//! }
+//! // Parse a full state message (EA is the CRC-8 of `0001:...:32`)
+//! let msg = b"G0001:100:-100:0:0:64:32*EA\n";
+//! let Ok(ParsedMessage::FullState(state)) = parse_message(msg) else {
+//!     panic!("valid message");
+//! };
+//! assert!(state.buttons.is_pressed(gamepad_proto::Buttons::A));
+//! assert_eq!(state.left_stick.x, 100);
 //! ```
 //!
 //! ## Serializing with the Serialize Trait
@@ -96,10 +146,17 @@
 //!
 //! # Features
 //!
-//! - **`std`**: Enable standard library support (for host testing)
+//! - **`std`**: Enable standard library support (for host testing) and
+//!   `GamepadState::to_normalized()` / `from_normalized()`
 //! - **`defmt`**: Enable defmt formatting (for embedded logging)
-//! - **`heapless`**: Enable `serialize_to_vec()` methods
+//! - **`heapless`**: Enable `serialize_to_vec()` methods and batched
+//!   `MessageBuilder::append_updates()` / `append_batch()`
+//! - **`alloc`**: Enable `GamepadState::serialize_vec()` (implied by `std`)
 //! - **`embedded-io`**: Enable `serialize_io()` methods for I/O peripherals
+//! - **`test-vectors`**: Expose canonical encoded messages in `vectors` for
+//!   checking other sender implementations
+//! - **`serde`**: Derive `Serialize`/`Deserialize` for `Buttons` (as the raw
+//!   bitfield), `AnalogStick`, `GamepadState` and `GamepadFieldUpdate`
 //!
 //! # No-std Support
 //!
@@ -111,16 +168,47 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod accumulator;
+pub mod binary;
 pub mod builder;
 pub mod crc;
 mod fmt;
 pub mod parser;
 pub mod serialize;
+pub mod transform;
 pub mod types;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod vectors;
 
 // Re-export types at crate root for convenience
-pub use builder::{serialize_full_state, FullStateBuilder, MessageBuilder, UpdateBuilder};
+pub use accumulator::LineAccumulator;
+pub use binary::{
+    decode_binary_state, encode_binary_frame, encode_binary_state, BinaryFrame, BinaryParser,
+    BINARY_STATE_SIZE, BINARY_SYNC, MAX_BINARY_FRAME_SIZE, MAX_BINARY_PAYLOAD,
+};
+#[cfg(feature = "heapless")]
+pub use builder::MultiUpdateBuilder;
+pub use builder::{
+    serialize_full_state, FullStateBuilder, MessageBuilder, UpdateBuilder, MAX_DELTA_UPDATES,
+};
 pub use crc::{calculate_crc8, Crc8Digest};
-pub use parser::{parse, parse_message, ParseError, ParsedMessage, MAX_LINE_LENGTH};
-pub use serialize::{Serialize, SerializeError, MAX_FULL_STATE_SIZE, MAX_UPDATE_SIZE};
-pub use types::{AnalogStick, Buttons, GamepadFieldUpdate, GamepadState};
+pub use parser::{
+    decode_stream, parse, parse_fields, parse_message, parse_message_detailed,
+    parse_message_detailed_with, parse_message_resync, parse_message_with, parse_with,
+    ChecksumMode, DecodeStream, DetailedParseError, FullStateFields, ParseError, ParseErrorKind,
+    ParseOptions, ParsedMessage, MAX_LINE_LENGTH,
+};
+pub use serialize::{
+    FullStateFrame, Serialize, SerializeError, MAX_FULL_STATE_SIZE, MAX_UPDATE_SIZE, NEUTRAL_FRAME,
+};
+pub use transform::{split_axis_to_triggers, stick_to_dpad, Transforms};
+pub use types::{
+    AnalogStick, ButtonEdges, ButtonEvent, Buttons, Dpad, DpadResolution, FieldLayout,
+    GamepadFieldUpdate, GamepadState, PressedButtons, StateField, StickAxis, StickTrim,
+};
+
+#[cfg(feature = "std")]
+pub use types::NormalizedState;
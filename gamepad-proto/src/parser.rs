@@ -1,13 +1,24 @@
 //! UART protocol parser for gamepad messages.
 //!
-//! Supports two message types:
+//! Supports four message types:
 //! - Full state (G prefix): `G<buttons>:<lx>:<ly>:<rx>:<ry>:<lt>:<rt>*<checksum>\n`
 //! - Update (U prefix): `U<field>:<value>*<checksum>\n`
+//! - Trim (C prefix): `C<axis>:<offset>*<checksum>\n`
+//! - Hold (H prefix): `H<0|1>*<checksum>\n`
+//!
+//! On reliable direct connections the `*<checksum>` suffix can be omitted
+//! ("trusted link" mode) by parsing with [`ParseOptions`] and
+//! [`ChecksumMode::Omitted`].
 
 use crate::crc::calculate_crc8;
-use crate::types::{AnalogStick, Buttons, GamepadFieldUpdate, GamepadState};
+use crate::types::{
+    AnalogStick, Buttons, FieldLayout, GamepadFieldUpdate, GamepadState, StateField, StickAxis,
+};
 
 /// Maximum line length for the protocol (including newline).
+///
+/// Default buffer size of [`LineAccumulator`](crate::LineAccumulator); the
+/// parse functions accept lines of any length.
 pub const MAX_LINE_LENGTH: usize = 64;
 
 /// Minimum valid full state message length: G0000:0:0:0:0:0:0*XX = 20 chars
@@ -16,6 +27,149 @@ const MIN_FULL_STATE_LEN: usize = 20;
 /// Minimum valid update message length: UB:0*XX = 7 chars
 const MIN_UPDATE_LEN: usize = 7;
 
+/// Minimum valid trim message length: CLX:0*XX = 8 chars
+const MIN_TRIM_LEN: usize = 8;
+
+/// Minimum valid hold message length: H1*XX = 5 chars
+const MIN_HOLD_LEN: usize = 5;
+
+/// Length of the `*XX` checksum suffix.
+const CHECKSUM_SUFFIX_LEN: usize = 3;
+
+/// How the `*<checksum>` suffix of a message is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChecksumMode {
+    /// Checksum must be present and valid (default).
+    #[default]
+    Required,
+    /// Checksum must be absent ("trusted link" mode).
+    ///
+    /// Frames look like `G0001:0:0:0:0:0:0\n`. Use only on reliable direct
+    /// connections where corruption is not a concern.
+    Omitted,
+    /// Accept frames with or without a checksum; verify it when present.
+    Optional,
+}
+
+/// Options controlling how protocol messages are parsed.
+///
+/// The default options match [`parse()`] and [`parse_message()`].
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{parse_message_with, ChecksumMode, ParseOptions, ParsedMessage};
+///
+/// let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+/// let msg = parse_message_with(b"G0001:0:0:0:0:0:0\n", &options).unwrap();
+/// assert!(matches!(msg, ParsedMessage::FullState(_)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseOptions {
+    /// Checksum handling mode.
+    pub checksum: ChecksumMode,
+    /// Sender reports buttons active-low (bit set = released).
+    ///
+    /// The parsed bitfield is inverted so downstream logic stays active-high.
+    pub active_low_buttons: bool,
+    /// Accept 1 to 4 hex digits for button fields (zero-extended).
+    ///
+    /// Off by default: strict mode requires exactly 4 digits, so every frame
+    /// for a given state is byte-identical.
+    pub lenient_button_width: bool,
+    /// Order of the fields in full state messages.
+    pub layout: FieldLayout,
+    /// Stick axes whose absolute updates (`LX`, ...) are applied as deltas,
+    /// indexed by [`StickAxis`].
+    ///
+    /// For senders of relative, mouse-like input that reuse the absolute
+    /// field tokens. The explicit delta tokens (`LX+`, ...) are always relative.
+    pub relative_axes: [bool; 4],
+}
+
+impl ParseOptions {
+    /// Create the default options (checksum required).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            checksum: ChecksumMode::Required,
+            active_low_buttons: false,
+            lenient_button_width: false,
+            layout: FieldLayout::DEFAULT,
+            relative_axes: [false; 4],
+        }
+    }
+
+    /// Set the checksum handling mode.
+    #[must_use]
+    pub const fn checksum(mut self, mode: ChecksumMode) -> Self {
+        self.checksum = mode;
+        self
+    }
+
+    /// Treat the button bitfield as active-low (see [`Self::active_low_buttons`]).
+    #[must_use]
+    pub const fn active_low_buttons(mut self, active_low: bool) -> Self {
+        self.active_low_buttons = active_low;
+        self
+    }
+
+    /// Accept short button fields such as `G1:...` (see [`Self::lenient_button_width`]).
+    #[must_use]
+    pub const fn lenient_button_width(mut self, lenient: bool) -> Self {
+        self.lenient_button_width = lenient;
+        self
+    }
+
+    /// Expect full state fields in the given order (see [`FieldLayout`]).
+    #[must_use]
+    pub const fn layout(mut self, layout: FieldLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Apply absolute updates of `axis` as deltas (see [`Self::relative_axes`]).
+    #[must_use]
+    pub const fn relative_axis(mut self, axis: StickAxis, relative: bool) -> Self {
+        self.relative_axes[axis as usize] = relative;
+        self
+    }
+
+    /// Build the update for an absolute stick field according to the
+    /// relative axis setting.
+    #[inline]
+    const fn stick(&self, axis: StickAxis, value: i16) -> GamepadFieldUpdate {
+        if self.relative_axes[axis as usize] {
+            GamepadFieldUpdate::stick_delta(axis, value)
+        } else {
+            GamepadFieldUpdate::stick(axis, value)
+        }
+    }
+
+    /// Parse a button bitfield field according to the width setting.
+    #[inline]
+    fn button_bits(&self, s: &[u8]) -> Result<u16, DetailedParseError> {
+        if self.lenient_button_width && (1..4).contains(&s.len()) {
+            // Fewer digits are implicitly zero-extended
+            parse_hex_digits(s)
+        } else {
+            parse_hex_u16(s)
+        }
+    }
+
+    /// Convert a received button bitfield to active-high.
+    #[inline]
+    const fn buttons(&self, raw: u16) -> Buttons {
+        if self.active_low_buttons {
+            Buttons(!raw)
+        } else {
+            Buttons(raw)
+        }
+    }
+}
+
 /// Error type for parsing protocol messages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -24,6 +178,105 @@ pub enum ParseError {
     Parse,
     /// Checksum verification failed
     Checksum,
+    /// Line exceeded [`MAX_LINE_LENGTH`] before a newline was received
+    Overflow,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "invalid message format"),
+            Self::Checksum => write!(f, "checksum mismatch"),
+            Self::Overflow => write!(f, "line too long"),
+        }
+    }
+}
+
+/// Why a message failed to parse, see [`DetailedParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParseErrorKind {
+    /// First byte is not a known message prefix
+    BadPrefix,
+    /// Message ends before all fields were received
+    MissingField,
+    /// Full state message has more than seven fields
+    TooManyFields,
+    /// Update field, trim axis or hold value is not recognized
+    UnknownField,
+    /// Invalid hex digit, or wrong number of hex digits
+    BadHex,
+    /// Invalid decimal number
+    BadInt,
+    /// Decimal number out of range for its field
+    IntRange,
+    /// Checksum suffix absent or truncated
+    MissingChecksum,
+    /// Checksum present although [`ChecksumMode::Omitted`] was requested
+    UnexpectedChecksum,
+    /// Checksum does not match the payload
+    ChecksumMismatch,
+}
+
+impl core::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::BadPrefix => "unknown message prefix",
+            Self::MissingField => "missing field",
+            Self::TooManyFields => "too many fields",
+            Self::UnknownField => "unknown field",
+            Self::BadHex => "invalid hex number",
+            Self::BadInt => "invalid decimal number",
+            Self::IntRange => "number out of range",
+            Self::MissingChecksum => "missing checksum",
+            Self::UnexpectedChecksum => "unexpected checksum",
+            Self::ChecksumMismatch => "checksum mismatch",
+        })
+    }
+}
+
+/// Parse error with the reason and the position in the line where parsing
+/// failed, returned by [`parse_message_detailed()`].
+///
+/// Meant for host tools debugging a sender. Converts into the lossy
+/// [`ParseError`] used everywhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DetailedParseError {
+    /// Why parsing failed
+    pub kind: ParseErrorKind,
+    /// Byte offset into the line of the offending byte (the line length if
+    /// the line ended too early)
+    pub offset: usize,
+}
+
+impl DetailedParseError {
+    /// Create an error of `kind` at byte `offset`.
+    #[must_use]
+    pub const fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+
+    /// Move the offset from a field to the line containing it.
+    #[inline]
+    const fn shifted(self, by: usize) -> Self {
+        Self::new(self.kind, self.offset + by)
+    }
+}
+
+impl core::fmt::Display for DetailedParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl From<DetailedParseError> for ParseError {
+    fn from(err: DetailedParseError) -> Self {
+        match err.kind {
+            ParseErrorKind::ChecksumMismatch => ParseError::Checksum,
+            _ => ParseError::Parse,
+        }
+    }
 }
 
 /// Parsed message - either a full gamepad state or an incremental update.
@@ -34,6 +287,10 @@ pub enum ParsedMessage {
     FullState(GamepadState),
     /// Single field update (U prefix)
     Update(GamepadFieldUpdate),
+    /// Stick center trim offset for one axis (C prefix)
+    Trim(StickAxis, i16),
+    /// Engage (`true`) or release (`false`) input hold (H prefix)
+    Hold(bool),
 }
 
 /// Parse a complete line into a [`GamepadState`].
@@ -66,46 +323,149 @@ pub enum ParsedMessage {
 /// This represents: A button pressed, sticks centered, triggers at 0.
 #[inline]
 pub fn parse(line: &[u8]) -> Result<GamepadState, ParseError> {
-    parse_full_state(strip_line_ending(line))
+    parse_with(line, &ParseOptions::new())
 }
 
-/// Internal parser for full gamepad state (assumes line endings already stripped).
-#[allow(clippy::similar_names)] // lx/ly/rx/ry/lt/rt are intentionally similar (protocol fields)
-fn parse_full_state(line: &[u8]) -> Result<GamepadState, ParseError> {
+/// Parse a complete line into a [`GamepadState`] using the given options.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Parse`] if the message format is invalid, or if the
+/// presence of a checksum does not match [`ParseOptions::checksum`].
+/// Returns [`ParseError::Checksum`] if the checksum verification fails.
+#[inline]
+pub fn parse_with(line: &[u8], options: &ParseOptions) -> Result<GamepadState, ParseError> {
+    Ok(parse_full_state(strip_line_ending(line), options)?)
+}
+
+/// Raw field slices of a full state message, before numeric conversion.
+///
+/// Returned by [`parse_fields()`]. Every slice borrows from the input line,
+/// so inspecting a frame (e.g. in a protocol analyzer) copies nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FullStateFields<'a> {
+    /// Button bitfield hex digits
+    pub buttons: &'a [u8],
+    /// Left stick X decimal digits
+    pub left_x: &'a [u8],
+    /// Left stick Y decimal digits
+    pub left_y: &'a [u8],
+    /// Right stick X decimal digits
+    pub right_x: &'a [u8],
+    /// Right stick Y decimal digits
+    pub right_y: &'a [u8],
+    /// Left trigger decimal digits
+    pub left_trigger: &'a [u8],
+    /// Right trigger decimal digits
+    pub right_trigger: &'a [u8],
+}
+
+/// Split a full state message into its raw fields without converting them.
+///
+/// The prefix, field count and checksum are verified like [`parse()`]; the
+/// field contents are not.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Parse`] if the message is not a full state message
+/// with exactly seven fields.
+/// Returns [`ParseError::Checksum`] if the checksum verification fails.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::parse_fields;
+///
+/// let fields = parse_fields(b"G0001:100:-100:0:0:64:32*EA\n").unwrap();
+/// assert_eq!(fields.buttons, b"0001");
+/// assert_eq!(fields.left_y, b"-100");
+/// ```
+#[inline]
+pub fn parse_fields(line: &[u8]) -> Result<FullStateFields<'_>, ParseError> {
+    Ok(split_full_state(
+        strip_line_ending(line),
+        &ParseOptions::new(),
+    )?)
+}
+
+/// Split a full state message into raw fields (assumes line endings already stripped).
+fn split_full_state<'a>(
+    line: &'a [u8],
+    options: &ParseOptions,
+) -> Result<FullStateFields<'a>, DetailedParseError> {
     // Must start with 'G'
     if line.first() != Some(&b'G') {
-        return Err(ParseError::Parse);
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0));
     }
 
+    // Lenient button width allows down to a single hex digit
+    let min_len = if options.lenient_button_width {
+        MIN_FULL_STATE_LEN - 3
+    } else {
+        MIN_FULL_STATE_LEN
+    };
+
     // Extract and verify checksum
-    let payload = extract_verified_payload(line, MIN_FULL_STATE_LEN)?;
+    let payload = extract_payload(line, min_len, options.checksum)?;
 
-    // Parse payload: buttons:lx:ly:rx:ry:lt:rt
+    // Split payload in layout order (default buttons:lx:ly:rx:ry:lt:rt)
     let mut parts = payload.split(|&b| b == b':');
+    let mut fields = FullStateFields {
+        buttons: &[],
+        left_x: &[],
+        left_y: &[],
+        right_x: &[],
+        right_y: &[],
+        left_trigger: &[],
+        right_trigger: &[],
+    };
 
-    let buttons_str = parts.next().ok_or(ParseError::Parse)?;
-    let lx_str = parts.next().ok_or(ParseError::Parse)?;
-    let ly_str = parts.next().ok_or(ParseError::Parse)?;
-    let rx_str = parts.next().ok_or(ParseError::Parse)?;
-    let ry_str = parts.next().ok_or(ParseError::Parse)?;
-    let lt_str = parts.next().ok_or(ParseError::Parse)?;
-    let rt_str = parts.next().ok_or(ParseError::Parse)?;
+    for field in options.layout.order() {
+        let part = parts.next().ok_or(DetailedParseError::new(
+            ParseErrorKind::MissingField,
+            offset_in(line, payload) + payload.len(),
+        ))?;
+        let slot = match field {
+            StateField::Buttons => &mut fields.buttons,
+            StateField::LeftX => &mut fields.left_x,
+            StateField::LeftY => &mut fields.left_y,
+            StateField::RightX => &mut fields.right_x,
+            StateField::RightY => &mut fields.right_y,
+            StateField::LeftTrigger => &mut fields.left_trigger,
+            StateField::RightTrigger => &mut fields.right_trigger,
+        };
+        *slot = part;
+    }
 
-    // Should have no more parts
-    if parts.next().is_some() {
-        return Err(ParseError::Parse);
+    // Should have no more parts (point at the separator before the first extra)
+    if let Some(extra) = parts.next() {
+        return Err(DetailedParseError::new(
+            ParseErrorKind::TooManyFields,
+            offset_in(line, extra) - 1,
+        ));
     }
 
-    let buttons = parse_hex_u16(buttons_str)?;
-    let lx = parse_i16(lx_str)?;
-    let ly = parse_i16(ly_str)?;
-    let rx = parse_i16(rx_str)?;
-    let ry = parse_i16(ry_str)?;
-    let lt = parse_u8(lt_str)?;
-    let rt = parse_u8(rt_str)?;
+    Ok(fields)
+}
+
+/// Internal parser for full gamepad state (assumes line endings already stripped).
+#[allow(clippy::similar_names)] // lx/ly/rx/ry/lt/rt are intentionally similar (protocol fields)
+fn parse_full_state(
+    line: &[u8],
+    options: &ParseOptions,
+) -> Result<GamepadState, DetailedParseError> {
+    let fields = split_full_state(line, options)?;
+
+    let buttons = in_line(line, fields.buttons, |s| options.button_bits(s))?;
+    let lx = in_line(line, fields.left_x, parse_i16)?;
+    let ly = in_line(line, fields.left_y, parse_i16)?;
+    let rx = in_line(line, fields.right_x, parse_i16)?;
+    let ry = in_line(line, fields.right_y, parse_i16)?;
+    let lt = in_line(line, fields.left_trigger, parse_u8)?;
+    let rt = in_line(line, fields.right_trigger, parse_u8)?;
 
     Ok(GamepadState {
-        buttons: Buttons(buttons),
+        buttons: options.buttons(buttons),
         left_stick: AnalogStick::new(lx, ly),
         right_stick: AnalogStick::new(rx, ry),
         left_trigger: lt,
@@ -118,6 +478,10 @@ fn parse_full_state(line: &[u8]) -> Result<GamepadState, ParseError> {
 /// Dispatches based on the message prefix:
 /// - `G` - Full gamepad state
 /// - `U` - Single field update
+/// - `C` - Stick center trim
+/// - `H` - Input hold
+///
+/// One leading `\r` before the prefix is skipped.
 ///
 /// # Errors
 ///
@@ -130,18 +494,159 @@ fn parse_full_state(line: &[u8]) -> Result<GamepadState, ParseError> {
 /// G0001:0:0:0:0:0:0*31\\n  -> ParsedMessage::FullState(...)
 /// UB:0001*31\\n            -> ParsedMessage::Update(Buttons(...))
 /// ULX:-500*XX\\n           -> ParsedMessage::Update(LeftStickX(-500))
+/// CLX:120*XX\\n            -> ParsedMessage::Trim(LeftX, 120)
 /// ```
+#[inline]
 pub fn parse_message(line: &[u8]) -> Result<ParsedMessage, ParseError> {
+    parse_message_with(line, &ParseOptions::new())
+}
+
+/// Parse any protocol message (full state or update) using the given options.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Parse`] if the message format is invalid, the prefix
+/// is unknown, or the presence of a checksum does not match
+/// [`ParseOptions::checksum`].
+/// Returns [`ParseError::Checksum`] if the checksum verification fails.
+pub fn parse_message_with(
+    line: &[u8],
+    options: &ParseOptions,
+) -> Result<ParsedMessage, ParseError> {
+    Ok(parse_message_detailed_with(line, options)?)
+}
+
+/// Parse any protocol message, reporting where and why parsing failed.
+///
+/// Same as [`parse_message()`], but the error keeps the detail that
+/// [`ParseError`] drops. Useful in host tools to find the offending byte of a
+/// malformed message.
+///
+/// # Errors
+///
+/// Returns a [`DetailedParseError`] with the [`ParseErrorKind`] and the byte
+/// offset into `line` where parsing failed.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{parse_message_detailed, ParseErrorKind};
+///
+/// let err = parse_message_detailed(b"G00x1:0:0:0:0:0:0*76\n").unwrap_err();
+/// assert_eq!(err.kind, ParseErrorKind::BadHex);
+/// assert_eq!(err.offset, 3);
+/// ```
+#[inline]
+pub fn parse_message_detailed(line: &[u8]) -> Result<ParsedMessage, DetailedParseError> {
+    parse_message_detailed_with(line, &ParseOptions::new())
+}
+
+/// Parse any protocol message using the given options, reporting where and
+/// why parsing failed.
+///
+/// # Errors
+///
+/// See [`parse_message_detailed()`].
+pub fn parse_message_detailed_with(
+    line: &[u8],
+    options: &ParseOptions,
+) -> Result<ParsedMessage, DetailedParseError> {
+    // A `\r` left over from the previous line's ending (e.g. a sender ending
+    // lines with `\n\r`) is skipped; offsets still refer to the raw line
+    let skipped = usize::from(line.first() == Some(&b'\r'));
+    let line = &line[skipped..];
+
+    // Reject noise on the first byte, before any other work. A valid first
+    // byte is never a line ending, so stripping leaves the line non-empty.
+    if !matches!(line.first(), Some(b'G' | b'U' | b'C' | b'H')) {
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, skipped));
+    }
+
     let line = strip_line_ending(line);
 
-    if line.is_empty() {
-        return Err(ParseError::Parse);
+    let result = match line[0] {
+        b'G' => parse_full_state(line, options).map(ParsedMessage::FullState),
+        b'U' => parse_update(line, options).map(ParsedMessage::Update),
+        b'C' => parse_trim(line, options).map(|(axis, offset)| ParsedMessage::Trim(axis, offset)),
+        b'H' => parse_hold(line, options).map(ParsedMessage::Hold),
+        _ => Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0)),
+    };
+    result.map_err(|e| DetailedParseError::new(e.kind, e.offset + skipped))
+}
+
+/// Parse a message that may be preceded by leading garbage.
+///
+/// Useful when a stream was joined mid-frame: the first line then starts with
+/// the tail of a previous message. Scans forward to each `G`/`U` byte in turn
+/// and returns the first successful parse together with the number of leading
+/// bytes that were skipped.
+///
+/// # Errors
+///
+/// Returns [`ParseError::Parse`] if the line contains no `G`/`U` prefix.
+/// Otherwise returns the error from parsing at the last candidate prefix.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{parse_message_resync, GamepadState, ParsedMessage};
+///
+/// let (msg, skipped) = parse_message_resync(b":12*4FG0000:0:0:0:0:0:0*30\n").unwrap();
+/// assert_eq!(msg, ParsedMessage::FullState(GamepadState::neutral()));
+/// assert_eq!(skipped, 6);
+/// ```
+pub fn parse_message_resync(line: &[u8]) -> Result<(ParsedMessage, usize), ParseError> {
+    let mut result = Err(ParseError::Parse);
+
+    for (skipped, _) in line
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b == b'G' || b == b'U')
+    {
+        match parse_message(&line[skipped..]) {
+            Ok(msg) => return Ok((msg, skipped)),
+            Err(e) => result = Err(e),
+        }
     }
 
-    match line[0] {
-        b'G' => parse_full_state(line).map(ParsedMessage::FullState),
-        b'U' => parse_update(line).map(ParsedMessage::Update),
-        _ => Err(ParseError::Parse),
+    result
+}
+
+/// Decode every complete line in a byte buffer.
+///
+/// Returns an iterator yielding one parse result per `\n`-terminated line,
+/// e.g. for frames written back to back by
+/// [`MessageBuilder::update_many()`](crate::MessageBuilder::update_many).
+/// Trailing bytes without a newline are treated as an incomplete line and
+/// ignored.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{decode_stream, GamepadState, ParsedMessage};
+///
+/// let mut messages = decode_stream(b"G0000:0:0:0:0:0:0*30\nG0000:0:0:0:0:0:0*30\n");
+/// assert_eq!(messages.next(), Some(Ok(ParsedMessage::FullState(GamepadState::neutral()))));
+/// assert_eq!(messages.count(), 1);
+/// ```
+pub fn decode_stream(bytes: &[u8]) -> DecodeStream<'_> {
+    DecodeStream { remaining: bytes }
+}
+
+/// Iterator over the messages in a byte buffer, created by [`decode_stream()`].
+#[derive(Debug, Clone)]
+pub struct DecodeStream<'a> {
+    remaining: &'a [u8],
+}
+
+impl Iterator for DecodeStream<'_> {
+    type Item = Result<ParsedMessage, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let newline = self.remaining.iter().position(|&b| b == b'\n')?;
+        let (line, rest) = self.remaining.split_at(newline + 1);
+        self.remaining = rest;
+        Some(parse_message(line))
     }
 }
 
@@ -153,7 +658,7 @@ pub fn parse_message(line: &[u8]) -> Result<ParsedMessage, ParseError> {
 /// U<field>:<value>*<checksum>\n
 /// ```
 ///
-/// Field identifiers:
+/// Field identifiers (see [`GamepadFieldUpdate::FIELD_TOKENS`]):
 /// - `B` - Buttons (4 hex digits)
 /// - `LX` - Left stick X (signed i16)
 /// - `LY` - Left stick Y (signed i16)
@@ -161,37 +666,142 @@ pub fn parse_message(line: &[u8]) -> Result<ParsedMessage, ParseError> {
 /// - `RY` - Right stick Y (signed i16)
 /// - `LT` - Left trigger (unsigned u8)
 /// - `RT` - Right trigger (unsigned u8)
-fn parse_update(line: &[u8]) -> Result<GamepadFieldUpdate, ParseError> {
+/// - `B+` - Buttons to press (4 hex digits, other buttons unchanged)
+/// - `B-` - Buttons to release (4 hex digits, other buttons unchanged)
+/// - `LX+`, `LY+`, `RX+`, `RY+` - Stick deltas (signed i16, saturating add)
+fn parse_update(
+    line: &[u8],
+    options: &ParseOptions,
+) -> Result<GamepadFieldUpdate, DetailedParseError> {
     // Must start with 'U'
     if line.first() != Some(&b'U') {
-        return Err(ParseError::Parse);
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0));
     }
 
     // Extract and verify checksum
-    let payload = extract_verified_payload(line, MIN_UPDATE_LEN)?;
+    let payload = extract_payload(line, MIN_UPDATE_LEN, options.checksum)?;
+    let (field, value) = split_colon(line, payload)?;
 
-    // Find the colon separator between field and value
-    let colon_pos = payload
-        .iter()
-        .position(|&b| b == b':')
-        .ok_or(ParseError::Parse)?;
-
-    let field = &payload[..colon_pos];
-    let value = &payload[colon_pos + 1..];
+    let buttons = || in_line(line, value, |s| options.button_bits(s));
+    let i16_value = || in_line(line, value, parse_i16);
+    let u8_value = || in_line(line, value, parse_u8);
 
     // Parse based on field identifier
     Ok(match field {
-        b"B" => GamepadFieldUpdate::Buttons(Buttons(parse_hex_u16(value)?)),
-        b"LX" => GamepadFieldUpdate::LeftStickX(parse_i16(value)?),
-        b"LY" => GamepadFieldUpdate::LeftStickY(parse_i16(value)?),
-        b"RX" => GamepadFieldUpdate::RightStickX(parse_i16(value)?),
-        b"RY" => GamepadFieldUpdate::RightStickY(parse_i16(value)?),
-        b"LT" => GamepadFieldUpdate::LeftTrigger(parse_u8(value)?),
-        b"RT" => GamepadFieldUpdate::RightTrigger(parse_u8(value)?),
-        _ => return Err(ParseError::Parse),
+        b"B" => GamepadFieldUpdate::Buttons(options.buttons(buttons()?)),
+        b"LX" => options.stick(StickAxis::LeftX, i16_value()?),
+        b"LY" => options.stick(StickAxis::LeftY, i16_value()?),
+        b"RX" => options.stick(StickAxis::RightX, i16_value()?),
+        b"RY" => options.stick(StickAxis::RightY, i16_value()?),
+        b"LT" => GamepadFieldUpdate::LeftTrigger(u8_value()?),
+        b"RT" => GamepadFieldUpdate::RightTrigger(u8_value()?),
+        // Bit masks, not levels, so active-low inversion does not apply
+        b"B+" => GamepadFieldUpdate::ButtonsSet(Buttons(buttons()?)),
+        b"B-" => GamepadFieldUpdate::ButtonsClear(Buttons(buttons()?)),
+        b"LX+" => GamepadFieldUpdate::LeftStickXDelta(i16_value()?),
+        b"LY+" => GamepadFieldUpdate::LeftStickYDelta(i16_value()?),
+        b"RX+" => GamepadFieldUpdate::RightStickXDelta(i16_value()?),
+        b"RY+" => GamepadFieldUpdate::RightStickYDelta(i16_value()?),
+        _ => {
+            return Err(DetailedParseError::new(
+                ParseErrorKind::UnknownField,
+                offset_in(line, field),
+            ))
+        }
     })
 }
 
+/// Parse a stick trim message (C prefix).
+///
+/// # Protocol Format
+///
+/// ```text
+/// C<axis>:<offset>*<checksum>\n
+/// ```
+///
+/// Axis identifiers are `LX`, `LY`, `RX` and `RY`; the offset is a signed i16
+/// that input sources subtract from subsequent readings of that axis.
+fn parse_trim(line: &[u8], options: &ParseOptions) -> Result<(StickAxis, i16), DetailedParseError> {
+    // Must start with 'C'
+    if line.first() != Some(&b'C') {
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0));
+    }
+
+    let payload = extract_payload(line, MIN_TRIM_LEN, options.checksum)?;
+    let (field, value) = split_colon(line, payload)?;
+
+    let axis = match field {
+        b"LX" => StickAxis::LeftX,
+        b"LY" => StickAxis::LeftY,
+        b"RX" => StickAxis::RightX,
+        b"RY" => StickAxis::RightY,
+        _ => {
+            return Err(DetailedParseError::new(
+                ParseErrorKind::UnknownField,
+                offset_in(line, field),
+            ))
+        }
+    };
+
+    Ok((axis, in_line(line, value, parse_i16)?))
+}
+
+/// Parse an input hold message (H prefix).
+///
+/// # Protocol Format
+///
+/// ```text
+/// H<0|1>*<checksum>\n
+/// ```
+///
+/// `1` freezes the state reported by input sources, `0` releases it.
+fn parse_hold(line: &[u8], options: &ParseOptions) -> Result<bool, DetailedParseError> {
+    // Must start with 'H'
+    if line.first() != Some(&b'H') {
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0));
+    }
+
+    match extract_payload(line, MIN_HOLD_LEN, options.checksum)? {
+        b"0" => Ok(false),
+        b"1" => Ok(true),
+        payload => Err(DetailedParseError::new(
+            ParseErrorKind::UnknownField,
+            offset_in(line, payload),
+        )),
+    }
+}
+
+/// Split an update or trim payload at its colon into field and value.
+#[inline]
+fn split_colon<'a>(
+    line: &[u8],
+    payload: &'a [u8],
+) -> Result<(&'a [u8], &'a [u8]), DetailedParseError> {
+    let colon_pos = payload.iter().position(|&b| b == b':').ok_or_else(|| {
+        DetailedParseError::new(
+            ParseErrorKind::MissingField,
+            offset_in(line, payload) + payload.len(),
+        )
+    })?;
+    Ok((&payload[..colon_pos], &payload[colon_pos + 1..]))
+}
+
+/// Byte offset of `field` within `line`; `field` must borrow from `line`.
+#[inline]
+fn offset_in(line: &[u8], field: &[u8]) -> usize {
+    field.as_ptr() as usize - line.as_ptr() as usize
+}
+
+/// Parse a field of `line`, moving error offsets from the field to the line.
+#[inline]
+fn in_line<'a, T>(
+    line: &[u8],
+    field: &'a [u8],
+    parse: impl FnOnce(&'a [u8]) -> Result<T, DetailedParseError>,
+) -> Result<T, DetailedParseError> {
+    parse(field).map_err(|e| e.shifted(offset_in(line, field)))
+}
+
 /// Strip trailing CR and/or LF from a line.
 #[inline]
 fn strip_line_ending(line: &[u8]) -> &[u8] {
@@ -205,32 +815,82 @@ fn strip_line_ending(line: &[u8]) -> &[u8] {
     &line[..end]
 }
 
+/// Extract the payload slice according to the checksum mode.
+///
+/// The `min_len` parameter is the minimum valid message length including the
+/// checksum suffix. The input line should have line endings already stripped.
+#[inline]
+fn extract_payload(
+    line: &[u8],
+    min_len: usize,
+    mode: ChecksumMode,
+) -> Result<&[u8], DetailedParseError> {
+    let checksum_pos = line.iter().rposition(|&b| b == b'*');
+    match (mode, checksum_pos) {
+        (ChecksumMode::Required | ChecksumMode::Optional, Some(_)) => {
+            extract_verified_payload(line, min_len)
+        }
+        (ChecksumMode::Required, None) => Err(DetailedParseError::new(
+            ParseErrorKind::MissingChecksum,
+            line.len(),
+        )),
+        (ChecksumMode::Omitted, Some(pos)) => Err(DetailedParseError::new(
+            ParseErrorKind::UnexpectedChecksum,
+            pos,
+        )),
+        (ChecksumMode::Omitted | ChecksumMode::Optional, None) => {
+            if line.len() < min_len - CHECKSUM_SUFFIX_LEN {
+                return Err(DetailedParseError::new(
+                    ParseErrorKind::MissingField,
+                    line.len(),
+                ));
+            }
+            Ok(&line[1..])
+        }
+    }
+}
+
 /// Extract and verify checksum, returning the payload slice.
 ///
 /// The `min_len` parameter is the minimum valid message length.
 /// The input line should have line endings already stripped.
 #[inline]
-fn extract_verified_payload(line: &[u8], min_len: usize) -> Result<&[u8], ParseError> {
-    if line.len() < min_len {
-        return Err(ParseError::Parse);
-    }
-
+fn extract_verified_payload(line: &[u8], min_len: usize) -> Result<&[u8], DetailedParseError> {
     let checksum_pos = line
         .iter()
         .rposition(|&b| b == b'*')
-        .ok_or(ParseError::Parse)?;
-
-    if checksum_pos + 3 > line.len() {
-        return Err(ParseError::Parse);
+        .ok_or(DetailedParseError::new(
+            ParseErrorKind::MissingChecksum,
+            line.len(),
+        ))?;
+
+    // The prefix byte must precede the '*' (guards the `1..checksum_pos` slice)
+    if checksum_pos == 0 {
+        return Err(DetailedParseError::new(ParseErrorKind::BadPrefix, 0));
+    }
+    if checksum_pos + CHECKSUM_SUFFIX_LEN > line.len() {
+        return Err(DetailedParseError::new(
+            ParseErrorKind::MissingChecksum,
+            line.len(),
+        ));
+    }
+    if line.len() < min_len {
+        return Err(DetailedParseError::new(
+            ParseErrorKind::MissingField,
+            checksum_pos,
+        ));
     }
 
     let payload = &line[1..checksum_pos];
     let checksum_str = &line[checksum_pos + 1..];
     let expected_checksum = calculate_crc8(payload);
-    let received_checksum = parse_hex_u8(checksum_str)?;
+    let received_checksum = in_line(line, checksum_str, parse_hex_u8)?;
 
     if expected_checksum != received_checksum {
-        return Err(ParseError::Checksum);
+        return Err(DetailedParseError::new(
+            ParseErrorKind::ChecksumMismatch,
+            checksum_pos + 1,
+        ));
     }
 
     Ok(payload)
@@ -238,111 +898,100 @@ fn extract_verified_payload(line: &[u8], min_len: usize) -> Result<&[u8], ParseE
 
 /// Parse a 4-character hex string as u16.
 #[inline]
-fn parse_hex_u16(s: &[u8]) -> Result<u16, ParseError> {
+fn parse_hex_u16(s: &[u8]) -> Result<u16, DetailedParseError> {
+    let value = parse_hex_digits(s)?;
     if s.len() != 4 {
-        return Err(ParseError::Parse);
-    }
-    let mut value: u16 = 0;
-    for &b in s {
-        let digit = hex_digit(b)?;
-        // Shift can never overflow: max 4 iterations shifting by 0, 4, 8, 12
-        value = (value << 4) | u16::from(digit);
+        return Err(DetailedParseError::new(
+            ParseErrorKind::BadHex,
+            s.len().min(4),
+        ));
     }
     Ok(value)
 }
 
 /// Parse a 2-character hex string as u8.
 #[inline]
-fn parse_hex_u8(s: &[u8]) -> Result<u8, ParseError> {
+fn parse_hex_u8(s: &[u8]) -> Result<u8, DetailedParseError> {
+    let value = parse_hex_digits(s)?;
     if s.len() != 2 {
-        return Err(ParseError::Parse);
+        return Err(DetailedParseError::new(
+            ParseErrorKind::BadHex,
+            s.len().min(2),
+        ));
     }
-    let high = hex_digit(s[0])?;
-    let low = hex_digit(s[1])?;
-    Ok((high << 4) | low)
+    // SAFETY: Two hex digits always fit in u8
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(value as u8)
+}
+
+/// Parse hex digits as u16 without checking the digit count.
+///
+/// Digits beyond the fourth shift earlier ones out; callers check the count.
+#[inline]
+fn parse_hex_digits(s: &[u8]) -> Result<u16, DetailedParseError> {
+    let mut value: u16 = 0;
+    for (i, &b) in s.iter().enumerate() {
+        let digit = hex_digit(b).ok_or(DetailedParseError::new(ParseErrorKind::BadHex, i))?;
+        value = (value << 4) | u16::from(digit);
+    }
+    Ok(value)
 }
 
 /// Convert a hex character to its value.
 #[inline]
-fn hex_digit(b: u8) -> Result<u8, ParseError> {
+fn hex_digit(b: u8) -> Option<u8> {
     match b {
-        b'0'..=b'9' => Ok(b - b'0'),
-        b'A'..=b'F' => Ok(b - b'A' + 10),
-        b'a'..=b'f' => Ok(b - b'a' + 10),
-        _ => Err(ParseError::Parse),
+        b'0'..=b'9' => Some(b - b'0'),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        _ => None,
     }
 }
 
 /// Parse a decimal string as i16 (with optional leading whitespace and sign).
 #[inline]
-fn parse_i16(s: &[u8]) -> Result<i16, ParseError> {
-    let s = trim_leading_whitespace(s);
-    if s.is_empty() {
-        return Err(ParseError::Parse);
-    }
-
-    let (negative, s) = if s[0] == b'-' {
-        (true, &s[1..])
-    } else if s[0] == b'+' {
-        (false, &s[1..])
+fn parse_i16(s: &[u8]) -> Result<i16, DetailedParseError> {
+    let start = s.len() - trim_leading_whitespace(s).len();
+    let sign_len = usize::from(matches!(s.get(start), Some(b'-' | b'+')));
+    let negative = s.get(start) == Some(&b'-');
+
+    let digits = start + sign_len;
+    let magnitude = parse_digits(&s[digits..]).map_err(|e| e.shifted(digits))?;
+    let value = if negative {
+        -i64::from(magnitude)
     } else {
-        (false, s)
+        i64::from(magnitude)
     };
 
-    if s.is_empty() {
-        return Err(ParseError::Parse);
-    }
-
-    let mut value: i32 = 0;
-    for &b in s {
-        if !b.is_ascii_digit() {
-            return Err(ParseError::Parse);
-        }
-        value = value
-            .checked_mul(10)
-            .and_then(|v| v.checked_add(i32::from(b - b'0')))
-            .ok_or(ParseError::Parse)?;
-    }
-
-    if negative {
-        value = -value;
-    }
-
-    if value < i32::from(i16::MIN) || value > i32::from(i16::MAX) {
-        return Err(ParseError::Parse);
-    }
-
-    // SAFETY: Range check above guarantees value fits in i16
-    #[allow(clippy::cast_possible_truncation)]
-    Ok(value as i16)
+    i16::try_from(value).map_err(|_| DetailedParseError::new(ParseErrorKind::IntRange, digits))
 }
 
 /// Parse a decimal string as u8 (with optional leading whitespace).
 #[inline]
-fn parse_u8(s: &[u8]) -> Result<u8, ParseError> {
-    let s = trim_leading_whitespace(s);
+fn parse_u8(s: &[u8]) -> Result<u8, DetailedParseError> {
+    let start = s.len() - trim_leading_whitespace(s).len();
+    let value = parse_digits(&s[start..]).map_err(|e| e.shifted(start))?;
+    u8::try_from(value).map_err(|_| DetailedParseError::new(ParseErrorKind::IntRange, start))
+}
+
+/// Parse a non-empty string of decimal digits.
+#[inline]
+fn parse_digits(s: &[u8]) -> Result<u32, DetailedParseError> {
     if s.is_empty() {
-        return Err(ParseError::Parse);
+        return Err(DetailedParseError::new(ParseErrorKind::BadInt, 0));
     }
 
-    let mut value: u16 = 0;
-    for &b in s {
+    let mut value: u32 = 0;
+    for (i, &b) in s.iter().enumerate() {
         if !b.is_ascii_digit() {
-            return Err(ParseError::Parse);
+            return Err(DetailedParseError::new(ParseErrorKind::BadInt, i));
         }
         value = value
             .checked_mul(10)
-            .and_then(|v| v.checked_add(u16::from(b - b'0')))
-            .ok_or(ParseError::Parse)?;
+            .and_then(|v| v.checked_add(u32::from(b - b'0')))
+            .ok_or(DetailedParseError::new(ParseErrorKind::IntRange, 0))?;
     }
-
-    if value > u16::from(u8::MAX) {
-        return Err(ParseError::Parse);
-    }
-
-    // SAFETY: Range check above guarantees value fits in u8
-    #[allow(clippy::cast_possible_truncation)]
-    Ok(value as u8)
+    Ok(value)
 }
 
 /// Trim leading ASCII whitespace (spaces).
@@ -490,6 +1139,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_update_relative_axes() {
+        let options = ParseOptions::new()
+            .checksum(ChecksumMode::Omitted)
+            .relative_axis(StickAxis::LeftX, true)
+            .relative_axis(StickAxis::RightY, true);
+        let parse = |line: &[u8]| parse_message_with(line, &options);
+
+        // Absolute tokens of relative axes become deltas, the others stay absolute
+        let mut state = GamepadState::neutral();
+        for line in [
+            &b"ULX:20000\n"[..],
+            b"ULX:20000\n",
+            b"ULY:-300\n",
+            b"ULY:-400\n",
+            b"URY:-100\n",
+            b"URY+:-50\n",
+        ] {
+            match parse(line) {
+                Ok(ParsedMessage::Update(update)) => state.apply_update(update),
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+        assert_eq!(state.left_stick.x, i16::MAX);
+        assert_eq!(state.left_stick.y, -400);
+        assert_eq!(state.right_stick.y, -150);
+
+        assert_eq!(
+            parse(b"URX:7\n"),
+            Ok(ParsedMessage::Update(GamepadFieldUpdate::RightStickX(7)))
+        );
+        assert_eq!(
+            parse(b"ULX:7\n"),
+            Ok(ParsedMessage::Update(GamepadFieldUpdate::LeftStickXDelta(
+                7
+            )))
+        );
+    }
+
+    #[test]
+    fn test_field_tokens_round_trip() {
+        let updates = [
+            (GamepadFieldUpdate::Buttons(Buttons(0x0102)), "0102"),
+            (GamepadFieldUpdate::LeftStickX(-5), "-5"),
+            (GamepadFieldUpdate::LeftStickY(6), "6"),
+            (GamepadFieldUpdate::RightStickX(-7), "-7"),
+            (GamepadFieldUpdate::RightStickY(8), "8"),
+            (GamepadFieldUpdate::LeftTrigger(9), "9"),
+            (GamepadFieldUpdate::RightTrigger(10), "10"),
+            (GamepadFieldUpdate::ButtonsSet(Buttons(0x0001)), "0001"),
+            (GamepadFieldUpdate::ButtonsClear(Buttons(0x8000)), "8000"),
+            (GamepadFieldUpdate::LeftStickXDelta(-11), "-11"),
+            (GamepadFieldUpdate::LeftStickYDelta(12), "12"),
+            (GamepadFieldUpdate::RightStickXDelta(-32768), "-32768"),
+            (GamepadFieldUpdate::RightStickYDelta(32767), "32767"),
+        ];
+
+        for (index, (update, value)) in updates.iter().enumerate() {
+            assert_eq!(update.field_index(), index);
+            let payload = format!("{}:{value}", update.field_token());
+            let checksum = calculate_crc8(payload.as_bytes());
+            let line = format!("U{payload}*{checksum:02X}\n");
+            assert_eq!(
+                parse_message(line.as_bytes()),
+                Ok(ParsedMessage::Update(*update))
+            );
+        }
+
+        let tokens = GamepadFieldUpdate::FIELD_TOKENS;
+        for (i, a) in tokens.iter().enumerate() {
+            assert!(
+                tokens[i + 1..].iter().all(|b| a != b),
+                "duplicate token {a}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_update_checksum_mismatch() {
         let line = b"UB:0001*00\n";
@@ -609,4 +1335,518 @@ mod tests {
         let line = format!("G0000:0:0:0:0:0*{:02X}\n", checksum);
         assert_eq!(parse(line.as_bytes()), Err(ParseError::Parse));
     }
+
+    // --- Trusted link (checksum-less) tests ---
+
+    #[test]
+    fn test_parse_trusted_full_state() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let state = parse_with(b"G0001:100:-100:0:0:64:32\n", &options).unwrap();
+        assert!(state.buttons.is_pressed(Buttons::A));
+        assert_eq!(state.left_stick.x, 100);
+        assert_eq!(state.left_stick.y, -100);
+        assert_eq!(state.left_trigger, 64);
+        assert_eq!(state.right_trigger, 32);
+    }
+
+    #[test]
+    fn test_parse_trusted_update() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let result = parse_message_with(b"ULX:-500\n", &options).unwrap();
+        assert_eq!(
+            result,
+            ParsedMessage::Update(GamepadFieldUpdate::LeftStickX(-500))
+        );
+    }
+
+    #[test]
+    fn test_checksummed_parser_rejects_trusted_frame() {
+        assert_eq!(parse(b"G0000:0:0:0:0:0:0\n"), Err(ParseError::Parse));
+        assert_eq!(parse_message(b"ULT:10\n"), Err(ParseError::Parse));
+    }
+
+    #[test]
+    fn test_trusted_parser_rejects_checksummed_frame() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        assert_eq!(
+            parse_with(b"G0000:0:0:0:0:0:0*30\n", &options),
+            Err(ParseError::Parse)
+        );
+    }
+
+    #[test]
+    fn test_optional_checksum_accepts_both() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Optional);
+        assert_eq!(
+            parse_with(b"G0000:0:0:0:0:0:0\n", &options),
+            Ok(GamepadState::neutral())
+        );
+        assert_eq!(
+            parse_with(b"G0000:0:0:0:0:0:0*30\n", &options),
+            Ok(GamepadState::neutral())
+        );
+    }
+
+    #[test]
+    fn test_optional_checksum_still_verified() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Optional);
+        assert_eq!(
+            parse_with(b"G0000:0:0:0:0:0:0*FF\n", &options),
+            Err(ParseError::Checksum)
+        );
+    }
+
+    #[test]
+    fn test_resync_skips_leading_junk() {
+        let checksum = calculate_crc8(b"LX:-500");
+        let line = format!("\x00\x7F17:0*A3ULX:-500*{:02X}\n", checksum);
+        let (msg, skipped) = parse_message_resync(line.as_bytes()).unwrap();
+        assert_eq!(
+            msg,
+            ParsedMessage::Update(GamepadFieldUpdate::LeftStickX(-500))
+        );
+        assert_eq!(skipped, 9);
+    }
+
+    #[test]
+    fn test_resync_clean_line_skips_nothing() {
+        let (msg, skipped) = parse_message_resync(b"G0000:0:0:0:0:0:0*30\n").unwrap();
+        assert_eq!(msg, ParsedMessage::FullState(GamepadState::neutral()));
+        assert_eq!(skipped, 0);
+    }
+
+    #[test]
+    fn test_resync_junk_containing_prefix_bytes() {
+        // Junk contains 'U' and 'G' that do not start a valid frame
+        let (msg, skipped) = parse_message_resync(b"UGx:GG0000:0:0:0:0:0:0*30\n").unwrap();
+        assert_eq!(msg, ParsedMessage::FullState(GamepadState::neutral()));
+        assert_eq!(skipped, 5);
+    }
+
+    #[test]
+    fn test_resync_errors() {
+        assert_eq!(parse_message_resync(b"junk*00\n"), Err(ParseError::Parse));
+        assert_eq!(parse_message_resync(b""), Err(ParseError::Parse));
+        assert_eq!(
+            parse_message_resync(b"xxG0000:0:0:0:0:0:0*FF\n"),
+            Err(ParseError::Checksum)
+        );
+    }
+
+    #[test]
+    fn test_parse_trim() {
+        let cases = [
+            ("LX", "120", StickAxis::LeftX, 120),
+            ("LY", "-32768", StickAxis::LeftY, i16::MIN),
+            ("RX", "0", StickAxis::RightX, 0),
+            ("RY", "32767", StickAxis::RightY, i16::MAX),
+        ];
+        for (field, value, axis, offset) in cases {
+            let payload = format!("{field}:{value}");
+            let checksum = calculate_crc8(payload.as_bytes());
+            let line = format!("C{payload}*{checksum:02X}\n");
+            assert_eq!(
+                parse_message(line.as_bytes()),
+                Ok(ParsedMessage::Trim(axis, offset))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_trim_invalid() {
+        // Triggers and buttons cannot be trimmed
+        let checksum = calculate_crc8(b"LT:5");
+        let line = format!("CLT:5*{checksum:02X}\n");
+        assert_eq!(parse_message(line.as_bytes()), Err(ParseError::Parse));
+
+        // Out of range offset
+        let checksum = calculate_crc8(b"LX:40000");
+        let line = format!("CLX:40000*{checksum:02X}\n");
+        assert_eq!(parse_message(line.as_bytes()), Err(ParseError::Parse));
+
+        assert_eq!(parse_message(b"CLX:5*00\n"), Err(ParseError::Checksum));
+    }
+
+    #[test]
+    fn test_parse_trim_trusted() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        assert_eq!(
+            parse_message_with(b"CRY:-40\n", &options),
+            Ok(ParsedMessage::Trim(StickAxis::RightY, -40))
+        );
+    }
+
+    #[test]
+    fn test_degenerate_frames_do_not_panic() {
+        let lines: [&[u8]; 10] = [
+            b"G*30\n",
+            b"U*00\n",
+            b"C*00\n",
+            b"H*00\n",
+            b"*30\n",
+            b"*\n",
+            b"G*\n",
+            b"G**\n",
+            b"G0000:0:0:0:0:0:0*\n",
+            b"G0000:0:0:0:0:0:0*3\n",
+        ];
+        for line in lines {
+            let result = parse_message(line);
+            assert!(
+                matches!(result, Err(ParseError::Parse | ParseError::Checksum)),
+                "{line:?}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verified_payload_rejects_leading_star() {
+        let verify =
+            |line, min_len| extract_verified_payload(line, min_len).map_err(ParseError::from);
+        assert_eq!(verify(b"*30", 3), Err(ParseError::Parse));
+        assert_eq!(verify(b"*", 0), Err(ParseError::Parse));
+        assert_eq!(verify(b"", 0), Err(ParseError::Parse));
+    }
+
+    #[test]
+    fn test_parse_hold() {
+        for (value, held) in [("0", false), ("1", true)] {
+            let checksum = calculate_crc8(value.as_bytes());
+            let line = format!("H{value}*{checksum:02X}\n");
+            assert_eq!(
+                parse_message(line.as_bytes()),
+                Ok(ParsedMessage::Hold(held))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_hold_invalid() {
+        for value in ["2", "10", "", "x"] {
+            let checksum = calculate_crc8(value.as_bytes());
+            let line = format!("H{value}*{checksum:02X}\n");
+            assert_eq!(parse_message(line.as_bytes()), Err(ParseError::Parse));
+        }
+
+        let checksum = calculate_crc8(b"1");
+        let line = format!("H1*{:02X}\n", checksum ^ 0xFF);
+        assert_eq!(parse_message(line.as_bytes()), Err(ParseError::Checksum));
+    }
+
+    #[test]
+    fn test_parse_hold_trusted() {
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        assert_eq!(
+            parse_message_with(b"H1\n", &options),
+            Ok(ParsedMessage::Hold(true))
+        );
+        assert_eq!(parse_message_with(b"H\n", &options), Err(ParseError::Parse));
+    }
+
+    #[test]
+    fn test_decode_stream_reports_each_line() {
+        let mut stream =
+            decode_stream(b"G0000:0:0:0:0:0:0*30\r\ngarbage\nG0000:0:0:0:0:0:0*30\nG00");
+        assert_eq!(
+            stream.next(),
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+        assert_eq!(stream.next(), Some(Err(ParseError::Parse)));
+        assert_eq!(
+            stream.next(),
+            Some(Ok(ParsedMessage::FullState(GamepadState::neutral())))
+        );
+        // Incomplete trailing line is not yielded
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn test_active_low_buttons() {
+        let options = ParseOptions::new()
+            .checksum(ChecksumMode::Omitted)
+            .active_low_buttons(true);
+
+        let state = parse_with(b"GFFFE:0:0:0:0:0:0\n", &options).unwrap();
+        assert_eq!(state.buttons, Buttons::A);
+        assert_eq!(state.buttons.raw().count_ones(), 1);
+
+        // All released on an active-low wire
+        let state = parse_with(b"GFFFF:0:0:0:0:0:0\n", &options).unwrap();
+        assert_eq!(state.buttons, Buttons::NONE);
+
+        let msg = parse_message_with(b"UB:FFFD\n", &options).unwrap();
+        assert_eq!(
+            msg,
+            ParsedMessage::Update(GamepadFieldUpdate::Buttons(Buttons::B))
+        );
+
+        // Default options leave the bitfield alone
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let state = parse_with(b"GFFFE:0:0:0:0:0:0\n", &options).unwrap();
+        assert_eq!(state.buttons, Buttons(0xFFFE));
+    }
+
+    #[test]
+    fn test_lenient_button_width() {
+        let lenient = ParseOptions::new()
+            .checksum(ChecksumMode::Omitted)
+            .lenient_button_width(true);
+        let strict = ParseOptions::new().checksum(ChecksumMode::Omitted);
+
+        for buttons in ["1", "01", "001", "0001"] {
+            let line = format!("G{buttons}:0:0:0:0:0:0\n");
+            let state = parse_with(line.as_bytes(), &lenient).unwrap();
+            assert_eq!(state.buttons, Buttons::A, "{buttons}");
+
+            let update = format!("UB:{buttons}\n");
+            assert_eq!(
+                parse_message_with(update.as_bytes(), &lenient),
+                Ok(ParsedMessage::Update(GamepadFieldUpdate::Buttons(
+                    Buttons::A
+                )))
+            );
+
+            let expected = if buttons.len() == 4 {
+                Ok(GamepadState {
+                    buttons: Buttons::A,
+                    ..GamepadState::neutral()
+                })
+            } else {
+                Err(ParseError::Parse)
+            };
+            assert_eq!(parse_with(line.as_bytes(), &strict), expected, "{buttons}");
+        }
+
+        // Still at most 4 digits, and at least one
+        assert_eq!(
+            parse_with(b"G00001:0:0:0:0:0:0\n", &lenient),
+            Err(ParseError::Parse)
+        );
+        assert_eq!(
+            parse_with(b"G:0:0:0:0:0:0\n", &lenient),
+            Err(ParseError::Parse)
+        );
+        // Checksummed short frames verify as usual
+        assert!(parse_with(
+            b"G1:0:0:0:0:0:0*E9\n",
+            &lenient.checksum(ChecksumMode::Required)
+        )
+        .is_ok_and(|state| state.buttons == Buttons::A));
+    }
+
+    #[test]
+    fn test_parse_fields_borrows_substrings() {
+        let line: &[u8] = b"G0001:100:-100:0:0:64:32*EA\n";
+        let fields = parse_fields(line).unwrap();
+
+        assert_eq!(fields.buttons, b"0001");
+        assert_eq!(fields.left_x, b"100");
+        assert_eq!(fields.left_y, b"-100");
+        assert_eq!(fields.right_x, b"0");
+        assert_eq!(fields.right_y, b"0");
+        assert_eq!(fields.left_trigger, b"64");
+        assert_eq!(fields.right_trigger, b"32");
+
+        // Slices point into the input line itself
+        let offset = |field: &[u8]| field.as_ptr() as usize - line.as_ptr() as usize;
+        assert_eq!(offset(fields.buttons), 1);
+        assert_eq!(offset(fields.left_y), 10);
+        assert_eq!(offset(fields.right_trigger), 22);
+        assert_eq!(&line[22..24], fields.right_trigger);
+    }
+
+    #[test]
+    fn test_parse_fields_errors() {
+        // Field contents are not converted, only the frame is checked
+        assert_eq!(parse_fields(b"G0001:0:0:0:0:0\n"), Err(ParseError::Parse));
+        assert_eq!(
+            parse_fields(b"U0001:100:-100:0:0:64:32*EA\n"),
+            Err(ParseError::Parse)
+        );
+        assert_eq!(
+            parse_fields(b"G0001:100:-100:0:0:64:33*EA\n"),
+            Err(ParseError::Checksum)
+        );
+    }
+
+    #[test]
+    fn test_parse_custom_field_layout() {
+        let layout = FieldLayout::new([
+            StateField::Buttons,
+            StateField::LeftTrigger,
+            StateField::RightTrigger,
+            StateField::LeftX,
+            StateField::LeftY,
+            StateField::RightX,
+            StateField::RightY,
+        ])
+        .unwrap();
+        let options = ParseOptions::new().layout(layout);
+        let line = b"G0001:64:32:100:-100:0:0*23\n";
+
+        let state = parse_with(line, &options).unwrap();
+        assert_eq!(state.buttons, Buttons::A);
+        assert_eq!(state.left_stick, AnalogStick::new(100, -100));
+        assert_eq!(state.right_stick, AnalogStick::NEUTRAL);
+        assert_eq!(state.left_trigger, 64);
+        assert_eq!(state.right_trigger, 32);
+
+        // The default layout misreads the same frame
+        let misread = parse(line).unwrap();
+        assert_eq!(misread.left_stick, AnalogStick::new(64, 32));
+        assert_eq!(misread.left_trigger, 0);
+        // Default frame under the custom layout: field count still checked
+        assert!(parse_with(b"G0001:0:0:0:0:0*XX\n", &options).is_err());
+    }
+
+    #[test]
+    fn test_detailed_error_points_at_bad_hex_digit() {
+        let line = b"G00x1:0:0:0:0:0:0*76\n";
+        let err = parse_message_detailed(line).unwrap_err();
+        assert_eq!(err, DetailedParseError::new(ParseErrorKind::BadHex, 3));
+        assert_eq!(line[err.offset], b'x');
+        assert_eq!(parse_message(line), Err(ParseError::Parse));
+
+        // Update value and checksum digits are located in the whole line
+        let err = parse_message_detailed(b"UB:00G1*40\n").unwrap_err();
+        assert_eq!(err, DetailedParseError::new(ParseErrorKind::BadHex, 5));
+        let err = parse_message_detailed(b"UB:0001*3Z\n").unwrap_err();
+        assert_eq!(err, DetailedParseError::new(ParseErrorKind::BadHex, 9));
+    }
+
+    #[test]
+    fn test_detailed_error_kinds() {
+        let detailed = |line: &str| {
+            let err = parse_message_detailed(line.as_bytes()).unwrap_err();
+            (err.kind, err.offset)
+        };
+        let with_crc = |prefix: char, payload: &str| {
+            format!(
+                "{prefix}{payload}*{:02X}\n",
+                calculate_crc8(payload.as_bytes())
+            )
+        };
+
+        assert_eq!(detailed("X0000\n"), (ParseErrorKind::BadPrefix, 0));
+        assert_eq!(detailed(""), (ParseErrorKind::BadPrefix, 0));
+        assert_eq!(
+            detailed(&with_crc('G', "0000:0:0:0:0:0:0:0")),
+            (ParseErrorKind::TooManyFields, 17)
+        );
+        assert_eq!(
+            detailed(&with_crc('G', "0000:0:0:0:0:0000")),
+            (ParseErrorKind::MissingField, 18)
+        );
+        assert_eq!(
+            detailed(&with_crc('G', "0000:0:1x:0:0:0:0")),
+            (ParseErrorKind::BadInt, 9)
+        );
+        assert_eq!(
+            detailed(&with_crc('G', "0000:0:-40000:0:0:0:0")),
+            (ParseErrorKind::IntRange, 9)
+        );
+        assert_eq!(
+            detailed(&with_crc('U', "LT: 256")),
+            (ParseErrorKind::IntRange, 5)
+        );
+        assert_eq!(
+            detailed(&with_crc('U', "XX:1")),
+            (ParseErrorKind::UnknownField, 1)
+        );
+        assert_eq!(
+            detailed("G0000:0:0:0:0:0:0\n"),
+            (ParseErrorKind::MissingChecksum, 17)
+        );
+        assert_eq!(
+            detailed("G0000:0:0:0:0:0:0*31\n"),
+            (ParseErrorKind::ChecksumMismatch, 18)
+        );
+        assert_eq!(
+            parse_message(b"G0000:0:0:0:0:0:0*31\n"),
+            Err(ParseError::Checksum)
+        );
+
+        let options = ParseOptions::new().checksum(ChecksumMode::Omitted);
+        let err = parse_message_detailed_with(b"H1*XX\n", &options).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedChecksum);
+        assert_eq!(format!("{err}"), "unexpected checksum at byte 2");
+    }
+
+    #[test]
+    fn test_non_prefix_lines_rejected() {
+        let junk: [&[u8]; 9] = [
+            b"",
+            b"\n",
+            b"\r\n",
+            b"\r\rG0000:0:0:0:0:0:0*30\n",
+            b"\r G0000:0:0:0:0:0:0*30\n",
+            b" G0000:0:0:0:0:0:0*30\n",
+            b"g0000:0:0:0:0:0:0*30\n",
+            b"\x00\xFF\x7F",
+            b"hello world\n",
+        ];
+        for line in junk {
+            assert_eq!(parse_message(line), Err(ParseError::Parse), "{line:?}");
+        }
+
+        let valid: [&[u8]; 5] = [
+            b"G0000:0:0:0:0:0:0*30\n",
+            b"G0000:0:0:0:0:0:0*30\r\n",
+            b"G0000:0:0:0:0:0:0*30",
+            b"\rG0000:0:0:0:0:0:0*30\n",
+            b"UB:0001*89\n",
+        ];
+        for line in valid {
+            assert!(parse_message(line).is_ok(), "{line:?}");
+        }
+
+        // Offsets still count the skipped `\r`
+        assert_eq!(
+            parse_message_detailed(b"\rG0000:0:0:0:0:0:0*31\n")
+                .unwrap_err()
+                .offset,
+            parse_message_detailed(b"G0000:0:0:0:0:0:0*31\n")
+                .unwrap_err()
+                .offset
+                + 1
+        );
+    }
+
+    /// Host benchmark: `cargo test -p gamepad-proto --release -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_parse_noisy_stream() {
+        extern crate std;
+        use std::time::Instant;
+
+        const LINES: u32 = 1_000_000;
+        let lines: [&[u8]; 10] = [
+            b"G0001:100:-100:0:0:64:32*EA\n",
+            b"\x12\x9A garbage from a wrong baud rate\n",
+            b"~~~~~~~~~~~~~~~~~~~~~~~~~~~~\n",
+            b"\r\n",
+            b"ERROR: sensor timeout\n",
+            b"\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\n",
+            b"0:0:0:0:0:0*30\n",
+            b"log: tick\n",
+            b"\n",
+            b"#########\n",
+        ];
+
+        let mut parsed = 0u32;
+        let start = Instant::now();
+        for i in 0..LINES {
+            let line = core::hint::black_box(lines[i as usize % lines.len()]);
+            if parse_message(line).is_ok() {
+                parsed += 1;
+            }
+        }
+        let elapsed = start.elapsed();
+
+        assert_eq!(parsed, LINES / 10);
+        std::println!(
+            "parse_message: {LINES} lines in {elapsed:?} ({:.1} ns/line)",
+            elapsed.as_nanos() as f64 / f64::from(LINES)
+        );
+    }
 }
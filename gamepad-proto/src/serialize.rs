@@ -31,8 +31,8 @@
 //! ```
 
 use crate::crc::Crc8Digest;
-use crate::fmt::{write_hex_u16, write_hex_u8, write_i16, write_u8};
-use crate::types::{GamepadFieldUpdate, GamepadState};
+use crate::fmt::{i16_len, u8_len, write_hex_u16, write_hex_u8, write_i16, write_u8};
+use crate::types::{FieldLayout, GamepadFieldUpdate, GamepadState, StateField};
 
 /// Helper for buffer management with incremental CRC-8 checksum calculation.
 ///
@@ -120,6 +120,23 @@ impl<'a> SerializeBuf<'a> {
 
         pos
     }
+
+    /// Finalize with only a newline, omitting the checksum (trusted link mode).
+    #[inline]
+    fn finalize_trusted(self) -> usize {
+        self.buf[self.pos] = b'\n';
+        self.pos + 1
+    }
+
+    /// Finalize with or without the checksum suffix.
+    #[inline]
+    fn finish(self, checksum: bool) -> usize {
+        if checksum {
+            self.finalize()
+        } else {
+            self.finalize_trusted()
+        }
+    }
 }
 
 /// Maximum size of a serialized full state message.
@@ -130,10 +147,50 @@ pub const MAX_FULL_STATE_SIZE: usize = 48;
 
 /// Maximum size of a serialized update message.
 ///
-/// Breakdown: U(1) + field(2) + colon(1) + value(6) + *(1) + checksum(2) + \n(1) = 14
+/// Breakdown: U(1) + field(3) + colon(1) + value(6) + *(1) + checksum(2) + \n(1) = 15
 /// We use 16 for safety margin.
 pub const MAX_UPDATE_SIZE: usize = 16;
 
+/// Serialized [`GamepadState::neutral()`] full state message.
+///
+/// Error paths and keyframe senders can write this ready-made frame instead of
+/// serializing the neutral state each time.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{parse, GamepadState, NEUTRAL_FRAME};
+///
+/// assert_eq!(parse(NEUTRAL_FRAME), Ok(GamepadState::neutral()));
+/// ```
+pub const NEUTRAL_FRAME: &[u8] = b"G0000:0:0:0:0:0:0*30\n";
+
+/// A serialized full state message in a fixed-size array.
+///
+/// Returned by [`GamepadState::to_frame()`]. Needs neither a caller-provided
+/// buffer nor the `heapless` feature, so it can be built alongside other
+/// fixed-size encodings (e.g. a HID report) and passed around by value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FullStateFrame {
+    buf: [u8; MAX_FULL_STATE_SIZE],
+    len: usize,
+}
+
+impl FullStateFrame {
+    /// Get the frame bytes, including checksum and newline.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for FullStateFrame {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 /// Error type for serialization operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -168,6 +225,13 @@ impl core::fmt::Display for SerializeError {
 /// let len = state.serialize(&mut buf).unwrap();
 /// ```
 pub trait Serialize {
+    /// Minimum buffer size [`serialize`](Self::serialize) accepts.
+    ///
+    /// Large enough for any message of this type. Defaults to 0 for
+    /// implementations that do not declare a size; `serialize` alone then
+    /// decides whether a buffer is large enough.
+    const MAX_SIZE: usize = 0;
+
     /// Serialize to the provided buffer.
     ///
     /// Returns the number of bytes written on success.
@@ -180,12 +244,20 @@ pub trait Serialize {
     /// Serialize to a `heapless::Vec`.
     ///
     /// This is a convenience method that creates a new vector and serializes into it.
+    /// `N` must be at least [`MAX_SIZE`](Self::MAX_SIZE), even if the message
+    /// itself is shorter; the inherent `serialize_to_vec_min()` methods pick
+    /// that capacity automatically.
     ///
     /// # Errors
     ///
-    /// Returns [`SerializeError::BufferTooSmall`] if `N` is not large enough.
+    /// Returns [`SerializeError::BufferTooSmall`] if `N` is smaller than
+    /// [`MAX_SIZE`](Self::MAX_SIZE) or than the message.
     #[cfg(feature = "heapless")]
     fn serialize_to_vec<const N: usize>(&self) -> Result<heapless::Vec<u8, N>, SerializeError> {
+        // Fail before filling the vector; serialize() would reject it anyway
+        if N < Self::MAX_SIZE {
+            return Err(SerializeError::BufferTooSmall);
+        }
         let mut vec = heapless::Vec::new();
         // Resize to full capacity to allow serialize() to write
         vec.resize(N, 0)
@@ -215,34 +287,51 @@ pub trait Serialize {
     fn serialize_io<W: embedded_io::Write>(&self, writer: &mut W) -> Result<(), SerializeError>;
 }
 
-impl Serialize for GamepadState {
-    fn serialize(&self, buf: &mut [u8]) -> Result<usize, SerializeError> {
-        if buf.len() < MAX_FULL_STATE_SIZE {
-            return Err(SerializeError::BufferTooSmall);
+/// Serialize a full state message, optionally omitting the checksum.
+///
+/// With `checksum == false` the `*<checksum>` suffix is left out entirely
+/// (trusted link mode), producing frames like `G0001:0:0:0:0:0:0\n`. Fields
+/// are written in `layout` order.
+pub(crate) fn serialize_state_with(
+    state: &GamepadState,
+    buf: &mut [u8],
+    checksum: bool,
+    layout: &FieldLayout,
+) -> Result<usize, SerializeError> {
+    if buf.len() < MAX_FULL_STATE_SIZE {
+        return Err(SerializeError::BufferTooSmall);
+    }
+
+    let mut sb = SerializeBuf::new(buf);
+
+    // Prefix (not checksummed)
+    sb.write_raw(b'G');
+
+    // Payload (checksummed)
+    for (i, field) in layout.order().iter().enumerate() {
+        if i > 0 {
+            sb.write(b':');
         }
+        match field {
+            StateField::Buttons => sb.write_hex_u16(state.buttons.raw()),
+            StateField::LeftX => sb.write_i16(state.left_stick.x),
+            StateField::LeftY => sb.write_i16(state.left_stick.y),
+            StateField::RightX => sb.write_i16(state.right_stick.x),
+            StateField::RightY => sb.write_i16(state.right_stick.y),
+            StateField::LeftTrigger => sb.write_u8(state.left_trigger),
+            StateField::RightTrigger => sb.write_u8(state.right_trigger),
+        }
+    }
 
-        let mut sb = SerializeBuf::new(buf);
-
-        // Prefix (not checksummed)
-        sb.write_raw(b'G');
+    // Finalize with checksum (if enabled) and newline
+    Ok(sb.finish(checksum))
+}
 
-        // Payload (checksummed)
-        sb.write_hex_u16(self.buttons.raw());
-        sb.write(b':');
-        sb.write_i16(self.left_stick.x);
-        sb.write(b':');
-        sb.write_i16(self.left_stick.y);
-        sb.write(b':');
-        sb.write_i16(self.right_stick.x);
-        sb.write(b':');
-        sb.write_i16(self.right_stick.y);
-        sb.write(b':');
-        sb.write_u8(self.left_trigger);
-        sb.write(b':');
-        sb.write_u8(self.right_trigger);
+impl Serialize for GamepadState {
+    const MAX_SIZE: usize = MAX_FULL_STATE_SIZE;
 
-        // Finalize with checksum and newline
-        Ok(sb.finalize())
+    fn serialize(&self, buf: &mut [u8]) -> Result<usize, SerializeError> {
+        serialize_state_with(self, buf, true, &FieldLayout::DEFAULT)
     }
 
     fn serialize_fmt<W: core::fmt::Write>(&self, writer: &mut W) -> Result<(), SerializeError> {
@@ -264,51 +353,146 @@ impl Serialize for GamepadState {
     }
 }
 
-impl Serialize for GamepadFieldUpdate {
-    fn serialize(&self, buf: &mut [u8]) -> Result<usize, SerializeError> {
-        if buf.len() < MAX_UPDATE_SIZE {
-            return Err(SerializeError::BufferTooSmall);
-        }
+impl GamepadState {
+    /// Exact length of the checksummed full state message for this state.
+    ///
+    /// Matches the value returned by [`Serialize::serialize`], so buffers can
+    /// be sized precisely without serializing first. Note that `serialize`
+    /// itself still requires a buffer of at least [`MAX_FULL_STATE_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::GamepadState;
+    ///
+    /// // G0000:0:0:0:0:0:0*30\n
+    /// assert_eq!(GamepadState::neutral().serialized_len(), 21);
+    /// ```
+    #[must_use]
+    pub const fn serialized_len(&self) -> usize {
+        // G + buttons(4) + 6 colons + '*' + checksum(2) + '\n'
+        const OVERHEAD: usize = 1 + 4 + 6 + 1 + 2 + 1;
+
+        OVERHEAD
+            + i16_len(self.left_stick.x)
+            + i16_len(self.left_stick.y)
+            + i16_len(self.right_stick.x)
+            + i16_len(self.right_stick.y)
+            + u8_len(self.left_trigger)
+            + u8_len(self.right_trigger)
+    }
+
+    /// Serialize the checksummed full state message into a [`FullStateFrame`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadState, NEUTRAL_FRAME};
+    ///
+    /// assert_eq!(GamepadState::neutral().to_frame().as_bytes(), NEUTRAL_FRAME);
+    /// ```
+    #[must_use]
+    pub fn to_frame(&self) -> FullStateFrame {
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = serialize_state_with(self, &mut buf, true, &FieldLayout::DEFAULT)
+            .expect("MAX_FULL_STATE_SIZE fits any full state message");
+        FullStateFrame { buf, len }
+    }
 
-        let mut sb = SerializeBuf::new(buf);
+    /// Serialize to a `heapless::Vec` of exactly [`MAX_FULL_STATE_SIZE`] capacity.
+    ///
+    /// Like [`Serialize::serialize_to_vec`], without having to pick a capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadState, NEUTRAL_FRAME};
+    ///
+    /// let vec = GamepadState::neutral().serialize_to_vec_min();
+    /// assert_eq!(vec.as_slice(), NEUTRAL_FRAME);
+    /// ```
+    #[cfg(feature = "heapless")]
+    #[must_use]
+    pub fn serialize_to_vec_min(&self) -> heapless::Vec<u8, MAX_FULL_STATE_SIZE> {
+        self.serialize_to_vec()
+            .expect("MAX_FULL_STATE_SIZE fits any full state message")
+    }
 
-        // Prefix (not checksummed)
-        sb.write_raw(b'U');
+    /// Serialize the checksummed full state message into an owned `Vec`.
+    ///
+    /// The vector is allocated with exactly [`serialized_len`](Self::serialized_len)
+    /// bytes, for host tooling that doesn't want fixed-size buffers.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn serialize_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = serialize_state_with(self, &mut buf, true, &FieldLayout::DEFAULT)
+            .expect("MAX_FULL_STATE_SIZE fits any full state message");
 
-        // Field:value (checksummed)
-        match self {
-            Self::Buttons(b) => {
-                sb.write_slice(b"B:");
-                sb.write_hex_u16(b.raw());
-            }
-            Self::LeftStickX(v) => {
-                sb.write_slice(b"LX:");
-                sb.write_i16(*v);
-            }
-            Self::LeftStickY(v) => {
-                sb.write_slice(b"LY:");
-                sb.write_i16(*v);
-            }
-            Self::RightStickX(v) => {
-                sb.write_slice(b"RX:");
-                sb.write_i16(*v);
-            }
-            Self::RightStickY(v) => {
-                sb.write_slice(b"RY:");
-                sb.write_i16(*v);
-            }
-            Self::LeftTrigger(v) => {
-                sb.write_slice(b"LT:");
-                sb.write_u8(*v);
-            }
-            Self::RightTrigger(v) => {
-                sb.write_slice(b"RT:");
-                sb.write_u8(*v);
-            }
+        let mut vec = alloc::vec::Vec::with_capacity(self.serialized_len());
+        vec.extend_from_slice(&buf[..len]);
+        vec
+    }
+}
+
+/// Serialize an update message, optionally omitting the checksum.
+///
+/// See [`serialize_state_with`] for the trusted link mode semantics.
+pub(crate) fn serialize_update_with(
+    update: &GamepadFieldUpdate,
+    buf: &mut [u8],
+    checksum: bool,
+) -> Result<usize, SerializeError> {
+    if buf.len() < MAX_UPDATE_SIZE {
+        return Err(SerializeError::BufferTooSmall);
+    }
+
+    let mut sb = SerializeBuf::new(buf);
+
+    // Prefix (not checksummed)
+    sb.write_raw(b'U');
+
+    // Field:value (checksummed)
+    sb.write_slice(update.field_token().as_bytes());
+    sb.write(b':');
+    match update {
+        GamepadFieldUpdate::Buttons(b)
+        | GamepadFieldUpdate::ButtonsSet(b)
+        | GamepadFieldUpdate::ButtonsClear(b) => sb.write_hex_u16(b.raw()),
+        GamepadFieldUpdate::LeftStickX(v)
+        | GamepadFieldUpdate::LeftStickY(v)
+        | GamepadFieldUpdate::RightStickX(v)
+        | GamepadFieldUpdate::RightStickY(v)
+        | GamepadFieldUpdate::LeftStickXDelta(v)
+        | GamepadFieldUpdate::LeftStickYDelta(v)
+        | GamepadFieldUpdate::RightStickXDelta(v)
+        | GamepadFieldUpdate::RightStickYDelta(v) => sb.write_i16(*v),
+        GamepadFieldUpdate::LeftTrigger(v) | GamepadFieldUpdate::RightTrigger(v) => {
+            sb.write_u8(*v);
         }
+    }
+
+    // Finalize with checksum (if enabled) and newline
+    Ok(sb.finish(checksum))
+}
 
-        // Finalize with checksum and newline
-        Ok(sb.finalize())
+impl GamepadFieldUpdate {
+    /// Serialize to a `heapless::Vec` of exactly [`MAX_UPDATE_SIZE`] capacity.
+    ///
+    /// Like [`Serialize::serialize_to_vec`], without having to pick a capacity.
+    #[cfg(feature = "heapless")]
+    #[must_use]
+    pub fn serialize_to_vec_min(&self) -> heapless::Vec<u8, MAX_UPDATE_SIZE> {
+        self.serialize_to_vec()
+            .expect("MAX_UPDATE_SIZE fits any update message")
+    }
+}
+
+impl Serialize for GamepadFieldUpdate {
+    const MAX_SIZE: usize = MAX_UPDATE_SIZE;
+
+    fn serialize(&self, buf: &mut [u8]) -> Result<usize, SerializeError> {
+        serialize_update_with(self, buf, true)
     }
 
     fn serialize_fmt<W: core::fmt::Write>(&self, writer: &mut W) -> Result<(), SerializeError> {
@@ -337,6 +521,40 @@ mod tests {
     use crate::parser::{parse, parse_message, ParsedMessage};
     use crate::types::{AnalogStick, Buttons};
 
+    #[test]
+    fn test_to_frame_matches_serialize() {
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::START | Buttons::GUIDE | Buttons::DPAD_LEFT,
+            left_stick: AnalogStick::new(i16::MIN, 1234),
+            right_stick: AnalogStick::new(-1, i16::MAX),
+            left_trigger: 255,
+            right_trigger: 7,
+        };
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = state.serialize(&mut buf).unwrap();
+
+        let frame = state.to_frame();
+        assert_eq!(frame.as_bytes(), &buf[..len]);
+        assert_eq!(frame.as_bytes().len(), state.serialized_len());
+        assert_eq!(parse(frame.as_ref()), Ok(state));
+    }
+
+    #[test]
+    fn test_neutral_frame_matches_serialize() {
+        let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+        let len = GamepadState::neutral().serialize(&mut buf).unwrap();
+        assert_eq!(&buf[..len], NEUTRAL_FRAME);
+    }
+
+    #[test]
+    fn test_neutral_frame_parses_to_neutral() {
+        assert_eq!(parse(NEUTRAL_FRAME), Ok(GamepadState::neutral()));
+        assert_eq!(
+            parse_message(NEUTRAL_FRAME),
+            Ok(ParsedMessage::FullState(GamepadState::neutral()))
+        );
+    }
+
     #[test]
     fn test_serialize_neutral_state() {
         let state = GamepadState::neutral();
@@ -420,6 +638,43 @@ mod tests {
         assert_eq!(result, Err(SerializeError::BufferTooSmall));
     }
 
+    #[test]
+    fn test_serialized_len_matches_serialize() {
+        let states = [
+            GamepadState::neutral(),
+            GamepadState {
+                buttons: Buttons::A | Buttons::START,
+                left_stick: AnalogStick::new(-1, -12),
+                right_stick: AnalogStick::new(-123, -1234),
+                left_trigger: 9,
+                right_trigger: 10,
+            },
+            GamepadState {
+                buttons: Buttons(0xFFFF),
+                left_stick: AnalogStick::new(i16::MIN, i16::MIN),
+                right_stick: AnalogStick::new(i16::MIN, i16::MIN),
+                left_trigger: 255,
+                right_trigger: 255,
+            },
+            GamepadState {
+                left_stick: AnalogStick::new(i16::MAX, 10_000),
+                right_stick: AnalogStick::new(9_999, 100),
+                left_trigger: 99,
+                right_trigger: 100,
+                ..GamepadState::neutral()
+            },
+        ];
+
+        let mut buf = [0u8; 64];
+        for state in states {
+            let len = state.serialize(&mut buf).unwrap();
+            assert_eq!(state.serialized_len(), len, "{state:?}");
+        }
+
+        // Longest possible frame still fits the fixed maximum
+        assert!(states[2].serialized_len() <= MAX_FULL_STATE_SIZE);
+    }
+
     #[test]
     fn test_serialize_update_buttons() {
         let update = GamepadFieldUpdate::Buttons(Buttons::A | Buttons::B);
@@ -520,4 +775,97 @@ mod tests {
         assert!(s.starts_with("ULT:64*"));
         assert!(s.ends_with('\n'));
     }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_serialize_to_vec_capacity() {
+        // Too small for serialize(), even though this message would fit
+        let state = GamepadState::neutral();
+        let result = state.serialize_to_vec::<{ MAX_FULL_STATE_SIZE - 1 }>();
+        assert_eq!(result, Err(SerializeError::BufferTooSmall));
+
+        let vec = state.serialize_to_vec::<MAX_FULL_STATE_SIZE>().unwrap();
+        assert_eq!(vec.as_slice(), NEUTRAL_FRAME);
+        assert_eq!(state.serialize_to_vec_min(), vec);
+
+        let update = GamepadFieldUpdate::LeftTrigger(128);
+        let result = update.serialize_to_vec::<{ MAX_UPDATE_SIZE - 1 }>();
+        assert_eq!(result, Err(SerializeError::BufferTooSmall));
+        assert_eq!(
+            update.serialize_to_vec_min().as_slice(),
+            update.serialize_to_vec::<64>().unwrap().as_slice()
+        );
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_serialize_to_vec_without_max_size() {
+        // An implementation that does not declare `MAX_SIZE`
+        struct Ping;
+
+        impl Serialize for Ping {
+            fn serialize(&self, buf: &mut [u8]) -> Result<usize, SerializeError> {
+                let out = buf.get_mut(..2).ok_or(SerializeError::BufferTooSmall)?;
+                out.copy_from_slice(b"P\n");
+                Ok(2)
+            }
+
+            fn serialize_fmt<W: core::fmt::Write>(
+                &self,
+                writer: &mut W,
+            ) -> Result<(), SerializeError> {
+                writer
+                    .write_str("P\n")
+                    .map_err(|_| SerializeError::WriteError)
+            }
+
+            #[cfg(feature = "embedded-io")]
+            fn serialize_io<W: embedded_io::Write>(
+                &self,
+                writer: &mut W,
+            ) -> Result<(), SerializeError> {
+                writer
+                    .write_all(b"P\n")
+                    .map_err(|_| SerializeError::WriteError)
+            }
+        }
+
+        assert_eq!(Ping::MAX_SIZE, 0);
+        assert_eq!(Ping.serialize_to_vec::<2>().unwrap().as_slice(), b"P\n");
+        assert_eq!(
+            Ping.serialize_to_vec::<1>(),
+            Err(SerializeError::BufferTooSmall)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialize_vec_matches_buffer() {
+        let states = [
+            GamepadState::neutral(),
+            GamepadState {
+                buttons: Buttons(0xFFFF),
+                left_stick: AnalogStick::new(i16::MIN, i16::MIN),
+                right_stick: AnalogStick::new(i16::MIN, i16::MIN),
+                left_trigger: 255,
+                right_trigger: 255,
+            },
+            GamepadState {
+                buttons: Buttons::A,
+                left_stick: AnalogStick::new(100, -100),
+                right_stick: AnalogStick::new(0, 9),
+                left_trigger: 64,
+                right_trigger: 0,
+            },
+        ];
+
+        for state in states {
+            let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+            let len = state.serialize(&mut buf).unwrap();
+
+            let vec = state.serialize_vec();
+            assert_eq!(vec.as_slice(), &buf[..len]);
+            assert_eq!(vec.capacity(), vec.len());
+        }
+    }
 }
@@ -0,0 +1,534 @@
+//! Analog transforms applied before a state is reported.
+//!
+//! [`Transforms`] collects the per-device tuning (calibration, deadzone, expo
+//! and output limits) and [`GamepadState::canonicalize()`] applies it in a
+//! fixed order:
+//!
+//! 1. **Calibration** - subtract the measured stick centers
+//! 2. **Axis swap** - exchange X and Y of sticks wired with swapped axes
+//! 3. **Deadzone** - zero small deflections and rescale the remaining range
+//! 4. **Expo** - blend in a cubic curve for finer control near center
+//! 5. **Clamp** - limit stick magnitude and trigger travel
+//! 6. **Stick D-pad** - optionally also press `DPAD_*` buttons from the left
+//!    stick direction, keeping the analog values
+//!
+//! Each step is also available as a standalone function so callers can compose
+//! them differently.
+
+use crate::types::{AnalogStick, Buttons, GamepadState};
+
+/// Maximum stick magnitude used as the full-scale reference.
+const STICK_MAX: i32 = i16::MAX as i32;
+
+/// Configuration for [`GamepadState::canonicalize()`].
+///
+/// The default configuration is the identity transform.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{AnalogStick, GamepadState, Transforms};
+///
+/// let transforms = Transforms::new().deadzone(1000).stick_limit(30000);
+/// let state = GamepadState {
+///     left_stick: AnalogStick::new(500, 32767),
+///     ..GamepadState::neutral()
+/// };
+/// let out = state.canonicalize(&transforms);
+/// assert_eq!(out.left_stick, AnalogStick::new(0, 30000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Transforms {
+    /// Measured rest position of the left stick.
+    pub left_center: AnalogStick,
+    /// Measured rest position of the right stick.
+    pub right_center: AnalogStick,
+    /// Exchange the left stick's X and Y axes.
+    pub left_swap_xy: bool,
+    /// Exchange the right stick's X and Y axes.
+    pub right_swap_xy: bool,
+    /// Per-axis stick deadzone (0 = disabled).
+    pub deadzone: u16,
+    /// Stick expo as a percentage (0 = linear, 100 = fully cubic).
+    pub expo: u8,
+    /// Maximum stick magnitude per axis (32767 = no limit).
+    pub stick_limit: i16,
+    /// Trigger deadzone (0 = disabled).
+    pub trigger_deadzone: u8,
+    /// Maximum trigger value (255 = no limit).
+    pub trigger_limit: u8,
+    /// Left stick deflection that additionally presses a D-pad direction
+    /// (0 = disabled).
+    pub stick_dpad_threshold: u16,
+}
+
+impl Transforms {
+    /// Create the identity configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            left_center: AnalogStick::new(0, 0),
+            right_center: AnalogStick::new(0, 0),
+            left_swap_xy: false,
+            right_swap_xy: false,
+            deadzone: 0,
+            expo: 0,
+            stick_limit: i16::MAX,
+            trigger_deadzone: 0,
+            trigger_limit: u8::MAX,
+            stick_dpad_threshold: 0,
+        }
+    }
+
+    /// Set the measured stick centers.
+    #[must_use]
+    pub const fn calibration(
+        mut self,
+        left_center: AnalogStick,
+        right_center: AnalogStick,
+    ) -> Self {
+        self.left_center = left_center;
+        self.right_center = right_center;
+        self
+    }
+
+    /// Set the per-axis stick deadzone.
+    #[must_use]
+    pub const fn deadzone(mut self, deadzone: u16) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// Set the stick expo percentage (values above 100 are treated as 100).
+    #[must_use]
+    pub const fn expo(mut self, expo: u8) -> Self {
+        self.expo = expo;
+        self
+    }
+
+    /// Set the maximum stick magnitude per axis.
+    #[must_use]
+    pub const fn stick_limit(mut self, limit: i16) -> Self {
+        self.stick_limit = limit;
+        self
+    }
+
+    /// Set the trigger deadzone.
+    #[must_use]
+    pub const fn trigger_deadzone(mut self, deadzone: u8) -> Self {
+        self.trigger_deadzone = deadzone;
+        self
+    }
+
+    /// Set which sticks have their X and Y axes exchanged.
+    ///
+    /// The swap happens after calibration, so the stick centers stay in the
+    /// hardware's own axis order.
+    #[must_use]
+    pub const fn swap_xy(mut self, left: bool, right: bool) -> Self {
+        self.left_swap_xy = left;
+        self.right_swap_xy = right;
+        self
+    }
+
+    /// Set the maximum trigger value.
+    #[must_use]
+    pub const fn trigger_limit(mut self, limit: u8) -> Self {
+        self.trigger_limit = limit;
+        self
+    }
+
+    /// Also press `DPAD_*` buttons while the left stick is past `threshold`.
+    ///
+    /// For menus that only read the D-pad. Unlike a stick-to-D-pad remap the
+    /// analog values are kept, so games reading the stick are unaffected.
+    #[must_use]
+    pub const fn stick_dpad(mut self, threshold: u16) -> Self {
+        self.stick_dpad_threshold = threshold;
+        self
+    }
+
+    /// Apply the full stick pipeline.
+    fn stick(&self, stick: AnalogStick, center: AnalogStick, swap_xy: bool) -> AnalogStick {
+        let calibrated = AnalogStick::new(
+            calibrate_axis(stick.x, center.x),
+            calibrate_axis(stick.y, center.y),
+        );
+        let oriented = if swap_xy {
+            calibrated.swap_xy()
+        } else {
+            calibrated
+        };
+        AnalogStick::new(self.stick_axis(oriented.x), self.stick_axis(oriented.y))
+    }
+
+    /// Apply the post-calibration stick steps to one axis.
+    fn stick_axis(&self, value: i16) -> i16 {
+        let value = apply_deadzone(value, self.deadzone);
+        let value = apply_expo(value, self.expo);
+        clamp_axis(value, self.stick_limit)
+    }
+
+    /// Apply the full trigger pipeline.
+    fn trigger(&self, value: u8) -> u8 {
+        apply_trigger_deadzone(value, self.trigger_deadzone).min(self.trigger_limit)
+    }
+}
+
+impl Default for Transforms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamepadState {
+    /// Produce the report-ready form of this state.
+    ///
+    /// Applies calibration, axis swap, deadzone, expo and clamping from
+    /// `transforms` in that order (see the [module docs](crate::transform)).
+    /// Buttons are passed through unchanged, except for D-pad directions added
+    /// from the transformed left stick when [`Transforms::stick_dpad`] is set.
+    #[must_use]
+    pub fn canonicalize(&self, transforms: &Transforms) -> GamepadState {
+        let left_stick = transforms.stick(
+            self.left_stick,
+            transforms.left_center,
+            transforms.left_swap_xy,
+        );
+        GamepadState {
+            buttons: self.buttons | stick_to_dpad(left_stick, transforms.stick_dpad_threshold),
+            left_stick,
+            right_stick: transforms.stick(
+                self.right_stick,
+                transforms.right_center,
+                transforms.right_swap_xy,
+            ),
+            left_trigger: transforms.trigger(self.left_trigger),
+            right_trigger: transforms.trigger(self.right_trigger),
+        }
+    }
+}
+
+/// Subtract a measured center from an axis value, saturating at the i16 range.
+#[must_use]
+pub fn calibrate_axis(value: i16, center: i16) -> i16 {
+    saturate_i16(i32::from(value) - i32::from(center))
+}
+
+/// Zero deflections within `deadzone` and rescale the rest to full range.
+///
+/// The output is continuous at the deadzone edge and still reaches ±32767.
+#[must_use]
+pub fn apply_deadzone(value: i16, deadzone: u16) -> i16 {
+    let deadzone = i32::from(deadzone);
+    if deadzone == 0 {
+        return value;
+    }
+    if deadzone >= STICK_MAX {
+        return 0;
+    }
+
+    let magnitude = i32::from(value).abs().min(STICK_MAX);
+    if magnitude <= deadzone {
+        return 0;
+    }
+
+    let scaled = (magnitude - deadzone) * STICK_MAX / (STICK_MAX - deadzone);
+    saturate_i16(scaled * i32::from(value).signum())
+}
+
+/// Blend a linear and cubic response curve.
+///
+/// `expo` is a percentage: 0 is linear, 100 is fully cubic. Full deflection
+/// maps to full deflection for any setting.
+#[must_use]
+pub fn apply_expo(value: i16, expo: u8) -> i16 {
+    let expo = i64::from(expo.min(100));
+    if expo == 0 {
+        return value;
+    }
+
+    let x = i64::from(value);
+    let max = i64::from(STICK_MAX);
+    let cubic = x * x * x / (max * max);
+    let blended = (x * (100 - expo) + cubic * expo) / 100;
+    // Blended value lies between x and cubic, both of which fit in i32
+    saturate_i16(blended as i32)
+}
+
+/// Limit an axis to `±limit` (negative limits are treated as their magnitude).
+///
+/// A limit of `i16::MAX` disables clamping so that `-32768` passes through.
+#[must_use]
+pub fn clamp_axis(value: i16, limit: i16) -> i16 {
+    if limit == i16::MAX {
+        return value;
+    }
+    let limit = i32::from(limit).abs().min(STICK_MAX);
+    // Clamped value fits in i16
+    i32::from(value).clamp(-limit, limit) as i16
+}
+
+/// Zero trigger values within `deadzone` and rescale the rest to 0-255.
+#[must_use]
+pub fn apply_trigger_deadzone(value: u8, deadzone: u8) -> u8 {
+    if deadzone == 0 {
+        return value;
+    }
+    if value <= deadzone {
+        return 0;
+    }
+
+    let range = u16::from(u8::MAX - deadzone);
+    let scaled = u16::from(value - deadzone) * u16::from(u8::MAX) / range;
+    // scaled <= 255 since value - deadzone <= range
+    scaled as u8
+}
+
+/// Split one centered axis into a pair of triggers `(left, right)`.
+///
+/// Negative deflection drives the left trigger, positive deflection the right
+/// one, as for a single pedal axis on steering setups. Deflections within
+/// `deadband` leave both triggers released; the rest of the travel is rescaled
+/// so each trigger still reaches 255 at full deflection.
+#[must_use]
+pub fn split_axis_to_triggers(value: i16, deadband: u16) -> (u8, u8) {
+    let value = i32::from(apply_deadzone(value, deadband));
+    // Magnitude <= 32768 after the deadzone, so the product fits in i32
+    let trigger = (value.abs() * i32::from(u8::MAX) / STICK_MAX).min(i32::from(u8::MAX)) as u8;
+    if value < 0 {
+        (trigger, 0)
+    } else {
+        (0, trigger)
+    }
+}
+
+/// D-pad directions a stick is deflected towards.
+///
+/// Each axis past `threshold` presses one direction (positive Y is down, as in
+/// HID), so diagonals press two. A threshold of 0 disables the mapping.
+#[must_use]
+pub fn stick_to_dpad(stick: AnalogStick, threshold: u16) -> Buttons {
+    if threshold == 0 {
+        return Buttons::NONE;
+    }
+
+    let threshold = i32::from(threshold);
+    let (x, y) = (i32::from(stick.x), i32::from(stick.y));
+    let mut dpad = Buttons::NONE;
+    dpad.set(Buttons::DPAD_LEFT, x < -threshold);
+    dpad.set(Buttons::DPAD_RIGHT, x > threshold);
+    dpad.set(Buttons::DPAD_UP, y < -threshold);
+    dpad.set(Buttons::DPAD_DOWN, y > threshold);
+    dpad
+}
+
+#[inline]
+fn saturate_i16(value: i32) -> i16 {
+    // Clamped value fits in i16
+    value.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Buttons;
+
+    #[test]
+    fn test_identity_is_noop() {
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::START,
+            left_stick: AnalogStick::new(-32768, 32767),
+            right_stick: AnalogStick::new(123, -456),
+            left_trigger: 1,
+            right_trigger: 255,
+        };
+        assert_eq!(state.canonicalize(&Transforms::new()), state);
+    }
+
+    #[test]
+    fn test_calibrate_axis_saturates() {
+        assert_eq!(calibrate_axis(1000, 200), 800);
+        assert_eq!(calibrate_axis(-32768, 100), -32768);
+        assert_eq!(calibrate_axis(32767, -100), 32767);
+    }
+
+    #[test]
+    fn test_deadzone() {
+        assert_eq!(apply_deadzone(999, 1000), 0);
+        assert_eq!(apply_deadzone(-1000, 1000), 0);
+        assert_eq!(apply_deadzone(32767, 1000), 32767);
+        assert_eq!(apply_deadzone(-32768, 1000), -32767);
+        // Just past the edge is near zero, not a jump to 1000
+        assert!(apply_deadzone(1001, 1000) < 10);
+        assert_eq!(apply_deadzone(5, u16::MAX), 0);
+    }
+
+    #[test]
+    fn test_expo() {
+        assert_eq!(apply_expo(0, 100), 0);
+        assert_eq!(apply_expo(32767, 50), 32767);
+        assert_eq!(apply_expo(-32767, 50), -32767);
+        assert_eq!(apply_expo(-32768, 100), -32768);
+        // Half deflection: cubic is 1/8, 50% blend is (1/2 + 1/8) / 2
+        let half = apply_expo(16384, 50);
+        assert!((10200..10300).contains(&half), "{half}");
+        // Expo above 100 is treated as 100
+        assert_eq!(apply_expo(16384, 200), apply_expo(16384, 100));
+    }
+
+    #[test]
+    fn test_split_axis_to_triggers() {
+        assert_eq!(split_axis_to_triggers(0, 0), (0, 0));
+        assert_eq!(split_axis_to_triggers(i16::MAX, 0), (0, 255));
+        assert_eq!(split_axis_to_triggers(-i16::MAX, 0), (255, 0));
+        assert_eq!(split_axis_to_triggers(i16::MIN, 0), (255, 0));
+        assert_eq!(split_axis_to_triggers(16384, 0), (0, 127));
+
+        // Deadband around center, full travel still reached
+        assert_eq!(split_axis_to_triggers(1500, 2000), (0, 0));
+        assert_eq!(split_axis_to_triggers(-2000, 2000), (0, 0));
+        assert_eq!(split_axis_to_triggers(-32767, 2000), (255, 0));
+        let (left, right) = split_axis_to_triggers(2100, 2000);
+        assert_eq!(left, 0);
+        assert!(right <= 1, "{right}");
+    }
+
+    #[test]
+    fn test_stick_dpad_keeps_analog() {
+        let transforms = Transforms::new().stick_dpad(16000);
+        let state = GamepadState {
+            buttons: Buttons::A,
+            left_stick: AnalogStick::new(20000, -30000),
+            ..GamepadState::neutral()
+        };
+
+        let out = state.canonicalize(&transforms);
+        assert_eq!(out.left_stick, state.left_stick);
+        assert_eq!(
+            out.buttons,
+            Buttons::A | Buttons::DPAD_RIGHT | Buttons::DPAD_UP
+        );
+
+        // Below the threshold only the analog values are reported
+        let small = GamepadState {
+            left_stick: AnalogStick::new(-16000, 15000),
+            ..GamepadState::neutral()
+        };
+        assert_eq!(small.canonicalize(&transforms), small);
+
+        // Disabled by default
+        assert_eq!(stick_to_dpad(state.left_stick, 0), Buttons::NONE);
+        assert_eq!(
+            stick_to_dpad(AnalogStick::new(-32768, 32767), 1),
+            Buttons::DPAD_LEFT | Buttons::DPAD_DOWN
+        );
+    }
+
+    #[test]
+    fn test_clamp_axis() {
+        assert_eq!(clamp_axis(32767, 30000), 30000);
+        assert_eq!(clamp_axis(-32768, 30000), -30000);
+        assert_eq!(clamp_axis(100, 30000), 100);
+        assert_eq!(clamp_axis(-32768, i16::MAX), -32768);
+        assert_eq!(clamp_axis(-32768, i16::MIN), -32767);
+    }
+
+    #[test]
+    fn test_trigger_deadzone() {
+        assert_eq!(apply_trigger_deadzone(10, 10), 0);
+        assert_eq!(apply_trigger_deadzone(255, 10), 255);
+        assert_eq!(apply_trigger_deadzone(11, 10), 1);
+    }
+
+    #[test]
+    fn test_canonicalize_matches_manual_composition() {
+        let transforms = Transforms::new()
+            .calibration(AnalogStick::new(300, -200), AnalogStick::new(0, 50))
+            .deadzone(2000)
+            .expo(40)
+            .stick_limit(28000)
+            .trigger_deadzone(20)
+            .trigger_limit(240);
+
+        let state = GamepadState {
+            buttons: Buttons::X,
+            left_stick: AnalogStick::new(2100, -20000),
+            right_stick: AnalogStick::new(32767, -32768),
+            left_trigger: 15,
+            right_trigger: 255,
+        };
+
+        let manual = |v: i16, c: i16| {
+            clamp_axis(
+                apply_expo(apply_deadzone(calibrate_axis(v, c), 2000), 40),
+                28000,
+            )
+        };
+
+        let out = state.canonicalize(&transforms);
+        assert_eq!(out.buttons, Buttons::X);
+        assert_eq!(out.left_stick.x, manual(2100, 300));
+        assert_eq!(out.left_stick.y, manual(-20000, -200));
+        assert_eq!(out.right_stick.x, manual(32767, 0));
+        assert_eq!(out.right_stick.y, manual(-32768, 50));
+        assert_eq!(out.left_trigger, 0);
+        assert_eq!(out.right_trigger, 240);
+
+        // Calibrated value falls inside the deadzone
+        assert_eq!(out.left_stick.x, 0);
+        // Full deflection survives deadzone and expo, then hits the limit
+        assert_eq!(out.right_stick.x, 28000);
+        assert_eq!(out.right_stick.y, -28000);
+    }
+
+    #[test]
+    fn test_canonicalize_order_matters() {
+        // Clamping before the deadzone rescale would give a different result
+        let transforms = Transforms::new().deadzone(10000).stick_limit(20000);
+        let state = GamepadState {
+            left_stick: AnalogStick::new(32767, 0),
+            ..GamepadState::neutral()
+        };
+        let out = state.canonicalize(&transforms);
+        assert_eq!(out.left_stick.x, 20000);
+        assert_ne!(
+            out.left_stick.x,
+            apply_deadzone(clamp_axis(32767, 20000), 10000)
+        );
+    }
+
+    #[test]
+    fn test_left_swap_xy_leaves_right_stick() {
+        let state = GamepadState {
+            left_stick: AnalogStick::new(1000, -2000),
+            right_stick: AnalogStick::new(3000, -4000),
+            ..GamepadState::neutral()
+        };
+
+        let out = state.canonicalize(&Transforms::new().swap_xy(true, false));
+        assert_eq!(out.left_stick, AnalogStick::new(-2000, 1000));
+        assert_eq!(out.right_stick, state.right_stick);
+
+        let out = state.canonicalize(&Transforms::new().swap_xy(false, true));
+        assert_eq!(out.left_stick, state.left_stick);
+        assert_eq!(out.right_stick, AnalogStick::new(-4000, 3000));
+    }
+
+    #[test]
+    fn test_swap_xy_after_calibration() {
+        // Centers are given in the hardware's axis order
+        let transforms = Transforms::new()
+            .calibration(AnalogStick::new(100, 0), AnalogStick::NEUTRAL)
+            .swap_xy(true, false);
+        let state = GamepadState {
+            left_stick: AnalogStick::new(100, 500),
+            ..GamepadState::neutral()
+        };
+        assert_eq!(
+            state.canonicalize(&transforms).left_stick,
+            AnalogStick::new(500, 0)
+        );
+    }
+}
@@ -19,6 +19,9 @@ use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
 /// ```
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// Serialized as the raw bitfield, which stays stable as names change
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Buttons(pub u16);
 
 impl Buttons {
@@ -39,6 +42,10 @@ impl Buttons {
     pub const DPAD_LEFT: Self = Self(1 << 13);
     pub const DPAD_RIGHT: Self = Self(1 << 14);
 
+    /// All four D-pad directions.
+    pub const DPAD: Self =
+        Self(Self::DPAD_UP.0 | Self::DPAD_DOWN.0 | Self::DPAD_LEFT.0 | Self::DPAD_RIGHT.0);
+
     /// No buttons pressed.
     pub const NONE: Self = Self(0);
 
@@ -49,6 +56,23 @@ impl Buttons {
         (self.0 & button.0) == button.0
     }
 
+    /// Check if any of the given buttons is pressed.
+    ///
+    /// Unlike [`contains`](Self::contains), which needs every bit of `mask`,
+    /// one pressed button is enough. An empty mask never matches.
+    #[inline]
+    #[must_use]
+    pub const fn any(self, mask: Buttons) -> bool {
+        (self.0 & mask.0) != 0
+    }
+
+    /// Number of pressed buttons.
+    #[inline]
+    #[must_use]
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
     /// Check if the given button is pressed (alias for contains).
     #[inline]
     #[must_use]
@@ -79,8 +103,318 @@ impl Buttons {
     pub const fn is_empty(self) -> bool {
         self.0 == 0
     }
+
+    /// Iterate over the button presses and releases between two states.
+    ///
+    /// Yields one [`ButtonEvent`] per changed bit, in ascending bit order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{ButtonEvent, Buttons};
+    ///
+    /// let mut edges = Buttons::edges(Buttons::A | Buttons::B, Buttons::B | Buttons::X);
+    /// assert_eq!(edges.next(), Some(ButtonEvent::Released(Buttons::A)));
+    /// assert_eq!(edges.next(), Some(ButtonEvent::Pressed(Buttons::X)));
+    /// assert_eq!(edges.next(), None);
+    /// ```
+    #[inline]
+    pub const fn edges(prev: Buttons, curr: Buttons) -> ButtonEdges {
+        ButtonEdges {
+            prev: prev.0,
+            changed: prev.0 ^ curr.0,
+        }
+    }
+
+    /// Iterate over the pressed buttons as single-bit values.
+    ///
+    /// Yields in ascending bit order, so the constants come out in the order
+    /// they are declared (`A`, `B`, `X`, ...).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::Buttons;
+    ///
+    /// let mut pressed = (Buttons::START | Buttons::A).iter_pressed();
+    /// assert_eq!(pressed.next(), Some(Buttons::A));
+    /// assert_eq!(pressed.next(), Some(Buttons::START));
+    /// assert_eq!(pressed.next(), None);
+    /// ```
+    #[inline]
+    pub const fn iter_pressed(self) -> PressedButtons {
+        PressedButtons { remaining: self.0 }
+    }
+
+    /// Get the D-pad direction.
+    ///
+    /// Opposing directions pressed together cancel out, so `UP + DOWN` reads
+    /// as no vertical direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{Buttons, Dpad};
+    ///
+    /// assert_eq!((Buttons::DPAD_UP | Buttons::DPAD_LEFT).dpad(), Dpad::UpLeft);
+    /// assert_eq!((Buttons::DPAD_UP | Buttons::DPAD_DOWN).dpad(), Dpad::Neutral);
+    /// ```
+    #[must_use]
+    pub const fn dpad(self) -> Dpad {
+        Dpad::from_buttons(self)
+    }
+
+    /// Replace the D-pad bits with `dir`, leaving the other buttons unchanged.
+    #[inline]
+    pub fn set_dpad(&mut self, dir: Dpad) {
+        self.0 = (self.0 & !Self::DPAD.0) | dir.buttons().0;
+    }
+
+    /// Name of a single button, matching its constant (`"A"`, `"DPAD_UP"`, ...).
+    ///
+    /// Returns `None` for the unassigned bit 15, an empty value, or more than
+    /// one button.
+    #[must_use]
+    pub const fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::LB => "LB",
+            Self::RB => "RB",
+            Self::BACK => "BACK",
+            Self::START => "START",
+            Self::GUIDE => "GUIDE",
+            Self::LS => "LS",
+            Self::RS => "RS",
+            Self::DPAD_UP => "DPAD_UP",
+            Self::DPAD_DOWN => "DPAD_DOWN",
+            Self::DPAD_LEFT => "DPAD_LEFT",
+            Self::DPAD_RIGHT => "DPAD_RIGHT",
+            _ => return None,
+        })
+    }
+}
+
+/// D-pad direction, clockwise from up.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Dpad {
+    /// No direction pressed
+    #[default]
+    Neutral,
+    /// Up
+    Up,
+    /// Up and right
+    UpRight,
+    /// Right
+    Right,
+    /// Down and right
+    DownRight,
+    /// Down
+    Down,
+    /// Down and left
+    DownLeft,
+    /// Left
+    Left,
+    /// Up and left
+    UpLeft,
+}
+
+impl Dpad {
+    /// The eight directions, clockwise from up.
+    pub const DIRECTIONS: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Hat switch value of the centered (neutral) D-pad.
+    ///
+    /// HID hat switches report 0-7 for the eight directions and a value
+    /// outside the logical range (the null state) when centered.
+    pub const HAT_CENTERED: u8 = 8;
+
+    /// Encode as a HID hat switch value: 0 = up, clockwise in 45 degree
+    /// steps to 7 = up-left, [`HAT_CENTERED`](Self::HAT_CENTERED) = neutral.
+    #[must_use]
+    pub const fn hat(self) -> u8 {
+        match self {
+            Self::Neutral => Self::HAT_CENTERED,
+            Self::Up => 0,
+            Self::UpRight => 1,
+            Self::Right => 2,
+            Self::DownRight => 3,
+            Self::Down => 4,
+            Self::DownLeft => 5,
+            Self::Left => 6,
+            Self::UpLeft => 7,
+        }
+    }
+
+    /// Decode the D-pad bits of `buttons` (see [`Buttons::dpad`]).
+    #[must_use]
+    pub const fn from_buttons(buttons: Buttons) -> Self {
+        let up = buttons.contains(Buttons::DPAD_UP);
+        let down = buttons.contains(Buttons::DPAD_DOWN);
+        let left = buttons.contains(Buttons::DPAD_LEFT);
+        let right = buttons.contains(Buttons::DPAD_RIGHT);
+
+        match (up != down, up, left != right, left) {
+            (false, _, false, _) => Self::Neutral,
+            (true, true, false, _) => Self::Up,
+            (true, false, false, _) => Self::Down,
+            (false, _, true, true) => Self::Left,
+            (false, _, true, false) => Self::Right,
+            (true, true, true, false) => Self::UpRight,
+            (true, true, true, true) => Self::UpLeft,
+            (true, false, true, false) => Self::DownRight,
+            (true, false, true, true) => Self::DownLeft,
+        }
+    }
+
+    /// Get the D-pad buttons pressed for this direction.
+    #[must_use]
+    pub const fn buttons(self) -> Buttons {
+        let (up, down, left, right) = (
+            Buttons::DPAD_UP.0,
+            Buttons::DPAD_DOWN.0,
+            Buttons::DPAD_LEFT.0,
+            Buttons::DPAD_RIGHT.0,
+        );
+        Buttons(match self {
+            Self::Neutral => 0,
+            Self::Up => up,
+            Self::UpRight => up | right,
+            Self::Right => right,
+            Self::DownRight => down | right,
+            Self::Down => down,
+            Self::DownLeft => down | left,
+            Self::Left => left,
+            Self::UpLeft => up | left,
+        })
+    }
+}
+
+/// How [`GamepadState::sanitize_dpad`] resolves opposing D-pad directions.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DpadResolution {
+    /// Release both directions of the pair.
+    #[default]
+    Neutral,
+    /// Keep the direction pressed most recently, i.e. the one that was not
+    /// pressed in the previous state. Falls back to [`Neutral`](Self::Neutral)
+    /// if both or neither were pressed before.
+    LastWins,
+}
+
+/// Resolve one pair of opposing directions in `buttons`.
+const fn resolve_opposing(
+    buttons: u16,
+    previous: u16,
+    a: Buttons,
+    b: Buttons,
+    resolution: DpadResolution,
+) -> u16 {
+    let pair = a.0 | b.0;
+    if buttons & pair != pair {
+        return buttons;
+    }
+
+    let keep = match resolution {
+        DpadResolution::LastWins => match previous & pair {
+            p if p == a.0 => b.0,
+            p if p == b.0 => a.0,
+            _ => 0,
+        },
+        DpadResolution::Neutral => 0,
+    };
+    (buttons & !pair) | keep
+}
+
+/// A single button transition between two [`Buttons`] states.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ButtonEvent {
+    /// The button went from released to pressed.
+    Pressed(Buttons),
+    /// The button went from pressed to released.
+    Released(Buttons),
+}
+
+/// Iterator over button transitions, created by [`Buttons::edges`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ButtonEdges {
+    prev: u16,
+    /// Bits that still have to be reported
+    changed: u16,
+}
+
+impl Iterator for ButtonEdges {
+    type Item = ButtonEvent;
+
+    fn next(&mut self) -> Option<ButtonEvent> {
+        if self.changed == 0 {
+            return None;
+        }
+
+        // Lowest changed bit first
+        let bit = self.changed & self.changed.wrapping_neg();
+        self.changed &= !bit;
+
+        Some(if self.prev & bit != 0 {
+            ButtonEvent::Released(Buttons(bit))
+        } else {
+            ButtonEvent::Pressed(Buttons(bit))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.changed.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for ButtonEdges {}
+
+/// Iterator over pressed buttons, created by [`Buttons::iter_pressed`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PressedButtons {
+    /// Bits that still have to be reported
+    remaining: u16,
+}
+
+impl Iterator for PressedButtons {
+    type Item = Buttons;
+
+    fn next(&mut self) -> Option<Buttons> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // Lowest pressed bit first
+        let bit = self.remaining & self.remaining.wrapping_neg();
+        self.remaining &= !bit;
+        Some(Buttons(bit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining.count_ones() as usize;
+        (n, Some(n))
+    }
 }
 
+impl ExactSizeIterator for PressedButtons {}
+
 impl BitOr for Buttons {
     type Output = Self;
 
@@ -122,11 +456,34 @@ impl Not for Buttons {
     }
 }
 
+/// Pressed button names joined by `+` (e.g. `A+START`), or `NONE`.
+///
+/// Unassigned bits print as `BIT<n>`.
+impl core::fmt::Display for Buttons {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_empty() {
+            return f.write_str("NONE");
+        }
+
+        for (i, button) in self.iter_pressed().enumerate() {
+            if i > 0 {
+                f.write_str("+")?;
+            }
+            match button.name() {
+                Some(name) => f.write_str(name)?,
+                None => write!(f, "BIT{}", button.0.trailing_zeros())?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Analog stick with X/Y axes.
 ///
 /// Range: [-32768, 32767] for full precision.
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnalogStick {
     pub x: i16,
     pub y: i16,
@@ -139,6 +496,15 @@ impl AnalogStick {
     }
 
     pub const NEUTRAL: Self = Self { x: 0, y: 0 };
+
+    /// Return the stick with its X and Y axes exchanged.
+    #[must_use]
+    pub const fn swap_xy(self) -> Self {
+        Self {
+            x: self.y,
+            y: self.x,
+        }
+    }
 }
 
 /// Complete gamepad state snapshot.
@@ -149,6 +515,7 @@ impl AnalogStick {
 /// - 2 triggers (left/right, 0-255)
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadState {
     pub buttons: Buttons,
     pub left_stick: AnalogStick,
@@ -181,6 +548,220 @@ impl GamepadState {
             GamepadFieldUpdate::RightStickY(y) => self.right_stick.y = y,
             GamepadFieldUpdate::LeftTrigger(t) => self.left_trigger = t,
             GamepadFieldUpdate::RightTrigger(t) => self.right_trigger = t,
+            GamepadFieldUpdate::ButtonsSet(b) => self.buttons |= b,
+            GamepadFieldUpdate::ButtonsClear(b) => self.buttons &= !b,
+            GamepadFieldUpdate::LeftStickXDelta(d) => {
+                self.left_stick.x = self.left_stick.x.saturating_add(d);
+            }
+            GamepadFieldUpdate::LeftStickYDelta(d) => {
+                self.left_stick.y = self.left_stick.y.saturating_add(d);
+            }
+            GamepadFieldUpdate::RightStickXDelta(d) => {
+                self.right_stick.x = self.right_stick.x.saturating_add(d);
+            }
+            GamepadFieldUpdate::RightStickYDelta(d) => {
+                self.right_stick.y = self.right_stick.y.saturating_add(d);
+            }
+        }
+    }
+
+    /// Updates that turn `self` into `other`, one per changed field.
+    ///
+    /// Fields are yielded in `G` message order and only absolute updates are
+    /// used, so applying them to `self` with
+    /// [`apply_update`](Self::apply_update) reproduces `other`. Yields nothing
+    /// if the states are equal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{GamepadFieldUpdate, GamepadState};
+    ///
+    /// let old = GamepadState::neutral();
+    /// let new = GamepadState { left_trigger: 200, ..old };
+    ///
+    /// let mut updates = old.diff(&new);
+    /// assert_eq!(updates.next(), Some(GamepadFieldUpdate::LeftTrigger(200)));
+    /// assert_eq!(updates.next(), None);
+    /// ```
+    pub fn diff(&self, other: &GamepadState) -> impl Iterator<Item = GamepadFieldUpdate> {
+        [
+            (
+                self.buttons != other.buttons,
+                GamepadFieldUpdate::Buttons(other.buttons),
+            ),
+            (
+                self.left_stick.x != other.left_stick.x,
+                GamepadFieldUpdate::LeftStickX(other.left_stick.x),
+            ),
+            (
+                self.left_stick.y != other.left_stick.y,
+                GamepadFieldUpdate::LeftStickY(other.left_stick.y),
+            ),
+            (
+                self.right_stick.x != other.right_stick.x,
+                GamepadFieldUpdate::RightStickX(other.right_stick.x),
+            ),
+            (
+                self.right_stick.y != other.right_stick.y,
+                GamepadFieldUpdate::RightStickY(other.right_stick.y),
+            ),
+            (
+                self.left_trigger != other.left_trigger,
+                GamepadFieldUpdate::LeftTrigger(other.left_trigger),
+            ),
+            (
+                self.right_trigger != other.right_trigger,
+                GamepadFieldUpdate::RightTrigger(other.right_trigger),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(changed, update)| changed.then_some(update))
+    }
+
+    /// Clear impossible D-pad combinations (Up+Down, Left+Right).
+    ///
+    /// A physical D-pad cannot press opposing directions at once, but a
+    /// corrupted or badly written sender can, and games react unpredictably.
+    /// `previous` is the button state before this one and is only used by
+    /// [`DpadResolution::LastWins`]. Diagonals are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{Buttons, DpadResolution, GamepadState};
+    ///
+    /// let mut state = GamepadState {
+    ///     buttons: Buttons::DPAD_UP | Buttons::DPAD_DOWN | Buttons::DPAD_LEFT,
+    ///     ..GamepadState::neutral()
+    /// };
+    /// state.sanitize_dpad(DpadResolution::Neutral, Buttons::NONE);
+    /// assert_eq!(state.buttons, Buttons::DPAD_LEFT);
+    /// ```
+    pub fn sanitize_dpad(&mut self, resolution: DpadResolution, previous: Buttons) {
+        let mut buttons = self.buttons.0;
+        buttons = resolve_opposing(
+            buttons,
+            previous.0,
+            Buttons::DPAD_UP,
+            Buttons::DPAD_DOWN,
+            resolution,
+        );
+        buttons = resolve_opposing(
+            buttons,
+            previous.0,
+            Buttons::DPAD_LEFT,
+            Buttons::DPAD_RIGHT,
+            resolution,
+        );
+        self.buttons = Buttons(buttons);
+    }
+
+    /// Compare two states, allowing for noise in the analog values.
+    ///
+    /// Buttons must match exactly. Each stick axis may differ by at most
+    /// `stick_tol` and each trigger by at most `trig_tol`. Useful for tests
+    /// comparing states after lossy conversions (e.g. i16 → i8 → i16).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::{AnalogStick, GamepadState};
+    ///
+    /// let a = GamepadState { left_stick: AnalogStick::new(1000, 0), ..GamepadState::neutral() };
+    /// let b = GamepadState { left_stick: AnalogStick::new(1010, 0), ..GamepadState::neutral() };
+    /// assert!(a.approx_eq(&b, 16, 0));
+    /// assert!(!a.approx_eq(&b, 8, 0));
+    /// ```
+    #[must_use]
+    pub fn approx_eq(&self, other: &GamepadState, stick_tol: i16, trig_tol: u8) -> bool {
+        let axis_close =
+            |a: i16, b: i16| (i32::from(a) - i32::from(b)).abs() <= i32::from(stick_tol);
+        let trigger_close = |a: u8, b: u8| a.abs_diff(b) <= trig_tol;
+
+        self.buttons == other.buttons
+            && axis_close(self.left_stick.x, other.left_stick.x)
+            && axis_close(self.left_stick.y, other.left_stick.y)
+            && axis_close(self.right_stick.x, other.right_stick.x)
+            && axis_close(self.right_stick.y, other.right_stick.y)
+            && trigger_close(self.left_trigger, other.left_trigger)
+            && trigger_close(self.right_trigger, other.right_trigger)
+    }
+}
+
+/// Compact one-line form for logs, e.g. `A+START LS(1000,-500) RS(0,0) LT0 RT128`.
+impl core::fmt::Display for GamepadState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} LS({},{}) RS({},{}) LT{} RT{}",
+            self.buttons,
+            self.left_stick.x,
+            self.left_stick.y,
+            self.right_stick.x,
+            self.right_stick.y,
+            self.left_trigger,
+            self.right_trigger
+        )
+    }
+}
+
+/// Full-scale stick value used for float conversion.
+#[cfg(feature = "std")]
+const STICK_SCALE: f32 = i16::MAX as f32;
+
+/// Full-scale trigger value used for float conversion.
+#[cfg(feature = "std")]
+const TRIGGER_SCALE: f32 = u8::MAX as f32;
+
+/// Gamepad state with analog values as floats, for host visualization.
+///
+/// Sticks are in `-1.0..=1.0`, triggers in `0.0..=1.0`.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct NormalizedState {
+    pub buttons: Buttons,
+    pub left_x: f32,
+    pub left_y: f32,
+    pub right_x: f32,
+    pub right_y: f32,
+    pub left_trigger: f32,
+    pub right_trigger: f32,
+}
+
+#[cfg(feature = "std")]
+impl GamepadState {
+    /// Convert to normalized floats (see [`NormalizedState`]).
+    ///
+    /// `i16::MAX` maps to `1.0`; `i16::MIN` is clamped to `-1.0`.
+    #[must_use]
+    pub fn to_normalized(&self) -> NormalizedState {
+        let axis = |v: i16| (f32::from(v) / STICK_SCALE).max(-1.0);
+        let trigger = |v: u8| f32::from(v) / TRIGGER_SCALE;
+
+        NormalizedState {
+            buttons: self.buttons,
+            left_x: axis(self.left_stick.x),
+            left_y: axis(self.left_stick.y),
+            right_x: axis(self.right_stick.x),
+            right_y: axis(self.right_stick.y),
+            left_trigger: trigger(self.left_trigger),
+            right_trigger: trigger(self.right_trigger),
+        }
+    }
+
+    /// Build a state from normalized floats, clamping out-of-range values.
+    #[must_use]
+    pub fn from_normalized(normalized: &NormalizedState) -> Self {
+        let axis = |v: f32| (v.clamp(-1.0, 1.0) * STICK_SCALE).round() as i16;
+        let trigger = |v: f32| (v.clamp(0.0, 1.0) * TRIGGER_SCALE).round() as u8;
+
+        Self {
+            buttons: normalized.buttons,
+            left_stick: AnalogStick::new(axis(normalized.left_x), axis(normalized.left_y)),
+            right_stick: AnalogStick::new(axis(normalized.right_x), axis(normalized.right_y)),
+            left_trigger: trigger(normalized.left_trigger),
+            right_trigger: trigger(normalized.right_trigger),
         }
     }
 }
@@ -191,6 +772,7 @@ impl GamepadState {
 /// without sending the full gamepad state.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[must_use]
 pub enum GamepadFieldUpdate {
     /// Update buttons (B field)
@@ -207,12 +789,430 @@ pub enum GamepadFieldUpdate {
     LeftTrigger(u8),
     /// Update right trigger (RT field)
     RightTrigger(u8),
+    /// Press the given buttons, leaving the others unchanged (B+ field)
+    ButtonsSet(Buttons),
+    /// Release the given buttons, leaving the others unchanged (B- field)
+    ButtonsClear(Buttons),
+    /// Add to left stick X, saturating at the axis limits (LX+ field)
+    LeftStickXDelta(i16),
+    /// Add to left stick Y, saturating at the axis limits (LY+ field)
+    LeftStickYDelta(i16),
+    /// Add to right stick X, saturating at the axis limits (RX+ field)
+    RightStickXDelta(i16),
+    /// Add to right stick Y, saturating at the axis limits (RY+ field)
+    RightStickYDelta(i16),
+}
+
+impl GamepadFieldUpdate {
+    /// Protocol field tokens of all variants.
+    ///
+    /// The order is stable and matches the field order of the `G` full state
+    /// message: buttons, left stick X/Y, right stick X/Y, left/right trigger.
+    /// The partial button tokens (set bits, clear bits) follow, then the
+    /// relative stick tokens.
+    pub const FIELD_TOKENS: [&'static str; 13] = [
+        "B", "LX", "LY", "RX", "RY", "LT", "RT", "B+", "B-", "LX+", "LY+", "RX+", "RY+",
+    ];
+
+    /// Absolute update setting one stick axis to `value`.
+    pub const fn stick(axis: StickAxis, value: i16) -> Self {
+        match axis {
+            StickAxis::LeftX => Self::LeftStickX(value),
+            StickAxis::LeftY => Self::LeftStickY(value),
+            StickAxis::RightX => Self::RightStickX(value),
+            StickAxis::RightY => Self::RightStickY(value),
+        }
+    }
+
+    /// Relative update adding `delta` to one stick axis.
+    pub const fn stick_delta(axis: StickAxis, delta: i16) -> Self {
+        match axis {
+            StickAxis::LeftX => Self::LeftStickXDelta(delta),
+            StickAxis::LeftY => Self::LeftStickYDelta(delta),
+            StickAxis::RightX => Self::RightStickXDelta(delta),
+            StickAxis::RightY => Self::RightStickYDelta(delta),
+        }
+    }
+
+    /// Protocol field token used in `U<field>:<value>` messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamepad_proto::GamepadFieldUpdate;
+    ///
+    /// assert_eq!(GamepadFieldUpdate::LeftStickX(-500).field_token(), "LX");
+    /// ```
+    #[must_use]
+    pub const fn field_token(&self) -> &'static str {
+        Self::FIELD_TOKENS[self.field_index()]
+    }
+
+    /// Index of this variant's token in [`FIELD_TOKENS`](Self::FIELD_TOKENS).
+    #[must_use]
+    pub const fn field_index(&self) -> usize {
+        match self {
+            Self::Buttons(_) => 0,
+            Self::LeftStickX(_) => 1,
+            Self::LeftStickY(_) => 2,
+            Self::RightStickX(_) => 3,
+            Self::RightStickY(_) => 4,
+            Self::LeftTrigger(_) => 5,
+            Self::RightTrigger(_) => 6,
+            Self::ButtonsSet(_) => 7,
+            Self::ButtonsClear(_) => 8,
+            Self::LeftStickXDelta(_) => 9,
+            Self::LeftStickYDelta(_) => 10,
+            Self::RightStickXDelta(_) => 11,
+            Self::RightStickYDelta(_) => 12,
+        }
+    }
+}
+
+/// Identifies a single analog stick axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StickAxis {
+    /// Left stick X axis (LX)
+    LeftX,
+    /// Left stick Y axis (LY)
+    LeftY,
+    /// Right stick X axis (RX)
+    RightX,
+    /// Right stick Y axis (RY)
+    RightY,
+}
+
+/// One payload field of a full state (`G`) message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StateField {
+    /// Button bitfield (4 hex digits)
+    Buttons,
+    /// Left stick X (signed i16)
+    LeftX,
+    /// Left stick Y (signed i16)
+    LeftY,
+    /// Right stick X (signed i16)
+    RightX,
+    /// Right stick Y (signed i16)
+    RightY,
+    /// Left trigger (unsigned u8)
+    LeftTrigger,
+    /// Right trigger (unsigned u8)
+    RightTrigger,
+}
+
+impl StateField {
+    /// All fields, in the documented protocol order.
+    pub const ALL: [Self; 7] = [
+        Self::Buttons,
+        Self::LeftX,
+        Self::LeftY,
+        Self::RightX,
+        Self::RightY,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+    ];
+}
+
+/// Order of the seven payload fields in a full state (`G`) message.
+///
+/// The default is the documented order `buttons:lx:ly:rx:ry:lt:rt`. A custom
+/// layout lets the parser and serializer interoperate with a fixed external
+/// sender that emits the fields differently, e.g. triggers before sticks.
+///
+/// # Example
+///
+/// ```
+/// use gamepad_proto::{FieldLayout, StateField};
+///
+/// let triggers_first = FieldLayout::new([
+///     StateField::Buttons,
+///     StateField::LeftTrigger,
+///     StateField::RightTrigger,
+///     StateField::LeftX,
+///     StateField::LeftY,
+///     StateField::RightX,
+///     StateField::RightY,
+/// ]);
+/// assert!(triggers_first.is_some());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FieldLayout([StateField; 7]);
+
+impl FieldLayout {
+    /// The documented protocol order.
+    pub const DEFAULT: Self = Self(StateField::ALL);
+
+    /// Create a layout from the on-wire field order.
+    ///
+    /// Returns `None` unless every field appears exactly once.
+    #[must_use]
+    pub const fn new(order: [StateField; 7]) -> Option<Self> {
+        let mut seen = 0u8;
+        let mut i = 0;
+        while i < order.len() {
+            seen |= 1 << order[i] as u8;
+            i += 1;
+        }
+        if seen == 0x7F {
+            Some(Self(order))
+        } else {
+            None
+        }
+    }
+
+    /// Get the on-wire field order.
+    #[inline]
+    #[must_use]
+    pub const fn order(&self) -> &[StateField; 7] {
+        &self.0
+    }
+}
+
+impl Default for FieldLayout {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Per-axis stick center trim, set at runtime with `C` protocol messages.
+///
+/// Each offset is subtracted (saturating) from the matching raw stick value,
+/// so an axis reads zero when its raw value equals the trim offset.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StickTrim {
+    /// Left stick X/Y offsets.
+    pub left: AnalogStick,
+    /// Right stick X/Y offsets.
+    pub right: AnalogStick,
+}
+
+impl StickTrim {
+    /// Create a trim with all offsets zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            left: AnalogStick::NEUTRAL,
+            right: AnalogStick::NEUTRAL,
+        }
+    }
+
+    /// Set the offset for a single axis.
+    pub fn set(&mut self, axis: StickAxis, offset: i16) {
+        match axis {
+            StickAxis::LeftX => self.left.x = offset,
+            StickAxis::LeftY => self.left.y = offset,
+            StickAxis::RightX => self.right.x = offset,
+            StickAxis::RightY => self.right.y = offset,
+        }
+    }
+
+    /// Return `state` with the trim offsets subtracted from the sticks.
+    #[must_use]
+    pub fn apply(&self, state: &GamepadState) -> GamepadState {
+        let trim = |s: AnalogStick, t: AnalogStick| {
+            AnalogStick::new(s.x.saturating_sub(t.x), s.y.saturating_sub(t.y))
+        };
+
+        GamepadState {
+            left_stick: trim(state.left_stick, self.left),
+            right_stick: trim(state.right_stick, self.right),
+            ..*state
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dpad_directions_round_trip() {
+        for dir in Dpad::DIRECTIONS {
+            let mut buttons = Buttons::A | Buttons::DPAD_UP | Buttons::DPAD_RIGHT;
+            buttons.set_dpad(dir);
+            assert_eq!(buttons.dpad(), dir);
+            assert_eq!(buttons, Buttons::A | dir.buttons());
+        }
+
+        assert_eq!(
+            Dpad::UpRight.buttons(),
+            Buttons::DPAD_UP | Buttons::DPAD_RIGHT
+        );
+        assert_eq!(
+            Dpad::DownLeft.buttons(),
+            Buttons::DPAD_DOWN | Buttons::DPAD_LEFT
+        );
+
+        let mut buttons = Buttons::B | Dpad::Left.buttons();
+        buttons.set_dpad(Dpad::Neutral);
+        assert_eq!(buttons, Buttons::B);
+        assert_eq!(buttons.dpad(), Dpad::Neutral);
+    }
+
+    #[test]
+    fn test_dpad_hat_codes() {
+        let codes: [u8; 8] = core::array::from_fn(|i| Dpad::DIRECTIONS[i].hat());
+        assert_eq!(codes, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(Dpad::Up.hat(), 0);
+        assert_eq!(Dpad::Right.hat(), 2);
+        assert_eq!(Dpad::Down.hat(), 4);
+        assert_eq!(Dpad::Left.hat(), 6);
+        assert_eq!(Dpad::Neutral.hat(), Dpad::HAT_CENTERED);
+
+        // Contradictory bits report a centered hat
+        let buttons = Buttons::DPAD_UP | Buttons::DPAD_DOWN;
+        assert_eq!(buttons.dpad().hat(), 8);
+    }
+
+    #[test]
+    fn test_dpad_contradictory_bits() {
+        let vertical = Buttons::DPAD_UP | Buttons::DPAD_DOWN;
+        let horizontal = Buttons::DPAD_LEFT | Buttons::DPAD_RIGHT;
+        assert_eq!(vertical.dpad(), Dpad::Neutral);
+        assert_eq!(horizontal.dpad(), Dpad::Neutral);
+        assert_eq!((vertical | horizontal).dpad(), Dpad::Neutral);
+
+        // The remaining axis still counts
+        assert_eq!((vertical | Buttons::DPAD_LEFT).dpad(), Dpad::Left);
+        assert_eq!((horizontal | Buttons::DPAD_DOWN).dpad(), Dpad::Down);
+    }
+
+    fn dpad(buttons: Buttons) -> GamepadState {
+        GamepadState {
+            buttons,
+            ..GamepadState::neutral()
+        }
+    }
+
+    #[test]
+    fn test_sanitize_dpad_neutral() {
+        let mut state = dpad(Buttons::A | Buttons::DPAD_UP | Buttons::DPAD_DOWN);
+        state.sanitize_dpad(DpadResolution::Neutral, Buttons::DPAD_UP);
+        assert_eq!(state.buttons, Buttons::A);
+
+        let all = Buttons::DPAD_UP | Buttons::DPAD_DOWN | Buttons::DPAD_LEFT | Buttons::DPAD_RIGHT;
+        assert_eq!(Buttons::DPAD, all);
+        let mut state = dpad(all);
+        state.sanitize_dpad(DpadResolution::default(), Buttons::NONE);
+        assert_eq!(state.buttons, Buttons::NONE);
+    }
+
+    #[test]
+    fn test_sanitize_dpad_last_wins() {
+        // Down was added to a held Up: Down wins
+        let mut state = dpad(Buttons::DPAD_UP | Buttons::DPAD_DOWN);
+        state.sanitize_dpad(DpadResolution::LastWins, Buttons::DPAD_UP);
+        assert_eq!(state.buttons, Buttons::DPAD_DOWN);
+
+        let mut state = dpad(Buttons::DPAD_LEFT | Buttons::DPAD_RIGHT);
+        state.sanitize_dpad(DpadResolution::LastWins, Buttons::DPAD_RIGHT | Buttons::B);
+        assert_eq!(state.buttons, Buttons::DPAD_LEFT);
+
+        // No way to tell which came last
+        let mut state = dpad(Buttons::DPAD_UP | Buttons::DPAD_DOWN);
+        state.sanitize_dpad(DpadResolution::LastWins, Buttons::NONE);
+        assert_eq!(state.buttons, Buttons::NONE);
+    }
+
+    #[test]
+    fn test_sanitize_dpad_keeps_diagonals() {
+        for diagonal in [
+            Buttons::DPAD_UP | Buttons::DPAD_LEFT,
+            Buttons::DPAD_UP | Buttons::DPAD_RIGHT,
+            Buttons::DPAD_DOWN | Buttons::DPAD_LEFT,
+            Buttons::DPAD_DOWN | Buttons::DPAD_RIGHT,
+        ] {
+            for resolution in [DpadResolution::Neutral, DpadResolution::LastWins] {
+                let mut state = dpad(diagonal);
+                state.sanitize_dpad(resolution, Buttons::NONE);
+                assert_eq!(state.buttons, diagonal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let state = GamepadState {
+            buttons: Buttons::A,
+            left_stick: AnalogStick::new(100, -100),
+            ..GamepadState::neutral()
+        };
+        assert_eq!(state.diff(&state).count(), 0);
+    }
+
+    #[test]
+    fn test_diff_single_field() {
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            right_stick: AnalogStick::new(0, -32768),
+            ..old
+        };
+
+        let mut updates = old.diff(&new);
+        assert_eq!(
+            updates.next(),
+            Some(GamepadFieldUpdate::RightStickY(-32768))
+        );
+        assert_eq!(updates.next(), None);
+    }
+
+    #[test]
+    fn test_diff_all_fields_reproduces_new() {
+        let old = GamepadState::neutral();
+        let new = GamepadState {
+            buttons: Buttons::START | Buttons::Y,
+            left_stick: AnalogStick::new(1, 2),
+            right_stick: AnalogStick::new(-3, -4),
+            left_trigger: 5,
+            right_trigger: 255,
+        };
+
+        let mut updates = [GamepadFieldUpdate::Buttons(Buttons::NONE); 7];
+        let mut count = 0;
+        for update in old.diff(&new) {
+            updates[count] = update;
+            count += 1;
+        }
+        assert_eq!(count, 7);
+        assert_eq!(updates[0], GamepadFieldUpdate::Buttons(new.buttons));
+        assert_eq!(updates[6], GamepadFieldUpdate::RightTrigger(255));
+
+        let mut state = old;
+        for update in updates {
+            state.apply_update(update);
+        }
+        assert_eq!(state, new);
+    }
+
+    #[test]
+    fn test_buttons_edges() {
+        let mut edges = Buttons::edges(Buttons::A | Buttons::B, Buttons::B | Buttons::X);
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges.next(), Some(ButtonEvent::Released(Buttons::A)));
+        assert_eq!(edges.next(), Some(ButtonEvent::Pressed(Buttons::X)));
+        assert_eq!(edges.next(), None);
+    }
+
+    #[test]
+    fn test_buttons_edges_unchanged_and_high_bit() {
+        assert_eq!(Buttons::edges(Buttons::Y, Buttons::Y).next(), None);
+
+        let mut edges = Buttons::edges(Buttons::NONE, Buttons(1 << 15));
+        assert_eq!(edges.next(), Some(ButtonEvent::Pressed(Buttons(1 << 15))));
+        assert_eq!(edges.next(), None);
+
+        let all = Buttons::edges(Buttons(0xFFFF), Buttons::NONE);
+        assert_eq!(all.len(), 16);
+        assert!(all
+            .into_iter()
+            .all(|event| matches!(event, ButtonEvent::Released(_))));
+    }
+
     #[test]
     fn test_buttons_bitwise_or() {
         let buttons = Buttons::A | Buttons::B;
@@ -221,6 +1221,38 @@ mod tests {
         assert!(!buttons.contains(Buttons::X));
     }
 
+    #[test]
+    fn test_buttons_iter_pressed() {
+        assert_eq!((Buttons::A | Buttons::START).iter_pressed().count(), 2);
+        assert_eq!(Buttons::NONE.iter_pressed().next(), None);
+
+        let all = Buttons(0xFFFF);
+        assert_eq!(all.iter_pressed().len(), all.count() as usize);
+
+        let mut combined = Buttons::NONE;
+        let mut last = 0;
+        for button in all.iter_pressed() {
+            assert_eq!(button.count(), 1);
+            assert!(button.0 > last);
+            last = button.0;
+            combined |= button;
+        }
+        assert_eq!(combined, all);
+    }
+
+    #[test]
+    fn test_buttons_count_and_any() {
+        assert_eq!(Buttons::NONE.count(), 0);
+        assert_eq!((Buttons::A | Buttons::B | Buttons::X).count(), 3);
+        assert_eq!(Buttons(0xFFFF).count(), 16);
+
+        let pressed = Buttons::A;
+        assert!(pressed.any(Buttons::A | Buttons::Y));
+        assert!(!pressed.contains(Buttons::A | Buttons::Y));
+        assert!(!Buttons::B.any(Buttons::A | Buttons::Y));
+        assert!(!pressed.any(Buttons::NONE));
+    }
+
     #[test]
     fn test_buttons_set_clear() {
         let mut buttons = Buttons::NONE;
@@ -250,4 +1282,267 @@ mod tests {
         assert_eq!(stick.x, 0);
         assert_eq!(stick.y, 0);
     }
+
+    #[test]
+    fn test_approx_eq_after_report_round_trip() {
+        // Simulate the USB report's i16 -> i8 -> i16 stick conversion
+        fn round_trip(v: i16) -> i16 {
+            i16::from((v >> 8) as i8) << 8
+        }
+
+        let original = GamepadState {
+            buttons: Buttons::A | Buttons::START,
+            left_stick: AnalogStick::new(1234, -5678),
+            right_stick: AnalogStick::new(-32768, 32767),
+            left_trigger: 100,
+            right_trigger: 200,
+        };
+        let round_tripped = GamepadState {
+            left_stick: AnalogStick::new(
+                round_trip(original.left_stick.x),
+                round_trip(original.left_stick.y),
+            ),
+            right_stick: AnalogStick::new(
+                round_trip(original.right_stick.x),
+                round_trip(original.right_stick.y),
+            ),
+            ..original
+        };
+
+        assert_ne!(original, round_tripped);
+        assert!(original.approx_eq(&round_tripped, 255, 0));
+        assert!(round_tripped.approx_eq(&original, 255, 0));
+    }
+
+    #[test]
+    fn test_approx_eq_buttons_exact() {
+        let a = GamepadState::neutral();
+        let b = GamepadState {
+            buttons: Buttons::A,
+            ..GamepadState::neutral()
+        };
+        assert!(!a.approx_eq(&b, i16::MAX, u8::MAX));
+    }
+
+    #[test]
+    fn test_approx_eq_tolerance_bounds() {
+        let a = GamepadState::neutral();
+        let b = GamepadState {
+            right_stick: AnalogStick::new(0, -10),
+            left_trigger: 5,
+            ..GamepadState::neutral()
+        };
+        assert!(a.approx_eq(&b, 10, 5));
+        assert!(!a.approx_eq(&b, 9, 5));
+        assert!(!a.approx_eq(&b, 10, 4));
+
+        // Extreme axis difference does not overflow
+        let c = GamepadState {
+            left_stick: AnalogStick::new(i16::MIN, 0),
+            ..GamepadState::neutral()
+        };
+        let d = GamepadState {
+            left_stick: AnalogStick::new(i16::MAX, 0),
+            ..GamepadState::neutral()
+        };
+        assert!(!c.approx_eq(&d, i16::MAX, 0));
+    }
+
+    #[test]
+    fn test_stick_trim_zeroes_axis_at_offset() {
+        let mut trim = StickTrim::new();
+        trim.set(StickAxis::LeftX, 512);
+        trim.set(StickAxis::RightY, -300);
+
+        let raw = GamepadState {
+            buttons: Buttons::A,
+            left_stick: AnalogStick::new(512, 100),
+            right_stick: AnalogStick::new(7, -300),
+            left_trigger: 9,
+            right_trigger: 10,
+        };
+        let trimmed = trim.apply(&raw);
+
+        assert_eq!(trimmed.left_stick, AnalogStick::new(0, 100));
+        assert_eq!(trimmed.right_stick, AnalogStick::new(7, 0));
+        assert_eq!(trimmed.buttons, Buttons::A);
+        assert_eq!(trimmed.left_trigger, 9);
+        assert_eq!(trimmed.right_trigger, 10);
+    }
+
+    #[test]
+    fn test_stick_trim_saturates() {
+        let mut trim = StickTrim::new();
+        trim.set(StickAxis::LeftY, 1000);
+        let raw = GamepadState {
+            left_stick: AnalogStick::new(0, i16::MIN),
+            ..GamepadState::neutral()
+        };
+        assert_eq!(trim.apply(&raw).left_stick.y, i16::MIN);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_normalized_ranges() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+
+        let state = GamepadState {
+            buttons: Buttons::A,
+            left_stick: AnalogStick::new(i16::MAX, i16::MIN),
+            right_stick: AnalogStick::new(0, 0),
+            left_trigger: 0,
+            right_trigger: 255,
+        };
+        let n = state.to_normalized();
+        assert_eq!(n.buttons, Buttons::A);
+        assert!(close(n.left_x, 1.0));
+        assert!(close(n.left_y, -1.0));
+        assert!(n.left_y >= -1.0);
+        assert_eq!(n.right_x, 0.0);
+        assert_eq!(n.right_y, 0.0);
+        assert_eq!(n.left_trigger, 0.0);
+        assert_eq!(n.right_trigger, 1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_normalized_round_trip() {
+        let state = GamepadState {
+            buttons: Buttons::X | Buttons::LB,
+            left_stick: AnalogStick::new(-12345, 32767),
+            right_stick: AnalogStick::new(1, -32767),
+            left_trigger: 128,
+            right_trigger: 7,
+        };
+        assert_eq!(GamepadState::from_normalized(&state.to_normalized()), state);
+
+        // Out-of-range inputs are clamped
+        let wild = NormalizedState {
+            left_x: 2.0,
+            left_y: -2.0,
+            left_trigger: -0.5,
+            right_trigger: 1.5,
+            ..NormalizedState::default()
+        };
+        let clamped = GamepadState::from_normalized(&wild);
+        assert_eq!(clamped.left_stick, AnalogStick::new(i16::MAX, -i16::MAX));
+        assert_eq!(clamped.left_trigger, 0);
+        assert_eq!(clamped.right_trigger, 255);
+    }
+
+    #[test]
+    fn test_apply_update_set_then_clear_bits() {
+        let mut state = GamepadState {
+            buttons: Buttons::B,
+            ..GamepadState::neutral()
+        };
+
+        state.apply_update(GamepadFieldUpdate::ButtonsSet(Buttons::A | Buttons::X));
+        assert_eq!(state.buttons, Buttons::A | Buttons::B | Buttons::X);
+
+        state.apply_update(GamepadFieldUpdate::ButtonsClear(Buttons::A));
+        assert_eq!(state.buttons, Buttons::B | Buttons::X);
+
+        // Clearing an already released button changes nothing
+        state.apply_update(GamepadFieldUpdate::ButtonsClear(Buttons::Y));
+        assert_eq!(state.buttons, Buttons::B | Buttons::X);
+    }
+
+    #[test]
+    fn test_apply_update_deltas_saturate() {
+        let mut state = GamepadState::neutral();
+
+        for _ in 0..3 {
+            state.apply_update(GamepadFieldUpdate::LeftStickXDelta(12_000));
+            state.apply_update(GamepadFieldUpdate::RightStickYDelta(-12_000));
+        }
+        assert_eq!(state.left_stick.x, i16::MAX);
+        assert_eq!(state.right_stick.y, i16::MIN);
+
+        state.apply_update(GamepadFieldUpdate::LeftStickXDelta(-767));
+        assert_eq!(state.left_stick.x, 32_000);
+        state.apply_update(GamepadFieldUpdate::stick_delta(StickAxis::LeftY, -5));
+        state.apply_update(GamepadFieldUpdate::stick_delta(StickAxis::LeftY, 2));
+        assert_eq!(state.left_stick.y, -3);
+
+        // An absolute update still overrides the accumulated value
+        state.apply_update(GamepadFieldUpdate::stick(StickAxis::RightY, 100));
+        state.apply_update(GamepadFieldUpdate::RightStickYDelta(i16::MIN));
+        assert_eq!(state.right_stick.y, -32_668);
+        state.apply_update(GamepadFieldUpdate::RightStickYDelta(i16::MIN));
+        assert_eq!(state.right_stick.y, i16::MIN);
+        assert_eq!(state.right_stick.x, 0);
+    }
+
+    #[test]
+    fn test_field_layout_requires_each_field_once() {
+        assert_eq!(
+            FieldLayout::new(StateField::ALL),
+            Some(FieldLayout::DEFAULT)
+        );
+        assert_eq!(FieldLayout::default(), FieldLayout::DEFAULT);
+
+        let mut reversed = StateField::ALL;
+        reversed.reverse();
+        assert!(FieldLayout::new(reversed).is_some());
+
+        let mut duplicate = StateField::ALL;
+        duplicate[6] = StateField::LeftTrigger;
+        assert_eq!(FieldLayout::new(duplicate), None);
+    }
+
+    #[test]
+    fn test_display_state() {
+        extern crate std;
+        use std::format;
+
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::START,
+            left_stick: AnalogStick::new(1000, -500),
+            right_stick: AnalogStick::default(),
+            left_trigger: 0,
+            right_trigger: 128,
+        };
+        assert_eq!(
+            format!("{state}"),
+            "A+START LS(1000,-500) RS(0,0) LT0 RT128"
+        );
+        assert_eq!(
+            format!("{}", GamepadState::neutral()),
+            "NONE LS(0,0) RS(0,0) LT0 RT0"
+        );
+        assert_eq!(
+            format!("{}", Buttons::DPAD_LEFT | Buttons(1 << 15)),
+            "DPAD_LEFT+BIT15"
+        );
+        assert_eq!(Buttons::GUIDE.name(), Some("GUIDE"));
+        assert_eq!((Buttons::A | Buttons::B).name(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        let state = GamepadState {
+            buttons: Buttons::A | Buttons::START,
+            left_stick: AnalogStick::new(1000, -500),
+            right_stick: AnalogStick::default(),
+            left_trigger: 0,
+            right_trigger: 128,
+        };
+
+        // Buttons as the raw bitfield (A | START = 0x0081)
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            json,
+            r#"{"buttons":129,"left_stick":{"x":1000,"y":-500},"right_stick":{"x":0,"y":0},"left_trigger":0,"right_trigger":128}"#
+        );
+        assert_eq!(serde_json::from_str::<GamepadState>(&json).unwrap(), state);
+
+        let update = GamepadFieldUpdate::LeftStickX(-500);
+        let json = serde_json::to_string(&update).unwrap();
+        assert_eq!(
+            serde_json::from_str::<GamepadFieldUpdate>(&json).unwrap(),
+            update
+        );
+    }
 }
@@ -0,0 +1,203 @@
+//! Canonical protocol test vectors.
+//!
+//! Known messages paired with their exact wire bytes (including checksum and
+//! line ending). Sender implementations in other languages can check their
+//! output against these byte for byte; this crate's own tests assert that
+//! [`Serialize`](crate::Serialize) produces every vector and
+//! [`parse_message`](crate::parse_message) recovers every value, so an
+//! accidental format change fails loudly.
+//!
+//! Available with the `test-vectors` feature.
+
+use crate::types::{AnalogStick, Buttons, GamepadFieldUpdate, GamepadState};
+
+/// A full state message and its wire encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct StateVector {
+    /// Short description of what the vector covers
+    pub name: &'static str,
+    /// Decoded state
+    pub state: GamepadState,
+    /// Serialized `G` message
+    pub bytes: &'static [u8],
+}
+
+/// An incremental update message and its wire encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateVector {
+    /// Short description of what the vector covers
+    pub name: &'static str,
+    /// Decoded update
+    pub update: GamepadFieldUpdate,
+    /// Serialized `U` message
+    pub bytes: &'static [u8],
+}
+
+/// Full state test vectors.
+pub const STATE_VECTORS: [StateVector; 6] = [
+    StateVector {
+        name: "neutral",
+        state: GamepadState::neutral(),
+        bytes: b"G0000:0:0:0:0:0:0*30\n",
+    },
+    StateVector {
+        name: "all buttons",
+        state: GamepadState {
+            buttons: Buttons(0xFFFF),
+            ..GamepadState::neutral()
+        },
+        bytes: b"GFFFF:0:0:0:0:0:0*71\n",
+    },
+    StateVector {
+        name: "maximum",
+        state: GamepadState {
+            buttons: Buttons(0x0000),
+            left_stick: AnalogStick::new(i16::MAX, i16::MAX),
+            right_stick: AnalogStick::new(i16::MAX, i16::MAX),
+            left_trigger: u8::MAX,
+            right_trigger: u8::MAX,
+        },
+        bytes: b"G0000:32767:32767:32767:32767:255:255*BD\n",
+    },
+    StateVector {
+        name: "minimum",
+        state: GamepadState {
+            buttons: Buttons(0x0000),
+            left_stick: AnalogStick::new(i16::MIN, i16::MIN),
+            right_stick: AnalogStick::new(i16::MIN, i16::MIN),
+            left_trigger: 0,
+            right_trigger: 0,
+        },
+        bytes: b"G0000:-32768:-32768:-32768:-32768:0:0*BE\n",
+    },
+    StateVector {
+        name: "typical",
+        state: GamepadState {
+            buttons: Buttons(0x0001),
+            left_stick: AnalogStick::new(100, -100),
+            right_stick: AnalogStick::new(0, 0),
+            left_trigger: 64,
+            right_trigger: 32,
+        },
+        bytes: b"G0001:100:-100:0:0:64:32*EA\n",
+    },
+    StateVector {
+        name: "mixed signs",
+        state: GamepadState {
+            buttons: Buttons(0x8421),
+            left_stick: AnalogStick::new(-1, 1),
+            right_stick: AnalogStick::new(16384, -16384),
+            left_trigger: 1,
+            right_trigger: 254,
+        },
+        bytes: b"G8421:-1:1:16384:-16384:1:254*61\n",
+    },
+];
+
+/// Incremental update test vectors.
+pub const UPDATE_VECTORS: [UpdateVector; 11] = [
+    UpdateVector {
+        name: "buttons",
+        update: GamepadFieldUpdate::Buttons(Buttons(0x0001)),
+        bytes: b"UB:0001*89\n",
+    },
+    UpdateVector {
+        name: "all buttons",
+        update: GamepadFieldUpdate::Buttons(Buttons(0xFFFF)),
+        bytes: b"UB:FFFF*4B\n",
+    },
+    UpdateVector {
+        name: "left stick X minimum",
+        update: GamepadFieldUpdate::LeftStickX(i16::MIN),
+        bytes: b"ULX:-32768*3E\n",
+    },
+    UpdateVector {
+        name: "left stick Y maximum",
+        update: GamepadFieldUpdate::LeftStickY(i16::MAX),
+        bytes: b"ULY:32767*93\n",
+    },
+    UpdateVector {
+        name: "right stick X center",
+        update: GamepadFieldUpdate::RightStickX(0),
+        bytes: b"URX:0*4E\n",
+    },
+    UpdateVector {
+        name: "right stick Y negative",
+        update: GamepadFieldUpdate::RightStickY(-1),
+        bytes: b"URY:-1*D2\n",
+    },
+    UpdateVector {
+        name: "left trigger maximum",
+        update: GamepadFieldUpdate::LeftTrigger(u8::MAX),
+        bytes: b"ULT:255*D9\n",
+    },
+    UpdateVector {
+        name: "right trigger released",
+        update: GamepadFieldUpdate::RightTrigger(0),
+        bytes: b"URT:0*B4\n",
+    },
+    UpdateVector {
+        name: "press buttons",
+        update: GamepadFieldUpdate::ButtonsSet(Buttons(0x0010)),
+        bytes: b"UB+:0010*4C\n",
+    },
+    UpdateVector {
+        name: "release buttons",
+        update: GamepadFieldUpdate::ButtonsClear(Buttons(0x8000)),
+        bytes: b"UB-:8000*1F\n",
+    },
+    UpdateVector {
+        name: "left stick X delta",
+        update: GamepadFieldUpdate::LeftStickXDelta(-100),
+        bytes: b"ULX+:-100*23\n",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_message, ParsedMessage};
+    use crate::serialize::{Serialize, MAX_FULL_STATE_SIZE, MAX_UPDATE_SIZE};
+
+    #[test]
+    fn test_state_vectors_serialize() {
+        for vector in &STATE_VECTORS {
+            let mut buf = [0u8; MAX_FULL_STATE_SIZE];
+            let len = vector.state.serialize(&mut buf).unwrap();
+            assert_eq!(&buf[..len], vector.bytes, "vector {:?}", vector.name);
+        }
+    }
+
+    #[test]
+    fn test_state_vectors_parse() {
+        for vector in &STATE_VECTORS {
+            assert_eq!(
+                parse_message(vector.bytes),
+                Ok(ParsedMessage::FullState(vector.state)),
+                "vector {:?}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_vectors_serialize() {
+        for vector in &UPDATE_VECTORS {
+            let mut buf = [0u8; MAX_UPDATE_SIZE];
+            let len = vector.update.serialize(&mut buf).unwrap();
+            assert_eq!(&buf[..len], vector.bytes, "vector {:?}", vector.name);
+        }
+    }
+
+    #[test]
+    fn test_update_vectors_parse() {
+        for vector in &UPDATE_VECTORS {
+            assert_eq!(
+                parse_message(vector.bytes),
+                Ok(ParsedMessage::Update(vector.update)),
+                "vector {:?}",
+                vector.name
+            );
+        }
+    }
+}
@@ -7,6 +7,8 @@
 //!
 //! - Minimal MAVLink parser for MANUAL_CONTROL (ID 69) and HEARTBEAT (ID 0)
 //! - Configurable axis mapping
+//! - Telemetry encoding to BATTERY_STATUS / SYS_STATUS / GPS_RAW_INT / ATTITUDE
+//!   ([`encode_telemetry`], [`MavlinkTelemetryEncoder`])
 //! - No chip-specific dependencies - works on any platform
 //! - Fully testable on host
 //!
@@ -49,6 +51,7 @@
 
 pub mod mapping;
 pub mod parser;
+pub mod telemetry;
 
 // Re-export main types from parser
 pub use parser::{
@@ -64,6 +67,12 @@ pub use mapping::{
     MAVLINK_Z_MIN,
 };
 
+// Re-export telemetry encoding
+pub use telemetry::{
+    encode_telemetry, MavlinkTelemetryEncoder, MAX_TELEMETRY_FRAME_SIZE, MSG_ID_ATTITUDE,
+    MSG_ID_BATTERY_STATUS, MSG_ID_GPS_RAW_INT, MSG_ID_SYS_STATUS,
+};
+
 /// Common MAVLink baud rates.
 pub const MAVLINK_BAUDRATE_TELEMETRY: u32 = 57_600;
 pub const MAVLINK_BAUDRATE_SERIAL: u32 = 115_200;
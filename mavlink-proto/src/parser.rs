@@ -35,7 +35,7 @@ const CRC_EXTRA_MANUAL_CONTROL: u8 = 243;
 const CRC_EXTRA_HEARTBEAT: u8 = 50;
 
 /// Parsed MANUAL_CONTROL message.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ManualControl {
     /// Target system ID.
     pub target: u8,
@@ -54,7 +54,7 @@ pub struct ManualControl {
 }
 
 /// Parsed MAVLink message.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MavMessage {
     ManualControl(ManualControl),
     Heartbeat,
@@ -62,7 +62,7 @@ pub enum MavMessage {
 }
 
 /// Parser error.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseError {
     /// Not enough data.
     Incomplete,
@@ -75,17 +75,27 @@ pub enum ParseError {
 }
 
 /// MAVLink frame parser.
+///
+/// The frame CRC is accumulated as bytes arrive, so completing a frame costs
+/// one extra CRC step (CRC_EXTRA) instead of a pass over the whole frame.
 pub struct MavlinkParser {
     buffer: [u8; MAX_FRAME_SIZE],
     pos: usize,
     state: ParserState,
+    /// CRC over the bytes received so far (header after STX, then payload)
+    crc: u16,
 }
 
 #[derive(Clone, Copy)]
 enum ParserState {
     WaitingForStart,
-    ReadingHeader,
-    ReadingPayload { expected_len: usize },
+    ReadingHeader {
+        header_size: usize,
+    },
+    ReadingPayload {
+        crc_start: usize,
+        expected_len: usize,
+    },
 }
 
 impl MavlinkParser {
@@ -96,6 +106,7 @@ impl MavlinkParser {
             buffer: [0u8; MAX_FRAME_SIZE],
             pos: 0,
             state: ParserState::WaitingForStart,
+            crc: CRC_INIT,
         }
     }
 
@@ -114,32 +125,44 @@ impl MavlinkParser {
                 if byte == MAVLINK_STX_V1 || byte == MAVLINK_STX_V2 {
                     self.buffer[0] = byte;
                     self.pos = 1;
-                    self.state = ParserState::ReadingHeader;
+                    self.crc = CRC_INIT;
+                    let header_size = if byte == MAVLINK_STX_V2 { 10 } else { 6 };
+                    self.state = ParserState::ReadingHeader { header_size };
                 }
                 Ok(None)
             }
-            ParserState::ReadingHeader => {
+            ParserState::ReadingHeader { header_size } => {
                 self.buffer[self.pos] = byte;
                 self.pos += 1;
-
-                let header_size = if self.buffer[0] == MAVLINK_STX_V2 { 10 } else { 6 };
+                self.crc = crc_accumulate(byte, self.crc);
 
                 if self.pos >= header_size {
                     // Got full header, extract payload length
                     let payload_len = self.buffer[1] as usize;
                     let checksum_len = 2;
-                    let expected_len = header_size + payload_len + checksum_len;
+                    let crc_start = header_size + payload_len;
+                    let expected_len = crc_start + checksum_len;
 
                     if expected_len > MAX_FRAME_SIZE {
                         self.reset();
                         return Err(ParseError::InvalidStart);
                     }
 
-                    self.state = ParserState::ReadingPayload { expected_len };
+                    self.state = ParserState::ReadingPayload {
+                        crc_start,
+                        expected_len,
+                    };
                 }
                 Ok(None)
             }
-            ParserState::ReadingPayload { expected_len } => {
+            ParserState::ReadingPayload {
+                crc_start,
+                expected_len,
+            } => {
+                // The payload is CRC-covered, the trailing checksum is not
+                if self.pos < crc_start {
+                    self.crc = crc_accumulate(byte, self.crc);
+                }
                 self.buffer[self.pos] = byte;
                 self.pos += 1;
 
@@ -181,9 +204,8 @@ impl MavlinkParser {
             _ => return Ok(Some(MavMessage::Unknown(msg_id))),
         };
 
-        // Calculate CRC over header (excluding STX) + payload + CRC_EXTRA
-        let crc_data_end = if is_v2 { 10 + payload_len } else { 6 + payload_len };
-        let calculated_crc = crc16_mcrf4xx(&self.buffer[1..crc_data_end], crc_extra);
+        // Header (excluding STX) and payload are already accumulated
+        let calculated_crc = crc_accumulate(crc_extra, self.crc);
 
         let received_crc = (self.buffer[crc_start] as u16)
             | ((self.buffer[crc_start + 1] as u16) << 8);
@@ -226,7 +248,7 @@ impl Default for MavlinkParser {
 }
 
 /// CRC-16/MCRF4XX calculation.
-fn crc16_mcrf4xx(data: &[u8], crc_extra: u8) -> u16 {
+pub(crate) fn crc16_mcrf4xx(data: &[u8], crc_extra: u8) -> u16 {
     let mut crc = CRC_INIT;
 
     for &byte in data {
@@ -265,4 +287,159 @@ mod tests {
         assert!(parser.push_byte(0x00).unwrap().is_none());
         assert!(parser.push_byte(0x42).unwrap().is_none());
     }
+
+    /// Build a MANUAL_CONTROL frame (v1 or v2) with the given fields.
+    fn manual_control_frame(v2: bool, seq: u8, mc: &ManualControl) -> ([u8; 32], usize) {
+        let mut payload = [0u8; 13];
+        payload[0] = mc.target;
+        payload[1..3].copy_from_slice(&mc.x.to_le_bytes());
+        payload[3..5].copy_from_slice(&mc.y.to_le_bytes());
+        payload[5..7].copy_from_slice(&mc.z.to_le_bytes());
+        payload[7..9].copy_from_slice(&mc.r.to_le_bytes());
+        payload[9..11].copy_from_slice(&mc.buttons.to_le_bytes());
+        payload[11..13].copy_from_slice(&mc.buttons2.to_le_bytes());
+        let payload = if v2 { &payload[..] } else { &payload[..11] };
+
+        let mut frame = [0u8; 32];
+        let header: &[u8] = if v2 {
+            &[
+                MAVLINK_STX_V2,
+                payload.len() as u8,
+                0,
+                0,
+                seq,
+                255,
+                0,
+                69,
+                0,
+                0,
+            ]
+        } else {
+            &[MAVLINK_STX_V1, payload.len() as u8, seq, 255, 0, 69]
+        };
+        frame[..header.len()].copy_from_slice(header);
+        let crc_start = header.len() + payload.len();
+        frame[header.len()..crc_start].copy_from_slice(payload);
+        let crc = crc16_mcrf4xx(&frame[1..crc_start], CRC_EXTRA_MANUAL_CONTROL);
+        frame[crc_start..crc_start + 2].copy_from_slice(&crc.to_le_bytes());
+        (frame, crc_start + 2)
+    }
+
+    /// Small deterministic PRNG (xorshift32) for generating test frames.
+    fn next_random(seed: &mut u32) -> u32 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        *seed
+    }
+
+    fn random_frame(seed: &mut u32, seq: u8) -> (ManualControl, [u8; 32], usize) {
+        let mut axis = || next_random(seed) as i16;
+        let mc = ManualControl {
+            target: seq,
+            x: axis(),
+            y: axis(),
+            z: axis(),
+            r: axis(),
+            buttons: next_random(seed) as u16,
+            buttons2: 0,
+        };
+        let v2 = next_random(seed) & 1 == 1;
+        let mc = ManualControl {
+            buttons2: if v2 { next_random(seed) as u16 } else { 0 },
+            ..mc
+        };
+        let (frame, len) = manual_control_frame(v2, seq, &mc);
+        (mc, frame, len)
+    }
+
+    #[test]
+    fn test_incremental_crc_matches_batch_crc() {
+        let mut parser = MavlinkParser::new();
+        let mut seed = 0x1234_5678;
+
+        for i in 0..2000u32 {
+            let (mc, mut frame, len) = random_frame(&mut seed, i as u8);
+
+            // Corrupt a payload or checksum byte in every fourth frame
+            if i % 4 == 0 {
+                let header = if frame[0] == MAVLINK_STX_V2 { 10 } else { 6 };
+                let at = header + next_random(&mut seed) as usize % (len - header);
+                frame[at] ^= 1 << (next_random(&mut seed) % 8);
+            }
+
+            // Expected outcome from the batch CRC over the complete frame
+            let crc_start = len - 2;
+            let batch = crc16_mcrf4xx(&frame[1..crc_start], CRC_EXTRA_MANUAL_CONTROL);
+            let received = u16::from_le_bytes([frame[crc_start], frame[crc_start + 1]]);
+
+            let mut result = Ok(None);
+            for &byte in &frame[..len] {
+                result = parser.push_byte(byte);
+                if !matches!(result, Ok(None)) {
+                    break;
+                }
+            }
+
+            if batch == received {
+                let parsed = result.unwrap().unwrap();
+                // A corrupted frame may still carry valid (different) fields
+                if i % 4 != 0 {
+                    assert_eq!(parsed, MavMessage::ManualControl(mc), "frame {i}");
+                }
+            } else {
+                assert_eq!(result, Err(ParseError::CrcError), "frame {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_heartbeat_after_garbage() {
+        let mut parser = MavlinkParser::new();
+        let mut frame = [0u8; 17];
+        frame[..10].copy_from_slice(&[MAVLINK_STX_V2, 5, 0, 0, 7, 1, 1, 0, 0, 0]);
+        frame[10..15].copy_from_slice(&[1, 2, 3, 4, 5]);
+        let crc = crc16_mcrf4xx(&frame[1..15], CRC_EXTRA_HEARTBEAT);
+        frame[15..].copy_from_slice(&crc.to_le_bytes());
+
+        for &byte in &[0x00, 0x42, 0x13] {
+            assert_eq!(parser.push_byte(byte), Ok(None));
+        }
+        let results: [_; 17] = core::array::from_fn(|i| parser.push_byte(frame[i]));
+        assert!(results[..16].iter().all(|r| *r == Ok(None)));
+        assert_eq!(results[16], Ok(Some(MavMessage::Heartbeat)));
+    }
+
+    /// Host benchmark: `cargo test -p mavlink-proto --release -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_push_byte() {
+        extern crate std;
+        use std::time::Instant;
+
+        const FRAMES: u32 = 200_000;
+        let mut seed = 0xDEAD_BEEF;
+        let frames: std::vec::Vec<_> = (0..256).map(|i| random_frame(&mut seed, i as u8)).collect();
+
+        let mut parser = MavlinkParser::new();
+        let mut bytes = 0usize;
+        let mut parsed = 0u32;
+        let start = Instant::now();
+        for i in 0..FRAMES {
+            let (_, frame, len) = &frames[i as usize % frames.len()];
+            for &byte in &frame[..*len] {
+                if let Ok(Some(_)) = parser.push_byte(core::hint::black_box(byte)) {
+                    parsed += 1;
+                }
+            }
+            bytes += len;
+        }
+        let elapsed = start.elapsed();
+
+        assert_eq!(parsed, FRAMES);
+        std::println!(
+            "push_byte: {bytes} bytes in {elapsed:?} ({:.1} ns/byte)",
+            elapsed.as_nanos() as f64 / bytes as f64
+        );
+    }
 }
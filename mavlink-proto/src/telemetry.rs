@@ -0,0 +1,486 @@
+//! Minimal MAVLink 2 telemetry encoding.
+//!
+//! Encodes [`TelemetryData`] as common MAVLink messages so a bridge can feed
+//! telemetry to a MAVLink flight controller or GCS. [`encode_telemetry`]
+//! writes one frame with an explicit sequence number:
+//!
+//! - Battery → BATTERY_STATUS (ID 147)
+//! - GPS → GPS_RAW_INT (ID 24)
+//! - Attitude → ATTITUDE (ID 30)
+//!
+//! [`MavlinkTelemetryEncoder`] keeps the sequence number itself and reports
+//! the battery in SYS_STATUS (ID 1) instead, which GCSs show as the main
+//! vehicle battery.
+//!
+//! Payloads are written with trailing zero bytes truncated, as required by
+//! MAVLink 2.
+
+use gamepad_core::{TelemetryData, TelemetryEncoder, TelemetryError};
+
+use crate::parser::{crc16_mcrf4xx, MAVLINK_STX_V2};
+
+/// SYS_STATUS message ID.
+pub const MSG_ID_SYS_STATUS: u32 = 1;
+
+/// GPS_RAW_INT message ID.
+pub const MSG_ID_GPS_RAW_INT: u32 = 24;
+
+/// ATTITUDE message ID.
+pub const MSG_ID_ATTITUDE: u32 = 30;
+
+/// BATTERY_STATUS message ID.
+pub const MSG_ID_BATTERY_STATUS: u32 = 147;
+
+/// SYS_STATUS CRC_EXTRA value.
+const CRC_EXTRA_SYS_STATUS: u8 = 124;
+
+/// GPS_RAW_INT CRC_EXTRA value.
+const CRC_EXTRA_GPS_RAW_INT: u8 = 24;
+
+/// ATTITUDE CRC_EXTRA value.
+const CRC_EXTRA_ATTITUDE: u8 = 39;
+
+/// BATTERY_STATUS CRC_EXTRA value.
+const CRC_EXTRA_BATTERY_STATUS: u8 = 154;
+
+/// MAVLink 2 header length (STX through message ID).
+const HEADER_LEN: usize = 10;
+
+/// Length of the trailing CRC.
+const CRC_LEN: usize = 2;
+
+/// SYS_STATUS payload length.
+const SYS_STATUS_LEN: usize = 31;
+
+/// BATTERY_STATUS payload length (without extension fields).
+const BATTERY_STATUS_LEN: usize = 36;
+
+/// GPS_RAW_INT payload length (without extension fields).
+const GPS_RAW_INT_LEN: usize = 30;
+
+/// ATTITUDE payload length.
+const ATTITUDE_LEN: usize = 28;
+
+/// Largest payload written by this module (BATTERY_STATUS).
+const MAX_PAYLOAD_LEN: usize = BATTERY_STATUS_LEN;
+
+/// Maximum size of an encoded telemetry frame.
+pub const MAX_TELEMETRY_FRAME_SIZE: usize = HEADER_LEN + MAX_PAYLOAD_LEN + CRC_LEN;
+
+/// GPS_FIX_TYPE_NO_FIX.
+const GPS_FIX_TYPE_NO_FIX: u8 = 1;
+
+/// GPS_FIX_TYPE_3D_FIX.
+const GPS_FIX_TYPE_3D_FIX: u8 = 3;
+
+/// MAV_BATTERY_FUNCTION_ALL.
+const BATTERY_FUNCTION_ALL: u8 = 1;
+
+/// MAV_BATTERY_TYPE_LIPO.
+const BATTERY_TYPE_LIPO: u8 = 1;
+
+/// Centidegrees to radians.
+const CENTIDEGREES_TO_RAD: f32 = core::f32::consts::PI / 18_000.0;
+
+/// Encode `data` as a single MAVLink 2 frame into `buf`.
+///
+/// The frame is sent as `sys_id`/`comp_id` with sequence number `seq`;
+/// callers sending a stream increment it per frame. Returns the frame length.
+///
+/// # Errors
+///
+/// Returns [`TelemetryError::NotSupported`] for link quality and temperature,
+/// which have no message here, or [`TelemetryError::BufferFull`] if `buf` is
+/// shorter than the frame.
+pub fn encode_telemetry(
+    data: &TelemetryData,
+    sys_id: u8,
+    comp_id: u8,
+    seq: u8,
+    buf: &mut [u8],
+) -> Result<usize, TelemetryError> {
+    let mut payload = [0u8; MAX_PAYLOAD_LEN];
+
+    let (msg_id, crc_extra, len) = match *data {
+        TelemetryData::Battery {
+            voltage_mv,
+            current_ma,
+            remaining_pct,
+        } => {
+            // current_consumed (mAh) and energy_consumed (hJ) unknown
+            payload[0..4].copy_from_slice(&(-1i32).to_le_bytes());
+            payload[4..8].copy_from_slice(&(-1i32).to_le_bytes());
+            // Temperature unknown
+            payload[8..10].copy_from_slice(&i16::MAX.to_le_bytes());
+            // Total voltage in the first cell slot, the others unused
+            payload[10..12].copy_from_slice(&voltage_mv.to_le_bytes());
+            for cell in payload[12..30].chunks_exact_mut(2) {
+                cell.copy_from_slice(&u16::MAX.to_le_bytes());
+            }
+            let current_ca = (current_ma / 10) as i16;
+            payload[30..32].copy_from_slice(&current_ca.to_le_bytes());
+            payload[32] = 0; // id
+            payload[33] = BATTERY_FUNCTION_ALL;
+            payload[34] = BATTERY_TYPE_LIPO;
+            payload[35] = (remaining_pct.min(100) as i8).to_le_bytes()[0];
+            (
+                MSG_ID_BATTERY_STATUS,
+                CRC_EXTRA_BATTERY_STATUS,
+                BATTERY_STATUS_LEN,
+            )
+        }
+
+        TelemetryData::Gps {
+            lat,
+            lon,
+            alt_m,
+            speed_mps,
+            sats,
+        } => {
+            let alt_mm = i32::from(alt_m) * 1000;
+            let vel_cms = u16::from(speed_mps) * 100;
+            let fix_type = if sats >= 4 {
+                GPS_FIX_TYPE_3D_FIX
+            } else {
+                GPS_FIX_TYPE_NO_FIX
+            };
+            // time_usec left at 0 (unknown)
+            payload[8..12].copy_from_slice(&lat.to_le_bytes());
+            payload[12..16].copy_from_slice(&lon.to_le_bytes());
+            payload[16..20].copy_from_slice(&alt_mm.to_le_bytes());
+            payload[20..22].copy_from_slice(&u16::MAX.to_le_bytes()); // eph unknown
+            payload[22..24].copy_from_slice(&u16::MAX.to_le_bytes()); // epv unknown
+            payload[24..26].copy_from_slice(&vel_cms.to_le_bytes());
+            payload[26..28].copy_from_slice(&u16::MAX.to_le_bytes()); // cog unknown
+            payload[28] = fix_type;
+            payload[29] = sats;
+            (MSG_ID_GPS_RAW_INT, CRC_EXTRA_GPS_RAW_INT, GPS_RAW_INT_LEN)
+        }
+
+        TelemetryData::Attitude { roll, pitch, yaw } => {
+            let rad = |centidegrees: i16| f32::from(centidegrees) * CENTIDEGREES_TO_RAD;
+            // time_boot_ms and angular speeds left at 0
+            payload[4..8].copy_from_slice(&rad(roll).to_le_bytes());
+            payload[8..12].copy_from_slice(&rad(pitch).to_le_bytes());
+            payload[12..16].copy_from_slice(&rad(yaw).to_le_bytes());
+            (MSG_ID_ATTITUDE, CRC_EXTRA_ATTITUDE, ATTITUDE_LEN)
+        }
+
+        TelemetryData::LinkQuality { .. } | TelemetryData::Temperature { .. } => {
+            return Err(TelemetryError::NotSupported);
+        }
+    };
+
+    let header = FrameHeader {
+        sys_id,
+        comp_id,
+        seq,
+    };
+    header.write_frame(msg_id, crc_extra, &payload[..len], buf)
+}
+
+/// Sender and sequence number of a frame.
+struct FrameHeader {
+    sys_id: u8,
+    comp_id: u8,
+    seq: u8,
+}
+
+impl FrameHeader {
+    /// Write a complete frame for `payload` into `buf`.
+    fn write_frame(
+        &self,
+        msg_id: u32,
+        crc_extra: u8,
+        payload: &[u8],
+        buf: &mut [u8],
+    ) -> Result<usize, TelemetryError> {
+        // Drop trailing zeros, keeping at least one payload byte
+        let len = payload
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(1, |last| last + 1);
+        let payload = &payload[..len];
+
+        let frame_len = HEADER_LEN + len + CRC_LEN;
+        if buf.len() < frame_len {
+            return Err(TelemetryError::BufferFull);
+        }
+
+        let id = msg_id.to_le_bytes();
+        buf[..HEADER_LEN].copy_from_slice(&[
+            MAVLINK_STX_V2,
+            len as u8,
+            0, // incompat_flags
+            0, // compat_flags
+            self.seq,
+            self.sys_id,
+            self.comp_id,
+            id[0],
+            id[1],
+            id[2],
+        ]);
+        buf[HEADER_LEN..HEADER_LEN + len].copy_from_slice(payload);
+
+        let crc = crc16_mcrf4xx(&buf[1..HEADER_LEN + len], crc_extra);
+        buf[HEADER_LEN + len..frame_len].copy_from_slice(&crc.to_le_bytes());
+
+        Ok(frame_len)
+    }
+}
+
+/// [`TelemetryEncoder`] producing MAVLink 2 frames.
+///
+/// Each encoded frame uses the next sequence number. Battery data is sent as
+/// SYS_STATUS, everything else as by [`encode_telemetry`].
+#[derive(Debug, Clone)]
+pub struct MavlinkTelemetryEncoder {
+    system_id: u8,
+    component_id: u8,
+    seq: u8,
+}
+
+impl MavlinkTelemetryEncoder {
+    /// Create an encoder sending as the given system and component.
+    #[must_use]
+    pub const fn new(system_id: u8, component_id: u8) -> Self {
+        Self {
+            system_id,
+            component_id,
+            seq: 0,
+        }
+    }
+}
+
+impl TelemetryEncoder for MavlinkTelemetryEncoder {
+    fn encode(&mut self, data: &TelemetryData, buf: &mut [u8]) -> Result<usize, TelemetryError> {
+        let result = match *data {
+            TelemetryData::Battery {
+                voltage_mv,
+                current_ma,
+                remaining_pct,
+            } => {
+                // voltage_battery (mV), current_battery (cA), battery_remaining (%)
+                let mut payload = [0u8; SYS_STATUS_LEN];
+                let current_ca = (current_ma / 10) as i16;
+                let remaining = remaining_pct.min(100) as i8;
+                payload[14..16].copy_from_slice(&voltage_mv.to_le_bytes());
+                payload[16..18].copy_from_slice(&current_ca.to_le_bytes());
+                payload[30] = remaining.to_le_bytes()[0];
+                let header = FrameHeader {
+                    sys_id: self.system_id,
+                    comp_id: self.component_id,
+                    seq: self.seq,
+                };
+                header.write_frame(MSG_ID_SYS_STATUS, CRC_EXTRA_SYS_STATUS, &payload, buf)
+            }
+            _ => encode_telemetry(data, self.system_id, self.component_id, self.seq, buf),
+        };
+
+        if result.is_ok() {
+            self.seq = self.seq.wrapping_add(1);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{MavMessage, MavlinkParser};
+
+    const BATTERY: TelemetryData = TelemetryData::Battery {
+        voltage_mv: 16_800,
+        current_ma: 12_345,
+        remaining_pct: 75,
+    };
+
+    fn encode(encoder: &mut dyn TelemetryEncoder, data: &TelemetryData) -> ([u8; 64], usize) {
+        let mut buf = [0u8; 64];
+        let len = encoder.encode(data, &mut buf).unwrap();
+        (buf, len)
+    }
+
+    #[test]
+    fn test_battery_sys_status() {
+        let mut encoder = MavlinkTelemetryEncoder::new(1, 191);
+        let (buf, len) = encode(&mut encoder, &BATTERY);
+
+        assert_eq!(len, 10 + SYS_STATUS_LEN + 2);
+        assert_eq!(&buf[..10], &[0xFD, 31, 0, 0, 0, 1, 191, 1, 0, 0]);
+
+        let payload = &buf[10..41];
+        assert_eq!(u16::from_le_bytes([payload[14], payload[15]]), 16_800);
+        assert_eq!(i16::from_le_bytes([payload[16], payload[17]]), 1234);
+        assert_eq!(payload[30], 75);
+
+        let crc = crc16_mcrf4xx(&buf[1..41], CRC_EXTRA_SYS_STATUS);
+        assert_eq!(&buf[41..43], &crc.to_le_bytes());
+    }
+
+    #[test]
+    fn test_frames_parse_and_sequence_advances() {
+        let mut encoder = MavlinkTelemetryEncoder::new(1, 1);
+        let frames = [
+            BATTERY,
+            TelemetryData::Gps {
+                lat: 473_977_420,
+                lon: 85_455_940,
+                alt_m: 500,
+                speed_mps: 12,
+                sats: 9,
+            },
+            TelemetryData::Attitude {
+                roll: 1000,
+                pitch: -500,
+                yaw: 9000,
+            },
+        ];
+
+        let mut parser = MavlinkParser::new();
+        for (seq, data) in frames.iter().enumerate() {
+            let (buf, len) = encode(&mut encoder, data);
+            assert_eq!(buf[4], seq as u8);
+
+            let mut parsed = None;
+            for &byte in &buf[..len] {
+                if let Some(msg) = parser.push_byte(byte).unwrap() {
+                    parsed = Some(msg);
+                }
+            }
+            let expected = [MSG_ID_SYS_STATUS, MSG_ID_GPS_RAW_INT, MSG_ID_ATTITUDE][seq];
+            assert!(matches!(parsed, Some(MavMessage::Unknown(id)) if id == expected));
+        }
+    }
+
+    #[test]
+    fn test_payload_truncated() {
+        let mut encoder = MavlinkTelemetryEncoder::new(1, 1);
+        let data = TelemetryData::Battery {
+            voltage_mv: 12_000,
+            current_ma: 0,
+            remaining_pct: 0,
+        };
+        let (buf, len) = encode(&mut encoder, &data);
+
+        // Payload ends with the voltage field
+        assert_eq!(buf[1], 16);
+        assert_eq!(len, 10 + 16 + 2);
+    }
+
+    #[test]
+    fn test_link_quality_not_supported_and_buffer_full() {
+        let mut encoder = MavlinkTelemetryEncoder::new(1, 1);
+        let mut buf = [0u8; 64];
+        let lq = TelemetryData::LinkQuality {
+            rssi: -70,
+            snr: 10,
+            lq: 100,
+        };
+        assert_eq!(
+            encoder.encode(&lq, &mut buf),
+            Err(TelemetryError::NotSupported)
+        );
+        assert_eq!(
+            encoder.encode(&BATTERY, &mut buf[..20]),
+            Err(TelemetryError::BufferFull)
+        );
+    }
+
+    /// Feed `frame` through the parser, returning the parsed message.
+    fn parse(frame: &[u8]) -> Option<MavMessage> {
+        let mut parser = MavlinkParser::new();
+        let mut parsed = None;
+        for &byte in frame {
+            if let Some(msg) = parser.push_byte(byte).unwrap() {
+                parsed = Some(msg);
+            }
+        }
+        parsed
+    }
+
+    /// Assert that the trailing CRC of `frame` covers everything after STX
+    /// plus `crc_extra`.
+    fn assert_crc(frame: &[u8], crc_extra: u8) {
+        let (body, crc) = frame.split_at(frame.len() - CRC_LEN);
+        assert_eq!(crc, crc16_mcrf4xx(&body[1..], crc_extra).to_le_bytes());
+    }
+
+    #[test]
+    fn test_encode_attitude_round_trip() {
+        let data = TelemetryData::Attitude {
+            roll: 4500,
+            pitch: -9000,
+            yaw: 18_000,
+        };
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&data, 7, 42, 200, &mut buf).unwrap();
+
+        // Header: sequence and sender as given
+        assert_eq!(&buf[..10], &[0xFD, 16, 0, 0, 200, 7, 42, 30, 0, 0]);
+        assert_eq!(len, 10 + 16 + 2);
+        assert!(matches!(
+            parse(&buf[..len]),
+            Some(MavMessage::Unknown(MSG_ID_ATTITUDE))
+        ));
+        // CRC_EXTRA of ATTITUDE in the common message set
+        assert_crc(&buf[..len], 39);
+
+        let angle =
+            |offset: usize| f32::from_le_bytes(buf[10 + offset..14 + offset].try_into().unwrap());
+        assert!((angle(4) - core::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        assert!((angle(8) + core::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((angle(12) - core::f32::consts::PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_encode_battery_status() {
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&BATTERY, 1, 1, 0, &mut buf).unwrap();
+        assert_eq!(len, MAX_TELEMETRY_FRAME_SIZE);
+        assert_eq!(&buf[7..10], &[147, 0, 0]);
+        assert!(matches!(
+            parse(&buf[..len]),
+            Some(MavMessage::Unknown(MSG_ID_BATTERY_STATUS))
+        ));
+        // CRC_EXTRA of BATTERY_STATUS in the common message set
+        assert_crc(&buf[..len], 154);
+
+        let payload = &buf[10..10 + BATTERY_STATUS_LEN];
+        assert_eq!(u16::from_le_bytes([payload[10], payload[11]]), 16_800);
+        assert_eq!(u16::from_le_bytes([payload[12], payload[13]]), u16::MAX);
+        assert_eq!(i16::from_le_bytes([payload[30], payload[31]]), 1234);
+        assert_eq!(payload[35], 75);
+    }
+
+    #[test]
+    fn test_encode_gps_and_unsupported() {
+        let gps = TelemetryData::Gps {
+            lat: 473_977_420,
+            lon: 85_455_940,
+            alt_m: 500,
+            speed_mps: 12,
+            sats: 9,
+        };
+        let mut buf = [0u8; MAX_TELEMETRY_FRAME_SIZE];
+        let len = encode_telemetry(&gps, 1, 1, 3, &mut buf).unwrap();
+        assert!(matches!(
+            parse(&buf[..len]),
+            Some(MavMessage::Unknown(MSG_ID_GPS_RAW_INT))
+        ));
+        // CRC_EXTRA of GPS_RAW_INT in the common message set
+        assert_crc(&buf[..len], 24);
+
+        let temp = TelemetryData::Temperature {
+            temp_decidegrees: 250,
+        };
+        assert_eq!(
+            encode_telemetry(&temp, 1, 1, 0, &mut buf),
+            Err(TelemetryError::NotSupported)
+        );
+        assert_eq!(
+            encode_telemetry(&gps, 1, 1, 0, &mut buf[..20]),
+            Err(TelemetryError::BufferFull)
+        );
+    }
+}